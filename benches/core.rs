@@ -0,0 +1,67 @@
+//! Benchmarks of the hot paths of the scanner, the recognizers and the
+//! peekables, with their generic and type-erased entry points side by side.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use elyze::bytes::token::Token;
+use elyze::matcher::Match;
+use elyze::peek::{peek, peek_dyn, Peekable};
+use elyze::recognizer::{recognize, recognize_slice, recognize_slice_dyn};
+use elyze::scanner::Scanner;
+
+/// A list of comma separated words, ended by a semicolon
+fn list() -> Vec<u8> {
+    let mut data = b"word,".repeat(1000);
+    data.push(b';');
+    data
+}
+
+fn scanner(c: &mut Criterion) {
+    let data = list();
+    c.bench_function("scanner/bump_by", |b| {
+        b.iter(|| {
+            let mut scanner = Scanner::new(black_box(&data));
+            while !scanner.is_empty() {
+                black_box(scanner.remaining());
+                scanner.bump_by(1);
+            }
+        })
+    });
+}
+
+fn recognizer(c: &mut Criterion) {
+    let data = b",".repeat(1000);
+    c.bench_function("recognizer/recognize", |b| {
+        b.iter(|| {
+            let mut scanner = Scanner::new(black_box(&data));
+            while recognize(Token::Comma, &mut scanner).is_ok() {}
+        })
+    });
+    c.bench_function("recognizer/recognize_slice", |b| {
+        b.iter(|| {
+            let mut scanner = Scanner::new(black_box(&data));
+            while recognize_slice(Token::Comma, &mut scanner).is_ok() {}
+        })
+    });
+    let matcher: &dyn Match<u8> = &Token::Comma;
+    c.bench_function("recognizer/recognize_slice_dyn", |b| {
+        b.iter(|| {
+            let mut scanner = Scanner::new(black_box(&data));
+            while recognize_slice_dyn(black_box(matcher), &mut scanner).is_ok() {}
+        })
+    });
+}
+
+fn peekable(c: &mut Criterion) {
+    let data = list();
+    let scanner = Scanner::new(&data);
+    c.bench_function("peek/peek", |b| {
+        b.iter(|| peek(Token::Semicolon, black_box(&scanner)))
+    });
+    let peekable: &dyn Peekable<u8> = &Token::Semicolon;
+    c.bench_function("peek/peek_dyn", |b| {
+        b.iter(|| peek_dyn(black_box(peekable), black_box(&scanner)))
+    });
+}
+
+criterion_group!(benches, scanner, recognizer, peekable);
+criterion_main!(benches);
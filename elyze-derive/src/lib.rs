@@ -0,0 +1,337 @@
+//! Derive macros of the [elyze](https://docs.rs/elyze) parser framework.
+//!
+//! The macros are re-exported by elyze behind the `derive` feature, use them
+//! from there rather than depending on this crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Generics,
+    Ident, Lifetime, LifetimeParam, Path,
+};
+
+/// Derive `Visitor` for a struct whose fields all implement `Visitor`.
+///
+/// The fields are accepted in their declaration order, then the struct is
+/// built from them. If a field can't be accepted, the scanner is restored to
+/// its position before the struct and the error of the field is returned.
+///
+/// The visitor is implemented for any type of data the fields can be visited
+/// from. If the struct has a lifetime parameter, the first one is the lifetime
+/// of the data, which allows fields borrowing from it.
+///
+/// # Example
+///
+/// ```ignore
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::visitor::Visitor;
+///
+/// #[derive(Visitor)]
+/// struct Version {
+///     major: Number<u8>,
+///     dot: Dot,
+///     minor: Number<u8>,
+/// }
+/// ```
+#[proc_macro_derive(Visitor)]
+pub fn derive_visitor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_visitor(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Validate` for a struct whose fields all implement `Validate`.
+///
+/// The fields are validated in their declaration order, without building
+/// them. The struct must also implement `Visitor`, usually derived alongside.
+///
+/// # Example
+///
+/// ```ignore
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::validate::Validate;
+/// use elyze::visitor::Visitor;
+///
+/// #[derive(Visitor, Validate)]
+/// struct Version {
+///     major: Number<u8>,
+///     dot: Dot,
+///     minor: Number<u8>,
+/// }
+/// ```
+#[proc_macro_derive(Validate)]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_validate(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Record` for a struct, storing its fields column-wise.
+///
+/// A `<Name>Columns` struct is generated next to the struct, with the same
+/// visibility and generics, holding a `Vec` per field under the name, or the
+/// index, of the field.
+///
+/// # Example
+///
+/// ```ignore
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::columnar::{parse_records, Record};
+/// use elyze::visitor::Visitor;
+///
+/// #[derive(Visitor, Record)]
+/// struct Row {
+///     id: Number<u32>,
+///     end: Newline,
+/// }
+///
+/// let batch = parse_records::<u8, Row>(b"1\n2\n")?;
+/// let ids: &Vec<Number<u32>> = &batch.columns().id;
+/// ```
+#[proc_macro_derive(Record)]
+pub fn derive_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_record(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The fields of the struct
+fn struct_fields(input: &DeriveInput, derived: &str) -> syn::Result<Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(data.fields.clone()),
+        Data::Enum(data) => Err(Error::new_spanned(
+            data.enum_token,
+            format!("`{derived}` can only be derived for structs"),
+        )),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            format!("`{derived}` can only be derived for structs"),
+        )),
+    }
+}
+
+/// The lifetime of the data, the first lifetime of the struct if it has one
+fn data_lifetime(input: &DeriveInput) -> Lifetime {
+    match input.generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => Lifetime::new("'__elyze", Span::call_site()),
+    }
+}
+
+/// The generics of the implementation of `bound` for the struct: the ones of
+/// the struct, the lifetime and the type of the data, each field being bound
+/// by `bound`
+fn impl_generics(input: &DeriveInput, fields: &Fields, bound: &Path) -> Generics {
+    let lifetime = data_lifetime(input);
+    let mut generics = input.generics.clone();
+    if input.generics.lifetimes().next().is_none() {
+        generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+        );
+    }
+    generics.params.push(parse_quote! { __ElyzeData });
+    let where_clause = generics.make_where_clause();
+    for field in fields.iter() {
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: #bound<#lifetime, __ElyzeData> });
+    }
+    generics
+}
+
+/// The name of the binding of the field at `index`
+fn binding(index: usize) -> Ident {
+    Ident::new(&format!("__field{index}"), Span::call_site())
+}
+
+/// Generate the `Visitor` implementation of the struct
+fn expand_visitor(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(&input, "Visitor")?;
+    let bound: Path = parse_quote! { ::elyze::visitor::Visitor };
+    let generics = impl_generics(&input, &fields, &bound);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+    let lifetime = data_lifetime(&input);
+    let name = &input.ident;
+
+    let accepts = fields.iter().enumerate().map(|(index, field)| {
+        let binding = binding(index);
+        let ty = &field.ty;
+        quote! { let #binding: #ty = checkpoint.visit()?; }
+    });
+    let bindings = (0..fields.len()).map(binding);
+    let construction = match &fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { #name { #(#names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { #name ( #(#bindings),* ) },
+        Fields::Unit => quote! { #name },
+    };
+    // a struct without fields accepts the empty input
+    let body = if fields.is_empty() {
+        quote! {
+            let _ = scanner;
+            ::core::result::Result::Ok(#construction)
+        }
+    } else {
+        quote! {
+            let mut checkpoint = scanner.checkpoint();
+            #(#accepts)*
+            checkpoint.commit();
+            ::core::result::Result::Ok(#construction)
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #bound<#lifetime, __ElyzeData> for #name #type_generics
+        #where_clause
+        {
+            fn accept(
+                scanner: &mut ::elyze::scanner::Scanner<#lifetime, __ElyzeData>,
+            ) -> ::elyze::errors::ParseResult<Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// Generate the `Validate` implementation of the struct
+fn expand_validate(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(&input, "Validate")?;
+    let bound: Path = parse_quote! { ::elyze::validate::Validate };
+    let generics = impl_generics(&input, &fields, &bound);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+    let lifetime = data_lifetime(&input);
+    let name = &input.ident;
+
+    let validations = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { checkpoint.validate::<#ty>()?; }
+    });
+    // a struct without fields accepts the empty input
+    let body = if fields.is_empty() {
+        quote! {
+            let _ = scanner;
+            ::core::result::Result::Ok(())
+        }
+    } else {
+        quote! {
+            let mut checkpoint = scanner.checkpoint();
+            #(#validations)*
+            checkpoint.commit();
+            ::core::result::Result::Ok(())
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #bound<#lifetime, __ElyzeData> for #name #type_generics
+        #where_clause
+        {
+            fn validate(
+                scanner: &mut ::elyze::scanner::Scanner<#lifetime, __ElyzeData>,
+            ) -> ::elyze::errors::ParseResult<()> {
+                #body
+            }
+        }
+    })
+}
+
+/// Generate the columns of the struct and its `Record` implementation
+fn expand_record(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(&input, "Record")?;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let name = &input.ident;
+    let vis = &input.vis;
+    let columns = Ident::new(&format!("{name}Columns"), name.span());
+    let generics = &input.generics;
+
+    let column_types = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { ::std::vec::Vec<#ty> }
+    });
+    let (definition, accessors) = match &fields {
+        Fields::Named(named) => {
+            let definitions = named.named.iter().zip(column_types).map(|(field, ty)| {
+                let (vis, ident) = (&field.vis, &field.ident);
+                quote! { #vis #ident: #ty }
+            });
+            let definition = quote! { { #(#definitions),* } };
+            let accessors = named
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = &field.ident;
+                    quote! { #ident }
+                })
+                .collect::<Vec<_>>();
+            (definition, accessors)
+        }
+        Fields::Unnamed(unnamed) => {
+            let definitions = unnamed.unnamed.iter().zip(column_types).map(|(field, ty)| {
+                let vis = &field.vis;
+                quote! { #vis #ty }
+            });
+            let definition = quote! { ( #(#definitions),* ) };
+            let accessors = (0..fields.len())
+                .map(|index| {
+                    let index = syn::Index::from(index);
+                    quote! { #index }
+                })
+                .collect::<Vec<_>>();
+            (definition, accessors)
+        }
+        Fields::Unit => (quote! { {} }, vec![]),
+    };
+    let bindings = (0..fields.len()).map(binding).collect::<Vec<_>>();
+    let destructuring = match &fields {
+        Fields::Named(_) => quote! { #name { #(#accessors: #bindings),* } },
+        Fields::Unnamed(_) => quote! { #name ( #(#bindings),* ) },
+        Fields::Unit => quote! { #name },
+    };
+    let constructions = accessors.iter().map(|accessor| {
+        quote! { #accessor: ::std::vec::Vec::new() }
+    });
+    let doc = format!("The columns of the [{name}] records, a `Vec` per field.");
+
+    // the where clause of a tuple struct follows its fields
+    let definition = match &fields {
+        Fields::Unnamed(_) => quote! { #vis struct #columns #generics #definition #where_clause; },
+        _ => quote! { #vis struct #columns #generics #where_clause #definition },
+    };
+
+    Ok(quote! {
+        #[doc = #doc]
+        #definition
+
+        #[automatically_derived]
+        impl #impl_generics ::core::default::Default for #columns #type_generics
+        #where_clause
+        {
+            fn default() -> Self {
+                #columns { #(#constructions),* }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::elyze::columnar::Record for #name #type_generics
+        #where_clause
+        {
+            type Columns = #columns #type_generics;
+
+            fn push_columns(self, columns: &mut Self::Columns) {
+                let #destructuring = self;
+                #(columns.#accessors.push(#bindings);)*
+            }
+        }
+    })
+}
@@ -1,6 +1,6 @@
 use elyze::bytes::token::Token;
 use elyze::errors::{ParseError, ParseResult};
-use elyze::peek::{peek, DefaultPeekableImplementation, Last, PeekSize, PeekableImplementation};
+use elyze::peek::{peek, Last, PeekVisitor};
 use elyze::recognizer::{recognize, Recognizer};
 use elyze::scanner::Scanner;
 use elyze::separated_list::{get_scanner_without_trailing_separator, SeparatedList};
@@ -21,7 +21,7 @@ fn main() -> ParseResult<()> {
         .try_or(Token::Plus)?
         .try_or(Token::Star)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken)?;
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
     assert_eq!(recognized, Token::Plus);
 
     // use the visitor
@@ -30,8 +30,8 @@ fn main() -> ParseResult<()> {
     assert_eq!(accepted, Token::Plus);
 
     // use peek
-    let mut scanner = Scanner::new(data);
-    let peeked = peek(Token::Dash, &mut scanner)?;
+    let scanner = Scanner::new(data);
+    let peeked = peek(Token::Dash, &scanner)?;
     if let Some(peeked) = peeked {
         assert_eq!(peeked.peeked_slice(), b"+");
     }
@@ -45,25 +45,17 @@ fn main() -> ParseResult<()> {
     }
 
     // separated list
-    // defines a structure to implement Peekable
-    // using the Visitor pattern excluding the comma token
+    // defines a structure to peek any token excluding the comma token
     struct AnyTokenExceptComma;
 
-    // Enable the Peekable trait using the Visitor pattern
-    impl PeekableImplementation for AnyTokenExceptComma {
-        type Type = DefaultPeekableImplementation;
-    }
-
-    // Define the PeekSize trait
-    impl PeekSize<u8> for AnyTokenExceptComma {}
-
     // Define the Visitor trait for the AnyTokenExceptComma structure
     // excluding the comma token
     impl<'a> Visitor<'a, u8> for AnyTokenExceptComma {
         fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            let start = scanner.current_position();
             let token = Token::accept(scanner)?;
             match token {
-                Token::Comma => Err(ParseError::UnexpectedToken),
+                Token::Comma => Err(ParseError::unexpected_token(start)),
                 _ => Ok(AnyTokenExceptComma),
             }
         }
@@ -75,9 +67,10 @@ fn main() -> ParseResult<()> {
 
     impl<'a> Visitor<'a, u8> for TokenData {
         fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            let start = scanner.current_position();
             let token = Token::accept(scanner)?;
             match token {
-                Token::Comma => Err(ParseError::UnexpectedToken),
+                Token::Comma => Err(ParseError::unexpected_token(start)),
                 _ => Ok(TokenData(token)),
             }
         }
@@ -97,7 +90,12 @@ fn main() -> ParseResult<()> {
     let scanner = Scanner::new(data);
     // clean up the data of its trailing comma
     let mut data_scanner =
-        get_scanner_without_trailing_separator(AnyTokenExceptComma, Token::Comma, &scanner)?;
+        // the visitor is made peekable by the PeekVisitor adapter
+        get_scanner_without_trailing_separator(
+            PeekVisitor::<AnyTokenExceptComma>::new(),
+            Token::Comma,
+            &scanner,
+        )?;
     assert_eq!(data_scanner.data(), b"*,-,+,/"); // data without a trailing comma
     // accept the separated list
     let list = SeparatedList::<u8, TokenData, SeparatorComma>::accept(&mut data_scanner)?;
@@ -2,7 +2,7 @@ use elyze::acceptor::Acceptor;
 use elyze::bytes::primitives::number::Number;
 use elyze::bytes::primitives::string::DataString;
 use elyze::bytes::token::Token;
-use elyze::errors::ParseError::UnexpectedToken;
+use elyze::errors::ParseError;
 use elyze::errors::ParseResult;
 use elyze::recognizer::recognize;
 use elyze::scanner::Scanner;
@@ -54,10 +54,11 @@ impl<'a> Visitor<'a, u8> for TupleColor {
 
 impl<'a> Visitor<'a, u8> for RgbColor {
     fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
         let prefix = DataString::<&str>::accept(scanner)?.0;
 
         if prefix != "rgb" {
-            return Err(UnexpectedToken);
+            return Err(ParseError::unexpected_token(start).expecting("`rgb`"));
         }
 
         // recognize the rgb color start "("
@@ -104,7 +105,7 @@ impl<'a> Visitor<'a, u8> for Color {
             .try_or(ColorInternal::Rgb)?
             .try_or(ColorInternal::Tuple)?
             .finish()
-            .ok_or(UnexpectedToken)?;
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
         Ok(color.into())
     }
 }
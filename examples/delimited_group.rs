@@ -3,8 +3,8 @@ use elyze::peek::peek;
 
 fn main() {
     let data = b"(2 * 3)";
-    let mut scanner = elyze::scanner::Scanner::new(data);
-    let result = peek(GroupKind::Parenthesis, &mut scanner)
+    let scanner = elyze::scanner::Scanner::new(data);
+    let result = peek(GroupKind::Parenthesis, &scanner)
         .expect("failed to parse")
         .expect("failed to peek");
     println!(
@@ -39,7 +39,7 @@ impl<'a> Visitor<'a, u8> for Reducted {
             .try_or(BinaryOperator::Add)?
             .try_or(BinaryOperator::Mul)?
             .finish()
-            .ok_or(ParseError::UnexpectedToken)?;
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
         OptionalWhitespaces::accept(scanner)?;
         let rhs = Number::accept(scanner)?.0;
         OptionalWhitespaces::accept(scanner)?;
@@ -67,7 +67,7 @@ impl<'a> Visitor<'a, u8> for RightExpression {
             .try_or(BinaryOperator::Add)?
             .try_or(BinaryOperator::Mul)?
             .finish()
-            .ok_or(ParseError::UnexpectedToken)?;
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
         OptionalWhitespaces::accept(scanner)?;
         let rhs = Expression::accept(scanner)?;
         OptionalWhitespaces::accept(scanner)?;
@@ -178,7 +178,7 @@ impl<'a> Visitor<'a, u8> for Expression {
                     .try_or(ExpressionInternal::RightExpression)?
                     .try_or(ExpressionInternal::Reducted)?
                     .finish()
-                    .ok_or(ParseError::UnexpectedToken)?;
+                    .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
                 Ok(accepted.into())
             }
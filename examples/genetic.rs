@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use elyze::bytes::matchers::match_pattern;
 use elyze::matcher::Match;
 use rand::prelude::Distribution;
@@ -276,5 +278,5 @@ fn main() {
         .sample_iter(&mut rng)
         .take(16)
         .collect::<Vec<AminoAcid>>();
-    let animo_acid = dbg!(protein);
+    let _animo_acid = dbg!(protein);
 }
@@ -35,7 +35,7 @@ fn main() -> ParseResult<()> {
         .try_or(OperatorTokens::NotEqual)?
         .try_or(OperatorTokens::Equal)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken)?;
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
     println!("{:?}", recognized); // ==
 
@@ -45,7 +45,7 @@ fn main() -> ParseResult<()> {
         .try_or(OperatorTokens::NotEqual)?
         .try_or(OperatorTokens::Equal)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken)?;
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
     println!("{:?}", recognized); // !=
 
@@ -55,7 +55,7 @@ fn main() -> ParseResult<()> {
         .try_or(OperatorTokens::NotEqual)?
         .try_or(OperatorTokens::Equal)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken);
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()));
 
     println!("{:?}", recognized); // error (UnexpectedToken)
 
@@ -93,7 +93,7 @@ fn main() -> ParseResult<()> {
         .try_or(Operator::HelloWorld)?
         .try_or(Operator::Minus)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken)?;
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
     println!("{:?}", accepted); // +
 
@@ -104,7 +104,7 @@ fn main() -> ParseResult<()> {
         .try_or(Operator::HelloWorld)?
         .try_or(Operator::Minus)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken)?;
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
     println!("{:?}", accepted); // -
 
@@ -115,7 +115,7 @@ fn main() -> ParseResult<()> {
         .try_or(Operator::HelloWorld)?
         .try_or(Operator::Minus)?
         .finish()
-        .ok_or(ParseError::UnexpectedToken)?;
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
     println!("{:?}", accepted); // HelloWorld
 
@@ -92,7 +92,7 @@ impl<'a> Visitor<'a, u8> for Operator {
             .try_or(Operator::HelloWorld)?
             .try_or(Operator::Minus)?
             .finish()
-            .ok_or(ParseError::UnexpectedToken)
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
     }
 }
 
@@ -25,7 +25,7 @@ struct ParenthesesGroup;
 impl<'a> Peekable<'a, u8> for ParenthesesGroup {
     fn peek(&self, scanner: &Scanner<'a, u8>) -> ParseResult<PeekResult> {
         // create an internal scanner allowing to peek data without alterating the original scanner
-        let mut inner_scanner = Scanner::new(&scanner.remaining());
+        let mut inner_scanner = Scanner::new(scanner.remaining());
 
         // loop on each byte until we find a close parenthesis
         loop {
@@ -1,7 +1,8 @@
+use elyze::bytes::token::Token;
 use elyze::errors::{ParseError, ParseResult};
 use elyze::matcher::Match;
 use elyze::peek::{
-    peek, DefaultPeekableImplementation, PeekResult, Peekable, PeekableImplementation,
+    peek, DefaultPeekableImplementation, FirstOf, PeekResult, Peekable, PeekableImplementation,
 };
 use elyze::peeker::Peeker;
 use elyze::recognizer::Recognizer;
@@ -35,11 +36,11 @@ impl Match<u8> for OperatorTokens {
 
 impl<'a> Visitor<'a, u8> for OperatorTokens {
     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
-        Ok(Recognizer::new(scanner)
+        Recognizer::new(scanner)
             .try_or(OperatorTokens::Times)?
             .try_or(OperatorTokens::Plus)?
             .finish()
-            .ok_or(ParseError::UnexpectedToken)?)
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
     }
 }
 
@@ -81,5 +82,13 @@ fn main() -> ParseResult<()> {
         println!("{:?}", String::from_utf8_lossy(slice.peeked_slice())); // "1 "
     }
 
+    // the same peeking composed inline
+    let data = b"1 + 2 * 7";
+    let scanner = Scanner::new(data);
+    let slice = peek(FirstOf((Token::Plus, Token::Star)), &scanner)?;
+    if let Some(slice) = slice {
+        println!("{:?}", String::from_utf8_lossy(slice.peeked_slice())); // "1 "
+    }
+
     Ok(())
 }
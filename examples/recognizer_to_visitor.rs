@@ -45,7 +45,7 @@ impl<'a> Visitor<'a, u8> for OperatorData {
             .try_or(Operator::Sub)?
             .finish()
             // If the recognizer fails, return an error
-            .ok_or(ParseError::UnexpectedToken)?;
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
 
         Ok(OperatorData(operator))
     }
@@ -68,7 +68,10 @@ fn main() -> ParseResult<()> {
     let mut scanner = Scanner::new(data);
     // Initialize the recognizer
     let result = OperatorData::accept(&mut scanner);
-    assert!(matches!(result, Err(ParseError::UnexpectedToken))); // Err(UnexpectedToken)
+    assert!(matches!(
+        result,
+        Err(ParseError::UnexpectedToken { offset: 0, .. })
+    )); // Err(UnexpectedToken)
 
     Ok(())
 }
@@ -1,7 +1,7 @@
 use elyze::bytes::primitives::number::{Number, TokenNumber};
 use elyze::bytes::token::Token;
 use elyze::errors::ParseResult;
-use elyze::peek::{DefaultPeekableImplementation, PeekSize, PeekableImplementation};
+use elyze::peek::{DefaultPeekableImplementation, PeekableImplementation};
 use elyze::recognizer::recognize;
 use elyze::scanner::Scanner;
 use elyze::separated_list::{get_scanner_without_trailing_separator, SeparatedList};
@@ -23,12 +23,6 @@ impl<'a> Visitor<'a, u8> for Separator {
     }
 }
 
-impl PeekSize<u8> for Separator {
-    fn peek_size(&self) -> usize {
-        3
-    }
-}
-
 #[derive(Debug)]
 struct NumberList {
     #[allow(dead_code)]
@@ -38,7 +32,7 @@ struct NumberList {
 impl<'a> Visitor<'a, u8> for NumberList {
     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
         let mut data_scanner =
-            get_scanner_without_trailing_separator(TokenNumber, Separator, &scanner)?;
+            get_scanner_without_trailing_separator(TokenNumber, Separator, scanner)?;
 
         let data = SeparatedList::<u8, Number<usize>, Separator>::accept(&mut data_scanner)?
             .data
@@ -0,0 +1,61 @@
+//! Exercise the cursor arithmetic of the scanner.
+//!
+//! Random cursor moves are applied to a scanner, and the components moving
+//! the cursor internally are run at each position. Nothing may panic and the
+//! cursor must always stay in the bounds of the data.
+#![no_main]
+
+use elyze::bytes::components::groups::GroupKind;
+use elyze::bytes::token::Token;
+use elyze::peek::{peek, Last};
+use elyze::recognizer::recognize_slice;
+use elyze::scanner::Scanner;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Vec<(bool, u8)>, &[u8])| {
+    let (moves, data) = input;
+    let mut scanner = Scanner::new(data);
+
+    for (forward, n) in moves {
+        let position = scanner.current_position();
+        let n = n as usize;
+        let result = if forward {
+            scanner.advance_checked(n)
+        } else {
+            scanner.rewind_checked(n)
+        };
+
+        match result {
+            Ok(()) if forward => assert_eq!(scanner.current_position(), position + n),
+            Ok(()) => assert_eq!(scanner.current_position(), position - n),
+            // a failed move must leave the cursor untouched
+            Err(_) => assert_eq!(scanner.current_position(), position),
+        }
+        assert!(scanner.current_position() <= data.len());
+        let position = scanner.current_position();
+
+        let mut jumped = scanner.clone();
+        match jumped.jump_to_checked(n) {
+            Ok(()) => assert_eq!(jumped.current_position(), n),
+            Err(_) => assert_eq!(jumped.current_position(), position),
+        }
+
+        // peeking must never alter the cursor
+        for kind in [
+            GroupKind::Parenthesis,
+            GroupKind::Quotes,
+            GroupKind::DoubledDoubleQuotes,
+            GroupKind::RustRawString,
+            GroupKind::Tag,
+        ] {
+            let _ = peek(kind, &scanner);
+        }
+        let _ = peek(Last::new(Token::Pipe), &scanner);
+        assert_eq!(scanner.current_position(), position);
+
+        let mut inner_scanner = scanner.clone();
+        if recognize_slice(Token::CrLn, &mut inner_scanner).is_ok() {
+            assert!(inner_scanner.current_position() <= data.len());
+        }
+    }
+});
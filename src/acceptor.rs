@@ -1,7 +1,9 @@
 //! A helper type for scanning and accepting values.
 
-use crate::errors::{ParseError, ParseResult};
+use crate::combinators::is_recoverable;
+use crate::errors::ParseResult;
 use crate::scanner::Scanner;
+use crate::span::{Span, Spanned};
 use crate::visitor::Visitor;
 
 /// A type that wraps a `Scanner` and holds a successfully accepted value.
@@ -71,7 +73,7 @@ impl<'a, T, V> Acceptor<'a, '_, T, V> {
             Ok(found) => {
                 self.data = Some(transformer(found));
             }
-            Err(ParseError::UnexpectedToken | ParseError::UnexpectedEndOfInput) => {
+            Err(err) if is_recoverable(&err) => {
                 self.scanner.jump_to(cursor);
             }
             Err(err) => {
@@ -93,3 +95,240 @@ impl<'a, T, V> Acceptor<'a, '_, T, V> {
         self.data
     }
 }
+
+/// An acceptor keeping all the alternatives accepted at the same position,
+/// for the inputs a grammar can't disambiguate.
+///
+/// Each alternative is tried from the start position, whatever the previous
+/// ones accepted. The accepted values form a parse forest, the caller picks
+/// one of them with its own semantic rules, then the scanner moves after it.
+/// The forest is bounded: once `limit` alternatives are accepted, the next
+/// ones aren't tried.
+///
+/// # Example
+///
+/// ```
+/// use elyze::acceptor::ForestAcceptor;
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::primitives::string::DataString;
+/// use elyze::scanner::Scanner;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Word<'a> {
+///     Year(u16),
+///     Tag(&'a str),
+/// }
+///
+/// // `2024rc1` starts with a year or is a tag
+/// let mut scanner = Scanner::new(b"2024rc1 rest");
+/// let forest = ForestAcceptor::new(&mut scanner, 4)
+///     .try_all(|Number(year)| Word::Year(year))
+///     .expect("failed to accept")
+///     .try_all(|DataString(tag)| Word::Tag(tag))
+///     .expect("failed to accept");
+/// assert_eq!(forest.alternatives().len(), 2);
+///
+/// // prefer the longest alternative
+/// let word = forest.select(|alternatives| {
+///     (0..alternatives.len()).max_by_key(|index| alternatives[*index].span.len())
+/// });
+/// assert_eq!(word, Some(Word::Tag("2024rc1")));
+/// assert_eq!(scanner.remaining(), b" rest");
+/// ```
+#[derive(Debug)]
+pub struct ForestAcceptor<'a, 'b, T, V> {
+    /// The accepted values and their spans, in the order of the alternatives
+    alternatives: Vec<Spanned<V>>,
+    /// The maximal number of accepted alternatives
+    limit: usize,
+    /// The position every alternative starts from
+    start: usize,
+    /// The scanner to use when consuming input
+    scanner: &'b mut Scanner<'a, T>,
+}
+
+impl<'a, 'b, T, V> ForestAcceptor<'a, 'b, T, V> {
+    /// Create a new forest acceptor.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to use when consuming input.
+    /// * `limit` - The maximal number of alternatives to accept.
+    ///
+    /// # Returns
+    ///
+    /// A new forest acceptor whose alternatives start at the current position
+    /// of the scanner.
+    pub fn new(scanner: &'b mut Scanner<'a, T>, limit: usize) -> ForestAcceptor<'a, 'b, T, V> {
+        ForestAcceptor {
+            alternatives: vec![],
+            limit,
+            start: scanner.current_position(),
+            scanner,
+        }
+    }
+}
+
+impl<'a, T, V> ForestAcceptor<'a, '_, T, V> {
+    /// Attempt to accept a `U` from the start position using the given
+    /// `transformer`, and keep the resulting value if it succeeds.
+    ///
+    /// The scanner is always rewound to the start position.
+    ///
+    /// # Arguments
+    ///
+    /// * `transformer` - A function that takes a `U` and returns a `V`.
+    ///
+    /// # Errors
+    ///
+    /// The errors of `U` other than a mismatch, an unexpected token or end of
+    /// input.
+    pub fn try_all<U: Visitor<'a, T>, F>(mut self, transformer: F) -> ParseResult<Self>
+    where
+        F: Fn(U) -> V,
+    {
+        if self.alternatives.len() >= self.limit {
+            return Ok(self);
+        }
+
+        let result = U::accept(self.scanner);
+        let end = self.scanner.current_position();
+        self.scanner.jump_to(self.start);
+        match result {
+            Ok(found) => self.alternatives.push(Spanned {
+                value: transformer(found),
+                span: Span::new(self.start, end),
+            }),
+            Err(err) if is_recoverable(&err) => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(self)
+    }
+
+    /// Return the accepted values and their spans, in the order of the
+    /// alternatives
+    pub fn alternatives(&self) -> &[Spanned<V>] {
+        &self.alternatives
+    }
+
+    /// Consume the acceptor and return all the accepted values and their
+    /// spans, the scanner staying at the start position.
+    pub fn finish(self) -> Vec<Spanned<V>> {
+        self.alternatives
+    }
+
+    /// Consume the acceptor, keep one of the accepted values and move the
+    /// scanner after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `choose` - A function receiving the accepted values and returning
+    ///   the index of the one to keep, if any.
+    ///
+    /// # Returns
+    ///
+    /// The chosen value, `None` if no value is chosen, the scanner then
+    /// staying at the start position.
+    pub fn select<F>(mut self, choose: F) -> Option<V>
+    where
+        F: FnOnce(&[Spanned<V>]) -> Option<usize>,
+    {
+        let index = choose(&self.alternatives)?;
+        if index >= self.alternatives.len() {
+            return None;
+        }
+        let chosen = self.alternatives.swap_remove(index);
+        self.scanner.jump_to(chosen.span.end);
+        Some(chosen.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acceptor::ForestAcceptor;
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::primitives::string::DataString;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::scanner::Scanner;
+    use crate::span::Span;
+    use crate::visitor::Visitor;
+
+    /// A visitor failing with an error which isn't a mismatch
+    struct Unbalanced;
+
+    impl<'a> Visitor<'a, u8> for Unbalanced {
+        fn accept(_scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            Err(ParseError::UnbalancedGroup)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Word {
+        Number(u32),
+        Text(String),
+    }
+
+    #[test]
+    fn test_forest_acceptor() {
+        let mut scanner = Scanner::new(b"42abc rest");
+        let alternatives = ForestAcceptor::new(&mut scanner, 8)
+            .try_all(|Number(number)| Word::Number(number))
+            .expect("failed to accept")
+            .try_all(|DataString(text): DataString<String>| Word::Text(text))
+            .expect("failed to accept")
+            .try_all(|Number(number): Number<u8>| Word::Number(number as u32))
+            .expect("failed to accept")
+            .finish();
+        let spans = alternatives
+            .iter()
+            .map(|alternative| alternative.span)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            vec![Span::new(0, 2), Span::new(0, 5), Span::new(0, 2)]
+        );
+        assert_eq!(alternatives[1].value, Word::Text("42abc".to_string()));
+        assert_eq!(scanner.current_position(), 0);
+
+        // the forest is bounded
+        let alternatives = ForestAcceptor::new(&mut scanner, 1)
+            .try_all(|Number(number)| Word::Number(number))
+            .expect("failed to accept")
+            .try_all(|Unbalanced| Word::Number(0))
+            .expect("failed to accept")
+            .finish();
+        assert_eq!(alternatives.len(), 1);
+
+        assert!(matches!(
+            ForestAcceptor::new(&mut scanner, 1).try_all(|Unbalanced| Word::Number(0)),
+            Err(ParseError::UnbalancedGroup)
+        ));
+    }
+
+    #[test]
+    fn test_forest_acceptor_select() {
+        let mut scanner = Scanner::new(b"42abc rest");
+        let forest = ForestAcceptor::new(&mut scanner, 8)
+            .try_all(|Number(number)| Word::Number(number))
+            .expect("failed to accept")
+            .try_all(|DataString(text): DataString<String>| Word::Text(text))
+            .expect("failed to accept");
+        assert_eq!(forest.select(|_| None), None);
+        assert_eq!(scanner.current_position(), 0);
+
+        let forest = ForestAcceptor::new(&mut scanner, 8)
+            .try_all(|Number(number)| Word::Number(number))
+            .expect("failed to accept")
+            .try_all(|DataString(text): DataString<String>| Word::Text(text))
+            .expect("failed to accept");
+        // prefer a number
+        let word = forest.select(|alternatives| {
+            alternatives
+                .iter()
+                .position(|alternative| matches!(alternative.value, Word::Number(_)))
+        });
+        assert_eq!(word, Some(Word::Number(42)));
+        assert_eq!(scanner.remaining(), b"abc rest");
+    }
+}
@@ -0,0 +1,175 @@
+//! Compile a set of literal alternatives into a prefix tree.
+
+use crate::matcher::Match;
+
+/// A node of the prefix tree
+#[derive(Debug, Clone)]
+struct Node<T> {
+    /// The next elements and the index of their node
+    children: Vec<(T, usize)>,
+    /// The index of the alternative ending at this node, if any
+    terminal: Option<usize>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Node {
+            children: vec![],
+            terminal: None,
+        }
+    }
+}
+
+/// A matcher recognizing one of several literal alternatives.
+///
+/// The alternatives are compiled once into a decision tree factoring their
+/// common prefixes, so matching scans each element of the input at most once
+/// instead of trying every alternative in turn.
+///
+/// When several alternatives match, the longest one wins, so `<=` is
+/// preferred over `<`.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::matcher::Match;
+///
+/// let keywords = CompiledAlternatives::compile(&["in", "int", "if"]);
+/// assert_eq!(keywords.find(b"int x"), Some((1, 3)));
+/// assert_eq!(keywords.is_matching(b"if x"), (true, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledAlternatives<T> {
+    /// The nodes of the tree, the root is the first one
+    nodes: Vec<Node<T>>,
+    /// The size of the shortest alternative
+    min_size: usize,
+}
+
+impl<T: PartialEq + Clone> CompiledAlternatives<T> {
+    /// Compile the alternatives into a prefix tree.
+    ///
+    /// Empty alternatives are ignored. If an alternative is given several
+    /// times, the first index is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `alternatives` - The literal alternatives to recognize.
+    pub fn compile<A: AsRef<[T]>>(alternatives: &[A]) -> Self {
+        let mut nodes = vec![Node::default()];
+        let mut min_size = None;
+
+        for (index, alternative) in alternatives.iter().enumerate() {
+            let alternative = alternative.as_ref();
+            if alternative.is_empty() {
+                continue;
+            }
+            let mut current = 0;
+            for element in alternative {
+                let next = nodes[current]
+                    .children
+                    .iter()
+                    .find(|(child, _)| child == element)
+                    .map(|(_, next)| *next);
+                current = match next {
+                    Some(next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[current].children.push((element.clone(), next));
+                        next
+                    }
+                };
+            }
+            nodes[current].terminal.get_or_insert(index);
+            min_size =
+                Some(min_size.map_or(alternative.len(), |min: usize| min.min(alternative.len())));
+        }
+
+        CompiledAlternatives {
+            nodes,
+            min_size: min_size.unwrap_or(0),
+        }
+    }
+
+    /// Find the longest alternative at the start of the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to match against.
+    ///
+    /// # Returns
+    ///
+    /// `Some((index, size))` with the index of the alternative in the
+    /// compiled list and its size, `None` if no alternative matches.
+    pub fn find(&self, data: &[T]) -> Option<(usize, usize)> {
+        let mut current = 0;
+        let mut found = None;
+        for (position, element) in data.iter().enumerate() {
+            let next = self.nodes[current]
+                .children
+                .iter()
+                .find(|(child, _)| child == element);
+            let Some((_, next)) = next else {
+                break;
+            };
+            current = *next;
+            if let Some(index) = self.nodes[current].terminal {
+                found = Some((index, position + 1));
+            }
+        }
+        found
+    }
+}
+
+impl<T: PartialEq + Clone> Match<T> for CompiledAlternatives<T> {
+    fn is_matching(&self, data: &[T]) -> (bool, usize) {
+        match self.find(data) {
+            Some((_, size)) => (true, size),
+            None => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.min_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alternatives::CompiledAlternatives;
+    use crate::matcher::Match;
+    use crate::recognizer::recognize_slice;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_compiled_alternatives() {
+        let operators = CompiledAlternatives::compile(&["<", "<=", "<<", "=", "=="]);
+        assert_eq!(operators.find(b"<= 2"), Some((1, 2)));
+        assert_eq!(operators.find(b"< 2"), Some((0, 1)));
+        assert_eq!(operators.find(b"<<"), Some((2, 2)));
+        assert_eq!(operators.find(b"=="), Some((4, 2)));
+        assert_eq!(operators.find(b"> 2"), None);
+        assert_eq!(operators.find(b""), None);
+        assert_eq!(operators.size(), 1);
+    }
+
+    #[test]
+    fn test_compiled_alternatives_partial_prefix() {
+        // "for" is a prefix of the data but "forall" is not complete
+        let keywords = CompiledAlternatives::compile(&["forall", "for"]);
+        assert_eq!(keywords.find(b"foral"), Some((1, 3)));
+        assert_eq!(keywords.find(b"fo"), None);
+        assert_eq!(keywords.size(), 3);
+    }
+
+    #[test]
+    fn test_compiled_alternatives_recognize() {
+        let keywords = CompiledAlternatives::compile(&["SELECT", "SET", "FROM"]);
+        let mut scanner = Scanner::new(b"SET x");
+        let slice = recognize_slice(keywords, &mut scanner).expect("failed to parse");
+        assert_eq!(slice, b"SET");
+        assert_eq!(scanner.remaining(), b" x");
+    }
+}
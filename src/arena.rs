@@ -0,0 +1,242 @@
+//! Allocate the parsed values in an arena.
+//!
+//! A large syntax tree built with `Box` and `Vec` allocates each of its
+//! nodes. Visiting the data with [parse_in], the [ArenaVisitor]s can move
+//! their nodes into a [Bump] arena with [InArena], the whole tree being freed
+//! at once with the arena.
+//!
+//! The arena isn't stored in the [Scanner], which stays `Send` and `Sync`:
+//! it's passed along the scanner to [ArenaVisitor::accept_in], each visitor
+//! handing it to the visitors of its nodes with [Scanner::visit_in].
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+
+pub use bumpalo::Bump;
+
+/// Trait for visitors allocating their values in an arena.
+///
+/// The nodes of a syntax tree not needing the arena, such as its leaves,
+/// are visited as usual with [Visitor](crate::visitor::Visitor).
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data to visit.
+///
+/// # Associated Functions
+///
+/// * `accept_in` - Try to accept the `Scanner`, allocating in the arena, and
+///   return the result of the visit.
+pub trait ArenaVisitor<'a, T>: Sized {
+    /// Try to accept the `Scanner` and return the result of the visit.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to accept.
+    /// * `arena` - The arena the values are allocated in.
+    ///
+    /// # Returns
+    ///
+    /// The result of the visit.
+    fn accept_in(scanner: &mut Scanner<'a, T>, arena: &'a Bump) -> ParseResult<Self>;
+}
+
+impl<'a, T> Scanner<'a, T> {
+    /// Visit the scanner with an arena visitor.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `V` - The type of the visitor to run.
+    ///
+    /// # Arguments
+    ///
+    /// * `arena` - The arena the values are allocated in.
+    ///
+    /// # Returns
+    ///
+    /// The result of the visit.
+    pub fn visit_in<V: ArenaVisitor<'a, T>>(&mut self, arena: &'a Bump) -> ParseResult<V> {
+        V::accept_in(self, arena)
+    }
+}
+
+/// A visited value moved into the arena.
+///
+/// The value is allocated by the arena rather than by a `Box`, which makes
+/// recursive nodes cheap.
+///
+/// The arena never runs the destructors of its values, a value owning heap
+/// data such as a `Vec` or a `String` would leak. The visitor is therefore
+/// required not to need dropping, which is checked at compile time:
+///
+/// ```compile_fail
+/// use elyze::arena::{ArenaVisitor, Bump, InArena};
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::combinators::Many0;
+/// use elyze::errors::ParseResult;
+/// use elyze::scanner::Scanner;
+///
+/// struct Numbers(Vec<Number<u8>>);
+///
+/// impl<'a> ArenaVisitor<'a, u8> for Numbers {
+///     fn accept_in(scanner: &mut Scanner<'a, u8>, _arena: &'a Bump) -> ParseResult<Self> {
+///         Ok(Numbers(scanner.visit::<Many0<Number<u8>>>()?.0))
+///     }
+/// }
+///
+/// let arena = Bump::new();
+/// let _ = Scanner::new(b"1").visit_in::<InArena<Numbers>>(&arena);
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use elyze::arena::{parse_in, ArenaVisitor, Bump, InArena};
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::token::Token;
+/// use elyze::errors::ParseResult;
+/// use elyze::recognizer::recognize;
+/// use elyze::scanner::Scanner;
+///
+/// /// A sum `1+2+3`, right-associated
+/// enum Sum<'a> {
+///     Number(u32),
+///     Add(u32, InArena<'a, Sum<'a>>),
+/// }
+///
+/// impl<'a> ArenaVisitor<'a, u8> for Sum<'a> {
+///     fn accept_in(scanner: &mut Scanner<'a, u8>, arena: &'a Bump) -> ParseResult<Self> {
+///         let Number(left) = scanner.visit()?;
+///         if recognize(Token::Plus, scanner).is_err() {
+///             return Ok(Sum::Number(left));
+///         }
+///         Ok(Sum::Add(left, scanner.visit_in(arena)?))
+///     }
+/// }
+///
+/// fn total(sum: &Sum) -> u32 {
+///     match sum {
+///         Sum::Number(number) => *number,
+///         Sum::Add(left, right) => left + total(right),
+///     }
+/// }
+///
+/// let arena = Bump::new();
+/// let sum = parse_in::<u8, Sum>(&arena, b"1+2+3").expect("failed to parse");
+/// assert_eq!(total(&sum), 6);
+/// assert!(arena.allocated_bytes() > 0);
+/// ```
+pub struct InArena<'a, V>(pub &'a V);
+
+impl<V: Debug> Debug for InArena<'_, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InArena").field(self.0).finish()
+    }
+}
+
+impl<V> Clone for InArena<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for InArena<'_, V> {}
+
+impl<V: PartialEq> PartialEq for InArena<'_, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<V> Deref for InArena<'_, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        self.0
+    }
+}
+
+impl<'a, T, V: ArenaVisitor<'a, T>> ArenaVisitor<'a, T> for InArena<'a, V> {
+    fn accept_in(scanner: &mut Scanner<'a, T>, arena: &'a Bump) -> ParseResult<Self> {
+        const {
+            assert!(
+                !std::mem::needs_drop::<V>(),
+                "the values of an arena are never dropped"
+            )
+        };
+        let value = scanner.visit_in::<V>(arena)?;
+        Ok(InArena(arena.alloc(value)))
+    }
+}
+
+/// Parse the data with the arena visitor, the values of [InArena] being
+/// allocated in the arena.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data
+/// * `V` - The visitor
+///
+/// # Arguments
+///
+/// * `arena` - The arena, living as long as the data
+/// * `data` - The data to parse
+///
+/// # Errors
+///
+/// The error of the visitor, or an `UnexpectedToken` error if the visitor
+/// doesn't consume the whole data.
+pub fn parse_in<'a, T, V: ArenaVisitor<'a, T>>(arena: &'a Bump, data: &'a [T]) -> ParseResult<V> {
+    let mut scanner = Scanner::new(data);
+    let value = scanner.visit_in::<V>(arena)?;
+    if !scanner.is_empty() {
+        return Err(ParseError::unexpected_token(scanner.current_position()));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arena::{parse_in, ArenaVisitor, Bump, InArena};
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_arena_visitor() {
+        /// A list `1;2;3`, each element pointing to the next one
+        struct List<'a>(Number<u8>, Option<InArena<'a, List<'a>>>);
+
+        impl<'a> ArenaVisitor<'a, u8> for List<'a> {
+            fn accept_in(scanner: &mut Scanner<'a, u8>, arena: &'a Bump) -> ParseResult<Self> {
+                let head = scanner.visit()?;
+                if recognize(Token::Semicolon, scanner).is_err() {
+                    return Ok(List(head, None));
+                }
+                Ok(List(head, Some(scanner.visit_in(arena)?)))
+            }
+        }
+
+        let arena = Bump::new();
+        let list = parse_in::<u8, List>(&arena, b"1;2;3").expect("failed to parse");
+        assert_eq!(list.0, Number(1));
+        let InArena(next) = list.1.expect("no next element");
+        assert_eq!(next.0, Number(2));
+        let InArena(last) = next.1.expect("no last element");
+        assert_eq!(last.0, Number(3));
+        assert!(last.1.is_none());
+        assert!(arena.allocated_bytes() > 0);
+        assert!(matches!(
+            parse_in::<u8, List>(&arena, b"1;2;"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            parse_in::<u8, List>(&arena, b"1;2 3"),
+            Err(ParseError::UnexpectedToken { offset: 3, .. })
+        ));
+    }
+}
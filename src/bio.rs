@@ -0,0 +1,445 @@
+//! Nucleotide sequence matchers.
+//!
+//! The IUPAC codes stand for a nucleotide or a set of nucleotides, `N` being
+//! any of them. A [Motif] made of these codes matches the sequences on their
+//! forward strand, and an [EitherStrand] also on their reverse complement.
+//!
+//! Available with the `bio` feature.
+//!
+//! ```
+//! use elyze::bio::{EitherStrand, Motif};
+//! use elyze::search::find_all;
+//!
+//! // the EcoRII restriction site
+//! let motif = Motif::new(b"CCWGG").expect("invalid motif");
+//! let sites = find_all(&motif, b"ACCAGGTTCCTGGA").collect::<Vec<_>>();
+//! assert_eq!(sites, vec![(1, 5), (8, 5)]);
+//!
+//! let motif = EitherStrand::new(Motif::new(b"GATN").expect("invalid motif"));
+//! let sites = find_all(&motif, b"GATCxCATC").collect::<Vec<_>>();
+//! assert_eq!(sites, vec![(0, 4), (5, 4)]);
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::Match;
+use std::slice::ChunksExact;
+
+/// The size of a codon
+const CODON_SIZE: usize = 3;
+
+/// A nucleotide IUPAC code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nucleotide {
+    /// Adenine
+    A,
+    /// Cytosine
+    C,
+    /// Guanine
+    G,
+    /// Thymine
+    T,
+    /// Uracil, equivalent to the thymine
+    U,
+    /// A purine: A or G
+    R,
+    /// A pyrimidine: C or T
+    Y,
+    /// A strong base: G or C
+    S,
+    /// A weak base: A or T
+    W,
+    /// A keto base: G or T
+    K,
+    /// An amino base: A or C
+    M,
+    /// Not A: C, G or T
+    B,
+    /// Not C: A, G or T
+    D,
+    /// Not G: A, C or T
+    H,
+    /// Not T: A, C or G
+    V,
+    /// Any base
+    N,
+}
+
+impl Nucleotide {
+    /// Decode an IUPAC code, regardless of its case
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        let nucleotide = match byte.to_ascii_uppercase() {
+            b'A' => Nucleotide::A,
+            b'C' => Nucleotide::C,
+            b'G' => Nucleotide::G,
+            b'T' => Nucleotide::T,
+            b'U' => Nucleotide::U,
+            b'R' => Nucleotide::R,
+            b'Y' => Nucleotide::Y,
+            b'S' => Nucleotide::S,
+            b'W' => Nucleotide::W,
+            b'K' => Nucleotide::K,
+            b'M' => Nucleotide::M,
+            b'B' => Nucleotide::B,
+            b'D' => Nucleotide::D,
+            b'H' => Nucleotide::H,
+            b'V' => Nucleotide::V,
+            b'N' => Nucleotide::N,
+            _ => return None,
+        };
+        Some(nucleotide)
+    }
+
+    /// Return the uppercase IUPAC code
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Nucleotide::A => b'A',
+            Nucleotide::C => b'C',
+            Nucleotide::G => b'G',
+            Nucleotide::T => b'T',
+            Nucleotide::U => b'U',
+            Nucleotide::R => b'R',
+            Nucleotide::Y => b'Y',
+            Nucleotide::S => b'S',
+            Nucleotide::W => b'W',
+            Nucleotide::K => b'K',
+            Nucleotide::M => b'M',
+            Nucleotide::B => b'B',
+            Nucleotide::D => b'D',
+            Nucleotide::H => b'H',
+            Nucleotide::V => b'V',
+            Nucleotide::N => b'N',
+        }
+    }
+
+    /// The set of bases of the code, one bit per base: A, C, G and T or U
+    fn bases(self) -> u8 {
+        const A: u8 = 1;
+        const C: u8 = 2;
+        const G: u8 = 4;
+        const T: u8 = 8;
+        match self {
+            Nucleotide::A => A,
+            Nucleotide::C => C,
+            Nucleotide::G => G,
+            Nucleotide::T | Nucleotide::U => T,
+            Nucleotide::R => A | G,
+            Nucleotide::Y => C | T,
+            Nucleotide::S => G | C,
+            Nucleotide::W => A | T,
+            Nucleotide::K => G | T,
+            Nucleotide::M => A | C,
+            Nucleotide::B => C | G | T,
+            Nucleotide::D => A | G | T,
+            Nucleotide::H => A | C | T,
+            Nucleotide::V => A | C | G,
+            Nucleotide::N => A | C | G | T,
+        }
+    }
+
+    /// Return the complementary code, the uracil being complemented by the
+    /// adenine
+    pub fn complement(self) -> Self {
+        match self {
+            Nucleotide::A => Nucleotide::T,
+            Nucleotide::C => Nucleotide::G,
+            Nucleotide::G => Nucleotide::C,
+            Nucleotide::T | Nucleotide::U => Nucleotide::A,
+            Nucleotide::R => Nucleotide::Y,
+            Nucleotide::Y => Nucleotide::R,
+            Nucleotide::S => Nucleotide::S,
+            Nucleotide::W => Nucleotide::W,
+            Nucleotide::K => Nucleotide::M,
+            Nucleotide::M => Nucleotide::K,
+            Nucleotide::B => Nucleotide::V,
+            Nucleotide::V => Nucleotide::B,
+            Nucleotide::D => Nucleotide::H,
+            Nucleotide::H => Nucleotide::D,
+            Nucleotide::N => Nucleotide::N,
+        }
+    }
+
+    /// Whether every base the other code stands for is one of this code
+    ///
+    /// An ambiguous code in the data only matches a code at least as
+    /// ambiguous: `N` matches `R`, but `R` doesn't match `N`.
+    pub fn includes(self, other: Nucleotide) -> bool {
+        other.bases() & !self.bases() == 0
+    }
+}
+
+impl Match<u8> for Nucleotide {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        match data.first().copied().and_then(Nucleotide::from_byte) {
+            Some(nucleotide) if self.includes(nucleotide) => (true, 1),
+            _ => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// Return the complement of a base, its case kept, the bytes which are not
+/// IUPAC codes being kept as is
+fn complement_byte(byte: u8) -> u8 {
+    match Nucleotide::from_byte(byte) {
+        Some(nucleotide) if byte.is_ascii_lowercase() => {
+            nucleotide.complement().to_byte().to_ascii_lowercase()
+        }
+        Some(nucleotide) => nucleotide.complement().to_byte(),
+        None => byte,
+    }
+}
+
+/// Return the reverse complement of a sequence
+///
+/// The case of the bases is kept, the bytes which are not IUPAC codes, like
+/// the gaps, are kept as is.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bio::reverse_complement;
+///
+/// assert_eq!(reverse_complement(b"AACGtn-"), b"-naCGTT");
+/// ```
+pub fn reverse_complement(sequence: &[u8]) -> Vec<u8> {
+    sequence
+        .iter()
+        .rev()
+        .copied()
+        .map(complement_byte)
+        .collect()
+}
+
+/// A sequence of IUPAC codes matching the sequences on their forward strand.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Motif {
+    /// The codes of the motif
+    codes: Vec<Nucleotide>,
+}
+
+impl Motif {
+    /// Create a new `Motif`
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The IUPAC codes of the motif, in any case
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` at the first byte which is not an IUPAC
+    /// code.
+    pub fn new(pattern: &[u8]) -> ParseResult<Self> {
+        let codes = pattern
+            .iter()
+            .enumerate()
+            .map(|(position, byte)| {
+                Nucleotide::from_byte(*byte).ok_or_else(|| {
+                    ParseError::unexpected_token(position).expecting("an IUPAC nucleotide code")
+                })
+            })
+            .collect::<ParseResult<_>>()?;
+        Ok(Motif { codes })
+    }
+
+    /// Return the codes of the motif
+    pub fn codes(&self) -> &[Nucleotide] {
+        &self.codes
+    }
+
+    /// Return the motif matching the reverse strand
+    pub fn reverse_complement(&self) -> Self {
+        Motif {
+            codes: self
+                .codes
+                .iter()
+                .rev()
+                .map(|code| code.complement())
+                .collect(),
+        }
+    }
+}
+
+impl Match<u8> for Motif {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        if data.len() < self.codes.len() {
+            return (false, 0);
+        }
+        let matching = self
+            .codes
+            .iter()
+            .zip(data)
+            .all(|(code, byte)| code.is_matching(std::slice::from_ref(byte)).0);
+        if matching {
+            (true, self.codes.len())
+        } else {
+            (false, 0)
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.codes.len()
+    }
+}
+
+/// The strand of a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    /// The sequence as written
+    Forward,
+    /// The reverse complement of the sequence
+    Reverse,
+}
+
+/// A [Motif] matching the sequences on their forward strand or on their
+/// reverse complement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EitherStrand {
+    /// The motif of the forward strand
+    forward: Motif,
+    /// The motif of the reverse strand
+    reverse: Motif,
+}
+
+impl EitherStrand {
+    /// Create a new `EitherStrand`
+    ///
+    /// # Arguments
+    ///
+    /// * `motif` - The motif of the forward strand
+    pub fn new(motif: Motif) -> Self {
+        EitherStrand {
+            reverse: motif.reverse_complement(),
+            forward: motif,
+        }
+    }
+
+    /// Return the strand on which the motif matches the start of the data,
+    /// the forward strand winning for the palindromic motifs
+    pub fn strand(&self, data: &[u8]) -> Option<Strand> {
+        if self.forward.is_matching(data).0 {
+            Some(Strand::Forward)
+        } else if self.reverse.is_matching(data).0 {
+            Some(Strand::Reverse)
+        } else {
+            None
+        }
+    }
+}
+
+impl Match<u8> for EitherStrand {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        match self.strand(data) {
+            Some(_) => (true, self.size()),
+            None => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.forward.size()
+    }
+}
+
+/// Iterate over the codons of a reading frame
+///
+/// The incomplete codon at the end of the sequence is skipped. The frames of
+/// the reverse strand are read from the [reverse_complement] of the
+/// sequence.
+///
+/// # Arguments
+///
+/// * `sequence` - The sequence to read
+/// * `frame` - The number of bases skipped before the first codon, 0, 1 or 2
+///
+/// # Example
+///
+/// ```
+/// use elyze::bio::codons;
+///
+/// let codons = codons(b"AUGGCCUAAG", 1).collect::<Vec<_>>();
+/// assert_eq!(codons, vec![b"UGG", b"CCU", b"AAG"]);
+/// ```
+pub fn codons(sequence: &[u8], frame: usize) -> ChunksExact<'_, u8> {
+    sequence
+        .get(frame..)
+        .unwrap_or_default()
+        .chunks_exact(CODON_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bio::{codons, reverse_complement, EitherStrand, Motif, Nucleotide, Strand};
+    use crate::errors::ParseError;
+    use crate::matcher::Match;
+    use crate::search::find_all;
+
+    #[test]
+    fn test_nucleotide() {
+        assert!(Nucleotide::N.is_matching(b"a").0);
+        assert!(Nucleotide::R.is_matching(b"G").0);
+        assert!(!Nucleotide::R.is_matching(b"C").0);
+        assert!(Nucleotide::T.is_matching(b"u").0);
+        assert!(Nucleotide::N.is_matching(b"Y").0);
+        assert!(!Nucleotide::R.is_matching(b"N").0);
+        assert!(!Nucleotide::N.is_matching(b"-").0);
+        assert!(!Nucleotide::N.is_matching(b"").0);
+
+        for byte in b"ACGTURYSWKMBDHVN" {
+            let nucleotide = Nucleotide::from_byte(*byte).expect("invalid code");
+            assert_eq!(nucleotide.to_byte(), *byte);
+            // the complement of the complement is the code, the uracil excepted
+            if nucleotide != Nucleotide::U {
+                assert_eq!(nucleotide.complement().complement(), nucleotide);
+            }
+        }
+    }
+
+    #[test]
+    fn test_motif() {
+        let motif = Motif::new(b"tAnnRy").expect("invalid motif");
+        assert_eq!(motif.is_matching(b"TAGCGCxx"), (true, 6));
+        assert_eq!(motif.is_matching(b"TAGCGG"), (false, 0));
+        assert_eq!(motif.is_matching(b"TAGCG"), (false, 0));
+        assert_eq!(
+            motif.reverse_complement(),
+            Motif::new(b"RYNNTA").expect("invalid motif")
+        );
+        assert!(matches!(
+            Motif::new(b"ACXT"),
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_either_strand() {
+        let motif = EitherStrand::new(Motif::new(b"AAC").expect("invalid motif"));
+        assert_eq!(motif.strand(b"AACG"), Some(Strand::Forward));
+        assert_eq!(motif.strand(b"GTTG"), Some(Strand::Reverse));
+        assert_eq!(motif.strand(b"GTAA"), None);
+
+        let data = b"AACxGTTxACG";
+        let sites = find_all(&motif, data).collect::<Vec<_>>();
+        assert_eq!(sites, vec![(0, 3), (4, 3)]);
+
+        // a sequence and its reverse complement have the same sites
+        let reversed = reverse_complement(data);
+        assert_eq!(find_all(&motif, &reversed).count(), 2);
+    }
+
+    #[test]
+    fn test_codons() {
+        let sequence = b"ATGAAATAG";
+        assert_eq!(
+            codons(sequence, 0).collect::<Vec<_>>(),
+            vec![b"ATG", b"AAA", b"TAG"]
+        );
+        assert_eq!(codons(sequence, 2).count(), 2);
+        assert_eq!(codons(sequence, 12).count(), 0);
+
+        let reversed = reverse_complement(sequence);
+        assert_eq!(reversed, b"CTATTTCAT");
+        assert_eq!(codons(&reversed, 0).next(), Some(&b"CTA"[..]));
+    }
+}
@@ -0,0 +1,159 @@
+//! Fixed-width integers of binary data.
+//!
+//! The text primitives read the digits of a number, the visitors of this
+//! module read the bytes of an integer encoded in a binary format, with an
+//! explicit byte order: [Be] for big-endian, the network byte order, and
+//! [Le] for little-endian.
+//!
+//! # Example
+//!
+//! ```
+//! use elyze::bytes::binary::{Be, Le};
+//! use elyze::errors::ParseResult;
+//! use elyze::scanner::Scanner;
+//! use elyze::visitor::Visitor;
+//!
+//! /// A frame header: a big-endian kind and a little-endian length
+//! struct Header {
+//!     kind: u16,
+//!     length: u32,
+//! }
+//!
+//! impl<'a> Visitor<'a, u8> for Header {
+//!     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+//!         let Be(kind) = scanner.visit()?;
+//!         let Le(length) = scanner.visit()?;
+//!         Ok(Header { kind, length })
+//!     }
+//! }
+//!
+//! let mut scanner = Scanner::new(&[0x01, 0x02, 0x10, 0x00, 0x00, 0x00, 0xff]);
+//! let header: Header = scanner.visit().expect("failed to parse");
+//! assert_eq!((header.kind, header.length), (0x0102, 16));
+//! assert_eq!(scanner.remaining(), [0xff]);
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+
+/// A big-endian integer, its most significant byte first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Be<T>(pub T);
+
+/// A little-endian integer, its least significant byte first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Le<T>(pub T);
+
+/// Read the bytes of an integer, the scanner being left unchanged if there
+/// aren't enough bytes
+fn read_bytes<const N: usize>(scanner: &mut Scanner<u8>) -> ParseResult<[u8; N]> {
+    let bytes = *scanner
+        .remaining()
+        .first_chunk::<N>()
+        .ok_or(ParseError::UnexpectedEndOfInput)?;
+    scanner.bump_by(N);
+    Ok(bytes)
+}
+
+/// Implement the `Visitor` trait for the integers in both byte orders.
+macro_rules! impl_binary {
+    ($type:ty) => {
+        impl Visitor<'_, u8> for Be<$type> {
+            fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+                Ok(Be(<$type>::from_be_bytes(read_bytes(scanner)?)))
+            }
+        }
+
+        impl Validate<'_, u8> for Be<$type> {}
+
+        impl Visitor<'_, u8> for Le<$type> {
+            fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+                Ok(Le(<$type>::from_le_bytes(read_bytes(scanner)?)))
+            }
+        }
+
+        impl Validate<'_, u8> for Le<$type> {}
+    };
+}
+
+impl_binary!(u8);
+impl_binary!(u16);
+impl_binary!(u32);
+impl_binary!(u64);
+impl_binary!(u128);
+impl_binary!(i8);
+impl_binary!(i16);
+impl_binary!(i32);
+impl_binary!(i64);
+impl_binary!(i128);
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::binary::{Be, Le};
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_binary() {
+        let data = [0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+        assert_eq!(Scanner::new(&data).visit::<Be<u8>>().ok(), Some(Be(0x80)));
+        assert_eq!(
+            Scanner::new(&data).visit::<Le<i8>>().ok(),
+            Some(Le(i8::MIN))
+        );
+        assert_eq!(
+            Scanner::new(&data).visit::<Be<u16>>().ok(),
+            Some(Be(0x8000))
+        );
+        assert_eq!(
+            Scanner::new(&data).visit::<Le<u16>>().ok(),
+            Some(Le(0x0080))
+        );
+        assert_eq!(
+            Scanner::new(&data).visit::<Be<i32>>().ok(),
+            Some(Be(i32::MIN))
+        );
+        assert_eq!(Scanner::new(&data).visit::<Le<u32>>().ok(), Some(Le(0x80)));
+        assert_eq!(
+            Scanner::new(&data).visit::<Be<u64>>().ok(),
+            Some(Be(0x8000_0000_0000_0001))
+        );
+        assert_eq!(
+            Scanner::new(&data).visit::<Le<i64>>().ok(),
+            Some(Le(0x0100_0000_0000_0080))
+        );
+        assert_eq!(
+            Scanner::new(&[0xff; 16]).visit::<Le<i128>>().ok(),
+            Some(Le(-1))
+        );
+        assert_eq!(
+            Scanner::new(&[0xff; 16]).visit::<Be<u128>>().ok(),
+            Some(Be(u128::MAX))
+        );
+
+        let mut scanner = Scanner::new(&data);
+        let Be(high) = scanner.visit::<Be<u32>>().expect("failed to parse");
+        let Le(low) = scanner.visit::<Le<u32>>().expect("failed to parse");
+        assert_eq!((high, low), (0x8000_0000, 0x0100_0000));
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_binary_end_of_input() {
+        let mut scanner = Scanner::new(&[0x01, 0x02, 0x03]);
+        assert!(matches!(
+            scanner.visit::<Be<u32>>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+        let Le(value) = scanner.visit::<Le<u16>>().expect("failed to parse");
+        assert_eq!(value, 0x0201);
+        assert!(matches!(
+            scanner.visit::<Le<u16>>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.remaining(), [0x03]);
+    }
+}
@@ -1,2 +1,9 @@
+pub mod address;
+pub mod call;
+pub mod comments;
+pub mod directives;
+pub mod documents;
 pub mod groups;
+pub mod object;
+pub mod top_level;
 mod until_end;
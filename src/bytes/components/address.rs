@@ -0,0 +1,457 @@
+//! Postal address line components
+//!
+//! Split a free form address line, as typed by a human, into its street
+//! number, street name, unit, postal code and locality. The splitting is
+//! lenient: a missing part is left empty and the unknown words end up in the
+//! street name or the locality, so dirty data can still be cleaned.
+//!
+//! How an address is written depends on its country, the conventions are
+//! described by an [AddressProfile].
+
+use crate::bytes::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::peek::{PeekResult, Peekable};
+use crate::peeker::Peeker;
+use crate::scanner::Scanner;
+
+/// Whether the byte separates the parts of an address
+fn is_separator(byte: &u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b',')
+}
+
+/// Whether a word may start at the position of the data
+fn is_word_start(data: &[u8], position: usize) -> bool {
+    position == 0 || is_separator(&data[position - 1])
+}
+
+/// Whether a word may end at the position of the data
+fn is_word_end(data: &[u8], position: usize) -> bool {
+    data.get(position)
+        .is_none_or(|byte| is_separator(byte) || *byte == b'.')
+}
+
+/// Whether the data starts with the postal code pattern, `9` standing for a
+/// digit and `A` for a letter
+fn is_postal_code(pattern: &str, data: &[u8]) -> bool {
+    data.len() >= pattern.len()
+        && pattern
+            .bytes()
+            .zip(data)
+            .all(|(expected, byte)| match expected {
+                b'9' => byte.is_ascii_digit(),
+                b'A' => byte.is_ascii_alphabetic(),
+                _ => expected == *byte,
+            })
+}
+
+/// The conventions of the addresses of a country.
+///
+/// The profiles of a few countries are provided, others can be described by
+/// building an `AddressProfile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressProfile {
+    /// Whether the street number follows the street name, `Hauptstraße 5`
+    pub number_after_street: bool,
+    /// The words which may follow a street number, `bis` in `12 bis`
+    pub number_suffixes: &'static [&'static str],
+    /// The words introducing a unit, compared case-insensitively
+    pub unit_markers: &'static [&'static str],
+    /// The shapes of the postal codes, `9` standing for a digit and `A` for a
+    /// letter, the longest first
+    pub postal_codes: &'static [&'static str],
+}
+
+impl AddressProfile {
+    /// United States: `123 Main St Apt 4B, Springfield, IL 62704`
+    pub const US: AddressProfile = AddressProfile {
+        number_after_street: false,
+        number_suffixes: &[],
+        unit_markers: &[
+            "Apartment",
+            "Apt",
+            "Suite",
+            "Ste",
+            "Unit",
+            "Floor",
+            "Fl",
+            "Room",
+            "Rm",
+            "#",
+        ],
+        postal_codes: &["99999-9999", "99999"],
+    };
+
+    /// France: `12 bis rue de la Paix, Bât B, 75002 Paris`
+    pub const FR: AddressProfile = AddressProfile {
+        number_after_street: false,
+        number_suffixes: &["bis", "ter", "quater"],
+        unit_markers: &[
+            "Appartement",
+            "Appt",
+            "Bâtiment",
+            "Bât",
+            "Étage",
+            "Escalier",
+        ],
+        postal_codes: &["99999"],
+    };
+
+    /// Germany: `Hauptstraße 5, Whg 3, 10115 Berlin`
+    pub const DE: AddressProfile = AddressProfile {
+        number_after_street: true,
+        number_suffixes: &[],
+        unit_markers: &["Wohnung", "Whg", "Zimmer"],
+        postal_codes: &["99999"],
+    };
+
+    /// United Kingdom: `Flat 3, 10 Downing Street, London SW1A 2AA`
+    pub const UK: AddressProfile = AddressProfile {
+        number_after_street: false,
+        number_suffixes: &[],
+        unit_markers: &["Apartment", "Flat", "Suite", "Unit"],
+        postal_codes: &[
+            "AA9A 9AA", "AA99 9AA", "A9A 9AA", "AA9 9AA", "A99 9AA", "A9 9AA",
+        ],
+    };
+
+    /// Return the size of the unit marker starting the data
+    fn unit_marker(&self, data: &[u8]) -> Option<usize> {
+        self.unit_markers
+            .iter()
+            .map(|marker| marker.as_bytes())
+            .find(|marker| {
+                data.len() >= marker.len()
+                    && data[..marker.len()].eq_ignore_ascii_case(marker)
+                    // `#4` is a unit, `Stephen` isn't
+                    && (is_word_end(data, marker.len())
+                        || data[marker.len()].is_ascii_digit())
+            })
+            .map(|marker| marker.len())
+    }
+
+    /// Return the size of the postal code starting the data
+    fn postal_code(&self, data: &[u8]) -> Option<usize> {
+        self.postal_codes
+            .iter()
+            .find(|pattern| is_postal_code(pattern, data) && is_word_end(data, pattern.len()))
+            .map(|pattern| pattern.len())
+    }
+
+    /// Return the size of the street number starting the data
+    fn street_number(&self, data: &[u8]) -> Option<usize> {
+        let mut size = data.iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if size == 0 {
+            return None;
+        }
+        // a range of numbers, `12-14`
+        if data.get(size) == Some(&b'-') && data.get(size + 1).is_some_and(u8::is_ascii_digit) {
+            size += 1 + data[size + 1..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+        }
+        // a letter, `12B`
+        if data.get(size).is_some_and(u8::is_ascii_alphabetic) && is_word_end(data, size + 1) {
+            size += 1;
+        }
+        // a suffix, `12 bis` or `12bis`
+        let spaces = data[size..]
+            .iter()
+            .take_while(|byte| **byte == b' ')
+            .count();
+        let rest = &data[size + spaces..];
+        if let Some(suffix) = self.number_suffixes.iter().find(|suffix| {
+            rest.len() >= suffix.len()
+                && rest[..suffix.len()].eq_ignore_ascii_case(suffix.as_bytes())
+                && is_word_end(rest, suffix.len())
+        }) {
+            size += spaces + suffix.len();
+        }
+        is_word_end(data, size).then_some(size)
+    }
+}
+
+/// Peek the first unit marker of a profile
+struct UnitMarker(AddressProfile);
+
+impl<'a> Peekable<'a, u8> for UnitMarker {
+    fn peek(&self, scanner: &Scanner<'a, u8>) -> ParseResult<PeekResult> {
+        let data = scanner.remaining();
+        for position in (0..data.len()).filter(|position| is_word_start(data, *position)) {
+            if let Some(size) = self.0.unit_marker(&data[position..]) {
+                return Ok(PeekResult::Found {
+                    end_slice: position + size,
+                    start_element_size: 0,
+                    end_element_size: size,
+                });
+            }
+        }
+        Ok(PeekResult::NotFound)
+    }
+}
+
+/// Peek the first postal code of a profile
+struct PostalCode(AddressProfile);
+
+impl<'a> Peekable<'a, u8> for PostalCode {
+    fn peek(&self, scanner: &Scanner<'a, u8>) -> ParseResult<PeekResult> {
+        let data = scanner.remaining();
+        for position in (0..data.len()).filter(|position| is_word_start(data, *position)) {
+            if let Some(size) = self.0.postal_code(&data[position..]) {
+                return Ok(PeekResult::Found {
+                    end_slice: position + size,
+                    start_element_size: 0,
+                    end_element_size: size,
+                });
+            }
+        }
+        Ok(PeekResult::NotFound)
+    }
+}
+
+/// Return the size of the segment starting the scanner, up to the next comma,
+/// unit marker or postal code
+fn segment(scanner: &Scanner<u8>, profile: &AddressProfile) -> ParseResult<usize> {
+    let peeking = Peeker::new(scanner)
+        .add_peekable(Token::Comma)
+        .add_peekable(UnitMarker(*profile))
+        .add_peekable(PostalCode(*profile))
+        .peek()?;
+    Ok(peeking.map_or(scanner.remaining().len(), |peeking| {
+        peeking.peeked_slice().len()
+    }))
+}
+
+/// Consume the separators
+fn skip_separators(scanner: &mut Scanner<u8>) {
+    scanner.take_while(is_separator);
+}
+
+/// An address line split into its parts.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::components::address::{AddressLine, AddressProfile};
+///
+/// let address = AddressLine::parse(
+///     b"123 Main St Apt 4B, Springfield, IL 62704",
+///     &AddressProfile::US,
+/// )
+/// .expect("failed to parse");
+/// assert_eq!(address.street_number, Some("123"));
+/// assert_eq!(address.street, "Main St");
+/// assert_eq!(address.unit, Some("Apt 4B"));
+/// assert_eq!(address.postal_code, Some("62704"));
+/// assert_eq!(address.locality, Some("Springfield, IL"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AddressLine<'a> {
+    /// The street number and its suffix, `12 bis`
+    pub street_number: Option<&'a str>,
+    /// The name of the street
+    pub street: &'a str,
+    /// The unit and its marker, `Apt 4B`
+    pub unit: Option<&'a str>,
+    /// The postal code
+    pub postal_code: Option<&'a str>,
+    /// The text around the postal code, usually the city
+    pub locality: Option<&'a str>,
+}
+
+impl<'a> AddressLine<'a> {
+    /// Split an address line.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The address line
+    /// * `profile` - The conventions of the country of the address
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` if there is no street name,
+    /// `ParseError::Utf8Error` if the line isn't valid UTF-8.
+    pub fn parse(data: &'a [u8], profile: &AddressProfile) -> ParseResult<Self> {
+        let line = std::str::from_utf8(data)?;
+        let text = |start: usize, end: usize| line[start..end].trim_matches([' ', '\t', ',']);
+        let mut scanner = Scanner::new(data);
+        let mut unit = None;
+        let mut postal_code = None;
+
+        // the unit may come first, `Flat 3, 10 Downing Street`
+        let unit_segment = |scanner: &mut Scanner<u8>, size: usize| -> ParseResult<&'a str> {
+            let start = scanner.current_position();
+            scanner.bump_by(size);
+            let size = segment(scanner, profile)?;
+            scanner.bump_by(size);
+            Ok(text(start, scanner.current_position()))
+        };
+        skip_separators(&mut scanner);
+        if let Some(size) = profile.unit_marker(scanner.remaining()) {
+            unit = Some(unit_segment(&mut scanner, size)?);
+            skip_separators(&mut scanner);
+        }
+
+        let mut street_number = None;
+        if !profile.number_after_street
+            && let Some(size) = profile.street_number(scanner.remaining())
+        {
+            let start = scanner.current_position();
+            scanner.bump_by(size);
+            street_number = Some(&line[start..start + size]);
+            skip_separators(&mut scanner);
+        }
+
+        let street_start = scanner.current_position();
+        let size = segment(&scanner, profile)?;
+        scanner.bump_by(size);
+        let mut street = text(street_start, scanner.current_position());
+        if profile.number_after_street
+            && let Some((name, number)) = street.rsplit_once(' ')
+            && profile.street_number(number.as_bytes()) == Some(number.len())
+        {
+            street = name.trim_end();
+            street_number = Some(number);
+        }
+        if street.is_empty() {
+            return Err(ParseError::unexpected_token(street_start).expecting("a street"));
+        }
+
+        // the locality spans the segments around the postal code
+        let mut locality: Option<(usize, usize)> = None;
+        let mut locality_closed = false;
+        loop {
+            skip_separators(&mut scanner);
+            let remaining = scanner.remaining();
+            if remaining.is_empty() {
+                break;
+            }
+            let start = scanner.current_position();
+            if unit.is_none()
+                && let Some(size) = profile.unit_marker(remaining)
+            {
+                unit = Some(unit_segment(&mut scanner, size)?);
+                continue;
+            }
+            if postal_code.is_none()
+                && let Some(size) = profile.postal_code(remaining)
+            {
+                scanner.bump_by(size);
+                postal_code = Some(&line[start..start + size]);
+                locality_closed = locality.is_some();
+                continue;
+            }
+            let size = segment(&scanner, profile)?.max(1);
+            scanner.bump_by(size);
+            let end = scanner.current_position();
+            match &mut locality {
+                None => locality = Some((start, end)),
+                Some((_, locality_end)) if !locality_closed => *locality_end = end,
+                Some(_) => {}
+            }
+        }
+
+        Ok(AddressLine {
+            street_number,
+            street,
+            unit,
+            postal_code,
+            locality: locality
+                .map(|(start, end)| text(start, end))
+                .filter(|locality| !locality.is_empty()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::components::address::{AddressLine, AddressProfile};
+    use crate::errors::ParseError;
+
+    #[test]
+    fn test_address_line() {
+        for (data, profile, expected) in [
+            (
+                "12 bis rue de la Paix, Bât B, 75002 Paris",
+                AddressProfile::FR,
+                AddressLine {
+                    street_number: Some("12 bis"),
+                    street: "rue de la Paix",
+                    unit: Some("Bât B"),
+                    postal_code: Some("75002"),
+                    locality: Some("Paris"),
+                },
+            ),
+            (
+                "Hauptstraße 5a, 10115 Berlin",
+                AddressProfile::DE,
+                AddressLine {
+                    street_number: Some("5a"),
+                    street: "Hauptstraße",
+                    unit: None,
+                    postal_code: Some("10115"),
+                    locality: Some("Berlin"),
+                },
+            ),
+            (
+                "Flat 3, 10 Downing Street, London SW1A 2AA",
+                AddressProfile::UK,
+                AddressLine {
+                    street_number: Some("10"),
+                    street: "Downing Street",
+                    unit: Some("Flat 3"),
+                    postal_code: Some("SW1A 2AA"),
+                    locality: Some("London"),
+                },
+            ),
+            (
+                "  1600 Pennsylvania Ave NW #4, Washington, DC 20500-0003, USA",
+                AddressProfile::US,
+                AddressLine {
+                    street_number: Some("1600"),
+                    street: "Pennsylvania Ave NW",
+                    unit: Some("#4"),
+                    postal_code: Some("20500-0003"),
+                    locality: Some("Washington, DC"),
+                },
+            ),
+            (
+                "Main Street",
+                AddressProfile::US,
+                AddressLine {
+                    street_number: None,
+                    street: "Main Street",
+                    unit: None,
+                    postal_code: None,
+                    locality: None,
+                },
+            ),
+            (
+                "12-14 Stephen Rd,Springfield",
+                AddressProfile::US,
+                AddressLine {
+                    street_number: Some("12-14"),
+                    street: "Stephen Rd",
+                    unit: None,
+                    postal_code: None,
+                    locality: Some("Springfield"),
+                },
+            ),
+        ] {
+            assert_eq!(
+                AddressLine::parse(data.as_bytes(), &profile).expect("failed to parse"),
+                expected,
+                "{data:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_line_without_street() {
+        assert!(matches!(
+            AddressLine::parse(b"12, 75002 Paris", &AddressProfile::FR),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert!(AddressLine::parse(b"", &AddressProfile::US).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Callable syntax components
+//!
+//! Parse a call like `name(arg1, arg2, ...)`, the arguments are split on the
+//! top level commas, see [TopLevelSeparator].
+
+use crate::bytes::components::groups::GroupKind;
+use crate::bytes::components::top_level::TopLevelSeparator;
+use crate::bytes::primitives::whitespace::OptionalWhitespaces;
+use crate::bytes::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::peek::peek;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::marker::PhantomData;
+
+/// A call of a callable: `name(arg1, arg2, ...)`.
+///
+/// The name is recognized by the `N` visitor and each argument by the `A`
+/// visitor, which must consume the whole argument, surrounding whitespaces
+/// excepted. Whitespaces are allowed between the name and the parenthesis.
+///
+/// A call may have no argument, `name()`, and its argument list may end with
+/// a trailing comma, `name(a, b,)`, unless `TRAILING_COMMA` is `false`.
+///
+/// # Type Parameters
+///
+/// * `N` - The visitor recognizing the name
+/// * `A` - The visitor of the arguments
+/// * `TRAILING_COMMA` - Whether a trailing comma is allowed
+pub struct Call<'a, N, A, const TRAILING_COMMA: bool = true> {
+    /// The name of the callable
+    pub name: &'a [u8],
+    /// The arguments of the call
+    pub args: Vec<A>,
+    _name: PhantomData<N>,
+}
+
+/// Parse the arguments of a call, without their parentheses.
+///
+/// # Errors
+///
+/// Returns `Err(ParseError::UnexpectedToken)` if an argument is empty or is
+/// not entirely consumed by its visitor, and any error of the visitor. The
+/// positions of the errors are relative to the data.
+fn accept_arguments<'a, A: Visitor<'a, u8>>(
+    data: &'a [u8],
+    trailing_comma: bool,
+) -> ParseResult<Vec<A>> {
+    let splitter = TopLevelSeparator::new(Token::Comma);
+    let mut args = vec![];
+    // the position of the current argument in the data
+    let mut start = 0;
+    loop {
+        let remaining = &data[start..];
+        let (arg, next) = match splitter.find(remaining)? {
+            Some((position, size)) => (&remaining[..position], Some(start + position + size)),
+            None => (remaining, None),
+        };
+
+        let arg_start = start + arg.len() - arg.trim_ascii_start().len();
+        let arg = arg.trim_ascii();
+        if arg.is_empty() {
+            return match next {
+                // no argument at all
+                None if args.is_empty() => Ok(args),
+                // trailing comma
+                None if trailing_comma => Ok(args),
+                _ => Err(ParseError::unexpected_token(arg_start).expecting("an argument")),
+            };
+        }
+
+        let mut scanner = Scanner::new(arg);
+        args.push(A::accept(&mut scanner).map_err(|err| err.shifted(arg_start))?);
+        if !scanner.is_empty() {
+            let err = ParseError::unexpected_token(arg_start + scanner.current_position());
+            return Err(err.expecting("`,`"));
+        }
+
+        match next {
+            Some(next) => start = next,
+            None => return Ok(args),
+        }
+    }
+}
+
+/// Parse a call, the scanner is left in an undefined position on error.
+fn call<'a, N, A>(
+    scanner: &mut Scanner<'a, u8>,
+    trailing_comma: bool,
+) -> ParseResult<(&'a [u8], Vec<A>)>
+where
+    N: Visitor<'a, u8>,
+    A: Visitor<'a, u8>,
+{
+    let start = scanner.current_position();
+    N::accept(scanner)?;
+    let name = scanner.since(start);
+    OptionalWhitespaces::accept(scanner)?;
+
+    let group = peek(GroupKind::Parenthesis, scanner)?
+        .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()).expecting("`(`"))?;
+    let args_start = scanner.current_position() + group.start_element_size;
+    let args = accept_arguments(group.peeked_slice(), trailing_comma)
+        .map_err(|err| err.shifted(args_start))?;
+    scanner.advance_checked(group.end_slice)?;
+    Ok((name, args))
+}
+
+impl<'a, N, A, const TRAILING_COMMA: bool> Visitor<'a, u8> for Call<'a, N, A, TRAILING_COMMA>
+where
+    N: Visitor<'a, u8>,
+    A: Visitor<'a, u8>,
+{
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let (name, args) = call::<N, A>(&mut checkpoint, TRAILING_COMMA)?;
+        checkpoint.commit();
+        Ok(Call {
+            name,
+            args,
+            _name: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acceptor::Acceptor;
+    use crate::bytes::components::call::Call;
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::matcher::Match;
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    struct Identifier;
+
+    impl Match<u8> for Identifier {
+        fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+            let size = data.iter().take_while(|b| b.is_ascii_alphabetic()).count();
+            (size > 0, size)
+        }
+
+        fn size(&self) -> usize {
+            0
+        }
+    }
+
+    impl<'a> Visitor<'a, u8> for Identifier {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            recognize(Identifier, scanner)
+        }
+    }
+
+    enum Expression<'a> {
+        Number(usize),
+        Call(Call<'a, Identifier, Expression<'a>>),
+    }
+
+    impl<'a> Visitor<'a, u8> for Expression<'a> {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            Acceptor::new(scanner)
+                .try_or(|Number(number)| Expression::Number(number))?
+                .try_or(Expression::Call)?
+                .finish()
+                .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+        }
+    }
+
+    #[test]
+    fn test_call() {
+        let mut scanner = Scanner::new(b"add(1, 22 ,3) + 4");
+        let call = scanner
+            .visit::<Call<Identifier, Number<usize>>>()
+            .expect("failed to parse");
+        assert_eq!(call.name, b"add");
+        assert_eq!(call.args, vec![Number(1), Number(22), Number(3)]);
+        assert_eq!(scanner.remaining(), b" + 4");
+
+        let call = Scanner::new(b"now ()")
+            .visit::<Call<Identifier, Number<usize>>>()
+            .expect("failed to parse");
+        assert_eq!(call.name, b"now");
+        assert!(call.args.is_empty());
+    }
+
+    #[test]
+    fn test_call_trailing_comma() {
+        let call = Scanner::new(b"f(1, 2, )")
+            .visit::<Call<Identifier, Number<usize>>>()
+            .expect("failed to parse");
+        assert_eq!(call.args, vec![Number(1), Number(2)]);
+
+        let mut scanner = Scanner::new(b"f(1, 2,)");
+        let result = scanner.visit::<Call<Identifier, Number<usize>, false>>();
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 7, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        for (data, offset) in [
+            (&b"f(,)"[..], 2),
+            (b"f(1,,2)", 4),
+            (b"f(,1)", 2),
+            (b"f(1 2)", 3),
+            (b"f[1]", 1),
+        ] {
+            let result = Scanner::new(data).visit::<Call<Identifier, Number<usize>>>();
+            assert!(
+                matches!(result, Err(ParseError::UnexpectedToken { offset: o, .. }) if o == offset),
+                "unexpected result for {:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+
+        let result = Scanner::new(b"f").visit::<Call<Identifier, Number<usize>>>();
+        assert!(matches!(result, Err(ParseError::UnexpectedEndOfInput)));
+    }
+
+    #[test]
+    fn test_nested_call() {
+        let call = Scanner::new(b"f(g(1, 2), h(), 3)")
+            .visit::<Call<Identifier, Expression>>()
+            .expect("failed to parse");
+        assert_eq!(call.name, b"f");
+        assert!(matches!(
+            call.args.as_slice(),
+            [
+                Expression::Call(g),
+                Expression::Call(h),
+                Expression::Number(3)
+            ] if g.name == b"g" && g.args.len() == 2 && h.name == b"h" && h.args.is_empty()
+        ));
+    }
+}
@@ -0,0 +1,325 @@
+//! Comment components
+//!
+//! Recognize and skip the line comments, like `// comment` or `# comment`,
+//! and the block comments, like `/* comment */`, of a language.
+//!
+//! Both components implement [Match], so they can be recognized like any
+//! token. Their `skip` method also reports an unterminated block comment,
+//! which a matcher can only see as a mismatch.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::Match;
+use crate::scanner::Scanner;
+
+/// Return the size of the line ending starting the data, if any
+fn line_ending(data: &[u8]) -> Option<usize> {
+    match data {
+        [b'\n', ..] => Some(1),
+        [b'\r', b'\n', ..] => Some(2),
+        _ => None,
+    }
+}
+
+/// A comment running up to the end of the line.
+///
+/// The line ending isn't part of the comment. With a continuation, a
+/// backslash at the end of a line continues the comment on the next line,
+/// as in C.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::bytes::components::comments::LineComment;
+/// use elyze::scanner::Scanner;
+///
+/// let comment = LineComment::new(CompiledAlternatives::compile(&[b"//"]));
+/// let mut scanner = Scanner::new(b"// a comment\nlet a = 1;");
+/// let content = comment.skip(&mut scanner).expect("failed to parse");
+/// assert_eq!(content, Some(&b" a comment"[..]));
+/// assert_eq!(scanner.remaining(), b"\nlet a = 1;");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineComment<S> {
+    start: S,
+    continuation: bool,
+}
+
+impl<S: Match<u8>> LineComment<S> {
+    /// Create a new `LineComment`
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The marker starting the comment
+    pub fn new(start: S) -> Self {
+        LineComment {
+            start,
+            continuation: false,
+        }
+    }
+
+    /// Continue the comment on the next line after a backslash ending the
+    /// line
+    pub fn with_continuation(mut self) -> Self {
+        self.continuation = true;
+        self
+    }
+
+    /// Return the size of the comment starting the data, if any
+    fn find(&self, data: &[u8]) -> Option<usize> {
+        let (true, start_size) = self.start.is_matching(data) else {
+            return None;
+        };
+        let mut position = start_size;
+        while position < data.len() {
+            if let Some(size) = line_ending(&data[position..]) {
+                if !self.continuation || data[position - 1] != b'\\' {
+                    break;
+                }
+                position += size;
+                continue;
+            }
+            position += 1;
+        }
+        Some(position)
+    }
+
+    /// Skip a comment starting at the current position of the scanner.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to move after the comment
+    ///
+    /// # Returns
+    ///
+    /// The content of the comment without its marker, `None` if there is no
+    /// comment at the current position.
+    pub fn skip<'a>(&self, scanner: &mut Scanner<'a, u8>) -> ParseResult<Option<&'a [u8]>> {
+        let remaining = scanner.remaining();
+        let Some(size) = self.find(remaining) else {
+            return Ok(None);
+        };
+        scanner.advance_checked(size)?;
+        Ok(Some(&remaining[self.start.is_matching(remaining).1..size]))
+    }
+}
+
+impl<S: Match<u8>> Match<u8> for LineComment<S> {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        match self.find(data) {
+            Some(size) => (true, size),
+            None => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.start.size()
+    }
+}
+
+/// A comment enclosed in a start and an end marker.
+///
+/// By default the comment ends at the first end marker, as in C. When the
+/// comments are nested, as in Rust, each start marker of the content must
+/// be closed by its own end marker.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::bytes::components::comments::BlockComment;
+/// use elyze::errors::ParseError;
+/// use elyze::scanner::Scanner;
+///
+/// let start = CompiledAlternatives::compile(&[b"/*"]);
+/// let end = CompiledAlternatives::compile(&[b"*/"]);
+/// let comment = BlockComment::new(start, end).nested();
+/// let mut scanner = Scanner::new(b"/* a /* nested */ comment */ code");
+/// let content = comment.skip(&mut scanner).expect("failed to parse");
+/// assert_eq!(content, Some(&b" a /* nested */ comment "[..]));
+/// assert_eq!(scanner.remaining(), b" code");
+///
+/// let mut scanner = Scanner::new(b"/* unterminated");
+/// assert!(matches!(
+///     comment.skip(&mut scanner),
+///     Err(ParseError::UnexpectedEndOfInput)
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockComment<S, E> {
+    start: S,
+    end: E,
+    nested: bool,
+}
+
+impl<S: Match<u8>, E: Match<u8>> BlockComment<S, E> {
+    /// Create a new `BlockComment`
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The marker starting the comment
+    /// * `end` - The marker ending the comment
+    pub fn new(start: S, end: E) -> Self {
+        BlockComment {
+            start,
+            end,
+            nested: false,
+        }
+    }
+
+    /// Balance the comments nested in the comment
+    pub fn nested(mut self) -> Self {
+        self.nested = true;
+        self
+    }
+
+    /// Return the size of the comment starting the data and the size of its
+    /// end marker
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if the comment isn't terminated.
+    fn find(&self, data: &[u8]) -> ParseResult<Option<(usize, usize)>> {
+        let (true, start_size) = self.start.is_matching(data) else {
+            return Ok(None);
+        };
+        let mut depth = 1usize;
+        let mut position = start_size;
+        while position < data.len() {
+            let rest = &data[position..];
+            if let (true, size) = self.end.is_matching(rest) {
+                depth -= 1;
+                position += size;
+                if depth == 0 {
+                    return Ok(Some((position, size)));
+                }
+                continue;
+            }
+            if self.nested {
+                let (matching, size) = self.start.is_matching(rest);
+                if matching {
+                    depth += 1;
+                    position += size;
+                    continue;
+                }
+            }
+            position += 1;
+        }
+        Err(ParseError::UnexpectedEndOfInput)
+    }
+
+    /// Skip a comment starting at the current position of the scanner.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to move after the comment
+    ///
+    /// # Returns
+    ///
+    /// The content of the comment without its markers, `None` if there is no
+    /// comment at the current position.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if the comment isn't terminated.
+    pub fn skip<'a>(&self, scanner: &mut Scanner<'a, u8>) -> ParseResult<Option<&'a [u8]>> {
+        let remaining = scanner.remaining();
+        let Some((size, end_size)) = self.find(remaining)? else {
+            return Ok(None);
+        };
+        scanner.advance_checked(size)?;
+        Ok(Some(
+            &remaining[self.start.is_matching(remaining).1..size - end_size],
+        ))
+    }
+}
+
+impl<S: Match<u8>, E: Match<u8>> Match<u8> for BlockComment<S, E> {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        match self.find(data) {
+            Ok(Some((size, _))) => (true, size),
+            _ => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.start.size() + self.end.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alternatives::CompiledAlternatives;
+    use crate::bytes::components::comments::{BlockComment, LineComment};
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::matcher::Match;
+    use crate::recognizer::recognize_slice;
+    use crate::scanner::Scanner;
+
+    fn literal(literal: &[u8]) -> CompiledAlternatives<u8> {
+        CompiledAlternatives::compile(&[literal])
+    }
+
+    #[test]
+    fn test_line_comment() {
+        let comment = LineComment::new(Token::Hash);
+        let mut scanner = Scanner::new(b"# first\r\n# second");
+        assert_eq!(
+            comment.skip(&mut scanner).expect("failed to parse"),
+            Some(&b" first"[..])
+        );
+        assert_eq!(scanner.remaining(), b"\r\n# second");
+        assert_eq!(comment.skip(&mut scanner).expect("failed to parse"), None);
+        scanner.bump_by(2);
+        assert_eq!(
+            comment.skip(&mut scanner).expect("failed to parse"),
+            Some(&b" second"[..])
+        );
+        assert!(scanner.is_empty());
+
+        // the continued lines are part of the comment
+        let data = b"// a \\\n continued \\\r\n comment\ncode";
+        assert_eq!(
+            LineComment::new(literal(b"//")).is_matching(data),
+            (true, 6)
+        );
+        let comment = LineComment::new(literal(b"//")).with_continuation();
+        assert_eq!(comment.is_matching(data), (true, 29));
+        let mut scanner = Scanner::new(data);
+        assert_eq!(
+            recognize_slice(comment, &mut scanner).expect("failed to parse"),
+            &data[..29]
+        );
+        assert_eq!(scanner.remaining(), b"\ncode");
+    }
+
+    #[test]
+    fn test_block_comment() {
+        let comment = BlockComment::new(literal(b"/*"), literal(b"*/"));
+        let data = b"/* a /* b */ c */";
+        assert_eq!(comment.is_matching(data), (true, 12));
+        assert_eq!(comment.clone().nested().is_matching(data), (true, 17));
+        assert_eq!(comment.is_matching(b"/**/"), (true, 4));
+        assert_eq!(comment.is_matching(b"/*/"), (false, 0));
+        assert_eq!(comment.is_matching(b"a /* */"), (false, 0));
+
+        let mut scanner = Scanner::new(b"(* ML (* style *) *) let");
+        let comment = BlockComment::new(literal(b"(*"), literal(b"*)")).nested();
+        assert_eq!(
+            comment.skip(&mut scanner).expect("failed to parse"),
+            Some(&b" ML (* style *) "[..])
+        );
+        assert_eq!(scanner.remaining(), b" let");
+        assert_eq!(comment.skip(&mut scanner).expect("failed to parse"), None);
+
+        let mut scanner = Scanner::new(b"/* a /* b */");
+        assert!(matches!(
+            BlockComment::new(literal(b"/*"), literal(b"*/"))
+                .nested()
+                .skip(&mut scanner),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
@@ -0,0 +1,133 @@
+//! Directive comments components
+//!
+//! Extract the directive comments, like `#pragma once` or `// elyze: strict`,
+//! from a whole input without parsing it.
+
+use crate::matcher::Match;
+use crate::search::find_all;
+
+/// A directive comment found in an input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Directive<'a> {
+    /// The content of the directive after its marker, without surrounding
+    /// whitespaces
+    pub content: &'a [u8],
+    /// The position of the marker in the input
+    pub start: usize,
+    /// The position of the end of the directive, before the end of line
+    pub end: usize,
+}
+
+/// An iterator over the directive comments of an input.
+///
+/// A directive starts at the first occurrence of the marker of a line and
+/// ends at the end of this line.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::bytes::components::directives::directives;
+///
+/// let marker = CompiledAlternatives::compile(&[b"// elyze:"]);
+/// let data = b"// elyze: strict\nlet a = 1; // elyze: allow(unused)\n";
+/// let contents = directives(&marker, data)
+///     .map(|directive| directive.content)
+///     .collect::<Vec<_>>();
+/// assert_eq!(contents, vec![&b"strict"[..], b"allow(unused)"]);
+/// ```
+pub struct Directives<'a, M> {
+    marker: M,
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a, M: Match<u8>> Iterator for Directives<'a, M> {
+    type Item = Directive<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.data.len() {
+            let line_start = self.position;
+            let line_end = self.data[line_start..]
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(self.data.len(), |position| line_start + position);
+            self.position = line_end + 1;
+
+            let line = self.data[line_start..line_end].trim_ascii_end();
+            if let Some((offset, length)) = find_all(&self.marker, line).next() {
+                return Some(Directive {
+                    content: line[offset + length..].trim_ascii(),
+                    start: line_start + offset,
+                    end: line_start + line.len(),
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Find the directive comments of an input.
+///
+/// # Arguments
+///
+/// * `marker` - The marker starting a directive, like `#pragma`
+/// * `data` - The input to scan
+///
+/// # Returns
+///
+/// An iterator over the directives, see [Directives].
+pub fn directives<M: Match<u8>>(marker: M, data: &[u8]) -> Directives<'_, M> {
+    Directives {
+        marker,
+        data,
+        position: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alternatives::CompiledAlternatives;
+    use crate::bytes::components::directives::{directives, Directive};
+
+    #[test]
+    fn test_directives() {
+        let marker = CompiledAlternatives::compile(&[b"#pragma"]);
+        let data = b"#pragma once\r\nint a;\n  #pragma   pack(1)  \n#pragma\n#include <a.h>";
+        let found = directives(&marker, data).collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                Directive {
+                    content: b"once",
+                    start: 0,
+                    end: 12
+                },
+                Directive {
+                    content: b"pack(1)",
+                    start: 23,
+                    end: 40
+                },
+                Directive {
+                    content: b"",
+                    start: 43,
+                    end: 50
+                },
+            ]
+        );
+        assert_eq!(&data[23..40], b"#pragma   pack(1)");
+
+        assert_eq!(directives(&marker, b"int a;\n").next(), None);
+    }
+
+    #[test]
+    fn test_directives_several_markers() {
+        // only the first marker of a line starts a directive
+        let marker = CompiledAlternatives::compile(&[&b"// elyze:"[..], b"# elyze:"]);
+        let data = b"a # elyze: x // elyze: y\n// elyze: z";
+        let contents = directives(marker, data)
+            .map(|directive| directive.content)
+            .collect::<Vec<_>>();
+        assert_eq!(contents, vec![&b"x // elyze: y"[..], b"z"]);
+    }
+}
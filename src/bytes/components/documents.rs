@@ -0,0 +1,215 @@
+//! Multi-documents components
+//!
+//! Split a stream in documents, like the YAML documents separated by `---`
+//! lines or the batches of logs separated by blank lines, and parse each of
+//! them independently.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::marker::PhantomData;
+
+/// The separator between two documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DocumentSeparator {
+    /// A line containing only the marker, trailing whitespaces excepted
+    Line(&'static [u8]),
+    /// A line containing only whitespaces
+    BlankLine,
+    /// The form feed character `\x0c`
+    FormFeed,
+}
+
+impl DocumentSeparator {
+    /// The YAML separator, a `---` line
+    pub const DASHES: DocumentSeparator = DocumentSeparator::Line(b"---");
+
+    /// Find the first separator of the data
+    ///
+    /// # Returns
+    ///
+    /// The start and the end of the separator, or `None` if there is no
+    /// separator.
+    fn find(&self, data: &[u8]) -> Option<(usize, usize)> {
+        if let DocumentSeparator::FormFeed = self {
+            let position = data.iter().position(|byte| *byte == b'\x0c')?;
+            return Some((position, position + 1));
+        }
+
+        let mut line_start = 0;
+        while line_start < data.len() {
+            let line_end = data[line_start..]
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(data.len(), |position| line_start + position + 1);
+            let line = data[line_start..line_end].trim_ascii_end();
+            let found = match self {
+                DocumentSeparator::Line(marker) => line == *marker,
+                _ => line.is_empty(),
+            };
+            if found {
+                return Some((line_start, line_end));
+            }
+            line_start = line_end;
+        }
+        None
+    }
+}
+
+/// A document of a stream.
+pub struct Document<'a, V> {
+    /// The index of the document in the stream, starting at 0
+    pub index: usize,
+    /// The position of the document in the stream
+    pub offset: usize,
+    /// The data of the document
+    pub data: &'a [u8],
+    /// The parsed document, or the error of its parse positioned in the stream
+    pub value: ParseResult<V>,
+}
+
+/// An iterator over the documents of a stream.
+///
+/// Each document is parsed by the `V` visitor, which must consume the whole
+/// document, trailing whitespaces excepted. A document failing to parse
+/// doesn't stop the iteration. The documents containing only whitespaces are
+/// skipped.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::components::documents::{DocumentSeparator, Documents};
+/// use elyze::bytes::primitives::number::Number;
+///
+/// let data = b"1\n---\n2x\n---\n3\n";
+/// let documents = Documents::<Number<u32>>::new(data, DocumentSeparator::DASHES)
+///     .map(|document| document.value.ok().map(|Number(number)| number))
+///     .collect::<Vec<_>>();
+/// assert_eq!(documents, vec![Some(1), None, Some(3)]);
+/// ```
+pub struct Documents<'a, V> {
+    data: &'a [u8],
+    separator: DocumentSeparator,
+    position: usize,
+    index: usize,
+    _visitor: PhantomData<V>,
+}
+
+impl<'a, V> Documents<'a, V> {
+    /// Create a new `Documents` iterator
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The stream of documents
+    /// * `separator` - The separator between two documents
+    pub fn new(data: &'a [u8], separator: DocumentSeparator) -> Self {
+        Documents {
+            data,
+            separator,
+            position: 0,
+            index: 0,
+            _visitor: PhantomData,
+        }
+    }
+}
+
+/// Parse a whole document
+fn document<'a, V: Visitor<'a, u8>>(data: &'a [u8]) -> ParseResult<V> {
+    let mut scanner = Scanner::new(data);
+    let value = V::accept(&mut scanner)?;
+    if !scanner.remaining().trim_ascii().is_empty() {
+        return Err(ParseError::unexpected_token(scanner.current_position()));
+    }
+    Ok(value)
+}
+
+impl<'a, V: Visitor<'a, u8>> Iterator for Documents<'a, V> {
+    type Item = Document<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.data.len() {
+            let offset = self.position;
+            let remaining = &self.data[offset..];
+            let data = match self.separator.find(remaining) {
+                Some((start, end)) => {
+                    self.position += end;
+                    &remaining[..start]
+                }
+                None => {
+                    self.position = self.data.len();
+                    remaining
+                }
+            };
+
+            if data.trim_ascii().is_empty() {
+                continue;
+            }
+
+            let index = self.index;
+            self.index += 1;
+            return Some(Document {
+                index,
+                offset,
+                data,
+                value: document(data).map_err(|err| err.shifted(offset)),
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::components::documents::{DocumentSeparator, Documents};
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::ParseError;
+
+    #[test]
+    fn test_documents_dashes() {
+        let data = b"---\n1\n--- \r\n 22\n---\n\n---\n3 4\n";
+        let documents =
+            Documents::<Number<u32>>::new(data, DocumentSeparator::DASHES).collect::<Vec<_>>();
+        assert_eq!(documents.len(), 3);
+
+        assert_eq!(documents[0].index, 0);
+        assert_eq!(documents[0].offset, 4);
+        assert_eq!(documents[0].data, b"1\n");
+        assert!(matches!(documents[0].value, Ok(Number(1))));
+
+        assert_eq!(documents[1].offset, 12);
+        assert_eq!(documents[1].data, b" 22\n");
+        assert!(matches!(
+            documents[1].value,
+            Err(ParseError::UnexpectedToken { offset: 12, .. })
+        ));
+
+        assert_eq!(documents[2].index, 2);
+        assert_eq!(documents[2].data, b"3 4\n");
+        assert!(matches!(
+            documents[2].value,
+            Err(ParseError::UnexpectedToken { offset, .. }) if offset == documents[2].offset + 1
+        ));
+    }
+
+    #[test]
+    fn test_documents_blank_line() {
+        let data = b"1\n\n\n2\n  \n3";
+        let numbers = Documents::<Number<u32>>::new(data, DocumentSeparator::BlankLine)
+            .map(|document| document.value.expect("failed to parse").0)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_documents_form_feed() {
+        let data = b"1\x0c2\n\x0c\x0c3";
+        let documents = Documents::<Number<u32>>::new(data, DocumentSeparator::FormFeed)
+            .map(|document| (document.offset, document.value.expect("failed to parse").0))
+            .collect::<Vec<_>>();
+        assert_eq!(documents, vec![(0, 1), (2, 2), (6, 3)]);
+
+        let mut documents = Documents::<Number<u32>>::new(b"", DocumentSeparator::FormFeed);
+        assert!(documents.next().is_none());
+    }
+}
@@ -1,16 +1,18 @@
 //! Group components
 
 use crate::bytes::token::Token;
-use crate::errors::ParseResult;
+use crate::errors::{ParseError, ParseResult};
 use crate::matcher::Match;
-use crate::peek::{peek, PeekResult, Peekable};
+use crate::peek::{PeekResult, Peekable};
 use crate::recognizer::Recognizable;
 use crate::scanner::Scanner;
+use std::borrow::Cow;
 
-/// Checks if the current token is escaped by looking for an escape token before it.
+/// Checks if the current token is escaped by looking for escape tokens before it.
 ///
-/// Rewinds the scanner by the size of the escape token and checks if the escape token
-/// is present at that position. Returns true if the escape token is found, false otherwise.
+/// Rewinds the scanner by the size of the escape token as long as an escape
+/// token is found. The token is escaped by an odd run of escape tokens, an even
+/// run being made of escaped escape tokens, as in `"a\\"`.
 ///
 /// # Arguments
 ///
@@ -20,36 +22,46 @@ use crate::scanner::Scanner;
 /// # Type Parameters
 ///
 /// * `T` - The type of the escape token
-/// * `V` - The return type of the escape token's recognize() method
 ///
 /// # Returns
 ///
-/// * `Ok(true)` if the token is escaped (escape token found before it)
+/// * `Ok(true)` if the token is escaped (odd run of escape tokens before it)
 /// * `Ok(false)` if the token is not escaped
 /// * `Err(ParseError)` if scanning fails
-fn is_escaped<'a, T, V>(mut scanner: Scanner<'a, u8>, escape_token: T) -> ParseResult<bool>
+fn is_escaped<T>(mut scanner: Scanner<'_, u8>, escape_token: &T) -> ParseResult<bool>
 where
-    T: Recognizable<'a, u8, V> + Copy,
+    T: Match<u8>,
 {
-    // If the current position is less than the size of the escape token, the token is not escaped
-    if scanner.current_position() < escape_token.size() {
-        return Ok(false);
+    let size = escape_token.size();
+    let mut escaped = false;
+    // If the current position is less than the size of the escape token, the run is over
+    while size > 0 && scanner.current_position() >= size {
+        scanner.rewind_checked(size)?;
+        // Try to recognize the escape token
+        if escape_token.recognize(&mut scanner.clone())?.is_none() {
+            break;
+        }
+        escaped = !escaped;
     }
+    Ok(escaped)
+}
 
-    scanner.rewind(escape_token.size());
-    // Try to recognize the escape token
-    if escape_token.recognize(&mut scanner)?.is_some() {
-        // If it is present, the token is escaped
-        return Ok(true);
+/// Peek a borrowed `Peekable` and return the end of the peeked slice if found.
+fn peek_end_slice<'a, P: Peekable<'a, u8>>(
+    peekable: &P,
+    scanner: &Scanner<'a, u8>,
+) -> ParseResult<Option<usize>> {
+    match peekable.peek(scanner)? {
+        PeekResult::Found { end_slice, .. } => Ok(Some(end_slice)),
+        PeekResult::NotFound => Ok(None),
     }
-    Ok(false)
 }
 
-/// Try to recognize either a start group or an end group token.
+/// Move the scanner after the closest start group or end group token.
 ///
-/// If the start group token is recognized, increment the balancing counter.
-/// If the end group token is recognized, decrement the balancing counter.
-/// If neither is recognized, move the tokenizer by one byte.
+/// If the start group token is the closest, increment the balancing counter.
+/// If the end group token is the closest, decrement the balancing counter.
+/// Escaped tokens are skipped without altering the balancing counter.
 ///
 /// # Arguments
 ///
@@ -60,11 +72,14 @@ where
 ///
 /// # Errors
 ///
+/// Returns `Err(ParseError::UnbalancedGroup)` if there is no more group token
+/// in the input or if an end group token is found while the balancing counter
+/// is already 0.
 /// Returns `Err(ParseError)` if the tokenizer encounters an error.
-///
-/// # Examples
-///
-///
+#[deprecated(
+    since = "2.0.0",
+    note = "use `match_for_balanced_group_by_ref`, which doesn't require `Copy` tokens"
+)]
 pub fn match_for_balanced_group<'a, T1, T2, T3, V3>(
     scanner: &mut Scanner<'a, u8>,
     balance: &mut usize,
@@ -77,38 +92,72 @@ where
     T2: Peekable<'a, u8> + Match<u8> + Copy,
     T3: Recognizable<'a, u8, V3> + Copy,
 {
-    match peek(start, scanner)? {
-        Some(peeking) => {
-            scanner.bump_by(peeking.end_slice);
-            let mut rewind_scanner = scanner.clone();
-            rewind_scanner.rewind(start.size());
-            // if start group token increment balancing counter
-            if !is_escaped(rewind_scanner, escape_token)? {
-                *balance += 1
+    match_for_balanced_group_by_ref(scanner, balance, &start, &end, &escape_token)
+}
+
+/// Move the scanner after the closest start group or end group token, the
+/// tokens being borrowed.
+///
+/// If the start group token is the closest, increment the balancing counter.
+/// If the end group token is the closest, decrement the balancing counter.
+/// Escaped tokens are skipped without altering the balancing counter.
+///
+/// # Arguments
+///
+/// * `tokenizer` - The tokenizer to use
+/// * `balance` - A mutable reference to the balancing counter
+/// * `start` - The start group token to recognize
+/// * `end` - The end group token to recognize
+///
+/// # Errors
+///
+/// Returns `Err(ParseError::UnbalancedGroup)` if there is no more group token
+/// in the input or if an end group token is found while the balancing counter
+/// is already 0.
+/// Returns `Err(ParseError)` if the tokenizer encounters an error.
+pub fn match_for_balanced_group_by_ref<'a, T1, T2, T3>(
+    scanner: &mut Scanner<'a, u8>,
+    balance: &mut usize,
+    start: &T1,
+    end: &T2,
+    escape_token: &T3,
+) -> ParseResult<()>
+where
+    T1: Peekable<'a, u8> + Match<u8>,
+    T2: Peekable<'a, u8> + Match<u8>,
+    T3: Match<u8>,
+{
+    let next_start = peek_end_slice(start, scanner)?;
+    let next_end = peek_end_slice(end, scanner)?;
+
+    // only the closest token is handled, the other one may belong to another group
+    let (end_slice, is_start) = match (next_start, next_end) {
+        (Some(next_start), Some(next_end)) => {
+            if next_start - start.size() <= next_end - end.size() {
+                (next_start, true)
+            } else {
+                (next_end, false)
             }
-            return Ok(());
         }
-        // it's not a start token
-        None => {}
+        (Some(next_start), None) => (next_start, true),
+        (None, Some(next_end)) => (next_end, false),
+        // the end of input is reached, the group can't be balanced anymore
+        (None, None) => return Err(ParseError::UnbalancedGroup),
+    };
+
+    scanner.advance_checked(end_slice)?;
+    let mut rewind_scanner = scanner.clone();
+    rewind_scanner.rewind_checked(if is_start { start.size() } else { end.size() })?;
+    if is_escaped(rewind_scanner, escape_token)? {
+        return Ok(());
     }
 
-    match peek(end, scanner)? {
+    if is_start {
+        // if start group token increment balancing counter
+        *balance += 1;
+    } else {
         // if end group token decrement balancing counter
-        Some(peeking) => {
-            scanner.bump_by(peeking.end_slice);
-            let mut rewind_scanner = scanner.clone();
-            rewind_scanner.rewind(end.size());
-            if is_escaped(rewind_scanner, escape_token)? {
-                return Ok(());
-            }
-
-            *balance -= 1;
-        }
-        // if neither, move by one byte
-        None => {
-            scanner.bump_by(1);
-            return Ok(());
-        }
+        *balance = balance.checked_sub(1).ok_or(ParseError::UnbalancedGroup)?;
     }
 
     Ok(())
@@ -123,8 +172,9 @@ where
 ///
 /// The closure returns `Ok(PeekResult::Found { end_slice, start, end })` if the
 /// slice matches a balanced group, `Ok(PeekResult::NotFound)` if the slice
-/// does not match a balanced group, and `Err(ParseError)` if there is an error
-/// recognizing the tokens.
+/// does not start with a start group token, `Err(ParseError::UnbalancedGroup)`
+/// if the end of the slice is reached before the group is balanced, and
+/// `Err(ParseError)` if there is an error recognizing the tokens.
 ///
 /// # Arguments
 ///
@@ -135,15 +185,17 @@ where
 ///
 /// A closure that takes a slice of bytes and returns a `PeekResult` indicating
 /// whether the slice matches a balanced group.
+///
+/// See [match_groups] to balance several pairs of tokens at once.
 pub fn match_group<'a, T1, T2, T3, V3>(
     start: T1,
     end: T2,
     escape_token: T3,
 ) -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> + 'a
 where
-    T1: Peekable<'a, u8> + Match<u8> + Copy + 'a,
-    T2: Peekable<'a, u8> + Match<u8> + Copy + 'a,
-    T3: Recognizable<'a, u8, V3> + Copy + 'a,
+    T1: Peekable<'a, u8> + Match<u8> + 'a,
+    T2: Peekable<'a, u8> + Match<u8> + 'a,
+    T3: Recognizable<'a, u8, V3> + 'a,
 {
     move |input: &'a [u8]| {
         // 0 if number of start token equals number of end token
@@ -153,12 +205,18 @@ where
 
         let mut scanner = Scanner::new(input);
 
-        if start.recognize(&mut scanner)?.is_none() {
+        if (&start).recognize(&mut scanner)?.is_none() {
             return Ok(PeekResult::NotFound);
         }
 
         loop {
-            match_for_balanced_group(&mut scanner, &mut balance, start, end, escape_token)?;
+            match_for_balanced_group_by_ref(
+                &mut scanner,
+                &mut balance,
+                &start,
+                &end,
+                &escape_token,
+            )?;
             // if balancing is 0 then either there is no group at all or is balanced
             if balance == 0 {
                 break;
@@ -178,6 +236,166 @@ where
     }
 }
 
+/// Move the scanner after the closest start or end token of any of the group
+/// pairs.
+///
+/// A start token pushes the index of its pair on the stack of the open
+/// groups. An end token must close the innermost open group, it pops the
+/// stack. Escaped tokens are skipped without altering the stack.
+///
+/// When several tokens are at the same position, the start tokens win over
+/// the end tokens, then the first pair wins.
+///
+/// # Arguments
+///
+/// * `scanner` - The scanner to move
+/// * `open_groups` - The indexes of the pairs of the open groups, the innermost last
+/// * `pairs` - The start and end tokens of each kind of group
+/// * `escape_token` - The escape token
+///
+/// # Errors
+///
+/// Returns `Err(ParseError::UnbalancedGroup)` if there is no more group token
+/// in the input, or if an end token doesn't close the innermost open group,
+/// as the `]` of `([)]`.
+/// Returns `Err(ParseError)` if the tokenizer encounters an error.
+pub fn match_for_balanced_groups<'a, T1, T2, T3>(
+    scanner: &mut Scanner<'a, u8>,
+    open_groups: &mut Vec<usize>,
+    pairs: &[(T1, T2)],
+    escape_token: &T3,
+) -> ParseResult<()>
+where
+    T1: Peekable<'a, u8> + Match<u8>,
+    T2: Peekable<'a, u8> + Match<u8>,
+    T3: Match<u8>,
+{
+    // the closest token: its start, its end slice, its size, whether it is a
+    // start token and the index of its pair
+    let mut closest: Option<(usize, usize, usize, bool, usize)> = None;
+    for (index, (start, end)) in pairs.iter().enumerate() {
+        for (peeked, size, is_start) in [
+            (peek_end_slice(start, scanner)?, start.size(), true),
+            (peek_end_slice(end, scanner)?, end.size(), false),
+        ] {
+            let Some(end_slice) = peeked else {
+                continue;
+            };
+            let position = end_slice - size;
+            let is_closer = match closest {
+                None => true,
+                Some((closest_position, _, _, closest_is_start, _)) => {
+                    position < closest_position
+                        || (position == closest_position && is_start && !closest_is_start)
+                }
+            };
+            if is_closer {
+                closest = Some((position, end_slice, size, is_start, index));
+            }
+        }
+    }
+
+    // the end of input is reached, the groups can't be balanced anymore
+    let (_, end_slice, size, is_start, index) = closest.ok_or(ParseError::UnbalancedGroup)?;
+
+    scanner.advance_checked(end_slice)?;
+    let mut rewind_scanner = scanner.clone();
+    rewind_scanner.rewind_checked(size)?;
+    if is_escaped(rewind_scanner, escape_token)? {
+        return Ok(());
+    }
+
+    if is_start {
+        open_groups.push(index);
+    } else if open_groups.pop() != Some(index) {
+        // the end token belongs to another group than the innermost one
+        return Err(ParseError::UnbalancedGroup);
+    }
+
+    Ok(())
+}
+
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a group balanced over several pairs of tokens.
+///
+/// This is [match_group] for grammars mixing several kinds of groups, such as
+/// `()`, `[]` and `{}`: each group must be closed by the end token of its own
+/// pair, and the groups must be correctly nested. `([])` is balanced, `([)]`
+/// is not.
+///
+/// The slice must start with the start token of one of the pairs. The
+/// closure returns `Ok(PeekResult::Found { end_slice, start, end })` up to
+/// the end token closing it, `Ok(PeekResult::NotFound)` if the slice doesn't
+/// start with a start token, `Err(ParseError::UnbalancedGroup)` if the groups
+/// are not balanced or not correctly nested, and `Err(ParseError)` if there
+/// is an error recognizing the tokens.
+///
+/// # Arguments
+///
+/// * `pairs` - The start and end tokens of each kind of group
+/// * `escape_token` - The escape token
+///
+/// # Returns
+///
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a balanced group.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::components::groups::match_groups;
+/// use elyze::bytes::token::Token;
+/// use elyze::errors::ParseError;
+/// use elyze::peek::PeekResult;
+///
+/// let matcher = match_groups(
+///     [
+///         (Token::OpenParen, Token::CloseParen),
+///         (Token::OpenBracket, Token::CloseBracket),
+///     ],
+///     Token::Backslash,
+/// );
+/// assert!(matches!(
+///     matcher(b"(a[0] + b) * c"),
+///     Ok(PeekResult::Found { end_slice: 10, .. })
+/// ));
+/// assert!(matches!(matcher(b"([)]"), Err(ParseError::UnbalancedGroup)));
+/// ```
+pub fn match_groups<'a, T1, T2, T3>(
+    pairs: impl Into<Vec<(T1, T2)>>,
+    escape_token: T3,
+) -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> + 'a
+where
+    T1: Peekable<'a, u8> + Match<u8> + 'a,
+    T2: Peekable<'a, u8> + Match<u8> + 'a,
+    T3: Match<u8> + 'a,
+{
+    let pairs = pairs.into();
+    move |input: &'a [u8]| {
+        let mut scanner = Scanner::new(input);
+
+        let Some((index, (start, _))) = pairs
+            .iter()
+            .enumerate()
+            .find(|(_, (start, _))| start.is_matching(input).0)
+        else {
+            return Ok(PeekResult::NotFound);
+        };
+        scanner.advance_checked(start.size())?;
+
+        let mut open_groups = vec![index];
+        while !open_groups.is_empty() {
+            match_for_balanced_groups(&mut scanner, &mut open_groups, &pairs, &escape_token)?;
+        }
+
+        Ok(PeekResult::Found {
+            end_slice: scanner.current_position(),
+            start_element_size: start.size(),
+            end_element_size: pairs[index].1.size(),
+        })
+    }
+}
+
 /// A closure that takes a slice of bytes and returns a `PeekResult` indicating
 /// whether the slice matches a delimited group.
 ///
@@ -205,8 +423,8 @@ pub fn match_for_delimited_group<'a, T, T2>(
     escape_token: T2,
 ) -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> + 'a
 where
-    T: Peekable<'a, u8> + Copy + 'a + Match<u8>,
-    T2: Peekable<'a, u8> + Copy + 'a + Match<u8>,
+    T: Peekable<'a, u8> + 'a + Match<u8>,
+    T2: Match<u8> + 'a,
 {
     move |input: &'a [u8]| {
         // The group must be at least two tokens long
@@ -218,7 +436,7 @@ where
         let mut scanner = Scanner::new(input);
 
         // The group must start with the token
-        if token.recognize(&mut scanner)?.is_none() {
+        if (&token).recognize(&mut scanner)?.is_none() {
             return Ok(PeekResult::NotFound);
         }
 
@@ -227,16 +445,13 @@ where
         // While there are still bytes in the input
         while !scanner.remaining().is_empty() {
             // If the token is recognized somewhere in the input
-            match peek(token, &mut scanner)? {
-                Some(peeking) => {
-                    scanner.bump_by(peeking.end_slice);
+            match peek_end_slice(&token, &scanner)? {
+                Some(end_slice) => {
+                    scanner.advance_checked(end_slice)?;
                     let mut rewind_scanner = scanner.clone();
-                    rewind_scanner.rewind(token.size());
-                    // Advance the scanner by the size of the peeked token
-                    // If the token is escaped
-                    if is_escaped(rewind_scanner, escape_token)? {
-                        // Advance the scanner by one byte
-                        scanner.bump_by(1);
+                    rewind_scanner.rewind_checked(token.size())?;
+                    // If the token is escaped, the scanner is already after it
+                    if is_escaped(rewind_scanner, &escape_token)? {
                         continue;
                     }
                     found = true;
@@ -259,16 +474,571 @@ where
     }
 }
 
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a group delimited by a token which is escaped by
+/// doubling it.
+///
+/// This is the quoting convention of CSV and SQL, where `"he said ""hi"""` is
+/// a single group whose content is `he said "hi"`.
+///
+/// The closure returns `Ok(PeekResult::Found { end_slice, start, end })` if the
+/// slice matches a delimited group, `Ok(PeekResult::NotFound)` if the slice
+/// does not match a delimited group, and `Err(ParseError)` if there is an error
+/// recognizing the tokens.
+///
+/// # Arguments
+///
+/// * `token` - The token to recognize at the start and end of the group
+///
+/// # Returns
+///
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a delimited group.
+pub fn match_for_doubled_delimited_group<'a, T>(
+    token: T,
+) -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> + 'a
+where
+    T: Peekable<'a, u8> + 'a + Match<u8>,
+{
+    move |input: &'a [u8]| {
+        // The group must be at least two tokens long
+        if input.len() < token.size() * 2 {
+            return Ok(PeekResult::NotFound);
+        }
+
+        let mut scanner = Scanner::new(input);
+
+        // The group must start with the token
+        if (&token).recognize(&mut scanner)?.is_none() {
+            return Ok(PeekResult::NotFound);
+        }
+
+        while let Some(end_slice) = peek_end_slice(&token, &scanner)? {
+            scanner.advance_checked(end_slice)?;
+            // A doubled token is an escaped token
+            if scanner.remaining().len() >= token.size() && token.is_matching(&scanner).0 {
+                scanner.advance_checked(token.size())?;
+                continue;
+            }
+            return Ok(PeekResult::Found {
+                end_slice: scanner.current_position(),
+                start_element_size: token.size(),
+                end_element_size: token.size(),
+            });
+        }
+
+        Ok(PeekResult::NotFound)
+    }
+}
+
+/// Unescape the content of a group delimited by a token escaped by doubling it.
+///
+/// # Arguments
+///
+/// * `token` - The delimiter token of the group
+/// * `data` - The content of the group, without its delimiters
+///
+/// # Returns
+///
+/// The content where each doubled token is replaced by a single one. The
+/// content is only copied if it contains escaped tokens.
+pub fn unescape_doubled<T: Match<u8>>(token: T, data: &[u8]) -> Cow<'_, [u8]> {
+    let size = token.size();
+    let is_doubled = |data: &[u8]| {
+        data.len() >= size * 2 && token.is_matching(data).0 && token.is_matching(&data[size..]).0
+    };
+
+    let mut result = Cow::Borrowed(data);
+    let mut position = 0;
+    let mut last = 0;
+    while position < data.len() {
+        if !is_doubled(&data[position..]) {
+            position += 1;
+            continue;
+        }
+        // keep the first token, drop the second one
+        let owned = result.to_mut();
+        if last == 0 {
+            owned.clear();
+        }
+        owned.extend_from_slice(&data[last..position + size]);
+        position += size * 2;
+        last = position;
+    }
+
+    if let Cow::Owned(owned) = &mut result {
+        owned.extend_from_slice(&data[last..]);
+    }
+    result
+}
+
+/// The shape of a fenced raw group
+///
+/// A fenced group is opened by `open_prefix`, a run of `fence` elements and
+/// `open_suffix`. It is closed by `close_prefix`, a run of exactly the same
+/// number of `fence` elements and `close_suffix`. The content is never
+/// escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawFence {
+    /// The bytes before the fence run of the opening delimiter
+    pub open_prefix: &'static [u8],
+    /// The bytes after the fence run of the opening delimiter
+    pub open_suffix: &'static [u8],
+    /// The bytes before the fence run of the closing delimiter
+    pub close_prefix: &'static [u8],
+    /// The bytes after the fence run of the closing delimiter
+    pub close_suffix: &'static [u8],
+    /// The repeated fence byte
+    pub fence: u8,
+    /// The minimal length of the fence run
+    pub min_fence: usize,
+}
+
+impl RawFence {
+    /// Rust raw strings: `r"..."`, `r#"..."#`, `r##"..."##`, ...
+    pub const RUST: RawFence = RawFence {
+        open_prefix: b"r",
+        open_suffix: b"\"",
+        close_prefix: b"\"",
+        close_suffix: b"",
+        fence: b'#',
+        min_fence: 0,
+    };
+
+    /// Lua long brackets: `[[...]]`, `[=[...]=]`, `[==[...]==]`, ...
+    pub const LUA: RawFence = RawFence {
+        open_prefix: b"[",
+        open_suffix: b"[",
+        close_prefix: b"]",
+        close_suffix: b"]",
+        fence: b'=',
+        min_fence: 0,
+    };
+
+    /// Markdown code spans and fences: `` `...` ``, ```` ```...``` ````, ...
+    pub const MARKDOWN: RawFence = RawFence {
+        open_prefix: b"",
+        open_suffix: b"",
+        close_prefix: b"",
+        close_suffix: b"",
+        fence: b'`',
+        min_fence: 1,
+    };
+
+    /// Return the size of the opening delimiter and its fence run length
+    fn open(&self, data: &[u8]) -> Option<(usize, usize)> {
+        let rest = data.strip_prefix(self.open_prefix)?;
+        let fence_size = rest.iter().take_while(|byte| **byte == self.fence).count();
+        if fence_size < self.min_fence {
+            return None;
+        }
+        rest[fence_size..].strip_prefix(self.open_suffix)?;
+        Some((
+            self.open_prefix.len() + fence_size + self.open_suffix.len(),
+            fence_size,
+        ))
+    }
+
+    /// Return the size of the closing delimiter if it starts the data
+    fn close(&self, data: &[u8], fence_size: usize) -> Option<usize> {
+        let rest = data.strip_prefix(self.close_prefix)?;
+        let run = rest.iter().take_while(|byte| **byte == self.fence).count();
+        // a bare fence run must have exactly the same length
+        let is_bare = self.close_prefix.is_empty() && self.close_suffix.is_empty();
+        if run < fence_size || (is_bare && run != fence_size) {
+            return None;
+        }
+        rest[fence_size..].strip_prefix(self.close_suffix)?;
+        Some(self.close_prefix.len() + fence_size + self.close_suffix.len())
+    }
+}
+
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a fenced raw group.
+///
+/// The closing delimiter must have the same fence length as the opening one,
+/// so `r#"a "quoted" word"#` is a single Rust raw string.
+///
+/// The closure returns `Ok(PeekResult::Found { end_slice, start, end })` if the
+/// slice matches a fenced group, `Ok(PeekResult::NotFound)` otherwise.
+///
+/// # Arguments
+///
+/// * `fence` - The shape of the group delimiters
+///
+/// # Returns
+///
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a fenced group.
+pub fn match_for_fenced_group<'a>(fence: RawFence) -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> {
+    move |input: &'a [u8]| {
+        let Some((open_size, fence_size)) = fence.open(input) else {
+            return Ok(PeekResult::NotFound);
+        };
+
+        let mut position = open_size;
+        while position < input.len() {
+            if let Some(close_size) = fence.close(&input[position..], fence_size) {
+                return Ok(PeekResult::Found {
+                    end_slice: position + close_size,
+                    start_element_size: open_size,
+                    end_element_size: close_size,
+                });
+            }
+            // skip a whole run of fences, a part of it can't close the group
+            let run = input[position..]
+                .iter()
+                .take_while(|byte| **byte == fence.fence)
+                .count();
+            position += run.max(1);
+        }
+
+        Ok(PeekResult::NotFound)
+    }
+}
+
+/// Return the name of the tag starting the data after the given prefix.
+fn tag_name_at<'a>(data: &'a [u8], prefix: &[u8]) -> Option<&'a [u8]> {
+    let rest = data.strip_prefix(prefix)?;
+    let is_name_start = |byte: &u8| byte.is_ascii_alphabetic() || *byte == b'_' || *byte == b':';
+    let is_name = |byte: &u8| is_name_start(byte) || byte.is_ascii_digit() || b".-".contains(byte);
+    if !rest.first().is_some_and(is_name_start) {
+        return None;
+    }
+    let size = rest.iter().take_while(|byte| is_name(byte)).count();
+    Some(&rest[..size])
+}
+
+/// Parse an opening tag `<name attr="value">` at the start of the data.
+///
+/// Returns the tag name, the size of the tag and whether it is self-closing.
+fn open_tag(data: &[u8]) -> Option<(&[u8], usize, bool)> {
+    let name = tag_name_at(data, b"<")?;
+    let mut position = 1 + name.len();
+    // the name must be followed by the attributes or the end of the tag
+    if !data
+        .get(position)
+        .is_some_and(|byte| byte.is_ascii_whitespace() || b"/>".contains(byte))
+    {
+        return None;
+    }
+    // skip the attributes, quoted values may contain a `>`
+    let mut quote = None;
+    while let Some(byte) = data.get(position) {
+        match quote {
+            Some(quote_byte) if *byte == quote_byte => quote = None,
+            Some(_) => {}
+            None if *byte == b'"' || *byte == b'\'' => quote = Some(*byte),
+            None if *byte == b'>' => {
+                let self_closing = data[position - 1] == b'/';
+                return Some((name, position + 1, self_closing));
+            }
+            None => {}
+        }
+        position += 1;
+    }
+    None
+}
+
+/// Parse a closing tag `</name>` at the start of the data.
+///
+/// Returns the size of the tag.
+fn close_tag(data: &[u8], name: &[u8]) -> Option<usize> {
+    if tag_name_at(data, b"</")? != name {
+        return None;
+    }
+    let position = 2 + name.len();
+    let spaces = data[position..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_whitespace())
+        .count();
+    match data.get(position + spaces) {
+        Some(b'>') => Some(position + spaces + 1),
+        _ => None,
+    }
+}
+
+/// Return the name of the tag opening a tag group.
+///
+/// # Arguments
+///
+/// * `data` - The data of a tag group, starting with its opening tag
+///
+/// # Returns
+///
+/// The name of the opening tag if the data starts with an opening tag.
+pub fn tag_name(data: &[u8]) -> Option<&[u8]> {
+    open_tag(data).map(|(name, _, _)| name)
+}
+
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a tag group.
+///
+/// A tag group is an element of a markup language such as `<div>...</div>`.
+/// The name captured by the opening tag is used to balance the nested elements
+/// of the same name, other elements are part of the content. Comments are
+/// skipped and a self-closing tag `<br/>` is a group without content.
+///
+/// The closure returns `Ok(PeekResult::Found { end_slice, start, end })` if the
+/// slice matches a tag group, `Ok(PeekResult::NotFound)` if the slice doesn't
+/// start with an opening tag, and `Err(ParseError::UnbalancedGroup)` if the
+/// element is never closed.
+///
+/// # Returns
+///
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a tag group.
+pub fn match_for_tag_group<'a>() -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> {
+    move |input: &'a [u8]| {
+        let Some((name, open_size, self_closing)) = open_tag(input) else {
+            return Ok(PeekResult::NotFound);
+        };
+
+        if self_closing {
+            return Ok(PeekResult::Found {
+                end_slice: open_size,
+                start_element_size: open_size,
+                end_element_size: 0,
+            });
+        }
+
+        let mut balance = 1usize;
+        let mut position = open_size;
+        while let Some(offset) = input[position..].iter().position(|byte| *byte == b'<') {
+            position += offset;
+            let data = &input[position..];
+
+            if data.starts_with(b"<!--") {
+                // a comment may contain tags, skip it entirely
+                match data.windows(3).position(|window| window == b"-->") {
+                    Some(end) => position += end + 3,
+                    None => break,
+                }
+                continue;
+            }
+
+            if let Some(close_size) = close_tag(data, name) {
+                balance -= 1;
+                position += close_size;
+                if balance == 0 {
+                    return Ok(PeekResult::Found {
+                        end_slice: position,
+                        start_element_size: open_size,
+                        end_element_size: close_size,
+                    });
+                }
+                continue;
+            }
+
+            match open_tag(data) {
+                Some((inner_name, inner_size, inner_self_closing)) => {
+                    if inner_name == name && !inner_self_closing {
+                        balance += 1;
+                    }
+                    position += inner_size;
+                }
+                None => position += 1,
+            }
+        }
+
+        Err(ParseError::UnbalancedGroup)
+    }
+}
+
+/// Parse the opening of a heredoc `<<WORD`, `<<-WORD`, `<<'WORD'` or
+/// `<<"WORD"` at the start of the data.
+///
+/// Returns the delimiter word, whether the closing line may be indented with
+/// tabs and the size of the opening, up to the end of its line.
+fn open_heredoc(data: &[u8]) -> Option<(&[u8], bool, usize)> {
+    let rest = data.strip_prefix(b"<<")?;
+    let (indented, rest) = match rest.strip_prefix(b"-") {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let (word, quotes_size) = match rest.first() {
+        Some(quote @ (b'\'' | b'"')) => {
+            let size = rest[1..].iter().position(|byte| byte == quote)?;
+            (&rest[1..1 + size], 2)
+        }
+        _ => {
+            let size = rest
+                .iter()
+                .take_while(|byte| byte.is_ascii_alphanumeric() || **byte == b'_')
+                .count();
+            (&rest[..size], 0)
+        }
+    };
+    if word.is_empty() || word.contains(&b'\n') {
+        return None;
+    }
+    // the body starts on the line after the opening
+    let word_end = data.len() - rest.len() + word.len() + quotes_size;
+    let line_size = data[word_end..].iter().position(|byte| *byte == b'\n')?;
+    Some((word, indented, word_end + line_size + 1))
+}
+
+/// Return the delimiter word of the heredoc opening a heredoc group.
+///
+/// # Arguments
+///
+/// * `data` - The data of a heredoc group, starting with its opening
+///
+/// # Returns
+///
+/// The delimiter word, without its quotes, if the data starts with a heredoc.
+pub fn heredoc_word(data: &[u8]) -> Option<&[u8]> {
+    open_heredoc(data).map(|(word, _, _)| word)
+}
+
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a heredoc group.
+///
+/// A heredoc is opened by `<<` and a delimiter word, which may be quoted. Its
+/// body starts on the next line and runs up to a line made of the delimiter
+/// word alone. With `<<-`, the closing line may be indented with tabs. The rest
+/// of the opening line belongs to the opening delimiter and the line ending
+/// of the closing line is left after the group.
+///
+/// The closure returns `Ok(PeekResult::Found { end_slice, start, end })` if the
+/// slice matches a heredoc group, `Ok(PeekResult::NotFound)` if the slice
+/// doesn't start with a heredoc opening, and `Err(ParseError::UnbalancedGroup)`
+/// if the delimiter word never closes the heredoc.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::components::groups::{heredoc_word, match_for_heredoc_group};
+/// use elyze::peek::PeekResult;
+///
+/// let data = b"<<EOF | sort\nb\na\nEOF\necho done";
+/// let result = match_for_heredoc_group()(data).expect("failed to parse");
+/// assert_eq!(
+///     result,
+///     PeekResult::Found {
+///         end_slice: 20,
+///         start_element_size: 13,
+///         end_element_size: 3
+///     }
+/// );
+/// assert_eq!(&data[13..17], b"b\na\n");
+/// assert_eq!(heredoc_word(&data[..20]), Some(&b"EOF"[..]));
+/// ```
+///
+/// # Returns
+///
+/// A closure that takes a slice of bytes and returns a `PeekResult` indicating
+/// whether the slice matches a heredoc group.
+pub fn match_for_heredoc_group<'a>() -> impl Fn(&'a [u8]) -> ParseResult<PeekResult> {
+    move |input: &'a [u8]| {
+        let Some((word, indented, open_size)) = open_heredoc(input) else {
+            return Ok(PeekResult::NotFound);
+        };
+
+        let mut position = open_size;
+        while position < input.len() {
+            let line_end = input[position..]
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(input.len(), |size| position + size);
+            let line = &input[position..line_end];
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let indent = if indented {
+                line.iter().take_while(|byte| **byte == b'\t').count()
+            } else {
+                0
+            };
+            if &line[indent..] == word {
+                return Ok(PeekResult::Found {
+                    end_slice: position + line.len(),
+                    start_element_size: open_size,
+                    end_element_size: line.len(),
+                });
+            }
+            position = line_end + 1;
+        }
+
+        Err(ParseError::UnbalancedGroup)
+    }
+}
+
 /// Types of groups
 ///
 /// This enum is used to specify the type of a group in a matcher.
+#[non_exhaustive]
 pub enum GroupKind {
     /// A group enclosed in parentheses
     Parenthesis,
+    /// A group enclosed in square brackets, `[...]`
+    Brackets,
+    /// A group enclosed in curly braces, `{...}`
+    Braces,
+    /// A group enclosed in angle brackets, `<...>`
+    AngleBrackets,
     /// A group enclosed in single quotes
     Quotes,
     /// A group enclosed in double quotes
     DoubleQuotes,
+    /// A group enclosed in single quotes, escaped by doubling them (SQL style)
+    DoubledQuotes,
+    /// A group enclosed in double quotes, escaped by doubling them (CSV style)
+    DoubledDoubleQuotes,
+    /// A Rust raw string, see [RawFence::RUST]
+    ///
+    /// Experimental, enabled by the `unstable` feature.
+    #[cfg(feature = "unstable")]
+    RustRawString,
+    /// A Lua long bracket, see [RawFence::LUA]
+    ///
+    /// Experimental, enabled by the `unstable` feature.
+    #[cfg(feature = "unstable")]
+    LuaLongBracket,
+    /// A Markdown code span or fence, see [RawFence::MARKDOWN]
+    ///
+    /// Experimental, enabled by the `unstable` feature.
+    #[cfg(feature = "unstable")]
+    CodeFence,
+    /// A markup element enclosed in an opening and a closing tag
+    ///
+    /// Experimental, enabled by the `unstable` feature.
+    #[cfg(feature = "unstable")]
+    Tag,
+    /// A shell heredoc, `<<EOF ... EOF`, see [match_for_heredoc_group]
+    ///
+    /// Experimental, enabled by the `unstable` feature.
+    #[cfg(feature = "unstable")]
+    Heredoc,
+    /// A group enclosed in user defined delimiters
+    ///
+    /// The nested groups are balanced as for [GroupKind::Parenthesis]. When
+    /// the start and end tokens are the same, the group ends at the next
+    /// unescaped token as for [GroupKind::Quotes].
+    ///
+    /// ```
+    /// use elyze::bytes::components::groups::GroupKind;
+    /// use elyze::bytes::token::Token;
+    /// use elyze::peek::peek;
+    /// use elyze::scanner::Scanner;
+    ///
+    /// let group = GroupKind::Custom {
+    ///     start: Token::Pipe,
+    ///     end: Token::Pipe,
+    ///     escape: Token::Backslash,
+    /// };
+    /// let scanner = Scanner::new(br"|a\|b| c");
+    /// let peeked = peek(group, &scanner)
+    ///     .expect("failed to parse")
+    ///     .expect("failed to peek");
+    /// assert_eq!(peeked.peeked_slice(), br"a\|b");
+    /// ```
+    Custom {
+        /// The token opening the group
+        start: Token,
+        /// The token closing the group
+        end: Token,
+        /// The token escaping a delimiter inside the group
+        escape: Token,
+    },
 }
 
 type GroupMatcher<'a> = Box<dyn Fn(&'a [u8]) -> ParseResult<PeekResult> + 'a>;
@@ -282,6 +1052,21 @@ where {
                 Token::CloseParen,
                 Token::Backslash,
             )),
+            GroupKind::Brackets => Box::new(match_group(
+                Token::OpenBracket,
+                Token::CloseBracket,
+                Token::Backslash,
+            )),
+            GroupKind::Braces => Box::new(match_group(
+                Token::OpenBrace,
+                Token::CloseBrace,
+                Token::Backslash,
+            )),
+            GroupKind::AngleBrackets => Box::new(match_group(
+                Token::LessThan,
+                Token::GreaterThan,
+                Token::Backslash,
+            )),
             GroupKind::Quotes => {
                 Box::new(match_for_delimited_group(Token::Quote, Token::Backslash))
             }
@@ -289,6 +1074,26 @@ where {
                 Token::DoubleQuote,
                 Token::Backslash,
             )),
+            GroupKind::DoubledQuotes => Box::new(match_for_doubled_delimited_group(Token::Quote)),
+            GroupKind::DoubledDoubleQuotes => {
+                Box::new(match_for_doubled_delimited_group(Token::DoubleQuote))
+            }
+            #[cfg(feature = "unstable")]
+            GroupKind::RustRawString => Box::new(match_for_fenced_group(RawFence::RUST)),
+            #[cfg(feature = "unstable")]
+            GroupKind::LuaLongBracket => Box::new(match_for_fenced_group(RawFence::LUA)),
+            #[cfg(feature = "unstable")]
+            GroupKind::CodeFence => Box::new(match_for_fenced_group(RawFence::MARKDOWN)),
+            #[cfg(feature = "unstable")]
+            GroupKind::Tag => Box::new(match_for_tag_group()),
+            #[cfg(feature = "unstable")]
+            GroupKind::Heredoc => Box::new(match_for_heredoc_group()),
+            GroupKind::Custom { start, end, escape } if start == end => {
+                Box::new(match_for_delimited_group(*start, *escape))
+            }
+            GroupKind::Custom { start, end, escape } => {
+                Box::new(match_group(*start, *end, *escape))
+            }
         }
     }
 }
@@ -301,11 +1106,19 @@ impl<'a> Peekable<'a, u8> for GroupKind {
 
 #[cfg(test)]
 mod tests {
-    use crate::bytes::components::groups::{match_for_delimited_group, match_group, GroupKind};
+    use crate::alternatives::CompiledAlternatives;
+    #[cfg(feature = "unstable")]
+    use crate::bytes::components::groups::{heredoc_word, tag_name};
+    use crate::bytes::components::groups::{
+        match_for_balanced_group_by_ref, match_for_delimited_group,
+        match_for_doubled_delimited_group, match_for_fenced_group, match_for_heredoc_group,
+        match_for_tag_group, match_group, match_groups, unescape_doubled, GroupKind, RawFence,
+    };
     use crate::bytes::token::Token;
-    use crate::errors::ParseResult;
+    use crate::errors::{ParseError, ParseResult};
     use crate::peek::{peek, PeekResult, Peeking};
     use crate::scanner::Scanner;
+    use std::borrow::Cow;
 
     #[test]
     fn test_match_group() {
@@ -321,14 +1134,14 @@ mod tests {
                 end_element_size: 1
             }
         );
-        assert_eq!(&data[..24].as_bytes(), b"( 5 + 3 - ( 10 * 8 ) \\))");
+        assert_eq!(&data.as_bytes()[..24], b"( 5 + 3 - ( 10 * 8 ) \\))");
     }
 
     #[test]
     fn test_match_group2() -> ParseResult<()> {
         let data = "( 5 + 3 - \\( ( 10 * 8 \\)) \\)) + 54";
-        let mut tokenizer = Scanner::new(data.as_bytes());
-        let result = peek(GroupKind::Parenthesis, &mut tokenizer)?;
+        let tokenizer = Scanner::new(data.as_bytes());
+        let result = peek(GroupKind::Parenthesis, &tokenizer)?;
 
         if let Some(peeked) = result {
             assert_eq!(peeked.peeked_slice(), b" 5 + 3 - \\( ( 10 * 8 \\)) \\)");
@@ -348,8 +1161,8 @@ mod tests {
     #[test]
     fn test_match_group_delimited() {
         let data = b"( 5 + 3 - ( 10 * 8 ) ) + 54";
-        let mut tokenizer = Scanner::new(data);
-        let result = peek(GroupKind::Parenthesis, &mut tokenizer).expect("failed to parse");
+        let tokenizer = Scanner::new(data);
+        let result = peek(GroupKind::Parenthesis, &tokenizer).expect("failed to parse");
         assert_eq!(
             result,
             Some(Peeking {
@@ -365,8 +1178,8 @@ mod tests {
     #[test]
     fn test_match_group_delimited2() {
         let data = b"( 5 + 3 - ( 10 * 8 ) ) + 54";
-        let mut tokenizer = Scanner::new(data);
-        let result = peek(GroupKind::Parenthesis, &mut tokenizer).expect("failed to parse");
+        let tokenizer = Scanner::new(data);
+        let result = peek(GroupKind::Parenthesis, &tokenizer).expect("failed to parse");
 
         if let Some(peeked) = result {
             assert_eq!(peeked.peeked_slice(), b" 5 + 3 - ( 10 * 8 ) ");
@@ -376,8 +1189,8 @@ mod tests {
     #[test]
     fn test_match_quotes2() {
         let data = b"'hello world' data";
-        let mut tokenizer = Scanner::new(data);
-        let result = peek(GroupKind::Quotes, &mut tokenizer).expect("failed to parse");
+        let tokenizer = Scanner::new(data);
+        let result = peek(GroupKind::Quotes, &tokenizer).expect("failed to parse");
 
         if let Some(peeked) = result {
             assert_eq!(peeked.peeked_slice(), b"hello world");
@@ -387,8 +1200,8 @@ mod tests {
     #[test]
     fn test_match_quotes3() {
         let data = "'I\\'m a quoted data' - 'yes me too'";
-        let mut tokenizer = Scanner::new(data.as_bytes());
-        let result = peek(GroupKind::Quotes, &mut tokenizer).expect("failed to parse");
+        let tokenizer = Scanner::new(data.as_bytes());
+        let result = peek(GroupKind::Quotes, &tokenizer).expect("failed to parse");
 
         if let Some(peeked) = result {
             assert_eq!(peeked.peeked_slice(), b"I\\'m a quoted data");
@@ -452,8 +1265,8 @@ mod tests {
         assert_eq!(&data[..13], r#""hello world""#);
 
         let data = r#""hello world" data"#;
-        let mut tokenizer = Scanner::new(data.as_bytes());
-        let result = peek(GroupKind::DoubleQuotes, &mut tokenizer).expect("failed to parse");
+        let tokenizer = Scanner::new(data.as_bytes());
+        let result = peek(GroupKind::DoubleQuotes, &tokenizer).expect("failed to parse");
 
         if let Some(peeked) = result {
             assert_eq!(peeked.peeked_slice(), b"hello world");
@@ -462,8 +1275,8 @@ mod tests {
         }
 
         let data = r#""""#;
-        let mut tokenizer = Scanner::new(data.as_bytes());
-        let result = peek(GroupKind::DoubleQuotes, &mut tokenizer).expect("failed to parse");
+        let tokenizer = Scanner::new(data.as_bytes());
+        let result = peek(GroupKind::DoubleQuotes, &tokenizer).expect("failed to parse");
 
         if let Some(peeked) = result {
             assert_eq!(peeked.peeked_slice(), b"");
@@ -471,4 +1284,425 @@ mod tests {
             panic!("failed to parse");
         }
     }
+
+    #[test]
+    fn test_match_quotes_escapes() {
+        let matcher = match_for_delimited_group(Token::DoubleQuote, Token::Backslash);
+        // an escaped quote right before the closing one
+        let result = matcher(br#""\"" data"#).expect("failed to parse");
+        assert!(matches!(result, PeekResult::Found { end_slice: 4, .. }));
+        // an escaped backslash doesn't escape the closing quote
+        let result = matcher(br#""a\\" data"#).expect("failed to parse");
+        assert!(matches!(result, PeekResult::Found { end_slice: 5, .. }));
+        let result = matcher(br#""a\\\"" data"#).expect("failed to parse");
+        assert!(matches!(result, PeekResult::Found { end_slice: 7, .. }));
+        assert_eq!(
+            matcher(br#""a\""#).expect("failed to parse"),
+            PeekResult::NotFound
+        );
+    }
+
+    #[test]
+    fn test_match_group_sibling_groups() {
+        let data = b"(a) (b)";
+        let result = match_group(Token::OpenParen, Token::CloseParen, Token::Backslash)(data)
+            .expect("failed to parse");
+        assert_eq!(
+            result,
+            PeekResult::Found {
+                end_slice: 3,
+                start_element_size: 1,
+                end_element_size: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_group_unbalanced() {
+        let matcher = match_group(Token::OpenParen, Token::CloseParen, Token::Backslash);
+        for data in [
+            &b"("[..],
+            b"(((",
+            b"( 5 + 3 - ( 10 * 8 )",
+            b"(\\)",
+            b"(abc\\)",
+            b"(((((((((((((((((((((((((((((((()",
+        ] {
+            let result = matcher(data);
+            assert!(
+                matches!(result, Err(ParseError::UnbalancedGroup)),
+                "unexpected result for {:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_match_groups() {
+        let matcher = match_groups(
+            [
+                (Token::OpenParen, Token::CloseParen),
+                (Token::OpenBracket, Token::CloseBracket),
+                (Token::OpenBrace, Token::CloseBrace),
+            ],
+            Token::Backslash,
+        );
+        for (data, expected) in [
+            (&b"(a) (b)"[..], 3),
+            (b"([]{()}) rest", 8),
+            (b"{ a: [1, (2)], b: \\] } rest", 22),
+            (b"[\\(]", 4),
+            (b"()", 2),
+        ] {
+            assert_eq!(
+                matcher(data).expect("failed to parse"),
+                PeekResult::Found {
+                    end_slice: expected,
+                    start_element_size: 1,
+                    end_element_size: 1
+                },
+                "unexpected result for {:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+        assert_eq!(
+            matcher(b"a(b)").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        for data in [&b"([)]"[..], b"(]", b"{[()]", b"[(])"] {
+            assert!(
+                matches!(matcher(data), Err(ParseError::UnbalancedGroup)),
+                "unexpected result for {:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_group_kinds() {
+        for (group, data, expected) in [
+            (
+                GroupKind::Brackets,
+                &b"[a[0], \\]] + b"[..],
+                &b"a[0], \\]"[..],
+            ),
+            (GroupKind::Braces, b"{ {} } }", b" {} "),
+            (GroupKind::AngleBrackets, b"<Vec<u8>> x", b"Vec<u8>"),
+            (
+                GroupKind::Custom {
+                    start: Token::Slash,
+                    end: Token::Backslash,
+                    escape: Token::Caret,
+                },
+                b"/a ^\\ /b\\\\",
+                b"a ^\\ /b\\",
+            ),
+        ] {
+            let scanner = Scanner::new(data);
+            let peeked = peek(group, &scanner)
+                .expect("failed to parse")
+                .expect("failed to peek");
+            assert_eq!(peeked.peeked_slice(), expected);
+        }
+
+        let scanner = Scanner::new(b"a[0]");
+        assert_eq!(
+            peek(GroupKind::Brackets, &scanner).expect("failed to parse"),
+            None
+        );
+        let scanner = Scanner::new(b"{ {}");
+        assert!(matches!(
+            peek(GroupKind::Braces, &scanner),
+            Err(ParseError::UnbalancedGroup)
+        ));
+    }
+
+    #[test]
+    fn test_match_for_balanced_group_underflow() {
+        let mut scanner = Scanner::new(b"a)");
+        let mut balance = 0;
+        let result = match_for_balanced_group_by_ref(
+            &mut scanner,
+            &mut balance,
+            &Token::OpenParen,
+            &Token::CloseParen,
+            &Token::Backslash,
+        );
+        assert!(matches!(result, Err(ParseError::UnbalancedGroup)));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_match_for_balanced_group_by_value() {
+        let mut scanner = Scanner::new(b"(a)");
+        scanner.bump_by(1);
+        let mut balance = 1;
+        crate::bytes::components::groups::match_for_balanced_group(
+            &mut scanner,
+            &mut balance,
+            Token::OpenParen,
+            Token::CloseParen,
+            Token::Backslash,
+        )
+        .expect("failed to parse");
+        assert_eq!((balance, scanner.current_position()), (0, 3));
+    }
+
+    #[test]
+    fn test_match_group_owned_escape_token() {
+        // the compiled alternatives hold owned data and are not `Copy`
+        let escape = CompiledAlternatives::compile(&[b"\\".as_slice(), b"^"]);
+        let data = b"(a ^) b \\) c) d";
+        let result = match_group(Token::OpenParen, Token::CloseParen, &escape)(data)
+            .expect("failed to parse");
+        assert_eq!(
+            result,
+            PeekResult::Found {
+                end_slice: 13,
+                start_element_size: 1,
+                end_element_size: 1
+            }
+        );
+
+        let result = match_for_delimited_group(Token::Quote, escape)(b"'a ^' b' c")
+            .expect("failed to parse");
+        assert_eq!(
+            result,
+            PeekResult::Found {
+                end_slice: 8,
+                start_element_size: 1,
+                end_element_size: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_doubled_quotes() {
+        let data = br#""he said ""hi""", next"#;
+        let result =
+            match_for_doubled_delimited_group(Token::DoubleQuote)(data).expect("failed to parse");
+        assert_eq!(
+            result,
+            PeekResult::Found {
+                end_slice: 16,
+                start_element_size: 1,
+                end_element_size: 1
+            }
+        );
+
+        let scanner = Scanner::new(data);
+        let peeked = peek(GroupKind::DoubledDoubleQuotes, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), br#"he said ""hi"""#);
+        assert_eq!(
+            unescape_doubled(Token::DoubleQuote, peeked.peeked_slice()),
+            &br#"he said "hi""#[..]
+        );
+
+        let scanner = Scanner::new(b"'''' rest");
+        let peeked = peek(GroupKind::DoubledQuotes, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"''");
+        assert_eq!(
+            unescape_doubled(Token::Quote, peeked.peeked_slice()),
+            &b"'"[..]
+        );
+
+        let scanner = Scanner::new(b"''");
+        let peeked = peek(GroupKind::DoubledQuotes, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"");
+
+        let result =
+            match_for_doubled_delimited_group(Token::Quote)(b"'abc''").expect("failed to parse");
+        assert_eq!(result, PeekResult::NotFound);
+    }
+
+    #[test]
+    fn test_unescape_doubled() {
+        let result = unescape_doubled(Token::Quote, b"no quote");
+        assert!(matches!(result, Cow::Borrowed(_)));
+
+        let result = unescape_doubled(Token::Quote, b"a''b''''c");
+        assert!(matches!(result, Cow::Owned(_)));
+        assert_eq!(result, &b"a'b''c"[..]);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn test_match_fenced_group() {
+        let data = br##"r#"a "quoted" word"# rest"##;
+        let scanner = Scanner::new(data);
+        let peeked = peek(GroupKind::RustRawString, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), br#"a "quoted" word"#);
+        assert_eq!(peeked.end_slice, 20);
+
+        let scanner = Scanner::new(br#"r"no escape \" rest"#);
+        let peeked = peek(GroupKind::RustRawString, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"no escape \\");
+
+        let scanner = Scanner::new(br###"r#"a"##"###);
+        let peeked = peek(GroupKind::RustRawString, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"a");
+        assert_eq!(peeked.end_slice, 6);
+
+        let scanner = Scanner::new(b"[==[a]] ]=] ]==] rest");
+        let peeked = peek(GroupKind::LuaLongBracket, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"a]] ]=] ");
+
+        let scanner = Scanner::new(b"``code ` and `` rest");
+        let peeked = peek(GroupKind::CodeFence, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"code ` and ");
+
+        let scanner = Scanner::new(b"```\nfn main() {}\n````\n```");
+        let peeked = peek(GroupKind::CodeFence, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"\nfn main() {}\n````\n");
+    }
+
+    #[test]
+    fn test_non_match_fenced_group() {
+        let matcher = match_for_fenced_group(RawFence::RUST);
+        assert_eq!(
+            matcher(br#"r#"unclosed""#).expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(
+            matcher(b"rust").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(matcher(b"").expect("failed to parse"), PeekResult::NotFound);
+
+        let matcher = match_for_fenced_group(RawFence::MARKDOWN);
+        assert_eq!(
+            matcher(b"no code").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(
+            matcher(b"``a`").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn test_match_tag_group() {
+        let data = b"<div class=\"a>b\"><div>x</div><p>y</p></div ><div>rest</div>";
+        let scanner = Scanner::new(data);
+        let peeked = peek(GroupKind::Tag, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"<div>x</div><p>y</p>");
+        assert_eq!(peeked.data, &data[..44]);
+        assert_eq!(tag_name(peeked.data), Some(&b"div"[..]));
+
+        let scanner = Scanner::new(b"<br/> text");
+        let peeked = peek(GroupKind::Tag, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.data, b"<br/>");
+        assert_eq!(peeked.peeked_slice(), b"");
+
+        let scanner = Scanner::new(b"<a><!-- </a> --></a>");
+        let peeked = peek(GroupKind::Tag, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"<!-- </a> -->");
+
+        // a tag whose name is a prefix of another one
+        let scanner = Scanner::new(b"<b><br/><bb></bb></b>");
+        let peeked = peek(GroupKind::Tag, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"<br/><bb></bb>");
+    }
+
+    #[test]
+    fn test_non_match_tag_group() {
+        let matcher = match_for_tag_group();
+        assert_eq!(
+            matcher(b"text <a></a>").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(
+            matcher(b"< a></a>").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(matcher(b"").expect("failed to parse"), PeekResult::NotFound);
+        assert!(matches!(
+            matcher(b"<a><a></a>"),
+            Err(ParseError::UnbalancedGroup)
+        ));
+        assert!(matches!(matcher(b"<a>"), Err(ParseError::UnbalancedGroup)));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn test_match_heredoc_group() {
+        let data = b"cat <<'END'
+$HOME
+ END
+END
+rest";
+        let scanner = Scanner::new(&data[4..]);
+        let peeked = peek(GroupKind::Heredoc, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"$HOME\n END\n");
+        assert_eq!(peeked.end_slice, 22);
+        assert_eq!(heredoc_word(peeked.data), Some(&b"END"[..]));
+
+        let scanner = Scanner::new(b"<<-EOF\n\tindented\n\t\tEOF");
+        let peeked = peek(GroupKind::Heredoc, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"\tindented\n");
+        assert_eq!(peeked.end_slice, 22);
+
+        let scanner = Scanner::new(b"<<EOF\nEOF");
+        let peeked = peek(GroupKind::Heredoc, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"");
+    }
+
+    #[test]
+    fn test_non_match_heredoc_group() {
+        let matcher = match_for_heredoc_group();
+        assert_eq!(
+            matcher(b"a << b").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(
+            matcher(b"<< EOF\nEOF").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        assert_eq!(
+            matcher(b"<<EOF").expect("failed to parse"),
+            PeekResult::NotFound
+        );
+        // the closing line can't be indented without `<<-`
+        assert!(matches!(
+            matcher(b"<<EOF\nbody\n\tEOF"),
+            Err(ParseError::UnbalancedGroup)
+        ));
+        assert!(matches!(
+            matcher(b"<<EOF\nEOFX\n"),
+            Err(ParseError::UnbalancedGroup)
+        ));
+    }
 }
@@ -0,0 +1,306 @@
+//! Object literal components
+//!
+//! Parse an object literal like `{ key: value, ... }`. Nested objects are
+//! supported through the value visitor, which may itself accept an
+//! [ObjectLiteral].
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::marker::PhantomData;
+
+/// How to handle a key defined several times in the same object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuplicateKeys {
+    /// Fail with `ParseError::DuplicateKey`
+    Reject,
+    /// Keep the first entry, the following ones are ignored
+    KeepFirst,
+    /// Keep the value of the last entry, at the position of the first one
+    KeepLast,
+}
+
+/// Define the duplicate keys policy of an [ObjectLiteral].
+pub trait DuplicateKeyPolicy {
+    /// The policy to apply
+    const DUPLICATE_KEYS: DuplicateKeys;
+}
+
+/// Reject the objects with duplicate keys, see [DuplicateKeys::Reject]
+pub struct RejectDuplicates;
+
+impl DuplicateKeyPolicy for RejectDuplicates {
+    const DUPLICATE_KEYS: DuplicateKeys = DuplicateKeys::Reject;
+}
+
+/// Keep the first entry of duplicate keys, see [DuplicateKeys::KeepFirst]
+pub struct KeepFirst;
+
+impl DuplicateKeyPolicy for KeepFirst {
+    const DUPLICATE_KEYS: DuplicateKeys = DuplicateKeys::KeepFirst;
+}
+
+/// Keep the last value of duplicate keys, see [DuplicateKeys::KeepLast]
+pub struct KeepLast;
+
+impl DuplicateKeyPolicy for KeepLast {
+    const DUPLICATE_KEYS: DuplicateKeys = DuplicateKeys::KeepLast;
+}
+
+/// An object literal: `{ key: value, ... }`.
+///
+/// Keys are recognized by the `K` visitor and values by the `V` visitor.
+/// Whitespaces are allowed around each token, and the last entry may be
+/// followed by a trailing comma. Two keys are the same if their source is the
+/// same.
+///
+/// # Type Parameters
+///
+/// * `K` - The visitor of the keys
+/// * `V` - The visitor of the values
+/// * `P` - The [DuplicateKeyPolicy], duplicate keys are rejected by default
+pub struct ObjectLiteral<K, V, P = RejectDuplicates> {
+    /// The entries of the object, in the order of the source
+    pub entries: Vec<(K, V)>,
+    _policy: PhantomData<P>,
+}
+
+/// Consume the leading whitespaces
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    let size = scanner
+        .iter()
+        .take_while(|byte| byte.is_ascii_whitespace())
+        .count();
+    scanner.bump_by(size);
+}
+
+/// Consume the expected byte
+fn expect(scanner: &mut Scanner<u8>, expected: u8) -> ParseResult<()> {
+    match scanner.first() {
+        Some(byte) if *byte == expected => {
+            scanner.bump_by(1);
+            Ok(())
+        }
+        Some(_) => Err(ParseError::unexpected_token(scanner.current_position())
+            .expecting(&format!("`{}`", expected as char))),
+        None => Err(ParseError::UnexpectedEndOfInput),
+    }
+}
+
+/// Parse the entries of an object, the scanner is left in an undefined
+/// position on error.
+fn object<'a, K, V, P>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Vec<(K, V)>>
+where
+    K: Visitor<'a, u8>,
+    V: Visitor<'a, u8>,
+    P: DuplicateKeyPolicy,
+{
+    expect(scanner, b'{')?;
+
+    let mut entries = vec![];
+    // the source of the keys, in the same order as the entries
+    let mut keys: Vec<&[u8]> = vec![];
+    loop {
+        skip_whitespaces(scanner);
+        // an empty object or a trailing comma
+        if scanner.first() == Some(&b'}') {
+            scanner.bump_by(1);
+            return Ok(entries);
+        }
+
+        let start = scanner.current_position();
+        let key = K::accept(scanner)?;
+        let source = scanner.since(start);
+        let duplicate = keys.iter().position(|key| *key == source);
+        if duplicate.is_some() && P::DUPLICATE_KEYS == DuplicateKeys::Reject {
+            return Err(ParseError::DuplicateKey {
+                start,
+                end: scanner.current_position(),
+            });
+        }
+
+        skip_whitespaces(scanner);
+        expect(scanner, b':')?;
+        skip_whitespaces(scanner);
+        let value = V::accept(scanner)?;
+
+        match duplicate {
+            None => {
+                keys.push(source);
+                entries.push((key, value));
+            }
+            Some(index) if P::DUPLICATE_KEYS == DuplicateKeys::KeepLast => {
+                entries[index] = (key, value);
+            }
+            Some(_) => {}
+        }
+
+        skip_whitespaces(scanner);
+        match scanner.first() {
+            Some(b',') => scanner.bump_by(1),
+            Some(b'}') => {
+                scanner.bump_by(1);
+                return Ok(entries);
+            }
+            Some(_) => {
+                let err = ParseError::unexpected_token(scanner.current_position());
+                return Err(err.expecting("`,` or `}`"));
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        }
+    }
+}
+
+impl<'a, K, V, P> Visitor<'a, u8> for ObjectLiteral<K, V, P>
+where
+    K: Visitor<'a, u8>,
+    V: Visitor<'a, u8>,
+    P: DuplicateKeyPolicy,
+{
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let entries = object::<K, V, P>(&mut checkpoint)?;
+        checkpoint.commit();
+        Ok(ObjectLiteral {
+            entries,
+            _policy: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acceptor::Acceptor;
+    use crate::bytes::components::object::{KeepFirst, KeepLast, ObjectLiteral};
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::matcher::Match;
+    use crate::recognizer::recognize_slice;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    #[derive(Debug, PartialEq)]
+    struct Key<'a>(&'a [u8]);
+
+    struct TokenKey;
+
+    impl Match<u8> for TokenKey {
+        fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+            let size = data.iter().take_while(|b| b.is_ascii_alphabetic()).count();
+            (size > 0, size)
+        }
+
+        fn size(&self) -> usize {
+            0
+        }
+    }
+
+    impl<'a> Visitor<'a, u8> for Key<'a> {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            Ok(Key(recognize_slice(TokenKey, scanner)?))
+        }
+    }
+
+    enum Value<'a> {
+        Number(usize),
+        Object(ObjectLiteral<Key<'a>, Value<'a>>),
+    }
+
+    impl<'a> Visitor<'a, u8> for Value<'a> {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            Acceptor::new(scanner)
+                .try_or(|Number(number)| Value::Number(number))?
+                .try_or(Value::Object)?
+                .finish()
+                .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+        }
+    }
+
+    #[test]
+    fn test_object_literal() {
+        let mut scanner = Scanner::new(b"{ a: 1,\n\tb :2, } rest");
+        let object = scanner
+            .visit::<ObjectLiteral<Key, Number<usize>>>()
+            .expect("failed to parse");
+        assert_eq!(
+            object.entries,
+            vec![(Key(b"a"), Number(1)), (Key(b"b"), Number(2))]
+        );
+        assert_eq!(scanner.remaining(), b" rest");
+
+        let object = Scanner::new(b"{ }")
+            .visit::<ObjectLiteral<Key, Number<usize>>>()
+            .expect("failed to parse");
+        assert!(object.entries.is_empty());
+
+        for (data, offset) in [
+            (&b"{ , }"[..], 2),
+            (b"{ a 1 }", 4),
+            (b"{ a: 1 b: 2 }", 7),
+            (b"{ a: }", 5),
+        ] {
+            let mut scanner = Scanner::new(data);
+            let result = scanner.visit::<ObjectLiteral<Key, Number<usize>>>();
+            assert!(
+                matches!(result, Err(ParseError::UnexpectedToken { offset: o, .. }) if o == offset),
+                "unexpected result for {:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(scanner.current_position(), 0);
+        }
+
+        let result = Scanner::new(b"{ a: 1").visit::<ObjectLiteral<Key, Number<usize>>>();
+        assert!(matches!(result, Err(ParseError::UnexpectedEndOfInput)));
+    }
+
+    #[test]
+    fn test_nested_object_literal() {
+        let object = Scanner::new(b"{ a: { b: 1, c: {} }, d: 2 }")
+            .visit::<ObjectLiteral<Key, Value>>()
+            .expect("failed to parse");
+        let [(Key(b"a"), Value::Object(a)), (Key(b"d"), Value::Number(2))] =
+            object.entries.as_slice()
+        else {
+            panic!("unexpected entries");
+        };
+        let [(Key(b"b"), Value::Number(1)), (Key(b"c"), Value::Object(c))] = a.entries.as_slice()
+        else {
+            panic!("unexpected nested entries");
+        };
+        assert!(c.entries.is_empty());
+
+        // the span of the duplicate key is relative to the outermost scanner
+        let result = Scanner::new(b"{ a: { b: 1, b: 2 } }").visit::<ObjectLiteral<Key, Value>>();
+        assert!(matches!(
+            result,
+            Err(ParseError::DuplicateKey { start: 13, end: 14 })
+        ));
+    }
+
+    #[test]
+    fn test_object_literal_duplicate_keys() {
+        let data = b"{ a: 1, b: 2, a: 3 }";
+        let result = Scanner::new(data).visit::<ObjectLiteral<Key, Number<usize>>>();
+        assert!(matches!(
+            result,
+            Err(ParseError::DuplicateKey { start: 14, end: 15 })
+        ));
+
+        let object = Scanner::new(data)
+            .visit::<ObjectLiteral<Key, Number<usize>, KeepFirst>>()
+            .expect("failed to parse");
+        assert_eq!(
+            object.entries,
+            vec![(Key(b"a"), Number(1)), (Key(b"b"), Number(2))]
+        );
+
+        let object = Scanner::new(data)
+            .visit::<ObjectLiteral<Key, Number<usize>, KeepLast>>()
+            .expect("failed to parse");
+        assert_eq!(
+            object.entries,
+            vec![(Key(b"a"), Number(3)), (Key(b"b"), Number(2))]
+        );
+    }
+}
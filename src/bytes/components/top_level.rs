@@ -0,0 +1,207 @@
+//! Top level separators components
+//!
+//! Split a list on the separators which are not enclosed in a group, like the
+//! arguments of a function call: `f(a, g(b, c)), "x,y", z` has 3 items.
+
+use crate::bytes::components::groups::GroupKind;
+use crate::bytes::token::Token;
+use crate::errors::ParseResult;
+use crate::matcher::Match;
+use crate::peek::{PeekResult, Peekable};
+use crate::scanner::Scanner;
+
+/// A `Peekable` which peeks until the first separator not enclosed in a group.
+///
+/// Groups are skipped as a whole, so a separator inside parentheses or quotes
+/// is part of the current item. By default, parenthesis, quotes and double
+/// quotes groups are skipped.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::components::top_level::TopLevelSeparator;
+/// use elyze::bytes::token::Token;
+///
+/// let items = TopLevelSeparator::new(Token::Comma)
+///     .split(br#"f(a, g(b, c)), "x,y", z"#)
+///     .expect("failed to split");
+/// assert_eq!(items, vec![&b"f(a, g(b, c))"[..], br#" "x,y""#, b" z"]);
+/// ```
+pub struct TopLevelSeparator<S> {
+    separator: S,
+    groups: Vec<GroupKind>,
+}
+
+impl<S> TopLevelSeparator<S> {
+    /// Create a new `TopLevelSeparator` skipping the default groups
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The separator to look for
+    pub fn new(separator: S) -> Self {
+        TopLevelSeparator {
+            separator,
+            groups: vec![
+                GroupKind::Parenthesis,
+                GroupKind::Quotes,
+                GroupKind::DoubleQuotes,
+            ],
+        }
+    }
+
+    /// Replace the groups skipped while looking for the separator
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - The groups in which the separator is ignored
+    pub fn with_groups(mut self, groups: Vec<GroupKind>) -> Self {
+        self.groups = groups;
+        self
+    }
+}
+
+impl<S: Match<u8>> TopLevelSeparator<S> {
+    /// Find the first top level separator of the data
+    ///
+    /// # Returns
+    ///
+    /// The position of the separator and its size, or `None` if there is no
+    /// top level separator.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(ParseError::UnbalancedGroup)` if a group is never closed.
+    pub(crate) fn find(&self, data: &[u8]) -> ParseResult<Option<(usize, usize)>> {
+        let mut position = 0;
+        'data: while position < data.len() {
+            let remaining = &data[position..];
+
+            // a group is skipped as a whole
+            for group in &self.groups {
+                if let PeekResult::Found { end_slice, .. } = group.peek(&Scanner::new(remaining))? {
+                    position += end_slice;
+                    continue 'data;
+                }
+            }
+
+            if remaining.len() >= self.separator.size() {
+                let (matched, size) = self.separator.is_matching(remaining);
+                if matched {
+                    return Ok(Some((position, size)));
+                }
+            }
+            position += 1;
+        }
+        Ok(None)
+    }
+
+    /// Split the data on each top level separator
+    ///
+    /// An empty data has no item and a trailing separator doesn't create an
+    /// empty item. Items are not trimmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to split
+    ///
+    /// # Returns
+    ///
+    /// The items of the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(ParseError::UnbalancedGroup)` if a group is never closed.
+    pub fn split<'a>(&self, data: &'a [u8]) -> ParseResult<Vec<&'a [u8]>> {
+        let mut items = vec![];
+        let mut remaining = data;
+        while !remaining.is_empty() {
+            match self.find(remaining)? {
+                Some((position, size)) => {
+                    items.push(&remaining[..position]);
+                    remaining = &remaining[position + size..];
+                }
+                None => {
+                    items.push(remaining);
+                    break;
+                }
+            }
+        }
+        Ok(items)
+    }
+}
+
+impl<'a, S: Match<u8>> Peekable<'a, u8> for TopLevelSeparator<S> {
+    fn peek(&self, data: &Scanner<'a, u8>) -> ParseResult<PeekResult> {
+        Ok(match self.find(data.remaining())? {
+            Some((position, size)) => PeekResult::Found {
+                end_slice: position + size,
+                start_element_size: 0,
+                end_element_size: size,
+            },
+            None => PeekResult::NotFound,
+        })
+    }
+}
+
+/// Split a comma separated list on the commas not enclosed in parenthesis,
+/// quotes or double quotes.
+///
+/// See [TopLevelSeparator::split].
+pub fn split_top_level(data: &[u8]) -> ParseResult<Vec<&[u8]>> {
+    TopLevelSeparator::new(Token::Comma).split(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "unstable")]
+    use crate::bytes::components::groups::GroupKind;
+    use crate::bytes::components::top_level::{split_top_level, TopLevelSeparator};
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::peek::peek;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_split_top_level() {
+        let items = split_top_level(br#"f(a, g(b, c)), "x,y", z"#).expect("failed to split");
+        assert_eq!(items, vec![&b"f(a, g(b, c))"[..], br#" "x,y""#, b" z"]);
+
+        let items = split_top_level(br#"'a\', b', c,"#).expect("failed to split");
+        assert_eq!(items, vec![&br#"'a\', b'"#[..], b" c"]);
+
+        let items = split_top_level(b"a,,b").expect("failed to split");
+        assert_eq!(items, vec![&b"a"[..], b"", b"b"]);
+
+        let items = split_top_level(b"").expect("failed to split");
+        assert!(items.is_empty());
+
+        assert!(matches!(
+            split_top_level(b"f(a, b"),
+            Err(ParseError::UnbalancedGroup)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn test_top_level_separator_groups() {
+        let splitter = TopLevelSeparator::new(Token::Semicolon).with_groups(vec![GroupKind::Tag]);
+        let items = splitter
+            .split(b"<a>x;y</a>;(b;c)")
+            .expect("failed to split");
+        assert_eq!(items, vec![&b"<a>x;y</a>"[..], b"(b", b"c)"]);
+    }
+
+    #[test]
+    fn test_peek_top_level_separator() {
+        let scanner = Scanner::new(b"(a, b), c");
+        let peeked = peek(TopLevelSeparator::new(Token::Comma), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"(a, b)");
+        assert_eq!(peeked.end_slice, 7);
+
+        let scanner = Scanner::new(b"(a, b)");
+        let peeked = peek(TopLevelSeparator::new(Token::Comma), &scanner).expect("failed to parse");
+        assert_eq!(peeked, None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Confusable characters matching.
+//!
+//! Text copy-pasted from a word processor often contains typographic variants
+//! of the ASCII characters, like curly quotes or dashes. An [AliasTable] maps
+//! these variants to their canonical form, and the [Confusable] matcher uses it
+//! to treat them as equivalent.
+
+use crate::matcher::Match;
+use std::borrow::Cow;
+
+/// A table of aliases, mapping a sequence of bytes to its canonical form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasTable {
+    /// The aliases and their canonical form
+    aliases: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Default for AliasTable {
+    /// The typographic aliases, see [AliasTable::typographic]
+    fn default() -> Self {
+        AliasTable::typographic()
+    }
+}
+
+impl AliasTable {
+    /// Create an empty table
+    pub fn new() -> Self {
+        AliasTable { aliases: vec![] }
+    }
+
+    /// Create a table of the common typographic variants
+    ///
+    /// * the curly and low single quotes and the prime are aliases of `'`
+    /// * the curly and low double quotes and the double prime are aliases of `"`
+    /// * the hyphen, the non-breaking hyphen, the figure dash, the en dash, the
+    ///   em dash and the minus sign are aliases of `-`
+    /// * the no-break space and the narrow no-break space are aliases of ` `
+    /// * the ellipsis is an alias of `...`
+    pub fn typographic() -> Self {
+        let mut table = AliasTable::new();
+        for alias in ["‘", "’", "‚", "′"] {
+            table = table.with_alias(alias, "'");
+        }
+        for alias in ["“", "”", "„", "″"] {
+            table = table.with_alias(alias, "\"");
+        }
+        for alias in ["‐", "‑", "‒", "–", "—", "−"] {
+            table = table.with_alias(alias, "-");
+        }
+        for alias in ["\u{a0}", "\u{202f}"] {
+            table = table.with_alias(alias, " ");
+        }
+        table.with_alias("…", "...")
+    }
+
+    /// Add an alias to the table
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The variant
+    /// * `canonical` - The canonical form of the variant
+    pub fn with_alias(mut self, alias: &str, canonical: &str) -> Self {
+        self.aliases
+            .push((alias.as_bytes().to_vec(), canonical.as_bytes().to_vec()));
+        self
+    }
+
+    /// Find the alias at the start of the data
+    ///
+    /// # Returns
+    ///
+    /// The alias and its canonical form, if any.
+    fn alias_at(&self, data: &[u8]) -> Option<(&[u8], &[u8])> {
+        self.aliases
+            .iter()
+            .find(|(alias, _)| !alias.is_empty() && data.starts_with(alias))
+            .map(|(alias, canonical)| (alias.as_slice(), canonical.as_slice()))
+    }
+
+    /// Replace each alias of the data by its canonical form
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to normalize
+    ///
+    /// # Returns
+    ///
+    /// The normalized data, only copied if it contains aliases.
+    pub fn normalize<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        let mut normalized: Option<Vec<u8>> = None;
+        let mut position = 0;
+        while position < data.len() {
+            match self.alias_at(&data[position..]) {
+                Some((alias, canonical)) => {
+                    normalized
+                        .get_or_insert_with(|| data[..position].to_vec())
+                        .extend_from_slice(canonical);
+                    position += alias.len();
+                }
+                None => {
+                    if let Some(normalized) = normalized.as_mut() {
+                        normalized.push(data[position]);
+                    }
+                    position += 1;
+                }
+            }
+        }
+        match normalized {
+            Some(normalized) => Cow::Owned(normalized),
+            None => Cow::Borrowed(data),
+        }
+    }
+}
+
+/// Match a pattern, an alias of the table matching its canonical form.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::confusables::{AliasTable, Confusable};
+/// use elyze::matcher::Match;
+///
+/// let table = AliasTable::typographic();
+/// let matcher = Confusable::new(b"\"quoted\"", &table);
+/// assert_eq!(matcher.is_matching("“quoted”".as_bytes()), (true, 12));
+/// assert_eq!(matcher.is_matching(b"\"quoted\""), (true, 8));
+/// ```
+pub struct Confusable<'t> {
+    /// The pattern to match
+    pattern: Vec<u8>,
+    /// The aliases accepted in the data
+    table: &'t AliasTable,
+}
+
+impl<'t> Confusable<'t> {
+    /// Create a new `Confusable` matcher
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to match, in its canonical form
+    /// * `table` - The aliases accepted in the data
+    pub fn new(pattern: &[u8], table: &'t AliasTable) -> Self {
+        Confusable {
+            pattern: pattern.to_vec(),
+            table,
+        }
+    }
+}
+
+impl Match<u8> for Confusable<'_> {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        if self.pattern.is_empty() {
+            return (false, 0);
+        }
+
+        let mut pattern = self.pattern.as_slice();
+        let mut position = 0;
+        while !pattern.is_empty() {
+            let remaining = &data[position..];
+            if remaining.first() == pattern.first() {
+                pattern = &pattern[1..];
+                position += 1;
+                continue;
+            }
+            match self.table.alias_at(remaining) {
+                Some((alias, canonical)) if pattern.starts_with(canonical) => {
+                    pattern = &pattern[canonical.len()..];
+                    position += alias.len();
+                }
+                _ => return (false, 0),
+            }
+        }
+        (true, position)
+    }
+
+    /// An alias may be shorter than its canonical form
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::confusables::{AliasTable, Confusable};
+    use crate::matcher::Match;
+    use crate::recognizer::recognize_slice;
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_confusable() {
+        let table = AliasTable::typographic();
+        let matcher = Confusable::new(b"a-b", &table);
+        assert_eq!(matcher.is_matching("a–b".as_bytes()), (true, 5));
+        assert_eq!(matcher.is_matching("a—b".as_bytes()), (true, 5));
+        assert_eq!(matcher.is_matching(b"a-b"), (true, 3));
+        assert_eq!(matcher.is_matching(b"a+b"), (false, 0));
+        assert_eq!(matcher.is_matching(b"a-"), (false, 0));
+
+        // an alias may stand for several bytes
+        let matcher = Confusable::new(b"wait...", &table);
+        assert_eq!(matcher.is_matching("wait… what".as_bytes()), (true, 7));
+
+        let mut scanner = Scanner::new("‘it’s’ rest".as_bytes());
+        let recognized = recognize_slice(Confusable::new(b"'it's'", &table), &mut scanner)
+            .expect("failed to parse");
+        assert_eq!(recognized, "‘it’s’".as_bytes());
+        assert_eq!(scanner.remaining(), b" rest");
+    }
+
+    #[test]
+    fn test_custom_alias_table() {
+        let table = AliasTable::new().with_alias("→", "->");
+        let matcher = Confusable::new(b"a->b", &table);
+        assert_eq!(matcher.is_matching("a→b".as_bytes()), (true, 5));
+        assert_eq!(matcher.is_matching("a–>b".as_bytes()), (false, 0));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let table = AliasTable::default();
+        assert_eq!(
+            table.normalize("“a” – b…".as_bytes()),
+            Cow::<[u8]>::Owned(b"\"a\" - b...".to_vec())
+        );
+        assert!(matches!(table.normalize(b"plain"), Cow::Borrowed(b"plain")));
+    }
+}
@@ -0,0 +1,326 @@
+//! Line continuation handling.
+//!
+//! Makefiles, shell scripts or INI files allow to split a logical line over
+//! several physical lines by ending them with a continuation character. The
+//! lines are joined before the parse, and the positions in the joined data can
+//! be mapped back to the original data to report errors.
+
+use std::borrow::Cow;
+
+/// Join the lines ending with a continuation character.
+///
+/// The continuation character and the following line break, `\n` or `\r\n`,
+/// are removed. A doubled continuation character is an escaped one and
+/// doesn't continue the line.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::continuation::LineContinuation;
+///
+/// let data = b"CFLAGS = -O2 \\\n  -Wall\nLDFLAGS =";
+/// let joined = LineContinuation::default().join(data);
+/// assert_eq!(joined.data(), b"CFLAGS = -O2   -Wall\nLDFLAGS =");
+/// // the position of `-Wall` in the original data
+/// assert_eq!(joined.original_offset(15), 17);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineContinuation {
+    /// The continuation character
+    marker: u8,
+}
+
+impl Default for LineContinuation {
+    /// The backslash continuation character
+    fn default() -> Self {
+        LineContinuation::new(b'\\')
+    }
+}
+
+impl LineContinuation {
+    /// Create a new `LineContinuation`
+    ///
+    /// # Arguments
+    ///
+    /// * `marker` - The continuation character
+    pub fn new(marker: u8) -> Self {
+        LineContinuation { marker }
+    }
+
+    /// Find the next continuation of the data
+    ///
+    /// # Returns
+    ///
+    /// The position of the continuation character and the size of the
+    /// continuation, line break included.
+    fn find(&self, data: &[u8]) -> Option<(usize, usize)> {
+        let mut position = 0;
+        while position < data.len() {
+            let line_break = data[position..].iter().position(|byte| *byte == b'\n')?;
+            let line_end = position + line_break;
+            let line = &data[position..line_end];
+            let (line, cr) = match line.strip_suffix(b"\r") {
+                Some(line) => (line, 1),
+                None => (line, 0),
+            };
+            let markers = line
+                .iter()
+                .rev()
+                .take_while(|byte| **byte == self.marker)
+                .count();
+            if markers % 2 == 1 {
+                let start = position + line.len() - 1;
+                return Some((start, 1 + cr + 1));
+            }
+            position = line_end + 1;
+        }
+        None
+    }
+
+    /// Join the continued lines of the data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to join
+    ///
+    /// # Returns
+    ///
+    /// The joined lines, only copied if a line is continued.
+    pub fn join<'a>(&self, data: &'a [u8]) -> JoinedLines<'a> {
+        JoinedLines::remove(data, |data| self.find(data))
+    }
+}
+
+/// Unfold the lines folded by a line break followed by a space or a tab, as
+/// done by the content lines of vCard and iCalendar or the headers of emails.
+///
+/// The line break, `\n` or `\r\n`, and the single whitespace following it
+/// are removed.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::continuation::LineFolding;
+///
+/// let data = b"DESCRIPTION:a long\r\n  description\r\nEND:VEVENT";
+/// let unfolded = LineFolding::default().unfold(data);
+/// assert_eq!(unfolded.data(), b"DESCRIPTION:a long description\r\nEND:VEVENT");
+/// // the position of `description` in the original data
+/// assert_eq!(unfolded.original_offset(19), 22);
+///
+/// let folded = LineFolding::new(10).fold(b"DESCRIPTION:a long description");
+/// assert_eq!(folded, b"DESCRIPTIO\r\n N:a long \r\n descripti\r\n on");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineFolding {
+    /// The maximal size of a folded line, in bytes, line break excluded
+    width: usize,
+}
+
+impl Default for LineFolding {
+    /// The folding of the content lines, at 75 bytes
+    fn default() -> Self {
+        LineFolding::new(75)
+    }
+}
+
+impl LineFolding {
+    /// Create a new `LineFolding`
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The maximal size of a folded line, in bytes, line break
+    ///   excluded, at least 2 bytes
+    pub fn new(width: usize) -> Self {
+        LineFolding {
+            width: width.max(2),
+        }
+    }
+
+    /// Find the next fold of the data
+    ///
+    /// # Returns
+    ///
+    /// The position of the line break and the size of the fold, whitespace
+    /// included.
+    fn find(data: &[u8]) -> Option<(usize, usize)> {
+        let line_break = data
+            .windows(2)
+            .position(|window| window[0] == b'\n' && matches!(window[1], b' ' | b'\t'))?;
+        if line_break > 0 && data[line_break - 1] == b'\r' {
+            Some((line_break - 1, 3))
+        } else {
+            Some((line_break, 2))
+        }
+    }
+
+    /// Unfold the folded lines of the data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to unfold
+    ///
+    /// # Returns
+    ///
+    /// The unfolded lines, only copied if a line is folded.
+    pub fn unfold<'a>(&self, data: &'a [u8]) -> JoinedLines<'a> {
+        JoinedLines::remove(data, LineFolding::find)
+    }
+
+    /// Fold a line, without line break, so each folded line fits the width
+    ///
+    /// The line is only broken between UTF-8 characters, the folded lines
+    /// are separated by `\r\n` and a space.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to fold
+    pub fn fold(&self, line: &[u8]) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(line.len() + line.len() / self.width * 3);
+        let mut position = 0;
+        // the first line has no leading space
+        let mut width = self.width;
+        while line.len() - position > width {
+            let mut end = position + width;
+            // don't break a UTF-8 character
+            while end > position + 1 && line[end] & 0b1100_0000 == 0b1000_0000 {
+                end -= 1;
+            }
+            folded.extend_from_slice(&line[position..end]);
+            folded.extend_from_slice(b"\r\n ");
+            position = end;
+            width = self.width - 1;
+        }
+        folded.extend_from_slice(&line[position..]);
+        folded
+    }
+}
+
+/// Lines joined by a [LineContinuation] or unfolded by a [LineFolding].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinedLines<'a> {
+    /// The joined data
+    data: Cow<'a, [u8]>,
+    /// The position of each segment in the joined data and in the original
+    /// data
+    segments: Vec<(usize, usize)>,
+}
+
+impl<'a> JoinedLines<'a> {
+    /// Remove the parts of the data found by the function
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to join
+    /// * `find` - Return the position and the size of the next part to remove
+    fn remove(data: &'a [u8], find: impl Fn(&[u8]) -> Option<(usize, usize)>) -> Self {
+        let mut joined: Option<Vec<u8>> = None;
+        let mut segments = vec![(0, 0)];
+        let mut position = 0;
+        while let Some((start, size)) = find(&data[position..]) {
+            let joined = joined.get_or_insert_with(Vec::new);
+            joined.extend_from_slice(&data[position..position + start]);
+            position += start + size;
+            segments.push((joined.len(), position));
+        }
+
+        let data = match joined {
+            Some(mut joined) => {
+                joined.extend_from_slice(&data[position..]);
+                Cow::Owned(joined)
+            }
+            None => Cow::Borrowed(data),
+        };
+        JoinedLines { data, segments }
+    }
+}
+
+impl JoinedLines<'_> {
+    /// Return the joined data
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Map a position of the joined data to the original data
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The position in the joined data
+    ///
+    /// # Returns
+    ///
+    /// The position in the original data.
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let index = self
+            .segments
+            .partition_point(|(joined, _)| *joined <= offset)
+            .saturating_sub(1);
+        let (joined, original) = self.segments[index];
+        original + offset - joined
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::continuation::{LineContinuation, LineFolding};
+    use crate::bytes::primitives::number::Number;
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_join_lines() {
+        let data = b"a \\\r\nb\\\n\\\nc\nd \\\\\ne\\";
+        let joined = LineContinuation::default().join(data);
+        assert_eq!(joined.data(), b"a bc\nd \\\\\ne\\");
+        assert_eq!(joined.original_offset(0), 0);
+        // b
+        assert_eq!(joined.original_offset(2), 5);
+        // c
+        assert_eq!(joined.original_offset(3), 10);
+        // e
+        assert_eq!(joined.original_offset(10), 17);
+
+        let joined = LineContinuation::default().join(b"a\nb");
+        assert!(matches!(joined.data, Cow::Borrowed(b"a\nb")));
+        assert_eq!(joined.original_offset(2), 2);
+    }
+
+    #[test]
+    fn test_join_lines_custom_marker() {
+        let data = b"x = 1 + &\n    2";
+        let joined = LineContinuation::new(b'&').join(data);
+        assert_eq!(joined.data(), b"x = 1 +     2");
+
+        // map the position of a parsed element back to the original data
+        let mut scanner = Scanner::new(joined.data());
+        scanner.bump_by(12);
+        let start = scanner.current_position();
+        let Number(number) = scanner.visit::<Number<u8>>().expect("failed to parse");
+        assert_eq!(number, 2);
+        assert_eq!(joined.original_offset(start), 14);
+        assert_eq!(&data[14..], b"2");
+    }
+
+    #[test]
+    fn test_line_folding() {
+        let data = b"A:1\r\n 2\n\t3\nB:\r\n\r\n 4";
+        let unfolded = LineFolding::default().unfold(data);
+        assert_eq!(unfolded.data(), b"A:123\nB:\r\n4");
+        // 2
+        assert_eq!(unfolded.original_offset(3), 6);
+        // 3
+        assert_eq!(unfolded.original_offset(4), 9);
+        // 4
+        assert_eq!(unfolded.original_offset(10), 18);
+
+        let unfolded = LineFolding::default().unfold(b"A:1\r\nB:2 \r\n");
+        assert!(matches!(unfolded.data, Cow::Borrowed(_)));
+
+        // the UTF-8 characters are not broken
+        let line = "NOTE:né à l'été".as_bytes();
+        let folded = LineFolding::new(7).fold(line);
+        assert_eq!(folded, "NOTE:n\r\n é à \r\n l'ét\r\n é".as_bytes());
+        assert_eq!(LineFolding::new(7).unfold(&folded).data(), line);
+        assert_eq!(LineFolding::default().fold(line), line);
+    }
+}
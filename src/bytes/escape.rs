@@ -0,0 +1,396 @@
+//! Escape sequence decoders.
+//!
+//! An [EscapeDecoder] knows how to decode one kind of escape sequence, the
+//! [unescape] and [unescape_str] functions apply it on a whole slice, so the
+//! primitives decoding escaped data share the same implementation.
+
+use crate::errors::{ParseError, ParseResult};
+use std::borrow::Cow;
+
+/// Decode a kind of escape sequence.
+pub trait EscapeDecoder {
+    /// The byte introducing an escape sequence
+    fn escape_byte(&self) -> u8;
+
+    /// Decode the escape sequence at the start of the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data starting with the escape byte
+    /// * `output` - The buffer receiving the decoded bytes
+    ///
+    /// # Returns
+    ///
+    /// The size of the escape sequence.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` at the start of the data if the escape
+    /// sequence is invalid, `ParseError::UnexpectedEndOfInput` if it is
+    /// truncated.
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize>;
+}
+
+/// Decode all the escape sequences of the data.
+///
+/// # Arguments
+///
+/// * `decoder` - The decoder of the escape sequences
+/// * `data` - The data to decode
+///
+/// # Returns
+///
+/// The decoded data, only copied if it contains escape sequences.
+///
+/// # Errors
+///
+/// Any error of the decoder, at the position of the escape sequence in the
+/// data.
+pub fn unescape<'a, D: EscapeDecoder>(decoder: &D, data: &'a [u8]) -> ParseResult<Cow<'a, [u8]>> {
+    let escape = decoder.escape_byte();
+    let Some(first) = data.iter().position(|byte| *byte == escape) else {
+        return Ok(Cow::Borrowed(data));
+    };
+
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&data[..first]);
+    let mut position = first;
+    while position < data.len() {
+        if data[position] == escape {
+            position += decoder
+                .decode(&data[position..], &mut output)
+                .map_err(|err| err.shifted(position))?;
+            continue;
+        }
+        let size = data[position..]
+            .iter()
+            .position(|byte| *byte == escape)
+            .unwrap_or(data.len() - position);
+        output.extend_from_slice(&data[position..position + size]);
+        position += size;
+    }
+    Ok(Cow::Owned(output))
+}
+
+/// Decode all the escape sequences of the data as an UTF-8 string.
+///
+/// # Arguments
+///
+/// * `decoder` - The decoder of the escape sequences
+/// * `data` - The data to decode
+///
+/// # Returns
+///
+/// The decoded string, only copied if it contains escape sequences.
+///
+/// # Errors
+///
+/// `ParseError::Utf8Error` if the decoded data is not valid UTF-8.
+pub fn unescape_str<'a, D: EscapeDecoder>(
+    decoder: &D,
+    data: &'a [u8],
+) -> ParseResult<Cow<'a, str>> {
+    match unescape(decoder, data)? {
+        Cow::Borrowed(data) => Ok(Cow::Borrowed(std::str::from_utf8(data)?)),
+        Cow::Owned(data) => match String::from_utf8(data) {
+            Ok(string) => Ok(Cow::Owned(string)),
+            Err(err) => Err(err.utf8_error().into()),
+        },
+    }
+}
+
+/// Push a char encoded as UTF-8
+fn push_char(code: u32, output: &mut Vec<u8>) -> ParseResult<()> {
+    let char = char::from_u32(code).ok_or(ParseError::unexpected_token(0))?;
+    output.extend_from_slice(char.encode_utf8(&mut [0; 4]).as_bytes());
+    Ok(())
+}
+
+/// Parse an hexadecimal number
+fn parse_hex(data: &[u8]) -> ParseResult<u32> {
+    if data.is_empty() || data.len() > 8 || !data.iter().all(u8::is_ascii_hexdigit) {
+        return Err(ParseError::unexpected_token(0));
+    }
+    let hex = std::str::from_utf8(data)?;
+    Ok(u32::from_str_radix(hex, 16)?)
+}
+
+/// Decode the character references `&...;` with the given named entities
+fn decode_entity(
+    data: &[u8],
+    output: &mut Vec<u8>,
+    named: impl Fn(&[u8]) -> Option<&'static str>,
+) -> ParseResult<usize> {
+    let end = data
+        .iter()
+        .position(|byte| *byte == b';')
+        .ok_or(ParseError::UnexpectedEndOfInput)?;
+    let entity = &data[1..end];
+    if let Some(hex) = entity
+        .strip_prefix(b"#x")
+        .or_else(|| entity.strip_prefix(b"#X"))
+    {
+        push_char(parse_hex(hex)?, output)?;
+    } else if let Some(decimal) = entity.strip_prefix(b"#") {
+        if decimal.is_empty() || !decimal.iter().all(u8::is_ascii_digit) {
+            return Err(ParseError::unexpected_token(0));
+        }
+        push_char(std::str::from_utf8(decimal)?.parse()?, output)?;
+    } else {
+        let decoded = named(entity).ok_or(ParseError::unexpected_token(0))?;
+        output.extend_from_slice(decoded.as_bytes());
+    }
+    Ok(end + 1)
+}
+
+/// The XML predefined entities `&amp;`, `&lt;`, `&gt;`, `&quot;`, `&apos;`
+/// and the numeric character references `&#65;` and `&#x41;`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct XmlEntities;
+
+fn xml_entity(entity: &[u8]) -> Option<&'static str> {
+    match entity {
+        b"amp" => Some("&"),
+        b"lt" => Some("<"),
+        b"gt" => Some(">"),
+        b"quot" => Some("\""),
+        b"apos" => Some("'"),
+        _ => None,
+    }
+}
+
+impl EscapeDecoder for XmlEntities {
+    fn escape_byte(&self) -> u8 {
+        b'&'
+    }
+
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize> {
+        decode_entity(data, output, xml_entity)
+    }
+}
+
+/// The XML entities and the most common HTML named entities.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HtmlEntities;
+
+impl EscapeDecoder for HtmlEntities {
+    fn escape_byte(&self) -> u8 {
+        b'&'
+    }
+
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize> {
+        decode_entity(data, output, |entity| {
+            xml_entity(entity).or(match entity {
+                b"nbsp" => Some("\u{a0}"),
+                b"copy" => Some("©"),
+                b"reg" => Some("®"),
+                b"trade" => Some("™"),
+                b"hellip" => Some("…"),
+                b"ndash" => Some("–"),
+                b"mdash" => Some("—"),
+                b"lsquo" => Some("‘"),
+                b"rsquo" => Some("’"),
+                b"ldquo" => Some("“"),
+                b"rdquo" => Some("”"),
+                b"laquo" => Some("«"),
+                b"raquo" => Some("»"),
+                b"euro" => Some("€"),
+                b"deg" => Some("°"),
+                b"times" => Some("×"),
+                _ => None,
+            })
+        })
+    }
+}
+
+/// The Rust and C backslash escapes: `\n`, `\r`, `\t`, `\0`, `\\`, `\'`,
+/// `\"`, `\a`, `\b`, `\f`, `\v`, `\xHH`, `\u{H...}` and `\uHHHH`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BackslashEscapes;
+
+impl EscapeDecoder for BackslashEscapes {
+    fn escape_byte(&self) -> u8 {
+        b'\\'
+    }
+
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize> {
+        let escaped = *data.get(1).ok_or(ParseError::UnexpectedEndOfInput)?;
+        let simple = match escaped {
+            b'n' => Some(b'\n'),
+            b'r' => Some(b'\r'),
+            b't' => Some(b'\t'),
+            b'0' => Some(b'\0'),
+            b'\\' => Some(b'\\'),
+            b'\'' => Some(b'\''),
+            b'"' => Some(b'"'),
+            b'a' => Some(0x07),
+            b'b' => Some(0x08),
+            b'f' => Some(0x0c),
+            b'v' => Some(0x0b),
+            _ => None,
+        };
+        if let Some(byte) = simple {
+            output.push(byte);
+            return Ok(2);
+        }
+
+        match escaped {
+            b'x' => {
+                let hex = data.get(2..4).ok_or(ParseError::UnexpectedEndOfInput)?;
+                output.push(parse_hex(hex)? as u8);
+                Ok(4)
+            }
+            b'u' if data.get(2) == Some(&b'{') => {
+                let end = data
+                    .iter()
+                    .position(|byte| *byte == b'}')
+                    .ok_or(ParseError::UnexpectedEndOfInput)?;
+                push_char(parse_hex(&data[3..end])?, output)?;
+                Ok(end + 1)
+            }
+            b'u' => {
+                let hex = data.get(2..6).ok_or(ParseError::UnexpectedEndOfInput)?;
+                push_char(parse_hex(hex)?, output)?;
+                Ok(6)
+            }
+            _ => Err(ParseError::unexpected_token(0)),
+        }
+    }
+}
+
+/// The URL percent-encoding `%HH`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PercentEncoding;
+
+impl EscapeDecoder for PercentEncoding {
+    fn escape_byte(&self) -> u8 {
+        b'%'
+    }
+
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize> {
+        let hex = data.get(1..3).ok_or(ParseError::UnexpectedEndOfInput)?;
+        output.push(parse_hex(hex)? as u8);
+        Ok(3)
+    }
+}
+
+/// The escapes of the structured data parameter values of syslog (RFC 5424):
+/// `\"`, `\\` and `\]`. A backslash before any other byte is kept as is.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SyslogEscapes;
+
+impl EscapeDecoder for SyslogEscapes {
+    fn escape_byte(&self) -> u8 {
+        b'\\'
+    }
+
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize> {
+        match data.get(1) {
+            Some(escaped @ (b'"' | b'\\' | b']')) => {
+                output.push(*escaped);
+                Ok(2)
+            }
+            _ => {
+                output.push(b'\\');
+                Ok(1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::escape::{
+        unescape, unescape_str, BackslashEscapes, HtmlEntities, PercentEncoding, SyslogEscapes,
+        XmlEntities,
+    };
+    use crate::errors::{ParseError, ParseResult};
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_unescape_borrowed() -> ParseResult<()> {
+        assert!(matches!(
+            unescape(&PercentEncoding, b"plain")?,
+            Cow::Borrowed(b"plain")
+        ));
+        assert!(matches!(
+            unescape_str(&XmlEntities, b"plain")?,
+            Cow::Borrowed("plain")
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_entities() -> ParseResult<()> {
+        let decoded = unescape_str(&XmlEntities, b"a &lt; b &amp;&amp; &#65;&#x42;")?;
+        assert_eq!(decoded, "a < b && AB");
+        assert!(matches!(
+            unescape_str(&XmlEntities, b"&nbsp;"),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        // the error is reported at the position of the escape sequence
+        assert!(matches!(
+            unescape_str(&XmlEntities, b"a &lt; &nbsp;"),
+            Err(ParseError::UnexpectedToken { offset: 7, .. })
+        ));
+        assert_eq!(
+            unescape_str(&HtmlEntities, b"a&nbsp;&mdash;&gt;")?,
+            "a\u{a0}—>"
+        );
+        assert!(matches!(
+            unescape_str(&HtmlEntities, b"&amp"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_backslash_escapes() -> ParseResult<()> {
+        let decoded = unescape_str(
+            &BackslashEscapes,
+            r#"a\tb\n\"c\" \\ \x41\u{1F600}é"#.as_bytes(),
+        )?;
+        assert_eq!(decoded, "a\tb\n\"c\" \\ A😀é");
+        assert!(matches!(
+            unescape(&BackslashEscapes, br"\q"),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert!(matches!(
+            unescape(&BackslashEscapes, br"\u{12"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            unescape(&BackslashEscapes, br"\u{D800}"),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_percent_encoding() -> ParseResult<()> {
+        assert_eq!(
+            unescape(&PercentEncoding, b"a%20b%2Fc%ff")?,
+            &b"a b/c\xff"[..]
+        );
+        assert!(matches!(
+            unescape_str(&PercentEncoding, b"%ff"),
+            Err(ParseError::Utf8Error(_))
+        ));
+        assert!(matches!(
+            unescape(&PercentEncoding, b"%zz"),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert!(matches!(
+            unescape(&PercentEncoding, b"%2"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_syslog_escapes() -> ParseResult<()> {
+        let decoded = unescape_str(&SyslogEscapes, br#"a \"b\" \] c:\\ \n\"#)?;
+        assert_eq!(decoded, r#"a "b" ] c:\ \n\"#);
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 //! Byte slice matchers.
+
+use crate::matcher::{Match, MatchOptions};
+
 /// Attempt to match a single character against a byte slice.
 ///
 /// # Arguments
@@ -14,7 +17,11 @@ pub fn match_char(pattern: char, data: &[u8]) -> (bool, usize) {
     (pattern as u8 == data[0], 1)
 }
 
-/// Attempt to match a byte slice against a byte slice.
+/// Attempt to match a byte slice against a byte slice, ignoring the ASCII
+/// case.
+///
+/// This is [match_pattern_with] with a case-insensitive comparison, see
+/// [Pattern] for a matcher of either sensitivity.
 ///
 /// # Arguments
 ///
@@ -26,6 +33,22 @@ pub fn match_char(pattern: char, data: &[u8]) -> (bool, usize) {
 /// A tuple containing a boolean indicating whether the match succeeded and
 /// the number of bytes consumed if the match succeeded.
 pub fn match_pattern(pattern: &[u8], data: &[u8]) -> (bool, usize) {
+    match_pattern_with(pattern, data, MatchOptions::default().case_sensitive(false))
+}
+
+/// Attempt to match a byte slice against a byte slice with the given options.
+///
+/// # Arguments
+///
+/// * `pattern` - The byte slice to match against.
+/// * `data` - The byte slice to match against.
+/// * `options` - How the pattern is compared to the data.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded.
+pub fn match_pattern_with(pattern: &[u8], data: &[u8], options: MatchOptions) -> (bool, usize) {
     if pattern.is_empty() {
         return (false, 0);
     }
@@ -34,13 +57,74 @@ pub fn match_pattern(pattern: &[u8], data: &[u8]) -> (bool, usize) {
         return (false, 0);
     }
 
-    if pattern.eq_ignore_ascii_case(&data[..pattern.len()]) {
+    let candidate = &data[..pattern.len()];
+    let matched = if options.is_case_sensitive() {
+        pattern == candidate
+    } else {
+        pattern.eq_ignore_ascii_case(candidate)
+    };
+    if matched {
         return (true, pattern.len());
     }
 
     (false, 0)
 }
 
+/// A matcher of a fixed byte pattern.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::matchers::Pattern;
+/// use elyze::recognizer::recognize_slice;
+/// use elyze::scanner::Scanner;
+///
+/// const SELECT: Pattern = Pattern::ignore_case(b"SELECT");
+///
+/// let mut scanner = Scanner::new(b"Select * FROM t");
+/// assert!(recognize_slice(Pattern::new(b"SELECT"), &mut scanner).is_err());
+/// let keyword = recognize_slice(SELECT, &mut scanner).expect("failed to parse");
+/// assert_eq!(keyword, b"Select");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pattern<'a> {
+    /// The bytes to match
+    pattern: &'a [u8],
+    /// How the pattern is compared to the data
+    options: MatchOptions,
+}
+
+impl<'a> Pattern<'a> {
+    /// Create a case-sensitive matcher of the pattern.
+    pub const fn new(pattern: &'a [u8]) -> Self {
+        Pattern {
+            pattern,
+            options: MatchOptions::new(),
+        }
+    }
+
+    /// Create a matcher of the pattern ignoring the ASCII case.
+    pub const fn ignore_case(pattern: &'a [u8]) -> Self {
+        Self::new(pattern).with_options(MatchOptions::new().case_sensitive(false))
+    }
+
+    /// Set how the pattern is compared to the data.
+    pub const fn with_options(mut self, options: MatchOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl Match<u8> for Pattern<'_> {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        match_pattern_with(self.pattern, data, self.options)
+    }
+
+    fn size(&self) -> usize {
+        self.pattern.len()
+    }
+}
+
 /// Attempt to match a number against a byte slice.
 ///
 /// # Arguments
@@ -74,6 +158,29 @@ pub fn match_number(data: &[u8]) -> (bool, usize) {
     (found, pos)
 }
 
+/// Attempt to match a number written in the given radix against a byte slice.
+///
+/// # Arguments
+///
+/// * `radix` - The radix of the number, digits above 9 are letters of any case.
+/// * `data` - The byte slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of bytes consumed if the match succeeded. A radix outside of
+/// `2..=36` never matches.
+pub fn match_number_radix(radix: u32, data: &[u8]) -> (bool, usize) {
+    if !(2..=36).contains(&radix) {
+        return (false, 0);
+    }
+    let size = data
+        .iter()
+        .take_while(|byte| (**byte as char).is_digit(radix))
+        .count();
+    (size > 0, size)
+}
+
 /// Attempt to match a string against a byte slice.
 /// Stop matching when a punctuation character is encountered.
 ///  * U+0021 ..= U+002F ! " # $ % & ' ( ) * + , - . /, or
@@ -114,7 +221,11 @@ pub fn match_string(data: &[u8]) -> (bool, usize) {
 
 #[cfg(test)]
 mod tests {
-    use crate::bytes::matchers::{match_char, match_number, match_pattern, match_string};
+    use crate::bytes::matchers::{
+        match_char, match_number, match_number_radix, match_pattern, match_pattern_with,
+        match_string, Pattern,
+    };
+    use crate::matcher::{Match, MatchOptions};
 
     #[test]
     fn test_match_char() {
@@ -138,6 +249,23 @@ mod tests {
         assert_eq!(consumed, 0);
     }
 
+    #[test]
+    fn test_match_pattern_case() {
+        assert_eq!(match_pattern(b"Abc", b"aBC"), (true, 3));
+        let options = MatchOptions::default();
+        assert!(options.is_case_sensitive());
+        assert_eq!(match_pattern_with(b"Abc", b"aBC", options), (false, 0));
+        assert_eq!(match_pattern_with(b"Abc", b"Abcd", options), (true, 3));
+        let options = options.case_sensitive(false);
+        assert_eq!(match_pattern_with(b"Abc", b"aBC", options), (true, 3));
+        assert_eq!(match_pattern_with(b"", b"aBC", options), (false, 0));
+
+        assert_eq!(Pattern::new(b"<=").is_matching(b"<=>"), (true, 2));
+        assert_eq!(Pattern::new(b"If").is_matching(b"if"), (false, 0));
+        assert_eq!(Pattern::ignore_case(b"If").is_matching(b"iF"), (true, 2));
+        assert_eq!(Pattern::ignore_case(b"If").size(), 2);
+    }
+
     #[test]
     fn test_match_number() {
         let (result, consumed) = match_number(b"123abc");
@@ -149,6 +277,26 @@ mod tests {
         assert_eq!(consumed, 0);
     }
 
+    #[test]
+    fn test_match_number_radix() {
+        let (result, consumed) = match_number_radix(16, b"1aF+");
+        assert!(result);
+        assert_eq!(consumed, 3);
+
+        let (result, consumed) = match_number_radix(2, b"1012");
+        assert!(result);
+        assert_eq!(consumed, 3);
+
+        let (result, consumed) = match_number_radix(8, b"9");
+        assert!(!result);
+        assert_eq!(consumed, 0);
+
+        // the radixes `char::is_digit` panics on
+        for radix in [0, 1, 37] {
+            assert_eq!(match_number_radix(radix, b"101"), (false, 0));
+        }
+    }
+
     #[test]
     fn test_match_string() {
         let (result, consumed) = match_string(b"abc123(");
@@ -1,5 +1,13 @@
 //! Specialized parsers for bytes.
+pub mod binary;
 pub mod components;
+pub mod confusables;
+pub mod continuation;
+pub mod escape;
 pub mod matchers;
+pub mod normalize;
+pub mod preprocessor;
 pub mod primitives;
 pub mod token;
+#[cfg(feature = "unicode")]
+pub mod unicode;
@@ -0,0 +1,264 @@
+//! Normalize the text matched by a visitor.
+//!
+//! A [Normalized] visitor runs a visitor, then applies a [Normalization] to
+//! the UTF-8 text it matched. The text is a `Cow` borrowing the data, it is
+//! only allocated when a step changes it, so the cleaning of the data stays in
+//! the declaration of the grammar at no cost for the already clean data.
+//!
+//! The steps are chained as a tuple, applied from left to right:
+//!
+//! ```
+//! use elyze::bytes::normalize::{CollapseWhitespaces, Lowercase, Normalized, Trim};
+//! use elyze::errors::ParseResult;
+//! use elyze::scanner::Scanner;
+//! use elyze::visitor::Visitor;
+//! use std::borrow::Cow;
+//!
+//! /// The data up to a `;`
+//! struct Field;
+//!
+//! impl<'a> Visitor<'a, u8> for Field {
+//!     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+//!         scanner.take_till(|byte| *byte == b';');
+//!         Ok(Field)
+//!     }
+//! }
+//!
+//! type Clean<'a> = Normalized<'a, Field, (Trim, CollapseWhitespaces, Lowercase)>;
+//!
+//! let mut scanner = Scanner::new(b"  Hello \t  World ;next");
+//! let clean = scanner.visit::<Clean>().expect("failed to parse");
+//! assert_eq!(clean.text, "hello world");
+//! assert_eq!(scanner.remaining(), b";next");
+//!
+//! // the clean data is borrowed
+//! let mut scanner = Scanner::new(b"hello world");
+//! let clean = scanner.visit::<Clean>().expect("failed to parse");
+//! assert!(matches!(clean.text, Cow::Borrowed("hello world")));
+//! ```
+
+use crate::errors::ParseResult;
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// A step transforming a text, borrowing it as long as it is unchanged.
+pub trait Normalization {
+    /// Transform the text
+    fn apply(text: Cow<'_, str>) -> Cow<'_, str>;
+}
+
+/// Remove the leading and trailing whitespaces.
+pub struct Trim;
+
+impl Normalization for Trim {
+    fn apply(text: Cow<'_, str>) -> Cow<'_, str> {
+        match text {
+            Cow::Borrowed(text) => Cow::Borrowed(text.trim()),
+            Cow::Owned(text) if text.trim().len() == text.len() => Cow::Owned(text),
+            Cow::Owned(text) => Cow::Owned(text.trim().to_string()),
+        }
+    }
+}
+
+/// Replace each run of whitespaces by a single space.
+pub struct CollapseWhitespaces;
+
+impl CollapseWhitespaces {
+    /// Return true if the text has a whitespace other than a space, or two
+    /// consecutive whitespaces
+    fn needs_collapse(text: &str) -> bool {
+        let mut previous = false;
+        text.chars().any(|c| {
+            let whitespace = c.is_whitespace();
+            let needs_collapse = whitespace && (previous || c != ' ');
+            previous = whitespace;
+            needs_collapse
+        })
+    }
+}
+
+impl Normalization for CollapseWhitespaces {
+    fn apply(text: Cow<'_, str>) -> Cow<'_, str> {
+        if !Self::needs_collapse(&text) {
+            return text;
+        }
+        let mut collapsed = String::with_capacity(text.len());
+        let mut previous = false;
+        for c in text.chars() {
+            let whitespace = c.is_whitespace();
+            if !whitespace {
+                collapsed.push(c);
+            } else if !previous {
+                collapsed.push(' ');
+            }
+            previous = whitespace;
+        }
+        Cow::Owned(collapsed)
+    }
+}
+
+/// Convert the text to lowercase, beyond the ASCII characters.
+pub struct Lowercase;
+
+impl Normalization for Lowercase {
+    fn apply(text: Cow<'_, str>) -> Cow<'_, str> {
+        let unchanged = text
+            .chars()
+            .all(|c| c.to_lowercase().eq(std::iter::once(c)));
+        if unchanged {
+            return text;
+        }
+        Cow::Owned(text.to_lowercase())
+    }
+}
+
+macro_rules! impl_normalization_chain {
+    ($($step:ident),+) => {
+        impl<$($step: Normalization),+> Normalization for ($($step,)+) {
+            fn apply(text: Cow<'_, str>) -> Cow<'_, str> {
+                $(let text = $step::apply(text);)+
+                text
+            }
+        }
+    };
+}
+
+impl_normalization_chain!(A);
+impl_normalization_chain!(A, B);
+impl_normalization_chain!(A, B, C);
+impl_normalization_chain!(A, B, C, D);
+
+/// The value of the visitor `V` along with its matched text normalized by `N`.
+///
+/// # Errors
+///
+/// The error of the visitor, or a `Utf8Error` if the matched data isn't
+/// valid UTF-8.
+pub struct Normalized<'a, V, N> {
+    /// The value of the visitor
+    pub value: V,
+    /// The normalized text matched by the visitor
+    pub text: Cow<'a, str>,
+    _normalization: PhantomData<N>,
+}
+
+/// The text matched by `V` without its leading and trailing whitespaces.
+pub type Trimmed<'a, V> = Normalized<'a, V, Trim>;
+
+/// The text matched by `V`, each run of whitespaces being a single space.
+pub type CollapsedWs<'a, V> = Normalized<'a, V, CollapseWhitespaces>;
+
+/// The text matched by `V` in lowercase.
+pub type Lowercased<'a, V> = Normalized<'a, V, Lowercase>;
+
+impl<V, N> Normalized<'_, V, N> {
+    /// Return the normalized text
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<V, N> Deref for Normalized<'_, V, N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<'a, V: Visitor<'a, u8>, N: Normalization> Visitor<'a, u8> for Normalized<'a, V, N> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let value = checkpoint.visit::<V>()?;
+        let text = std::str::from_utf8(checkpoint.since(start))?;
+        checkpoint.commit();
+        Ok(Normalized {
+            value,
+            text: N::apply(Cow::Borrowed(text)),
+            _normalization: PhantomData,
+        })
+    }
+}
+
+impl<'a, V: Visitor<'a, u8>, N: Normalization> Validate<'a, u8> for Normalized<'a, V, N> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::normalize::{
+        CollapseWhitespaces, CollapsedWs, Lowercase, Lowercased, Normalization, Trim, Trimmed,
+    };
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::ParseError;
+    use crate::errors::ParseResult;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+    use std::borrow::Cow;
+
+    /// The data up to a `;`
+    struct Field;
+
+    impl<'a> Visitor<'a, u8> for Field {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            scanner.take_till(|byte| *byte == b';');
+            Ok(Field)
+        }
+    }
+
+    #[test]
+    fn test_normalizations() {
+        let borrowed = |text| Cow::Borrowed(text);
+        assert!(matches!(
+            Trim::apply(borrowed(" a b\n")),
+            Cow::Borrowed("a b")
+        ));
+        assert_eq!(Trim::apply(Cow::Owned(" a ".to_string())), "a");
+        assert!(matches!(
+            CollapseWhitespaces::apply(borrowed("a b")),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(CollapseWhitespaces::apply(borrowed(" a\tb  c ")), " a b c ");
+        assert_eq!(CollapseWhitespaces::apply(borrowed("a\u{a0}b")), "a b");
+        assert!(matches!(
+            Lowercase::apply(borrowed("ß-1")),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(Lowercase::apply(borrowed("ΣΑΣ")), "σας");
+        assert!(matches!(
+            <(Trim, Lowercase)>::apply(borrowed(" ab ")),
+            Cow::Borrowed("ab")
+        ));
+    }
+
+    #[test]
+    fn test_normalized() {
+        let mut scanner = Scanner::new(b"42 ");
+        let number = scanner
+            .visit::<Lowercased<Number<u8>>>()
+            .expect("failed to parse");
+        assert_eq!(number.value, Number(42));
+        assert_eq!(number.as_str(), "42");
+        assert_eq!(scanner.current_position(), 2);
+
+        let mut scanner = Scanner::new(b" \xff ");
+        let result = scanner.visit::<Trimmed<Field>>();
+        assert!(matches!(result, Err(ParseError::Utf8Error(_))));
+        assert_eq!(scanner.current_position(), 0);
+
+        let mut scanner = Scanner::new(b" New \n York ;");
+        let city = scanner
+            .visit::<CollapsedWs<Field>>()
+            .expect("failed to parse");
+        assert_eq!(&*city, " New York ");
+        let mut scanner = Scanner::new(b" New York;");
+        let city = scanner.visit::<Trimmed<Field>>().expect("failed to parse");
+        assert!(matches!(city.text, Cow::Borrowed("New York")));
+
+        let mut scanner = Scanner::new(b"x");
+        assert!(scanner.visit::<CollapsedWs<Number<u8>>>().is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Preprocessing stage expanding regions of the input.
+//!
+//! An [Expand] implementation recognizes the regions to expand, like an
+//! `include "file"` directive or a macro reference, and returns their
+//! expansion. The [Preprocessor] splices the expansions into the data to
+//! parse, expanding them recursively, and keeps track of the origin of each
+//! position to report errors.
+
+use crate::errors::{ParseError, ParseResult};
+
+/// The expansion of a region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion {
+    /// The size of the expanded region, must not be 0
+    pub size: usize,
+    /// The name of the expanded source, like a file name or a macro name
+    pub source: String,
+    /// The data replacing the region
+    pub data: Vec<u8>,
+}
+
+/// Recognize and expand the regions of the input.
+pub trait Expand {
+    /// Try to expand the region at the start of the data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to expand, starting at the current position
+    ///
+    /// # Returns
+    ///
+    /// The expansion of the region, or `None` if there is no region to expand
+    /// at this position.
+    ///
+    /// # Errors
+    ///
+    /// Any error preventing the expansion, like a missing file.
+    fn expand(&mut self, data: &[u8]) -> ParseResult<Option<Expansion>>;
+}
+
+impl<F: FnMut(&[u8]) -> ParseResult<Option<Expansion>>> Expand for F {
+    fn expand(&mut self, data: &[u8]) -> ParseResult<Option<Expansion>> {
+        self(data)
+    }
+}
+
+/// The origin of a position of the preprocessed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Origin<'p> {
+    /// The name of the source
+    pub source: &'p str,
+    /// The position in the source
+    pub offset: usize,
+}
+
+/// A part of the preprocessed data copied from a single source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Segment {
+    /// The position of the segment in the preprocessed data
+    start: usize,
+    /// The index of the source
+    source: usize,
+    /// The position of the segment in the source
+    offset: usize,
+}
+
+/// The data produced by a [Preprocessor].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preprocessed {
+    /// The expanded data
+    data: Vec<u8>,
+    /// The names of the sources
+    sources: Vec<String>,
+    /// The segments of the expanded data, ordered by position
+    segments: Vec<Segment>,
+}
+
+impl Preprocessed {
+    /// Return the expanded data
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Find the origin of a position of the expanded data
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The position in the expanded data
+    ///
+    /// # Returns
+    ///
+    /// The source and the position in this source.
+    pub fn origin(&self, offset: usize) -> Origin<'_> {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.start <= offset)
+            .saturating_sub(1);
+        let segment = &self.segments[index];
+        Origin {
+            source: &self.sources[segment.source],
+            offset: segment.offset + offset - segment.start,
+        }
+    }
+}
+
+/// Expand the regions of an input recursively.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::preprocessor::{Expansion, Preprocessor};
+///
+/// let mut preprocessor = Preprocessor::new(|data: &[u8]| {
+///     Ok(data.starts_with(b"$PI").then(|| Expansion {
+///         size: 3,
+///         source: "PI".to_string(),
+///         data: b"3.14".to_vec(),
+///     }))
+/// });
+/// let preprocessed = preprocessor.run("main", b"2 * $PI").expect("failed to expand");
+/// assert_eq!(preprocessed.data(), b"2 * 3.14");
+/// assert_eq!(preprocessed.origin(5).source, "PI");
+/// ```
+pub struct Preprocessor<E> {
+    expander: E,
+}
+
+impl<E: Expand> Preprocessor<E> {
+    /// Create a new `Preprocessor`
+    ///
+    /// # Arguments
+    ///
+    /// * `expander` - Recognize and expand the regions
+    pub fn new(expander: E) -> Self {
+        Preprocessor { expander }
+    }
+
+    /// Expand the regions of the data, and the regions of their expansions.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The name of the data
+    /// * `data` - The data to expand
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(ParseError::ExpansionCycle)` if a source is expanded
+    /// inside its own expansion, `Err(ParseError::CursorOutOfBounds)` if the
+    /// size of an expansion is 0 or exceeds the data, and any error of the
+    /// expander.
+    pub fn run(&mut self, source: &str, data: &[u8]) -> ParseResult<Preprocessed> {
+        let mut preprocessed = Preprocessed {
+            data: vec![],
+            sources: vec![source.to_string()],
+            segments: vec![],
+        };
+        let mut stack = vec![source.to_string()];
+        self.expand_into(&mut preprocessed, &mut stack, 0, data)?;
+        Ok(preprocessed)
+    }
+
+    /// Expand the data of a source at the end of the preprocessed data
+    fn expand_into(
+        &mut self,
+        preprocessed: &mut Preprocessed,
+        stack: &mut Vec<String>,
+        source: usize,
+        data: &[u8],
+    ) -> ParseResult<()> {
+        preprocessed.segments.push(Segment {
+            start: preprocessed.data.len(),
+            source,
+            offset: 0,
+        });
+
+        let mut position = 0;
+        let mut copied = 0;
+        while position < data.len() {
+            let Some(expansion) = self.expander.expand(&data[position..])? else {
+                position += 1;
+                continue;
+            };
+            if expansion.size == 0 || expansion.size > data.len() - position {
+                return Err(ParseError::CursorOutOfBounds);
+            }
+            if stack.contains(&expansion.source) {
+                return Err(ParseError::ExpansionCycle(expansion.source));
+            }
+
+            preprocessed.data.extend_from_slice(&data[copied..position]);
+            preprocessed.sources.push(expansion.source.clone());
+            stack.push(expansion.source);
+            let index = preprocessed.sources.len() - 1;
+            self.expand_into(preprocessed, stack, index, &expansion.data)?;
+            stack.pop();
+
+            position += expansion.size;
+            copied = position;
+            preprocessed.segments.push(Segment {
+                start: preprocessed.data.len(),
+                source,
+                offset: position,
+            });
+        }
+        preprocessed.data.extend_from_slice(&data[copied..]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::preprocessor::{Expansion, Origin, Preprocessor};
+    use crate::errors::{ParseError, ParseResult};
+    use std::collections::HashMap;
+
+    /// Expand the `#include <name>` directives
+    fn includes<'f>(
+        files: &'f HashMap<&str, &[u8]>,
+    ) -> impl FnMut(&[u8]) -> ParseResult<Option<Expansion>> + 'f {
+        |data: &[u8]| {
+            let Some(directive) = data.strip_prefix(b"#include <") else {
+                return Ok(None);
+            };
+            let end = directive
+                .iter()
+                .position(|byte| *byte == b'>')
+                .ok_or(ParseError::UnexpectedEndOfInput)?;
+            let name = std::str::from_utf8(&directive[..end])?;
+            let file = files
+                .get(name)
+                .ok_or(ParseError::unexpected_token(b"#include <".len()))?;
+            Ok(Some(Expansion {
+                size: b"#include <".len() + end + 1,
+                source: name.to_string(),
+                data: file.to_vec(),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_preprocessor_includes() {
+        let files = HashMap::from([
+            ("a.h", &b"int a;\n#include <b.h>\nint c;"[..]),
+            ("b.h", b"int b;"),
+        ]);
+        let mut preprocessor = Preprocessor::new(includes(&files));
+        let preprocessed = preprocessor
+            .run("main.c", b"#include <a.h>\nint main;")
+            .expect("failed to expand");
+        assert_eq!(preprocessed.data(), b"int a;\nint b;\nint c;\nint main;");
+
+        let origin = |offset| preprocessed.origin(offset);
+        assert_eq!(
+            origin(0),
+            Origin {
+                source: "a.h",
+                offset: 0
+            }
+        );
+        assert_eq!(
+            origin(11),
+            Origin {
+                source: "b.h",
+                offset: 4
+            }
+        );
+        assert_eq!(
+            origin(13),
+            Origin {
+                source: "a.h",
+                offset: 21
+            }
+        );
+        assert_eq!(
+            origin(20),
+            Origin {
+                source: "main.c",
+                offset: 14
+            }
+        );
+    }
+
+    #[test]
+    fn test_preprocessor_cycle() {
+        let files = HashMap::from([("a.h", &b"#include <b.h>"[..]), ("b.h", b"#include <a.h>")]);
+        let mut preprocessor = Preprocessor::new(includes(&files));
+        let result = preprocessor.run("main.c", b"#include <a.h>");
+        assert!(matches!(result, Err(ParseError::ExpansionCycle(source)) if source == "a.h"));
+
+        // the same source may be expanded several times
+        let files = HashMap::from([("a.h", &b"a"[..])]);
+        let mut preprocessor = Preprocessor::new(includes(&files));
+        let preprocessed = preprocessor
+            .run("main.c", b"#include <a.h>#include <a.h>")
+            .expect("failed to expand");
+        assert_eq!(preprocessed.data(), b"aa");
+
+        let result = preprocessor.run("main.c", b"#include <missing.h>");
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 10, .. })
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+//! ANSI escape sequences
+//!
+//! Terminal outputs interleave the text with escape sequences changing the
+//! colors, moving the cursor or setting the title of the window. The control
+//! sequences (CSI) and the operating system commands (OSC) are recognized, to
+//! interpret them or to strip them from the text.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// The escape character starting the sequences
+const ESC: u8 = 0x1b;
+/// The bell character ending an operating system command
+const BEL: u8 = 0x07;
+
+/// A control sequence `ESC [ parameters intermediates final`, like the
+/// `ESC[1;31m` setting a bold red text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Csi<'a> {
+    /// The parameter bytes, in the range `0x30..=0x3F`
+    pub parameters: &'a [u8],
+    /// The intermediate bytes, in the range `0x20..=0x2F`
+    pub intermediates: &'a [u8],
+    /// The final byte, in the range `0x40..=0x7E`, selecting the function
+    pub final_byte: u8,
+}
+
+impl Csi<'_> {
+    /// Return the numeric parameters separated by `;`
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the parameters, `None` for an omitted or non-numeric
+    /// parameter which takes its default value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::bytes::primitives::ansi::AnsiEscape;
+    /// use elyze::scanner::Scanner;
+    ///
+    /// let escape = Scanner::new(b"\x1b[;31m").visit::<AnsiEscape>().expect("failed to parse");
+    /// let AnsiEscape::Csi(csi) = escape else { panic!("not a CSI") };
+    /// assert_eq!(csi.numbers().collect::<Vec<_>>(), vec![None, Some(31)]);
+    /// ```
+    pub fn numbers(&self) -> impl Iterator<Item = Option<u32>> + '_ {
+        self.parameters
+            .split(|byte| *byte == b';')
+            .map(|parameter| {
+                std::str::from_utf8(parameter)
+                    .ok()
+                    .and_then(|parameter| parameter.parse().ok())
+            })
+    }
+}
+
+/// An ANSI escape sequence.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::ansi::{AnsiEscape, Csi};
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"\x1b[1;31merror");
+/// let escape = scanner.visit::<AnsiEscape>().expect("failed to parse");
+/// assert_eq!(
+///     escape,
+///     AnsiEscape::Csi(Csi {
+///         parameters: b"1;31",
+///         intermediates: b"",
+///         final_byte: b'm',
+///     })
+/// );
+/// assert_eq!(scanner.remaining(), b"error");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnsiEscape<'a> {
+    /// A control sequence `ESC [ ... final`
+    Csi(Csi<'a>),
+    /// An operating system command `ESC ] data`, ended by `BEL` or `ESC \`
+    Osc(&'a [u8]),
+}
+
+/// Accept the bytes of the range, return them
+fn take_range<'a>(scanner: &mut Scanner<'a, u8>, range: std::ops::RangeInclusive<u8>) -> &'a [u8] {
+    let size = scanner
+        .remaining()
+        .iter()
+        .take_while(|byte| range.contains(byte))
+        .count();
+    let bytes = &scanner.remaining()[..size];
+    scanner.bump_by(size);
+    bytes
+}
+
+/// Accept a control sequence after its introducer
+fn csi<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Csi<'a>> {
+    let parameters = take_range(scanner, 0x30..=0x3f);
+    let intermediates = take_range(scanner, 0x20..=0x2f);
+    match scanner.first() {
+        Some(byte @ 0x40..=0x7e) => {
+            let final_byte = *byte;
+            scanner.bump_by(1);
+            Ok(Csi {
+                parameters,
+                intermediates,
+                final_byte,
+            })
+        }
+        Some(_) => Err(ParseError::unexpected_token(scanner.current_position())
+            .expecting("the final byte of the control sequence")),
+        None => Err(ParseError::UnexpectedEndOfInput),
+    }
+}
+
+/// Accept an operating system command after its introducer
+fn osc<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<&'a [u8]> {
+    let remaining = scanner.remaining();
+    for (position, byte) in remaining.iter().enumerate() {
+        let terminator = match (byte, remaining.get(position + 1)) {
+            (&BEL, _) => 1,
+            (&ESC, Some(b'\\')) => 2,
+            _ => continue,
+        };
+        scanner.bump_by(position + terminator);
+        return Ok(&remaining[..position]);
+    }
+    Err(ParseError::UnexpectedEndOfInput)
+}
+
+impl<'a> Visitor<'a, u8> for AnsiEscape<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let escape = match checkpoint.remaining() {
+            [ESC, b'[', ..] => {
+                checkpoint.bump_by(2);
+                AnsiEscape::Csi(csi(&mut checkpoint)?)
+            }
+            [ESC, b']', ..] => {
+                checkpoint.bump_by(2);
+                AnsiEscape::Osc(osc(&mut checkpoint)?)
+            }
+            [ESC] | [] => return Err(ParseError::UnexpectedEndOfInput),
+            _ => {
+                let err = ParseError::unexpected_token(checkpoint.current_position());
+                return Err(err.expecting("an escape sequence"));
+            }
+        };
+        checkpoint.commit();
+        Ok(escape)
+    }
+}
+
+impl<'a> Validate<'a, u8> for AnsiEscape<'a> {}
+
+/// Remove the escape sequences of the data.
+///
+/// The escape characters which don't start a valid sequence are kept.
+///
+/// # Arguments
+///
+/// * `data` - The data to strip
+///
+/// # Returns
+///
+/// The data without its escape sequences, borrowed if there is none.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::ansi::strip_ansi;
+///
+/// let data = b"\x1b]0;title\x07\x1b[1;31merror\x1b[0m: failed";
+/// assert_eq!(strip_ansi(data).as_ref(), b"error: failed");
+/// ```
+pub fn strip_ansi(data: &[u8]) -> Cow<'_, [u8]> {
+    let mut scanner = Scanner::new(data);
+    let mut stripped = vec![];
+    // the start of the text not copied yet
+    let mut text = 0;
+    while let Some(offset) = scanner.remaining().iter().position(|byte| *byte == ESC) {
+        scanner.bump_by(offset);
+        let start = scanner.current_position();
+        if scanner.visit::<AnsiEscape>().is_err() {
+            scanner.bump_by(1);
+            continue;
+        }
+        stripped.extend_from_slice(&data[text..start]);
+        text = scanner.current_position();
+    }
+    if text == 0 {
+        return Cow::Borrowed(data);
+    }
+    stripped.extend_from_slice(&data[text..]);
+    Cow::Owned(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::ansi::{strip_ansi, AnsiEscape, Csi};
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_csi() {
+        let mut scanner = Scanner::new(b"\x1b[?25l\x1b[2 q\x1b[H");
+        let escapes = (0..3)
+            .map(|_| scanner.visit::<AnsiEscape>().expect("failed to parse"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            escapes,
+            vec![
+                AnsiEscape::Csi(Csi {
+                    parameters: b"?25",
+                    intermediates: b"",
+                    final_byte: b'l'
+                }),
+                AnsiEscape::Csi(Csi {
+                    parameters: b"2",
+                    intermediates: b" ",
+                    final_byte: b'q'
+                }),
+                AnsiEscape::Csi(Csi {
+                    parameters: b"",
+                    intermediates: b"",
+                    final_byte: b'H'
+                }),
+            ]
+        );
+        assert!(scanner.is_empty());
+
+        // an invalid final byte
+        let mut scanner = Scanner::new(b"\x1b[31\x01m");
+        assert!(matches!(
+            scanner.visit::<AnsiEscape>(),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+        assert!(matches!(
+            Scanner::new(b"\x1b[31").visit::<AnsiEscape>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_osc() {
+        let mut scanner = Scanner::new(b"\x1b]8;;https://example.com\x1b\\link");
+        let escape = scanner.visit::<AnsiEscape>().expect("failed to parse");
+        assert_eq!(escape, AnsiEscape::Osc(b"8;;https://example.com"));
+        assert_eq!(scanner.remaining(), b"link");
+
+        assert!(matches!(
+            Scanner::new(b"\x1b]0;title").visit::<AnsiEscape>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            Scanner::new(b"text").visit::<AnsiEscape>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert!(matches!(
+            strip_ansi(b"plain text"),
+            Cow::Borrowed(b"plain text")
+        ));
+        assert_eq!(
+            strip_ansi(b"\x1b[32mok\x1b[0m \x1bX \x1b[").as_ref(),
+            b"ok \x1bX \x1b["
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::bytes::token::Token;
 use crate::errors::{ParseError, ParseResult};
 use crate::recognizer::recognize;
 use crate::scanner::Scanner;
+use crate::validate::Validate;
 use crate::visitor::Visitor;
 
 enum BinaryOperatorInternal {
@@ -27,6 +28,7 @@ enum BinaryOperatorInternal {
 /// * `LessThanOrEqual` - The `<=` operator
 /// * `GreaterThan` - The `>` operator
 /// * `GreaterThanOrEqual` - The `>=` operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BinaryOperator {
     /// The `==` operator
     Equal,
@@ -127,12 +129,40 @@ impl<'a> Visitor<'a, u8> for BinaryOperator {
         let acceptor = Acceptor::new(scanner)
             .try_or(BinaryOperatorInternal::Equal)?
             .try_or(BinaryOperatorInternal::NotEqual)?
-            .try_or(BinaryOperatorInternal::LessThan)?
+            // the longest operators first, `<` would stop at the start of `<=`
             .try_or(BinaryOperatorInternal::LessThanOrEqual)?
-            .try_or(BinaryOperatorInternal::GreaterThan)?
+            .try_or(BinaryOperatorInternal::LessThan)?
             .try_or(BinaryOperatorInternal::GreaterThanOrEqual)?
+            .try_or(BinaryOperatorInternal::GreaterThan)?
             .finish()
-            .ok_or(ParseError::UnexpectedToken)?;
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))?;
         Ok(acceptor.into())
     }
 }
+
+impl Validate<'_, u8> for BinaryOperator {}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::binary_operator::BinaryOperator;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_binary_operator() {
+        let cases: [(&[u8], BinaryOperator); 6] = [
+            (b"==", BinaryOperator::Equal),
+            (b"!=", BinaryOperator::NotEqual),
+            (b"<", BinaryOperator::LessThan),
+            (b"<=", BinaryOperator::LessThanOrEqual),
+            (b">", BinaryOperator::GreaterThan),
+            (b">=", BinaryOperator::GreaterThanOrEqual),
+        ];
+        for (data, expected) in cases {
+            let mut scanner = Scanner::new(data);
+            let operator = scanner.visit::<BinaryOperator>().expect("failed to parse");
+            assert_eq!(operator, expected);
+            assert!(scanner.is_empty());
+        }
+        assert!(Scanner::new(b"=").visit::<BinaryOperator>().is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Payment card numbers
+//!
+//! A card number is a run of digit groups, like `4111 1111 1111 1111`, whose
+//! last digit is a Luhn check digit. Checking it sorts the card numbers out
+//! of the other long numbers of a text, which makes the recognizer usable to
+//! scrub the card numbers from logs.
+
+use crate::matcher::Match;
+
+/// The minimal number of digits of a card number
+const MIN_DIGITS: usize = 12;
+/// The maximal number of digits of a card number, as set by ISO/IEC 7812
+const MAX_DIGITS: usize = 19;
+
+/// Whether the digits of the number pass the Luhn check.
+///
+/// The spaces and dashes between the digits are ignored, any other byte
+/// fails the check.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::card::is_luhn_valid;
+///
+/// assert!(is_luhn_valid(b"4111-1111-1111-1111"));
+/// assert!(!is_luhn_valid(b"4111-1111-1111-1112"));
+/// ```
+pub fn is_luhn_valid(number: &[u8]) -> bool {
+    let mut sum = 0;
+    let mut count = 0;
+    for byte in number.iter().rev() {
+        match byte {
+            b'0'..=b'9' => {
+                let mut digit = (byte - b'0') as usize;
+                // every second digit from the check digit is doubled
+                if count % 2 == 1 {
+                    digit *= 2;
+                    if digit > 9 {
+                        digit -= 9;
+                    }
+                }
+                sum += digit;
+                count += 1;
+            }
+            b' ' | b'-' => {}
+            _ => return false,
+        }
+    }
+    count > 0 && sum % 10 == 0
+}
+
+/// A payment card number validated by its Luhn check digit.
+///
+/// The number is made of 12 to 19 digits, its groups separated by at most
+/// one space or `-`. It is the longest run of groups whose digits pass the
+/// Luhn check, so a number written right after the card number, like an
+/// expiration year, isn't part of it. A trailing separator is left in the
+/// scanner.
+///
+/// A run of digits failing the check doesn't match, as any other token.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::card::CardNumber;
+/// use elyze::recognizer::recognize_slice;
+/// use elyze::scanner::Scanner;
+/// use elyze::search::find_all;
+///
+/// let mut scanner = Scanner::new(b"4111 1111 1111 1111 2030");
+/// let number = recognize_slice(CardNumber, &mut scanner).expect("failed to parse");
+/// assert_eq!(number, b"4111 1111 1111 1111");
+/// assert_eq!(scanner.remaining(), b" 2030");
+///
+/// // scrub the card numbers of a log line
+/// let mut line = b"paid with 5500-0000-0000-0004, order 1234567890123".to_vec();
+/// for (offset, length) in find_all(CardNumber, &line.clone()) {
+///     line[offset..offset + length].fill(b'*');
+/// }
+/// assert_eq!(line, b"paid with *******************, order 1234567890123");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CardNumber;
+
+impl Match<u8> for CardNumber {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        // the sizes of the runs of groups which could be a card number, the
+        // shortest first
+        let mut candidates = vec![];
+        let mut digits = 0;
+        let mut position = 0;
+        loop {
+            let group = data[position..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            if group == 0 {
+                break;
+            }
+            digits += group;
+            position += group;
+            if digits > MAX_DIGITS {
+                break;
+            }
+            if digits >= MIN_DIGITS {
+                candidates.push(position);
+            }
+            // a separator is only consumed between two digit groups
+            match data.get(position..position + 2) {
+                Some([b' ' | b'-', next]) if next.is_ascii_digit() => position += 1,
+                _ => break,
+            }
+        }
+
+        match candidates
+            .into_iter()
+            .rev()
+            .find(|size| is_luhn_valid(&data[..*size]))
+        {
+            Some(size) => (true, size),
+            None => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        MIN_DIGITS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::card::{is_luhn_valid, CardNumber};
+    use crate::matcher::Match;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_luhn() {
+        assert!(is_luhn_valid(b"79927398713"));
+        assert!(is_luhn_valid(b"0"));
+        assert!(!is_luhn_valid(b"79927398710"));
+        assert!(!is_luhn_valid(b""));
+        assert!(!is_luhn_valid(b"7992.7398.713"));
+    }
+
+    #[test]
+    fn test_card_number() {
+        for (data, size) in [
+            (&b"4111111111111111"[..], 16),
+            (b"4111 1111 1111 1111", 19),
+            (b"3782-822463-10005", 17),
+            (b"4111 1111 1111 1111 ", 19),
+            (b"4111 1111 1111 1111 2030", 19),
+            (b"6011000990139424 exp", 16),
+        ] {
+            assert_eq!(
+                CardNumber.is_matching(data),
+                (true, size),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+
+        for data in [
+            &b"4111111111111112"[..],
+            b"4111  1111 1111 1111",
+            b"79927398713",
+            b"41111111111111111111111",
+            b"card 4111111111111111",
+        ] {
+            assert_eq!(
+                CardNumber.is_matching(data),
+                (false, 0),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_visit_card_number() {
+        let mut scanner = Scanner::new(b"4111-1111-1111-1111;");
+        scanner.visit::<CardNumber>().expect("failed to parse");
+        assert_eq!(scanner.remaining(), b";");
+        assert!(Scanner::new(b"4111-1111-1111-1112;")
+            .visit::<CardNumber>()
+            .is_err());
+    }
+}
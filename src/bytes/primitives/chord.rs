@@ -0,0 +1,334 @@
+//! Chord symbols
+//!
+//! A chord symbol is made of ordered components, each of them optional but
+//! the root: the root note and its accidental, the quality, the extensions
+//! and a bass note after a slash, as in `F#m7b5/A`.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// The qualities, the longest first
+const QUALITIES: [(&[u8], Quality); 7] = [
+    (b"min", Quality::Minor),
+    (b"dim", Quality::Diminished),
+    (b"aug", Quality::Augmented),
+    (b"m", Quality::Minor),
+    (b"-", Quality::Minor),
+    (b"o", Quality::Diminished),
+    (b"+", Quality::Augmented),
+];
+
+/// The accidental of a note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Accidental {
+    /// `#`
+    Sharp,
+    /// `b`
+    Flat,
+}
+
+impl Accidental {
+    /// Return the accidental written with the byte
+    fn from_byte(byte: u8) -> Option<Accidental> {
+        match byte {
+            b'#' => Some(Accidental::Sharp),
+            b'b' => Some(Accidental::Flat),
+            _ => None,
+        }
+    }
+}
+
+/// A note, `A` to `G` and an optional accidental.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Note {
+    /// The letter of the note, `b'A'` to `b'G'`
+    pub letter: u8,
+    /// The accidental of the note
+    pub accidental: Option<Accidental>,
+}
+
+impl<'a> Visitor<'a, u8> for Note {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let letter = match scanner.first() {
+            Some(letter @ b'A'..=b'G') => *letter,
+            Some(_) => {
+                return Err(
+                    ParseError::unexpected_token(scanner.current_position()).expecting("a note")
+                );
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        };
+        scanner.bump_by(1);
+        let accidental = scanner.first().copied().and_then(Accidental::from_byte);
+        if accidental.is_some() {
+            scanner.bump_by(1);
+        }
+        Ok(Note { letter, accidental })
+    }
+}
+
+/// The quality of a chord, given by its third and its fifth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Quality {
+    /// No quality symbol
+    #[default]
+    Major,
+    /// `m`, `min` or `-`
+    Minor,
+    /// `dim` or `o`
+    Diminished,
+    /// `aug` or `+`
+    Augmented,
+}
+
+/// An extension of a chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Extension {
+    /// A degree stacked on the chord, `7`, `9`, `6/9`
+    Degree(u8),
+    /// A major seventh and the degrees up to this one, `maj7`, `M9`
+    Major(u8),
+    /// An altered degree, `b5`, `#11`
+    Altered(Accidental, u8),
+    /// An added degree, `add9`
+    Added(u8),
+    /// A suspended chord, the third replaced by the degree, `sus2`, `sus4`,
+    /// `sus` being `sus4`
+    Suspended(u8),
+}
+
+/// Accept a degree among the allowed ones
+fn degree(scanner: &mut Scanner<u8>, allowed: &[u8]) -> ParseResult<u8> {
+    let start = scanner.current_position();
+    let digits = scanner.take_while(u8::is_ascii_digit);
+    match std::str::from_utf8(digits)?.parse() {
+        Ok(degree) if allowed.contains(&degree) => Ok(degree),
+        _ => Err(ParseError::unexpected_token(start).expecting("a degree")),
+    }
+}
+
+impl<'a> Visitor<'a, u8> for Extension {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let remaining = checkpoint.remaining();
+        let extension = if remaining.starts_with(b"maj") || remaining.starts_with(b"M") {
+            checkpoint.bump_by(if remaining[0] == b'M' { 1 } else { 3 });
+            Extension::Major(degree(&mut checkpoint, &[7, 9, 11, 13])?)
+        } else if remaining.starts_with(b"add") {
+            checkpoint.bump_by(3);
+            Extension::Added(degree(&mut checkpoint, &[2, 4, 9, 11, 13])?)
+        } else if remaining.starts_with(b"sus") {
+            checkpoint.bump_by(3);
+            match checkpoint.first() {
+                Some(byte) if byte.is_ascii_digit() => {
+                    Extension::Suspended(degree(&mut checkpoint, &[2, 4])?)
+                }
+                _ => Extension::Suspended(4),
+            }
+        } else if let Some(accidental) = remaining.first().copied().and_then(Accidental::from_byte)
+        {
+            checkpoint.bump_by(1);
+            Extension::Altered(accidental, degree(&mut checkpoint, &[5, 9, 11, 13])?)
+        } else {
+            Extension::Degree(degree(&mut checkpoint, &[2, 4, 5, 6, 7, 9, 11, 13])?)
+        };
+        checkpoint.commit();
+        Ok(extension)
+    }
+}
+
+/// A chord symbol.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::chord::{Accidental, Chord, Extension, Note, Quality};
+/// use elyze::scanner::Scanner;
+///
+/// let chord: Chord = Scanner::new(b"F#m7b5/A").visit().expect("failed to parse");
+/// assert_eq!(
+///     chord.root,
+///     Note {
+///         letter: b'F',
+///         accidental: Some(Accidental::Sharp)
+///     }
+/// );
+/// assert_eq!(chord.quality, Quality::Minor);
+/// assert_eq!(
+///     chord.extensions,
+///     [Extension::Degree(7), Extension::Altered(Accidental::Flat, 5)]
+/// );
+/// assert_eq!(chord.bass.map(|bass| bass.letter), Some(b'A'));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chord {
+    /// The root note
+    pub root: Note,
+    /// The quality, major when omitted
+    pub quality: Quality,
+    /// The extensions, in their written order
+    pub extensions: Vec<Extension>,
+    /// The bass note written after a slash, when it isn't the root
+    pub bass: Option<Note>,
+}
+
+impl<'a> Visitor<'a, u8> for Chord {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let root = checkpoint.visit()?;
+
+        let remaining = checkpoint.remaining();
+        // `maj7` is a major chord with a major seventh, not a minor one
+        let quality = match QUALITIES
+            .iter()
+            .find(|(symbol, _)| remaining.starts_with(symbol) && !remaining.starts_with(b"maj"))
+        {
+            Some((symbol, quality)) => {
+                checkpoint.bump_by(symbol.len());
+                *quality
+            }
+            None => Quality::Major,
+        };
+
+        let mut extensions = vec![];
+        loop {
+            // `6/9` is an extension, not a bass note
+            if extensions.last() == Some(&Extension::Degree(6))
+                && checkpoint.remaining().starts_with(b"/9")
+            {
+                checkpoint.bump_by(2);
+                extensions.push(Extension::Degree(9));
+                continue;
+            }
+            let remaining = checkpoint.remaining();
+            let is_extension = remaining
+                .first()
+                .is_some_and(|byte| byte.is_ascii_digit() || matches!(byte, b'#' | b'b' | b'M'))
+                || [&b"maj"[..], b"add", b"sus"]
+                    .iter()
+                    .any(|prefix| remaining.starts_with(prefix));
+            if !is_extension {
+                break;
+            }
+            extensions.push(checkpoint.visit()?);
+        }
+
+        let bass = if checkpoint.first() == Some(&b'/') {
+            checkpoint.bump_by(1);
+            Some(checkpoint.visit()?)
+        } else {
+            None
+        };
+
+        checkpoint.commit();
+        Ok(Chord {
+            root,
+            quality,
+            extensions,
+            bass,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::chord::{Accidental, Chord, Extension, Note, Quality};
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    fn parse(data: &[u8]) -> Chord {
+        Scanner::new(data).visit().expect("failed to parse")
+    }
+
+    #[test]
+    fn test_chord() {
+        let chord = parse(b"C");
+        assert_eq!(chord.root.letter, b'C');
+        assert_eq!(chord.quality, Quality::Major);
+        assert!(chord.extensions.is_empty());
+        assert_eq!(chord.bass, None);
+
+        let chord = parse(b"Bbmaj7#11/D");
+        assert_eq!(
+            chord.root,
+            Note {
+                letter: b'B',
+                accidental: Some(Accidental::Flat)
+            }
+        );
+        assert_eq!(chord.quality, Quality::Major);
+        assert_eq!(
+            chord.extensions,
+            [
+                Extension::Major(7),
+                Extension::Altered(Accidental::Sharp, 11)
+            ]
+        );
+        assert_eq!(chord.bass.map(|bass| bass.letter), Some(b'D'));
+
+        for (data, quality, extensions) in [
+            (&b"Am"[..], Quality::Minor, &[][..]),
+            (b"Ebdim7", Quality::Diminished, &[Extension::Degree(7)]),
+            (
+                b"G7sus4",
+                Quality::Major,
+                &[Extension::Degree(7), Extension::Suspended(4)],
+            ),
+            (b"Dsus", Quality::Major, &[Extension::Suspended(4)]),
+            (b"C+", Quality::Augmented, &[]),
+            (b"C-add9", Quality::Minor, &[Extension::Added(9)]),
+            (b"CM9", Quality::Major, &[Extension::Major(9)]),
+            (b"Cmmaj7", Quality::Minor, &[Extension::Major(7)]),
+            (
+                b"F6/9",
+                Quality::Major,
+                &[Extension::Degree(6), Extension::Degree(9)],
+            ),
+        ] {
+            let chord = parse(data);
+            assert_eq!(
+                chord.quality,
+                quality,
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(
+                chord.extensions,
+                extensions,
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(chord.bass, None);
+        }
+
+        // the chord ends at the first byte which can't continue it
+        let mut scanner = Scanner::new(b"Am7 | D7");
+        scanner.visit::<Chord>().expect("failed to parse");
+        assert_eq!(scanner.remaining(), b" | D7");
+    }
+
+    #[test]
+    fn test_invalid_chord() {
+        for (data, position) in [
+            (&b"H7"[..], 0),
+            (b"C8", 1),
+            (b"Cadd", 4),
+            (b"C#b3", 3),
+            (b"C/x", 2),
+        ] {
+            assert!(
+                matches!(
+                    Scanner::new(data).visit::<Chord>(),
+                    Err(ParseError::UnexpectedToken { offset, .. }) if offset == position
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+        assert!(matches!(
+            Scanner::new(b"C/").visit::<Chord>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+//! Email addresses written in a text
+//!
+//! The recognition is pragmatic rather than a full RFC 5322 validation: the
+//! quoted local parts, the comments and the IP address literals used as
+//! domains aren't supported, as they are seldom met outside of the mail
+//! headers.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Whether the byte can appear in the local part of an address, besides the
+/// dots
+fn is_local_byte(byte: &u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+/=?^_`{|}~-".contains(byte)
+}
+
+/// Whether the byte can appear in a label of a domain, besides its ends
+fn is_label_byte(byte: &u8) -> bool {
+    byte.is_ascii_alphanumeric() || *byte == b'-'
+}
+
+/// Read the labels of a domain separated by dots.
+///
+/// # Returns
+///
+/// The size of the domain and its number of labels, a dot following the
+/// domain, as the one ending a sentence, not being part of it.
+///
+/// # Errors
+///
+/// The position of a label starting or ending with a `-`.
+fn domain(data: &[u8]) -> Result<(usize, usize), usize> {
+    let mut size = 0;
+    let mut labels = 0;
+    loop {
+        let label = &data[size..];
+        let length = label.iter().take_while(|byte| is_label_byte(byte)).count();
+        if length == 0 {
+            return Ok((size.saturating_sub(1), labels));
+        }
+        if label[0] == b'-' || label[length - 1] == b'-' {
+            return Err(size);
+        }
+        size += length;
+        labels += 1;
+        if data.get(size) != Some(&b'.') {
+            return Ok((size, labels));
+        }
+        size += 1;
+    }
+}
+
+/// An email address `local@domain`, along with its parts.
+///
+/// The local part is made of the characters allowed by RFC 5322 outside of
+/// quotes, its dots neither leading, trailing nor consecutive. The domain
+/// has at least two labels of letters, digits and `-` separated by dots.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::email::Email;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"jane.doe+news@mail.example.org.");
+/// let email: Email = scanner.visit().expect("failed to parse");
+/// assert_eq!(email.text, "jane.doe+news@mail.example.org");
+/// assert_eq!(email.local, "jane.doe+news");
+/// assert_eq!(email.domain, "mail.example.org");
+/// assert_eq!(scanner.remaining(), b".");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Email<'a> {
+    /// The whole address
+    pub text: &'a str,
+    /// The local part, before the `@`
+    pub local: &'a str,
+    /// The domain, after the `@`
+    pub domain: &'a str,
+}
+
+impl<'a> Visitor<'a, u8> for Email<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let data = scanner.remaining();
+        let error = |offset: usize| {
+            ParseError::unexpected_token(start + offset).expecting("an email address")
+        };
+
+        let local = data
+            .iter()
+            .take_while(|byte| is_local_byte(byte) || **byte == b'.')
+            .count();
+        if local == 0 || data.get(local) != Some(&b'@') {
+            return Err(error(0));
+        }
+        if let Some(dot) = data[..local]
+            .windows(2)
+            .position(|pair| pair == b"..")
+            .or((data[0] == b'.').then_some(0))
+            .or((data[local - 1] == b'.').then_some(local - 1))
+        {
+            return Err(error(dot));
+        }
+
+        let domain_start = local + 1;
+        let (domain, labels) =
+            domain(&data[domain_start..]).map_err(|position| error(domain_start + position))?;
+        if labels < 2 {
+            return Err(error(domain_start));
+        }
+
+        let size = domain_start + domain;
+        // the address bytes are ASCII
+        let text = std::str::from_utf8(&data[..size])?;
+        scanner.bump_by(size);
+        Ok(Email {
+            text,
+            local: &text[..local],
+            domain: &text[domain_start..],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::email::Email;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_email() {
+        for (data, local, domain) in [
+            (&b"a@b.c"[..], "a", "b.c"),
+            (
+                b"first.last@sub.example.com",
+                "first.last",
+                "sub.example.com",
+            ),
+            (
+                b"o'hara!#$%&*/=?^_`{|}~-@x-1.io",
+                "o'hara!#$%&*/=?^_`{|}~-",
+                "x-1.io",
+            ),
+            (b"me@example.com, you", "me", "example.com"),
+            (b"me@example.com.", "me", "example.com"),
+            (b"me@example.com..", "me", "example.com"),
+            (b"me@example.com>", "me", "example.com"),
+        ] {
+            let mut scanner = Scanner::new(data);
+            let email: Email = scanner.visit().expect("failed to parse");
+            assert_eq!((email.local, email.domain), (local, domain));
+            assert_eq!(email.text, format!("{local}@{domain}"));
+            assert_eq!(scanner.current_position(), email.text.len());
+        }
+    }
+
+    #[test]
+    fn test_invalid_email() {
+        for (data, position) in [
+            (&b"@example.com"[..], 0),
+            (b"me example.com", 0),
+            (b"\"me\"@example.com", 0),
+            (b".me@example.com", 0),
+            (b"me.@example.com", 2),
+            (b"m..e@example.com", 1),
+            (b"me@localhost", 3),
+            (b"me@", 3),
+            (b"me@-example.com", 3),
+            (b"me@example-.com", 3),
+            (b"me@example.-com", 11),
+        ] {
+            let mut scanner = Scanner::new(data);
+            assert!(
+                matches!(
+                    scanner.visit::<Email>(),
+                    Err(ParseError::UnexpectedToken { offset, .. }) if offset == position
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(scanner.current_position(), 0);
+        }
+    }
+}
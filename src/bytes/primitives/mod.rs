@@ -1,6 +1,14 @@
 //! Specialized primitive parsers for bytes.
 
+pub mod ansi;
 pub mod binary_operator;
+pub mod card;
+#[cfg(feature = "music")]
+pub mod chord;
+pub mod email;
+pub mod net;
 pub mod number;
+pub mod phone;
 pub mod string;
+pub mod url;
 pub mod whitespace;
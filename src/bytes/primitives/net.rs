@@ -0,0 +1,282 @@
+//! IP addresses
+//!
+//! The addresses are parsed into the types of `std::net`, from the dotted-quad
+//! notation of IPv4, `192.168.0.1`, and the hexadecimal notation of IPv6,
+//! whose longest run of zero groups may be compressed as `::`, as in `::1`.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::net;
+
+/// The number of 16-bit groups of an IPv6 address
+const IPV6_GROUPS: usize = 8;
+
+/// Read a decimal octet, without leading zero.
+///
+/// Return the octet and the number of bytes read.
+fn octet(data: &[u8]) -> Option<(u8, usize)> {
+    let size = data.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    if size == 0 || size > 3 || (size > 1 && data[0] == b'0') {
+        return None;
+    }
+    let value = data[..size]
+        .iter()
+        .fold(0_u16, |value, byte| value * 10 + (byte - b'0') as u16);
+    Some((u8::try_from(value).ok()?, size))
+}
+
+/// Read the four octets of an IPv4 address in dotted-quad notation.
+///
+/// Return the octets and the number of bytes read.
+fn octets(data: &[u8]) -> Option<([u8; 4], usize)> {
+    let mut octets = [0; 4];
+    let mut position = 0;
+    for (index, value) in octets.iter_mut().enumerate() {
+        if index > 0 {
+            if data.get(position) != Some(&b'.') {
+                return None;
+            }
+            position += 1;
+        }
+        let (octet, size) = octet(&data[position..])?;
+        *value = octet;
+        position += size;
+    }
+    Some((octets, position))
+}
+
+/// Read the groups of an IPv6 address in hexadecimal notation.
+///
+/// Return the groups and the number of bytes read.
+fn groups(data: &[u8]) -> Option<([u16; IPV6_GROUPS], usize)> {
+    // the groups before and after the `::`
+    let mut head = vec![];
+    let mut tail = vec![];
+    let mut compressed = false;
+    let mut position = 0;
+    if data.starts_with(b"::") {
+        compressed = true;
+        position = 2;
+    }
+    loop {
+        let groups = if compressed { &mut tail } else { &mut head };
+        let rest = &data[position..];
+        let size = rest
+            .iter()
+            .take_while(|byte| byte.is_ascii_hexdigit())
+            .count();
+        // an IPv4 address ends the address, as in `::ffff:192.168.0.1`
+        if rest.get(size) == Some(&b'.') {
+            let ([a, b, c, d], size) = octets(rest)?;
+            groups.extend([u16::from_be_bytes([a, b]), u16::from_be_bytes([c, d])]);
+            position += size;
+            break;
+        }
+        if size == 0 {
+            // the address may end right after the `::`
+            if compressed && tail.is_empty() {
+                break;
+            }
+            return None;
+        }
+        if size > 4 {
+            return None;
+        }
+        let group = std::str::from_utf8(&rest[..size]).ok()?;
+        groups.push(u16::from_str_radix(group, 16).ok()?);
+        position += size;
+        if head.len() + tail.len() > IPV6_GROUPS {
+            return None;
+        }
+
+        let rest = &data[position..];
+        if rest.starts_with(b"::") {
+            // only one run of zero groups can be compressed
+            if compressed {
+                return None;
+            }
+            compressed = true;
+            position += 2;
+        } else if rest.first() == Some(&b':') && rest.get(1).is_some_and(u8::is_ascii_hexdigit) {
+            position += 1;
+        } else {
+            break;
+        }
+    }
+
+    let count = head.len() + tail.len();
+    if (compressed && count >= IPV6_GROUPS) || (!compressed && count != IPV6_GROUPS) {
+        return None;
+    }
+    let mut groups = [0; IPV6_GROUPS];
+    groups[..head.len()].copy_from_slice(&head);
+    groups[IPV6_GROUPS - tail.len()..].copy_from_slice(&tail);
+    Some((groups, position))
+}
+
+/// An IPv4 address in dotted-quad notation.
+///
+/// The address is made of four decimal octets separated by `.`, an octet
+/// being at most 255 and written without leading zero, as its leading zeros
+/// are read as an octal number by some tools.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::net::Ipv4Addr;
+/// use elyze::scanner::Scanner;
+/// use std::net;
+///
+/// let mut scanner = Scanner::new(b"192.168.0.1:8080");
+/// let Ipv4Addr(address) = scanner.visit().expect("failed to parse");
+/// assert_eq!(address, net::Ipv4Addr::new(192, 168, 0, 1));
+/// assert_eq!(scanner.remaining(), b":8080");
+///
+/// assert!(Scanner::new(b"192.168.0.256").visit::<Ipv4Addr>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv4Addr(pub net::Ipv4Addr);
+
+impl<'a> Visitor<'a, u8> for Ipv4Addr {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let (octets, size) = octets(scanner.remaining())
+            .ok_or_else(|| ParseError::unexpected_token(start).expecting("an IPv4 address"))?;
+        scanner.bump_by(size);
+        Ok(Ipv4Addr(net::Ipv4Addr::from(octets)))
+    }
+}
+
+/// An IPv6 address in hexadecimal notation.
+///
+/// The address is made of eight groups of 1 to 4 hexadecimal digits separated
+/// by `:`, a run of zero groups being possibly compressed as `::`, and its
+/// last two groups possibly written as an IPv4 address. The zone of a
+/// link-local address, as in `fe80::1%eth0`, isn't part of the address.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::net::Ipv6Addr;
+/// use elyze::scanner::Scanner;
+/// use std::net;
+///
+/// let Ipv6Addr(address) = Scanner::new(b"::1").visit().expect("failed to parse");
+/// assert_eq!(address, net::Ipv6Addr::LOCALHOST);
+///
+/// let mut scanner = Scanner::new(b"2001:db8::ff00:42:8329]:443");
+/// let Ipv6Addr(address) = scanner.visit().expect("failed to parse");
+/// assert_eq!(address, net::Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329));
+/// assert_eq!(scanner.remaining(), b"]:443");
+///
+/// let Ipv6Addr(address) = Scanner::new(b"::ffff:192.0.2.128").visit().expect("failed to parse");
+/// assert_eq!(address.to_ipv4_mapped(), Some(net::Ipv4Addr::new(192, 0, 2, 128)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv6Addr(pub net::Ipv6Addr);
+
+impl<'a> Visitor<'a, u8> for Ipv6Addr {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let (groups, size) = groups(scanner.remaining())
+            .ok_or_else(|| ParseError::unexpected_token(start).expecting("an IPv6 address"))?;
+        scanner.bump_by(size);
+        Ok(Ipv6Addr(net::Ipv6Addr::from(groups)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::net::{Ipv4Addr, Ipv6Addr};
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+    use std::net;
+
+    #[test]
+    fn test_ipv4() {
+        for (data, size) in [
+            (&b"0.0.0.0"[..], 7),
+            (b"255.255.255.255", 15),
+            (b"10.0.0.1/8", 8),
+            (b"1.2.3.4.5", 7),
+        ] {
+            let mut scanner = Scanner::new(data);
+            let Ipv4Addr(address) = scanner.visit().expect("failed to parse");
+            let text = String::from_utf8_lossy(&data[..size]);
+            assert_eq!(Ok(address), text.parse::<net::Ipv4Addr>(), "{text:?}");
+            assert_eq!(scanner.current_position(), size);
+        }
+
+        for data in [
+            &b"1.2.3"[..],
+            b"1.2.3.",
+            b"256.0.0.1",
+            b"01.2.3.4",
+            b"1.2.3.1000",
+            b"1..2.3",
+            b".1.2.3.4",
+            b"",
+        ] {
+            let mut scanner = Scanner::new(data);
+            assert!(
+                matches!(
+                    scanner.visit::<Ipv4Addr>(),
+                    Err(ParseError::UnexpectedToken { offset: 0, .. })
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(scanner.current_position(), 0);
+        }
+    }
+
+    #[test]
+    fn test_ipv6() {
+        for (data, size) in [
+            (&b"::"[..], 2),
+            (b"::1", 3),
+            (b"1::", 3),
+            (b"1:2:3:4:5:6:7:8", 15),
+            (b"1:2:3:4:5:6::8", 14),
+            (b"1::8", 4),
+            (b"fe80::1%eth0", 7),
+            (b"2001:DB8:0:0:8:800:200C:417A", 28),
+            (b"::ffff:192.0.2.128", 18),
+            (b"64:ff9b::192.0.2.33", 19),
+            (b"1:2:3:4:5:6:1.2.3.4", 19),
+            (b"::1:", 3),
+            (b"::g", 2),
+            (b"1:2:3:4:5:6:7:8:", 15),
+        ] {
+            let mut scanner = Scanner::new(data);
+            let Ipv6Addr(address) = scanner.visit().expect("failed to parse");
+            let text = String::from_utf8_lossy(&data[..size]);
+            assert_eq!(Ok(address), text.parse::<net::Ipv6Addr>(), "{text:?}");
+            assert_eq!(scanner.current_position(), size);
+        }
+
+        for data in [
+            &b":1"[..],
+            b"1:2:3:4:5:6:7",
+            b"1:2:3:4:5:6:7:8:9",
+            b"1::2::3",
+            b"1:2:3:4::5:6:7:8",
+            b"12345::",
+            b"::1.2.3",
+            b"1:2:3:4:5:6:7:1.2.3.4",
+            b"",
+        ] {
+            let mut scanner = Scanner::new(data);
+            assert!(
+                matches!(
+                    scanner.visit::<Ipv6Addr>(),
+                    Err(ParseError::UnexpectedToken { offset: 0, .. })
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(scanner.current_position(), 0);
+        }
+    }
+}
@@ -1,12 +1,17 @@
 //! Define the number token and its acceptor.
 
-use crate::bytes::matchers::match_number;
+use crate::bytes::matchers::{match_number, match_number_radix};
 use crate::errors::ParseResult;
+use crate::generate::{ascii_except, Generate, MAX_REPETITIONS};
 use crate::matcher::Match;
 use crate::peek::{DefaultPeekableImplementation, PeekableImplementation};
 use crate::recognizer::recognize_slice;
 use crate::scanner::Scanner;
+use crate::validate::Validate;
 use crate::visitor::Visitor;
+use rand::Rng;
+use std::marker::PhantomData;
+use std::num::ParseIntError;
 
 #[derive(Default)]
 pub struct TokenNumber;
@@ -20,6 +25,24 @@ impl Match<u8> for TokenNumber {
     fn size(&self) -> usize {
         0
     }
+
+    fn describe(&self) -> Option<String> {
+        Some("a number".to_string())
+    }
+}
+
+impl Generate<u8> for TokenNumber {
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let size = rng.random_range(1..=MAX_REPETITIONS);
+        (0..size).map(|_| rng.random_range(b'0'..=b'9')).collect()
+    }
+
+    fn generate_miss<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        // digits behind a character which isn't one
+        let mut miss = vec![ascii_except(rng, |byte| byte.is_ascii_digit())];
+        miss.extend(self.generate(rng));
+        miss
+    }
 }
 
 impl PeekableImplementation for TokenNumber {
@@ -41,6 +64,8 @@ macro_rules! impl_number {
                 Ok(Number(result))
             }
         }
+
+        impl Validate<'_, u8> for Number<$type> {}
     };
 }
 
@@ -56,3 +81,246 @@ impl_number!(i16);
 impl_number!(i32);
 impl_number!(i64);
 impl_number!(i128);
+
+/// The integers a number written in a given radix can be parsed to.
+///
+/// The signed integers accept an optional leading `-`, the unsigned ones
+/// only digits.
+pub trait RadixInteger: Sized {
+    /// Whether a leading `-` is accepted
+    const SIGNED: bool;
+
+    /// Parse the optional sign and the digits in the radix.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The sign and the digits
+    /// * `radix` - The radix of the digits
+    fn from_str_radix(data: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+/// Implement the `RadixInteger` trait for the integer type.
+macro_rules! impl_radix_integer {
+    ($type:ty, $signed:literal) => {
+        impl RadixInteger for $type {
+            const SIGNED: bool = $signed;
+
+            fn from_str_radix(data: &str, radix: u32) -> Result<Self, ParseIntError> {
+                <$type>::from_str_radix(data, radix)
+            }
+        }
+    };
+}
+
+impl_radix_integer!(usize, false);
+impl_radix_integer!(u8, false);
+impl_radix_integer!(u16, false);
+impl_radix_integer!(u32, false);
+impl_radix_integer!(u64, false);
+impl_radix_integer!(u128, false);
+impl_radix_integer!(isize, true);
+impl_radix_integer!(i8, true);
+impl_radix_integer!(i16, true);
+impl_radix_integer!(i32, true);
+impl_radix_integer!(i64, true);
+impl_radix_integer!(i128, true);
+
+/// A number written in a given radix.
+///
+/// Unlike [Number], the radix is only known at runtime, so the number is
+/// parsed by [NumberRadix::parse] instead of a [Visitor], see [Radix] for a
+/// radix known at compile time. Only the digits valid for the radix are
+/// consumed, preceded by an optional `-` if the integer is signed.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::NumberRadix;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"zz-1");
+/// let id = NumberRadix::<u32>::new(36).parse(&mut scanner).expect("failed to parse");
+/// assert_eq!(id, 1295);
+/// assert_eq!(scanner.remaining(), b"-1");
+///
+/// let mut scanner = Scanner::new(b"-ff");
+/// let offset = NumberRadix::<i16>::new(16).parse(&mut scanner).expect("failed to parse");
+/// assert_eq!(offset, -255);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberRadix<T> {
+    radix: u32,
+    _marker: PhantomData<T>,
+}
+
+impl<T> NumberRadix<T> {
+    /// Create a number parser for the given radix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix is not in the range `2..=36`.
+    pub fn new(radix: u32) -> Self {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36, got {radix}"
+        );
+        NumberRadix {
+            radix,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the radix of the number.
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+}
+
+impl<T: RadixInteger + Copy> NumberRadix<T> {
+    /// Consume the optional sign and the digits valid for the radix and
+    /// parse them.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to parse the number from
+    ///
+    /// # Errors
+    ///
+    /// An `UnexpectedToken` error if the scanner doesn't start with a digit,
+    /// or a `ParseIntError` if the number overflows the integer.
+    pub fn parse(&self, scanner: &mut Scanner<u8>) -> ParseResult<T> {
+        let raw_data = recognize_slice(*self, scanner)?;
+        let str_data = std::str::from_utf8(raw_data)?;
+        Ok(T::from_str_radix(str_data, self.radix)?)
+    }
+}
+
+/// Implement the `Match` trait for the optional sign and the digits of the
+/// radix.
+impl<T: RadixInteger> Match<u8> for NumberRadix<T> {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        let sign = usize::from(T::SIGNED && data.first() == Some(&b'-'));
+        match match_number_radix(self.radix, &data[sign..]) {
+            (true, size) => (true, sign + size),
+            _ => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+/// A number written in a radix known at compile time.
+///
+/// The [Visitor] counterpart of [NumberRadix], the number is made of an
+/// optional `-` if the integer is signed and of the digits valid for the
+/// radix. A radix outside of the range `2..=36` fails to compile.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Radix;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"zz-1");
+/// let Radix(id) = scanner.visit::<Radix<u32, 36>>().expect("failed to parse");
+/// assert_eq!(id, 1295);
+/// let Radix(offset) = scanner.visit::<Radix<i8, 10>>().expect("failed to parse");
+/// assert_eq!(offset, -1);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Radix<T, const RADIX: u32>(pub T);
+
+impl<T: RadixInteger + Copy, const RADIX: u32> Visitor<'_, u8> for Radix<T, RADIX> {
+    fn accept(scanner: &mut Scanner<u8>) -> ParseResult<Self> {
+        const {
+            assert!(
+                RADIX >= 2 && RADIX <= 36,
+                "radix must be in the range 2..=36"
+            )
+        };
+        Ok(Radix(NumberRadix::new(RADIX).parse(scanner)?))
+    }
+}
+
+impl<T: RadixInteger + Copy, const RADIX: u32> Validate<'_, u8> for Radix<T, RADIX> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::{NumberRadix, Radix};
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_number_radix() {
+        let mut scanner = Scanner::new(b"ff 17");
+        let result = NumberRadix::<u8>::new(16).parse(&mut scanner);
+        assert_eq!(result.expect("failed to parse"), 255);
+        assert_eq!(scanner.remaining(), b" 17");
+
+        let mut scanner = Scanner::new(b"1019");
+        let result = NumberRadix::<u64>::new(2).parse(&mut scanner);
+        assert_eq!(result.expect("failed to parse"), 5);
+        assert_eq!(scanner.remaining(), b"9");
+
+        let mut scanner = Scanner::new(b"9");
+        let result = NumberRadix::<u64>::new(8).parse(&mut scanner);
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+
+        let mut scanner = Scanner::new(b"100");
+        let result = NumberRadix::<u8>::new(16).parse(&mut scanner);
+        assert!(matches!(result, Err(ParseError::ParseIntError(_))));
+    }
+
+    #[test]
+    fn test_number_radix_sign() {
+        let mut scanner = Scanner::new(b"-7f");
+        let result = NumberRadix::<i8>::new(16).parse(&mut scanner);
+        assert_eq!(result.expect("failed to parse"), -127);
+        assert!(scanner.is_empty());
+
+        // the unsigned integers don't consume the sign
+        let mut scanner = Scanner::new(b"-7f");
+        let result = NumberRadix::<u8>::new(16).parse(&mut scanner);
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+
+        // a sign without digits is not a number
+        let mut scanner = Scanner::new(b"-g");
+        let result = NumberRadix::<i8>::new(16).parse(&mut scanner);
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_radix_visitor() {
+        let mut scanner = Scanner::new(b"z1;-101");
+        let result = scanner.visit::<Radix<u16, 36>>();
+        assert_eq!(result.expect("failed to parse"), Radix(1261));
+        scanner.bump_by(1);
+        let result = scanner.visit::<Radix<i32, 2>>();
+        assert_eq!(result.expect("failed to parse"), Radix(-5));
+        assert!(scanner.is_empty());
+
+        let mut scanner = Scanner::new(b"8");
+        assert!(matches!(
+            scanner.visit::<Radix<u8, 8>>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in the range 2..=36")]
+    fn test_number_radix_invalid() {
+        NumberRadix::<u32>::new(37);
+    }
+}
@@ -0,0 +1,188 @@
+//! Phone numbers written by humans
+//!
+//! The numbers are written with all kinds of separators between their digit
+//! groups: `+33 6 12 34 56 78`, `(555) 123-4567`, `06.12.34.56.78`. The
+//! separators are skipped and only the digits are kept.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// The maximal number of digits of a phone number, as set by E.164
+const MAX_DIGITS: usize = 15;
+/// The minimal number of digits of a national number, without its area code
+const MIN_NATIONAL_DIGITS: usize = 7;
+/// The minimal number of digits of an international number, its country code
+/// included
+const MIN_INTERNATIONAL_DIGITS: usize = 8;
+
+/// Whether the byte separates two digit groups
+fn is_separator(byte: &u8) -> bool {
+    matches!(byte, b' ' | b'-' | b'.' | b'/')
+}
+
+/// A phone number, its digits normalized.
+///
+/// The number may start with a `+` for an international number. Its digit
+/// groups are separated by at most one space, `-`, `.` or `/`, and one group
+/// may be enclosed in parentheses, as an area code. The trunk prefix `(0)`
+/// written after a country code, as in `+44 (0)20 7946 0958`, is dropped.
+///
+/// The number ends at the first byte which can't continue it, a trailing
+/// separator is left in the scanner.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::phone::PhoneNumber;
+/// use elyze::scanner::Scanner;
+///
+/// let phone: PhoneNumber = Scanner::new(b"(555) 123-4567").visit().expect("failed to parse");
+/// assert_eq!(phone.digits, "5551234567");
+/// assert!(!phone.international);
+///
+/// let phone: PhoneNumber = Scanner::new(b"+33 6 12 34 56 78").visit().expect("failed to parse");
+/// assert_eq!(phone.to_string(), "+33612345678");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhoneNumber {
+    /// Whether the number starts with a `+` and a country code
+    pub international: bool,
+    /// The digits of the number, without its `+` and separators
+    pub digits: String,
+}
+
+impl std::fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.international {
+            write!(f, "+")?;
+        }
+        write!(f, "{}", self.digits)
+    }
+}
+
+impl<'a> Visitor<'a, u8> for PhoneNumber {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let international = checkpoint.first() == Some(&b'+');
+        if international {
+            checkpoint.bump_by(1);
+        }
+
+        let mut digits = String::new();
+        // the position of the parenthesis of the open area code, if any
+        let mut open_group = None;
+        let mut area_code = false;
+        loop {
+            let remaining = checkpoint.remaining();
+            // a separator may precede a digit or the area code
+            let continues = remaining
+                .get(1)
+                .is_some_and(|byte| byte.is_ascii_digit() || (*byte == b'(' && !area_code));
+            match remaining.first() {
+                Some(byte) if byte.is_ascii_digit() => {
+                    digits.push(*byte as char);
+                    checkpoint.bump_by(1);
+                }
+                Some(b'(')
+                    if international && !digits.is_empty() && remaining.starts_with(b"(0)") =>
+                {
+                    checkpoint.bump_by(3);
+                }
+                Some(b'(') if !area_code && remaining.get(1).is_some_and(u8::is_ascii_digit) => {
+                    open_group = Some(checkpoint.current_position());
+                    area_code = true;
+                    checkpoint.bump_by(1);
+                }
+                Some(b')') if open_group.is_some() => {
+                    open_group = None;
+                    checkpoint.bump_by(1);
+                }
+                // a separator is only consumed between two digit groups
+                Some(byte) if is_separator(byte) && !digits.is_empty() && continues => {
+                    checkpoint.bump_by(1);
+                }
+                _ => break,
+            }
+        }
+
+        if let Some(position) = open_group {
+            return Err(ParseError::unexpected_token(position).expecting("a closed area code"));
+        }
+        let min_digits = if international {
+            MIN_INTERNATIONAL_DIGITS
+        } else {
+            MIN_NATIONAL_DIGITS
+        };
+        if !(min_digits..=MAX_DIGITS).contains(&digits.len()) {
+            return Err(ParseError::unexpected_token(start).expecting("a phone number"));
+        }
+
+        checkpoint.commit();
+        Ok(PhoneNumber {
+            international,
+            digits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::phone::PhoneNumber;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_phone_number() {
+        for (data, international, digits) in [
+            (&b"+33 6 12 34 56 78"[..], true, "33612345678"),
+            (b"+33612345678", true, "33612345678"),
+            (b"06.12.34.56.78", false, "0612345678"),
+            (b"(555) 123-4567", false, "5551234567"),
+            (b"555/123 4567", false, "5551234567"),
+            (b"+44 (0)20 7946 0958", true, "442079460958"),
+            (b"+1 (555) 123-4567", true, "15551234567"),
+            (b"123-4567", false, "1234567"),
+        ] {
+            let phone: PhoneNumber = Scanner::new(data).visit().expect("failed to parse");
+            assert_eq!(
+                phone,
+                PhoneNumber {
+                    international,
+                    digits: digits.to_string()
+                },
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+
+        // the separators after the number are left in the scanner
+        let mut scanner = Scanner::new(b"555-1234 - mobile");
+        let phone: PhoneNumber = scanner.visit().expect("failed to parse");
+        assert_eq!(phone.digits, "5551234");
+        assert_eq!(scanner.remaining(), b" - mobile");
+    }
+
+    #[test]
+    fn test_invalid_phone_number() {
+        for (data, position) in [
+            (&b"555-123"[..], 0),
+            (b"+33 61234", 0),
+            (b"1234567890123456", 0),
+            (b"(555 123-4567", 0),
+            (b"+ 33 6 12 34 56 78", 0),
+            (b"phone", 0),
+            (b"1 (555) (123) 4567", 0),
+        ] {
+            assert!(
+                matches!(
+                    Scanner::new(data).visit::<PhoneNumber>(),
+                    Err(ParseError::UnexpectedToken { offset, .. }) if offset == position
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+    }
+}
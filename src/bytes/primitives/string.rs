@@ -1,11 +1,14 @@
 //! String primitives
 
+use crate::bytes::components::groups::GroupKind;
+use crate::bytes::escape::{unescape_str, BackslashEscapes};
 use crate::bytes::matchers::match_string;
-use crate::errors::ParseResult;
+use crate::errors::{ParseError, ParseResult};
 use crate::matcher::Match;
-use crate::peek::{DefaultPeekableImplementation, PeekableImplementation};
+use crate::peek::{peek, DefaultPeekableImplementation, PeekableImplementation};
 use crate::recognizer::recognize_slice;
 use crate::scanner::Scanner;
+use crate::validate::Validate;
 use crate::visitor::Visitor;
 use std::borrow::Cow;
 
@@ -37,9 +40,156 @@ macro_rules! impl_string {
                 Ok(DataString(str_data.into()))
             }
         }
+
+        impl<$a> Validate<$a, u8> for DataString<$type> {
+            fn validate(scanner: &mut Scanner<$a, u8>) -> ParseResult<()> {
+                let raw_data = recognize_slice(TokenString, scanner)?;
+                std::str::from_utf8(raw_data)?;
+                Ok(())
+            }
+        }
     };
 }
 
 impl_string!(&'a str, 'a);
 impl_string!(String, 'a);
 impl_string!(Cow<'a, str>, 'a);
+
+/// A string enclosed in single or double quotes, as written.
+///
+/// The content is the one of a [GroupKind::Quotes] or
+/// [GroupKind::DoubleQuotes] group, a backslash escapes the quote, but the
+/// escape sequences aren't decoded, see [EscapedString] to decode them.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::string::QuotedString;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(br#"'it\'s' rest"#);
+/// let string: QuotedString = scanner.visit().expect("failed to parse");
+/// assert_eq!(string.0, br#"it\'s"#);
+/// assert_eq!(scanner.remaining(), b" rest");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotedString<'a>(pub &'a [u8]);
+
+impl<'a> Visitor<'a, u8> for QuotedString<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let kind = match scanner.first() {
+            Some(b'"') => GroupKind::DoubleQuotes,
+            Some(b'\'') => GroupKind::Quotes,
+            Some(_) => {
+                return Err(ParseError::unexpected_token(scanner.current_position())
+                    .expecting("a quoted string"));
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        };
+        // the string is never closed
+        let peeked = peek(kind, scanner)?.ok_or(ParseError::UnexpectedEndOfInput)?;
+        scanner.bump_by(peeked.end_slice);
+        Ok(QuotedString(peeked.peeked_slice()))
+    }
+}
+
+/// A string enclosed in single or double quotes, its escape sequences
+/// decoded.
+///
+/// The escapes are the [BackslashEscapes], among them `\n`, `\t`, `\\`,
+/// `\'`, `\"` and `\u{...}`. The string is only copied if it contains
+/// escape sequences.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::string::EscapedString;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(br#""say \"hi\"\n\u{1F600}" rest"#);
+/// let string: EscapedString = scanner.visit().expect("failed to parse");
+/// assert_eq!(string.0, "say \"hi\"\n\u{1F600}");
+/// assert_eq!(scanner.remaining(), b" rest");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapedString<'a>(pub Cow<'a, str>);
+
+impl<'a> Visitor<'a, u8> for EscapedString<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        // the content starts after the opening quote
+        let start = checkpoint.current_position() + 1;
+        let QuotedString(content) = checkpoint.visit()?;
+        let value = unescape_str(&BackslashEscapes, content).map_err(|err| err.shifted(start))?;
+        checkpoint.commit();
+        Ok(EscapedString(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::string::{EscapedString, QuotedString};
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_quoted_string() {
+        let mut scanner = Scanner::new(br#""a, \"b\"", 'c' "#);
+        let string: QuotedString = scanner.visit().expect("failed to parse");
+        assert_eq!(string.0, br#"a, \"b\""#);
+        scanner.bump_by(2);
+        let string: QuotedString = scanner.visit().expect("failed to parse");
+        assert_eq!(string.0, b"c");
+        assert_eq!(scanner.remaining(), b" ");
+
+        let mut scanner = Scanner::new(br#""unterminated"#);
+        assert!(matches!(
+            scanner.visit::<QuotedString>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_escaped_string() {
+        for (data, expected) in [
+            (&br#""a\tb\\""#[..], "a\tb\\"),
+            (br#"'it\'s'"#, "it's"),
+            (br#"'"'"#, "\""),
+            (br#""\u{e9}t\u{E9}""#, "\u{e9}t\u{e9}"),
+            (br#""""#, ""),
+        ] {
+            let string: EscapedString = Scanner::new(data).visit().expect("failed to parse");
+            assert_eq!(string.0, expected, "{:?}", String::from_utf8_lossy(data));
+        }
+
+        // the string is only copied when it is decoded
+        let string: EscapedString = Scanner::new(br#""plain""#)
+            .visit()
+            .expect("failed to parse");
+        assert!(matches!(string.0, Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn test_invalid_escaped_string() {
+        let mut scanner = Scanner::new(br#"x "a\qb""#);
+        scanner.bump_by(2);
+        assert!(matches!(
+            scanner.visit::<EscapedString>(),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert_eq!(scanner.current_position(), 2);
+
+        for data in [&br#""unterminated"#[..], br#""a\""#, b""] {
+            assert!(matches!(
+                Scanner::new(data).visit::<EscapedString>(),
+                Err(ParseError::UnexpectedEndOfInput)
+            ));
+        }
+        assert!(matches!(
+            Scanner::new(b"plain").visit::<EscapedString>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+    }
+}
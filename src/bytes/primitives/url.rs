@@ -0,0 +1,301 @@
+//! URLs written in a text
+//!
+//! The recognition is pragmatic rather than a full RFC 3986 validation: a URL
+//! is an absolute `scheme://authority/path?query#fragment` made of the
+//! printable ASCII characters allowed in a URL. The punctuation ending a
+//! sentence, as in `see https://example.com.`, isn't part of the URL.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Whether the byte can appear in a URL
+fn is_url_byte(byte: &u8) -> bool {
+    byte.is_ascii_graphic()
+        && !matches!(
+            byte,
+            b'"' | b'<' | b'>' | b'\\' | b'^' | b'`' | b'{' | b'|' | b'}'
+        )
+}
+
+/// Whether the byte can continue the scheme of a URL
+fn is_scheme_byte(byte: &u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.')
+}
+
+/// Whether the byte can appear in a host name
+fn is_host_byte(byte: &u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'%')
+}
+
+/// The size of the URL once the punctuation following it is dropped
+fn trim_punctuation(url: &[u8]) -> usize {
+    let mut size = url.len();
+    loop {
+        match url[..size].last() {
+            Some(b'.' | b',' | b';' | b':' | b'!' | b'?' | b'\'') => size -= 1,
+            // a closing parenthesis is kept if the URL opens it
+            Some(b')') => {
+                let opened = url[..size].iter().filter(|byte| **byte == b'(').count();
+                let closed = url[..size].iter().filter(|byte| **byte == b')').count();
+                if closed <= opened {
+                    return size;
+                }
+                size -= 1;
+            }
+            _ => return size,
+        }
+    }
+}
+
+/// Split the data at the first of the bytes.
+///
+/// Return the data before the byte, and the data after it if the byte is
+/// found.
+fn split_at_first<'a>(data: &'a str, bytes: &[u8]) -> (&'a str, Option<(u8, &'a str)>) {
+    match data.bytes().position(|byte| bytes.contains(&byte)) {
+        Some(index) => (
+            &data[..index],
+            Some((data.as_bytes()[index], &data[index + 1..])),
+        ),
+        None => (data, None),
+    }
+}
+
+/// An absolute URL, along with its parts.
+///
+/// The URL starts with a scheme followed by `://` and a non-empty host, an
+/// IPv6 host being enclosed in brackets. Its path, query and fragment are
+/// kept as written, their percent-encoded bytes are left undecoded.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::url::Url;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"https://user@example.com:8443/a/b?q=1#top, then");
+/// let url: Url = scanner.visit().expect("failed to parse");
+/// assert_eq!(url.text, "https://user@example.com:8443/a/b?q=1#top");
+/// assert_eq!(url.scheme, "https");
+/// assert_eq!(url.userinfo, Some("user"));
+/// assert_eq!(url.host, "example.com");
+/// assert_eq!(url.port, Some(8443));
+/// assert_eq!(url.path, "/a/b");
+/// assert_eq!(url.query, Some("q=1"));
+/// assert_eq!(url.fragment, Some("top"));
+/// assert_eq!(scanner.remaining(), b", then");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Url<'a> {
+    /// The whole URL
+    pub text: &'a str,
+    /// The scheme, without `://`
+    pub scheme: &'a str,
+    /// The user information before the `@` of the authority, if any
+    pub userinfo: Option<&'a str>,
+    /// The host, with the brackets of an IPv6 address
+    pub host: &'a str,
+    /// The port, if any
+    pub port: Option<u16>,
+    /// The path, starting with a `/` unless it is empty
+    pub path: &'a str,
+    /// The query, without its `?`
+    pub query: Option<&'a str>,
+    /// The fragment, without its `#`
+    pub fragment: Option<&'a str>,
+}
+
+impl<'a> Visitor<'a, u8> for Url<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let data = scanner.remaining();
+        let error = |offset: usize| ParseError::unexpected_token(start + offset).expecting("a URL");
+
+        let scheme = data.iter().take_while(|byte| is_scheme_byte(byte)).count();
+        if !data.first().is_some_and(u8::is_ascii_alphabetic) || !data[scheme..].starts_with(b"://")
+        {
+            return Err(error(0));
+        }
+        let size = data.iter().take_while(|byte| is_url_byte(byte)).count();
+        let size = trim_punctuation(&data[..size]);
+        // the URL bytes are ASCII
+        let text = std::str::from_utf8(&data[..size])?;
+        let authority_start = scheme + 3;
+        if size <= authority_start {
+            return Err(error(size));
+        }
+
+        let (authority, rest) = split_at_first(&text[authority_start..], b"/?#");
+        let (userinfo, host_port) = match authority.rfind('@') {
+            Some(index) => (Some(&authority[..index]), &authority[index + 1..]),
+            None => (None, authority),
+        };
+        let host_start = authority_start + authority.len() - host_port.len();
+        let host_size = match host_port.strip_prefix('[') {
+            Some(address) => match address.find(']') {
+                Some(index) => index + 2,
+                None => return Err(error(host_start)),
+            },
+            None => host_port.bytes().take_while(is_host_byte).count(),
+        };
+        if host_size == 0 {
+            return Err(error(host_start));
+        }
+        let port = match &host_port[host_size..] {
+            "" | ":" => None,
+            port => {
+                let digits = port.strip_prefix(':').unwrap_or(port);
+                if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return Err(error(host_start + host_size));
+                }
+                Some(
+                    digits
+                        .parse()
+                        .map_err(|_| error(host_start + host_size + 1))?,
+                )
+            }
+        };
+
+        let (path, query, fragment) = match rest {
+            None => ("", None, None),
+            Some((separator, rest)) => {
+                // the path keeps its leading `/`
+                let rest = &text[text.len() - rest.len() - 1..];
+                let (path, rest) = match separator {
+                    b'/' => split_at_first(rest, b"?#"),
+                    _ => ("", Some((separator, &rest[1..]))),
+                };
+                match rest {
+                    None => (path, None, None),
+                    Some((b'?', rest)) => {
+                        let (query, fragment) = split_at_first(rest, b"#");
+                        (path, Some(query), fragment.map(|(_, fragment)| fragment))
+                    }
+                    Some((_, fragment)) => (path, None, Some(fragment)),
+                }
+            }
+        };
+
+        scanner.bump_by(size);
+        Ok(Url {
+            text,
+            scheme: &text[..scheme],
+            userinfo,
+            host: &host_port[..host_size],
+            port,
+            path,
+            query,
+            fragment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::url::Url;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_url() {
+        for (data, text, host, path, query, fragment) in [
+            (
+                &b"http://example.com"[..],
+                "http://example.com",
+                "example.com",
+                "",
+                None,
+                None,
+            ),
+            (b"http://a.b/", "http://a.b/", "a.b", "/", None, None),
+            (b"ftp://h?x#y", "ftp://h?x#y", "h", "", Some("x"), Some("y")),
+            (
+                b"svn+ssh://h#y?z",
+                "svn+ssh://h#y?z",
+                "h",
+                "",
+                None,
+                Some("y?z"),
+            ),
+            (
+                b"http://[::1]:80/p",
+                "http://[::1]:80/p",
+                "[::1]",
+                "/p",
+                None,
+                None,
+            ),
+            (
+                b"https://w.org/a_(b).",
+                "https://w.org/a_(b)",
+                "w.org",
+                "/a_(b)",
+                None,
+                None,
+            ),
+            (
+                b"https://w.org/a)",
+                "https://w.org/a",
+                "w.org",
+                "/a",
+                None,
+                None,
+            ),
+            (b"http://h/a b", "http://h/a", "h", "/a", None, None),
+            (
+                b"http://h/?q=\"x\"",
+                "http://h/?q=",
+                "h",
+                "/",
+                Some("q="),
+                None,
+            ),
+        ] {
+            let mut scanner = Scanner::new(data);
+            let url: Url = scanner.visit().expect("failed to parse");
+            assert_eq!(
+                (url.text, url.host, url.path, url.query, url.fragment),
+                (text, host, path, query, fragment)
+            );
+            assert_eq!(scanner.current_position(), text.len());
+        }
+
+        let url: Url = Scanner::new(b"http://h:/")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!((url.userinfo, url.port), (None, None));
+        let url: Url = Scanner::new(b"http://a:b@c@h")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!((url.userinfo, url.host), (Some("a:b@c"), "h"));
+    }
+
+    #[test]
+    fn test_invalid_url() {
+        for (data, position) in [
+            (&b"example.com"[..], 0),
+            (b"1http://h", 0),
+            (b"http:/h", 0),
+            (b"http://", 7),
+            (b"http://.", 7),
+            (b"http:///path", 7),
+            (b"http://user@/path", 12),
+            (b"http://[::1/path", 7),
+            (b"http://h:8x", 8),
+            (b"http://h:99999", 9),
+            (b"http://h!x", 8),
+        ] {
+            let mut scanner = Scanner::new(data);
+            assert!(
+                matches!(
+                    scanner.visit::<Url>(),
+                    Err(ParseError::UnexpectedToken { offset, .. }) if offset == position
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+            assert_eq!(scanner.current_position(), 0);
+        }
+    }
+}
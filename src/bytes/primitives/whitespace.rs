@@ -2,18 +2,181 @@
 
 use crate::bytes::token::Token;
 use crate::errors::{ParseError, ParseResult};
+use crate::matcher::Match;
 use crate::recognizer::Recognizable;
 use crate::scanner::Scanner;
+use crate::validate::Validate;
 use crate::visitor::Visitor;
+use std::marker::PhantomData;
 
 /// Recognize at least one whitespace
+///
+/// Only the space character is a whitespace, see [WhitespacesOf] to also
+/// recognize tabs and newlines.
 pub struct Whitespaces;
 
 /// Recognize zero or more whitespaces
+///
+/// Only the space character is a whitespace, see [OptionalWhitespacesOf] to
+/// also skip tabs and newlines.
 pub struct OptionalWhitespaces;
 
+/// A class of whitespaces, matching a single whitespace.
+///
+/// On bytes, the Unicode whitespaces are decoded from UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::whitespace::WhitespaceClass;
+/// use elyze::matcher::Match;
+///
+/// assert_eq!(WhitespaceClass::Ascii.is_matching(b"\tx"), (true, 1));
+/// // a no-break space
+/// assert_eq!(WhitespaceClass::Ascii.is_matching(b"\xc2\xa0x"), (false, 0));
+/// assert_eq!(WhitespaceClass::Unicode.is_matching(b"\xc2\xa0x"), (true, 2));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WhitespaceClass {
+    /// The space, the tabulation, the carriage return and the line feed
+    #[default]
+    Ascii,
+    /// The characters with the Unicode `White_Space` property, which include
+    /// the ASCII whitespaces
+    Unicode,
+}
+
+impl WhitespaceClass {
+    /// Return whether the character belongs to the class
+    fn contains(&self, c: char) -> bool {
+        match self {
+            WhitespaceClass::Ascii => matches!(c, ' ' | '\t' | '\r' | '\n'),
+            WhitespaceClass::Unicode => c.is_whitespace(),
+        }
+    }
+}
+
+/// Decode the first UTF-8 character of the data and return it with its
+/// length
+fn decode_char(data: &[u8]) -> Option<(char, usize)> {
+    let width = match data.first()? {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return None,
+    };
+    let c = std::str::from_utf8(data.get(..width)?)
+        .ok()?
+        .chars()
+        .next()?;
+    Some((c, width))
+}
+
+impl Match<u8> for WhitespaceClass {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        match decode_char(data) {
+            Some((c, width)) if self.contains(c) => (true, width),
+            _ => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+impl Match<char> for WhitespaceClass {
+    fn is_matching(&self, data: &[char]) -> (bool, usize) {
+        match data.first() {
+            Some(c) if self.contains(*c) => (true, 1),
+            _ => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// The [WhitespaceClass::Unicode] whitespaces, as a type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnicodeWhitespace;
+
+impl<T> Match<T> for UnicodeWhitespace
+where
+    WhitespaceClass: Match<T>,
+{
+    fn is_matching(&self, data: &[T]) -> (bool, usize) {
+        WhitespaceClass::Unicode.is_matching(data)
+    }
+
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// Recognize at least one whitespace of the class `M`, by default the
+/// [WhitespaceClass::Ascii] whitespaces.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::whitespace::{UnicodeWhitespace, WhitespacesOf};
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b" \t\r\nx");
+/// scanner.visit::<WhitespacesOf>().expect("failed to parse");
+/// assert_eq!(scanner.remaining(), b"x");
+///
+/// let mut scanner = Scanner::new("\u{3000} x".as_bytes());
+/// scanner
+///     .visit::<WhitespacesOf<UnicodeWhitespace>>()
+///     .expect("failed to parse");
+/// assert_eq!(scanner.remaining(), b"x");
+/// ```
+pub struct WhitespacesOf<M = WhitespaceClass>(PhantomData<M>);
+
+/// Recognize zero or more whitespaces of the class `M`, by default the
+/// [WhitespaceClass::Ascii] whitespaces.
+pub struct OptionalWhitespacesOf<M = WhitespaceClass>(PhantomData<M>);
+
+/// Skip the whitespaces matched by the matcher and return how many elements
+/// were skipped
+fn skip_whitespaces<T, M: Match<T>>(
+    matcher: M,
+    scanner: &mut Scanner<'_, T>,
+) -> ParseResult<usize> {
+    let start = scanner.current_position();
+    while !scanner.is_empty() && (&matcher).recognize(scanner)?.is_some() {}
+    Ok(scanner.current_position() - start)
+}
+
+impl<'a, T, M: Match<T> + Default> Visitor<'a, T> for WhitespacesOf<M> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        if skip_whitespaces(M::default(), scanner)? == 0 {
+            return Err(ParseError::unexpected_token(start).expecting("a whitespace"));
+        }
+        Ok(WhitespacesOf(PhantomData))
+    }
+}
+
+impl<'a, T, M: Match<T> + Default> Visitor<'a, T> for OptionalWhitespacesOf<M> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        skip_whitespaces(M::default(), scanner)?;
+        Ok(OptionalWhitespacesOf(PhantomData))
+    }
+}
+
+impl<T, M: Match<T> + Default> Validate<'_, T> for WhitespacesOf<M> {}
+
+impl<T, M: Match<T> + Default> Validate<'_, T> for OptionalWhitespacesOf<M> {}
+
 impl<'a> Visitor<'a, u8> for Whitespaces {
     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
         let mut found = false;
 
         while Token::Whitespace.recognize(scanner)?.is_some() {
@@ -24,7 +187,7 @@ impl<'a> Visitor<'a, u8> for Whitespaces {
             found = true;
         }
         if !found {
-            return Err(ParseError::UnexpectedToken);
+            return Err(ParseError::unexpected_token(start).expecting("a whitespace"));
         }
         Ok(Whitespaces)
     }
@@ -44,6 +207,10 @@ impl<'a> Visitor<'a, u8> for OptionalWhitespaces {
     }
 }
 
+impl Validate<'_, u8> for Whitespaces {}
+
+impl Validate<'_, u8> for OptionalWhitespaces {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,7 +224,7 @@ mod tests {
 
         let mut scanner = Scanner::new(b"aaaaaaaaa");
         let result = Whitespaces::accept(&mut scanner);
-        assert!(!result.is_ok());
+        assert!(result.is_err());
         assert!(!scanner.is_empty());
     }
 
@@ -74,4 +241,45 @@ mod tests {
         assert!(result.is_ok());
         assert!(scanner.is_empty());
     }
+
+    #[test]
+    fn test_whitespace_class() {
+        assert_eq!(WhitespaceClass::Ascii.is_matching(b"\r\n"), (true, 1));
+        assert_eq!(WhitespaceClass::Ascii.is_matching(b"a"), (false, 0));
+        assert_eq!(WhitespaceClass::Ascii.is_matching(b""), (false, 0));
+        let ideographic = "\u{3000}".as_bytes();
+        assert_eq!(WhitespaceClass::Unicode.is_matching(ideographic), (true, 3));
+        assert_eq!(
+            WhitespaceClass::Unicode.is_matching(&ideographic[..2]),
+            (false, 0)
+        );
+        assert_eq!(WhitespaceClass::Unicode.is_matching(b"\xff"), (false, 0));
+        assert_eq!(UnicodeWhitespace.is_matching(&['\u{a0}']), (true, 1));
+        assert_eq!(WhitespaceClass::Ascii.is_matching(&['\u{a0}']), (false, 0));
+    }
+
+    #[test]
+    fn test_whitespaces_of() {
+        let mut scanner = Scanner::new(b"\t \r\n1\n");
+        scanner
+            .visit::<OptionalWhitespacesOf>()
+            .expect("failed to parse");
+        assert_eq!(scanner.current_position(), 4);
+        assert!(scanner.visit::<WhitespacesOf>().is_err());
+        scanner
+            .visit::<OptionalWhitespacesOf>()
+            .expect("failed to parse");
+        assert_eq!(scanner.current_position(), 4);
+        scanner.bump_by(1);
+        scanner.visit::<WhitespacesOf>().expect("failed to parse");
+        assert!(scanner.is_empty());
+
+        let data = "\u{a0}\u{2003}x".chars().collect::<Vec<_>>();
+        let mut scanner = Scanner::new(&data);
+        assert!(scanner.visit::<WhitespacesOf>().is_err());
+        scanner
+            .visit::<WhitespacesOf<UnicodeWhitespace>>()
+            .expect("failed to parse");
+        assert_eq!(scanner.remaining(), ['x']);
+    }
 }
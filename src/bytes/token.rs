@@ -2,11 +2,14 @@
 
 use crate::bytes::matchers::{match_char, match_pattern};
 use crate::errors::{ParseError, ParseResult};
+use crate::generate::{ascii_except, Generate};
 use crate::matcher::Match;
 use crate::peek::{CustomizedPeekableImplementation, PeekResult, Peekable, PeekableImplementation};
 use crate::recognizer::{recognize, Recognizer};
 use crate::scanner::Scanner;
+use crate::validate::Validate;
 use crate::visitor::Visitor;
+use rand::Rng;
 
 #[derive(Copy, Clone)]
 /// The token type
@@ -16,6 +19,14 @@ pub enum Token {
     OpenParen,
     /// The `)` character
     CloseParen,
+    /// The `[` character
+    OpenBracket,
+    /// The `]` character
+    CloseBracket,
+    /// The `{` character
+    OpenBrace,
+    /// The `}` character
+    CloseBrace,
     /// The `,` character
     Comma,
     /// The `;` character
@@ -85,6 +96,10 @@ impl Match<u8> for Token {
         match self {
             Token::OpenParen => match_char('(', data),
             Token::CloseParen => match_char(')', data),
+            Token::OpenBracket => match_char('[', data),
+            Token::CloseBracket => match_char(']', data),
+            Token::OpenBrace => match_char('{', data),
+            Token::CloseBrace => match_char('}', data),
             Token::Comma => match_char(',', data),
             Token::Semicolon => match_char(';', data),
             Token::Colon => match_char(':', data),
@@ -123,6 +138,10 @@ impl Match<u8> for Token {
         match self {
             Token::OpenParen => 1,
             Token::CloseParen => 1,
+            Token::OpenBracket => 1,
+            Token::CloseBracket => 1,
+            Token::OpenBrace => 1,
+            Token::CloseBrace => 1,
             Token::Comma => 1,
             Token::Semicolon => 1,
             Token::Colon => 1,
@@ -156,6 +175,10 @@ impl Match<u8> for Token {
             Token::CrLn => 2,
         }
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("`{}`", self.pattern().escape_ascii()))
+    }
 }
 
 /// Implement Visitor for Token make it possible to use Token::accept
@@ -166,6 +189,10 @@ impl<'a> Visitor<'a, u8> for Token {
         Recognizer::new(scanner)
             .try_or(Token::OpenParen)?
             .try_or(Token::CloseParen)?
+            .try_or(Token::OpenBracket)?
+            .try_or(Token::CloseBracket)?
+            .try_or(Token::OpenBrace)?
+            .try_or(Token::CloseBrace)?
             .try_or(Token::Comma)?
             .try_or(Token::Semicolon)?
             .try_or(Token::Colon)?
@@ -198,7 +225,68 @@ impl<'a> Visitor<'a, u8> for Token {
             .try_or(Token::Tab)?
             .try_or(Token::CrLn)?
             .finish()
-            .ok_or(ParseError::UnexpectedToken)
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+    }
+}
+
+impl Validate<'_, u8> for Token {}
+
+impl Token {
+    /// The bytes of the token
+    fn pattern(&self) -> &'static [u8] {
+        match self {
+            Token::OpenParen => b"(",
+            Token::CloseParen => b")",
+            Token::OpenBracket => b"[",
+            Token::CloseBracket => b"]",
+            Token::OpenBrace => b"{",
+            Token::CloseBrace => b"}",
+            Token::Comma => b",",
+            Token::Semicolon => b";",
+            Token::Colon => b":",
+            Token::Whitespace => b" ",
+            Token::GreaterThan => b">",
+            Token::LessThan => b"<",
+            Token::Exclamation => b"!",
+            Token::Quote => b"'",
+            Token::DoubleQuote => b"\"",
+            Token::Equal => b"=",
+            Token::Plus => b"+",
+            Token::Dash => b"-",
+            Token::Slash => b"/",
+            Token::Star => b"*",
+            Token::Percent => b"%",
+            Token::Ampersand => b"&",
+            Token::Pipe => b"|",
+            Token::Caret => b"^",
+            Token::Tilde => b"~",
+            Token::Dot => b".",
+            Token::Question => b"?",
+            Token::At => b"@",
+            Token::Hash => b"#",
+            Token::Dollar => b"$",
+            Token::Backslash => b"\\",
+            Token::Underscore => b"_",
+            Token::Sharp => b"#",
+            Token::Ln => b"\n",
+            Token::Cr => b"\r",
+            Token::Tab => b"\t",
+            Token::CrLn => b"\r\n",
+        }
+    }
+}
+
+impl Generate<u8> for Token {
+    fn generate<R: Rng + ?Sized>(&self, _rng: &mut R) -> Vec<u8> {
+        self.pattern().to_vec()
+    }
+
+    fn generate_miss<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        // differ from the token on its last byte only
+        let (last, start) = self.pattern().split_last().expect("empty token");
+        let mut miss = start.to_vec();
+        miss.push(ascii_except(rng, |byte| byte == *last));
+        miss
     }
 }
 
@@ -219,8 +307,8 @@ impl<'a> Peekable<'a, u8> for Token {
                         end_element_size: element.size(),
                     });
                 }
-                Err(ParseError::UnexpectedToken) => {
-                    scanner.bump_by(1);
+                Err(ParseError::UnexpectedToken { .. }) => {
+                    scanner.advance_checked(1)?;
                     continue;
                 }
                 Err(err) => {
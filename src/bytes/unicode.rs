@@ -0,0 +1,206 @@
+//! Unicode aware matchers for UTF-8 bytes.
+//!
+//! Available with the `unicode` feature.
+
+use crate::matcher::Match;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Get the longest valid UTF-8 prefix of the data.
+fn utf8_prefix(data: &[u8]) -> &str {
+    match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(err) => {
+            // the prefix is valid by definition
+            std::str::from_utf8(&data[..err.valid_up_to()]).unwrap_or_default()
+        }
+    }
+}
+
+/// Fold the case of a character.
+///
+/// The character is lowercased, then the sharp s is folded to `ss` and the
+/// final sigma to the sigma, like the Unicode full case folding does.
+fn fold(c: char) -> impl Iterator<Item = char> {
+    c.to_lowercase().flat_map(|c| {
+        let (first, second) = match c {
+            'ß' => ('s', Some('s')),
+            'ς' => ('σ', None),
+            c => (c, None),
+        };
+        std::iter::once(first).chain(second)
+    })
+}
+
+/// Match a pattern regardless of the case, beyond the ASCII characters.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::unicode::CaseFold;
+/// use elyze::matcher::Match;
+///
+/// let matcher = CaseFold::new("straße");
+/// assert_eq!(matcher.is_matching("STRASSE 12".as_bytes()), (true, 7));
+/// assert_eq!(matcher.is_matching("Straße 12".as_bytes()), (true, 7));
+/// ```
+pub struct CaseFold {
+    /// The folded pattern
+    pattern: Vec<char>,
+}
+
+impl CaseFold {
+    /// Create a new `CaseFold` matcher
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to match
+    pub fn new(pattern: &str) -> Self {
+        CaseFold {
+            pattern: pattern.chars().flat_map(fold).collect(),
+        }
+    }
+}
+
+impl Match<u8> for CaseFold {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        let text = utf8_prefix(data);
+        let mut index = 0;
+        for (offset, c) in text.char_indices() {
+            if index == self.pattern.len() {
+                return (true, offset);
+            }
+            for folded in fold(c) {
+                if self.pattern.get(index) != Some(&folded) {
+                    return (false, 0);
+                }
+                index += 1;
+            }
+        }
+        if index == self.pattern.len() {
+            return (true, text.len());
+        }
+        (false, 0)
+    }
+
+    /// The size of the matched data depends on its case
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+/// Match a pattern regardless of the Unicode normalization form of the data.
+///
+/// The data matches if its NFC form is the NFC form of the pattern, so a
+/// precomposed `é` matches an `e` followed by a combining acute accent.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::unicode::NfcEquals;
+/// use elyze::matcher::Match;
+///
+/// let matcher = NfcEquals::new("café");
+/// assert_eq!(matcher.is_matching("cafe\u{301}!".as_bytes()), (true, 6));
+/// assert_eq!(matcher.is_matching("caf\u{e9}!".as_bytes()), (true, 5));
+/// ```
+pub struct NfcEquals {
+    /// The pattern in NFC form
+    pattern: String,
+    /// The number of characters of the pattern in NFD form
+    decomposed_size: usize,
+}
+
+impl NfcEquals {
+    /// Create a new `NfcEquals` matcher
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to match
+    pub fn new(pattern: &str) -> Self {
+        NfcEquals {
+            pattern: pattern.nfc().collect(),
+            decomposed_size: pattern.nfd().count(),
+        }
+    }
+}
+
+impl Match<u8> for NfcEquals {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        let text = utf8_prefix(data);
+        let boundaries = text
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .skip(1)
+            .chain(std::iter::once(text.len()));
+        for end in boundaries {
+            // a combining mark belongs to the previous character
+            if text[end..].chars().next().is_some_and(is_combining_mark) {
+                continue;
+            }
+            let prefix = &text[..end];
+            if prefix.nfd().count() > self.decomposed_size {
+                break;
+            }
+            if prefix.nfc().eq(self.pattern.chars()) {
+                return (true, end);
+            }
+        }
+        (false, 0)
+    }
+
+    /// The size of the matched data depends on its normalization form
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::unicode::{CaseFold, NfcEquals};
+    use crate::matcher::Match;
+    use crate::recognizer::recognize_slice;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_case_fold() {
+        let matcher = CaseFold::new("Σοφία");
+        assert_eq!(matcher.is_matching("ΣΟΦΊΑ".as_bytes()), (true, 10));
+        assert_eq!(matcher.is_matching("σοφία rest".as_bytes()), (true, 10));
+        assert_eq!(matcher.is_matching("σοφ".as_bytes()), (false, 0));
+        assert_eq!(matcher.is_matching("σοφός".as_bytes()), (false, 0));
+
+        // the final sigma is folded to a sigma
+        let matcher = CaseFold::new("ΟΔΟΣ");
+        assert_eq!(matcher.is_matching("οδος".as_bytes()), (true, 8));
+        assert_eq!(matcher.is_matching("οδοσ".as_bytes()), (true, 8));
+
+        // the match must end on a character boundary
+        let matcher = CaseFold::new("s");
+        assert_eq!(matcher.is_matching("ß".as_bytes()), (false, 0));
+
+        let mut scanner = Scanner::new("ÉCOLE normale".as_bytes());
+        let recognized =
+            recognize_slice(CaseFold::new("école"), &mut scanner).expect("failed to parse");
+        assert_eq!(recognized, "ÉCOLE".as_bytes());
+        assert_eq!(scanner.remaining(), b" normale");
+    }
+
+    #[test]
+    fn test_nfc_equals() {
+        let matcher = NfcEquals::new("e\u{301}t\u{e9}");
+        assert_eq!(matcher.is_matching("\u{e9}t\u{e9}".as_bytes()), (true, 5));
+        assert_eq!(
+            matcher.is_matching("e\u{301}te\u{301} rest".as_bytes()),
+            (true, 7)
+        );
+
+        // the combining mark belongs to the last character
+        assert_eq!(
+            matcher.is_matching("\u{e9}t\u{e9}\u{327}".as_bytes()),
+            (false, 0)
+        );
+        assert_eq!(matcher.is_matching("\u{e9}te".as_bytes()), (false, 0));
+        assert_eq!(matcher.is_matching(b"\xff"), (false, 0));
+    }
+}
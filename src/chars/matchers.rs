@@ -0,0 +1,247 @@
+//! Char slice matchers.
+
+use crate::matcher::{Match, MatchOptions};
+
+/// Attempt to match a single character against a char slice.
+///
+/// # Arguments
+///
+/// * `pattern` - The character to match against.
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_char(pattern: char, data: &[char]) -> (bool, usize) {
+    (data.first() == Some(&pattern), 1)
+}
+
+/// Attempt to match a string against a char slice, ignoring the ASCII case.
+///
+/// This is [match_pattern_with] with a case-insensitive comparison, see
+/// [Pattern] for a matcher of either sensitivity.
+///
+/// # Arguments
+///
+/// * `pattern` - The string to match against.
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_pattern(pattern: &str, data: &[char]) -> (bool, usize) {
+    match_pattern_with(pattern, data, MatchOptions::default().case_sensitive(false))
+}
+
+/// Attempt to match a string against a char slice with the given options.
+///
+/// # Arguments
+///
+/// * `pattern` - The string to match against.
+/// * `data` - The char slice to match against.
+/// * `options` - How the pattern is compared to the data.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_pattern_with(pattern: &str, data: &[char], options: MatchOptions) -> (bool, usize) {
+    if pattern.is_empty() {
+        return (false, 0);
+    }
+
+    let mut size = 0;
+    let mut data = data.iter();
+    for expected in pattern.chars() {
+        match data.next() {
+            Some(c) if *c == expected => size += 1,
+            Some(c) if !options.is_case_sensitive() && c.eq_ignore_ascii_case(&expected) => {
+                size += 1
+            }
+            _ => return (false, 0),
+        }
+    }
+
+    (true, size)
+}
+
+/// A matcher of a fixed string pattern.
+///
+/// # Example
+///
+/// ```
+/// use elyze::chars::matchers::Pattern;
+/// use elyze::matcher::Match;
+///
+/// let data = "Überall".chars().collect::<Vec<_>>();
+/// assert_eq!(Pattern::new("Über").is_matching(&data), (true, 4));
+/// assert_eq!(Pattern::new("über").is_matching(&data), (false, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pattern<'a> {
+    /// The string to match
+    pattern: &'a str,
+    /// How the pattern is compared to the data
+    options: MatchOptions,
+}
+
+impl<'a> Pattern<'a> {
+    /// Create a case-sensitive matcher of the pattern.
+    pub const fn new(pattern: &'a str) -> Self {
+        Pattern {
+            pattern,
+            options: MatchOptions::new(),
+        }
+    }
+
+    /// Create a matcher of the pattern ignoring the ASCII case.
+    pub const fn ignore_case(pattern: &'a str) -> Self {
+        Self::new(pattern).with_options(MatchOptions::new().case_sensitive(false))
+    }
+
+    /// Set how the pattern is compared to the data.
+    pub const fn with_options(mut self, options: MatchOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl Match<char> for Pattern<'_> {
+    fn is_matching(&self, data: &[char]) -> (bool, usize) {
+        match_pattern_with(self.pattern, data, self.options)
+    }
+
+    fn size(&self) -> usize {
+        self.pattern.chars().count()
+    }
+}
+
+/// Attempt to match a number against a char slice.
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_number(data: &[char]) -> (bool, usize) {
+    let size = data.iter().take_while(|c| c.is_ascii_digit()).count();
+    (size > 0, size)
+}
+
+/// Attempt to match a number written in the given radix against a char slice.
+///
+/// # Arguments
+///
+/// * `radix` - The radix of the number, digits above 9 are letters of any case.
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded. A radix outside of
+/// `2..=36` never matches.
+pub fn match_number_radix(radix: u32, data: &[char]) -> (bool, usize) {
+    if !(2..=36).contains(&radix) {
+        return (false, 0);
+    }
+    let size = data.iter().take_while(|c| c.is_digit(radix)).count();
+    (size > 0, size)
+}
+
+/// Attempt to match a string against a char slice.
+/// Stop matching when an ASCII punctuation character or a whitespace is
+/// encountered, see [crate::bytes::matchers::match_string].
+///
+/// # Arguments
+///
+/// * `data` - The char slice to match against.
+///
+/// # Returns
+///
+/// A tuple containing a boolean indicating whether the match succeeded and
+/// the number of chars consumed if the match succeeded.
+pub fn match_string(data: &[char]) -> (bool, usize) {
+    let size = data
+        .iter()
+        .take_while(|c| !c.is_ascii_punctuation() && !c.is_whitespace())
+        .count();
+    (size > 0, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::matchers::{
+        match_char, match_number, match_number_radix, match_pattern, match_pattern_with,
+        match_string, Pattern,
+    };
+    use crate::matcher::{Match, MatchOptions};
+
+    /// Decode the chars of a string
+    fn chars(data: &str) -> Vec<char> {
+        data.chars().collect()
+    }
+
+    #[test]
+    fn test_match_char() {
+        assert_eq!(match_char('é', &chars("éa")), (true, 1));
+        assert_eq!(match_char('a', &chars("éa")), (false, 1));
+        assert_eq!(match_char('a', &[]), (false, 1));
+    }
+
+    #[test]
+    fn test_match_pattern() {
+        assert_eq!(match_pattern("déjà", &chars("DÉJÀ vu")), (false, 0));
+        assert_eq!(match_pattern("déjà", &chars("Déjà vu")), (true, 4));
+        assert_eq!(match_pattern("déjà", &chars("déj")), (false, 0));
+        assert_eq!(match_pattern("", &chars("déjà")), (false, 0));
+    }
+
+    #[test]
+    fn test_match_pattern_case() {
+        let options = MatchOptions::default();
+        assert_eq!(
+            match_pattern_with("Déjà", &chars("déjà"), options),
+            (false, 0)
+        );
+        assert_eq!(
+            match_pattern_with("Déjà", &chars("Déjà"), options),
+            (true, 4)
+        );
+        let options = options.case_sensitive(false);
+        assert_eq!(
+            match_pattern_with("Déjà", &chars("dÉjà"), options),
+            (false, 0)
+        );
+        assert_eq!(
+            match_pattern_with("Déjà", &chars("déjà"), options),
+            (true, 4)
+        );
+
+        assert_eq!(
+            Pattern::ignore_case("Déjà").is_matching(&chars("DéJà")),
+            (true, 4)
+        );
+        assert_eq!(Pattern::ignore_case("Déjà").size(), 4);
+    }
+
+    #[test]
+    fn test_match_number() {
+        assert_eq!(match_number(&chars("123€")), (true, 3));
+        assert_eq!(match_number(&chars("€123")), (false, 0));
+        assert_eq!(match_number_radix(16, &chars("1aF+")), (true, 3));
+        assert_eq!(match_number_radix(8, &chars("9")), (false, 0));
+        assert_eq!(match_number_radix(37, &chars("1")), (false, 0));
+    }
+
+    #[test]
+    fn test_match_string() {
+        assert_eq!(match_string(&chars("héllo wörld")), (true, 5));
+        assert_eq!(match_string(&chars("(héllo")), (false, 0));
+        assert_eq!(match_string(&chars("日本語\u{3000}")), (true, 3));
+    }
+}
@@ -0,0 +1,25 @@
+//! Specialized parsers for chars.
+//!
+//! The tokens, the matchers and the primitives mirror the ones of
+//! [bytes](crate::bytes) for a `Scanner<char>`, which allows to parse a decoded
+//! text. The number, string and whitespace visitors are the same types.
+//!
+//! # Example
+//!
+//! ```
+//! use elyze::chars::primitives::number::Number;
+//! use elyze::chars::primitives::string::DataString;
+//! use elyze::chars::token::Token;
+//! use elyze::recognizer::recognize;
+//! use elyze::scanner::Scanner;
+//!
+//! let data = "prix=42".chars().collect::<Vec<_>>();
+//! let mut scanner = Scanner::new(&data);
+//! let DataString(name) = scanner.visit::<DataString<String>>().expect("failed to parse");
+//! recognize(Token::Equal, &mut scanner).expect("failed to parse");
+//! let Number(value) = scanner.visit::<Number<u32>>().expect("failed to parse");
+//! assert_eq!((name.as_str(), value), ("prix", 42));
+//! ```
+pub mod matchers;
+pub mod primitives;
+pub mod token;
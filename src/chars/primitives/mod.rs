@@ -0,0 +1,5 @@
+//! Specialized primitive parsers for chars.
+
+pub mod number;
+pub mod string;
+pub mod whitespace;
@@ -0,0 +1,80 @@
+//! Accept the numbers of a char scanner.
+//!
+//! The number is the one of [crate::bytes::primitives::number], parsed
+//! from chars.
+
+use crate::chars::matchers::match_number;
+use crate::errors::ParseResult;
+use crate::matcher::Match;
+use crate::recognizer::recognize_slice;
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+
+pub use crate::bytes::primitives::number::Number;
+
+/// Match the digits of a number
+#[derive(Default)]
+pub struct TokenNumber;
+
+/// Implement the `Match` trait for the token number.
+impl Match<char> for TokenNumber {
+    fn is_matching(&self, data: &[char]) -> (bool, usize) {
+        match_number(data)
+    }
+
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+/// Implement the `Visitor` trait for the token number.
+macro_rules! impl_number {
+    ($type:ty) => {
+        impl Visitor<'_, char> for Number<$type> {
+            fn accept(scanner: &mut Scanner<char>) -> ParseResult<Self> {
+                let raw_data = recognize_slice(TokenNumber, scanner)?;
+                let str_data = raw_data.iter().collect::<String>();
+                let result = str_data.parse::<$type>()?;
+                Ok(Number(result))
+            }
+        }
+
+        impl Validate<'_, char> for Number<$type> {}
+    };
+}
+
+impl_number!(usize);
+impl_number!(u8);
+impl_number!(u16);
+impl_number!(u32);
+impl_number!(u64);
+impl_number!(u128);
+impl_number!(isize);
+impl_number!(i8);
+impl_number!(i16);
+impl_number!(i32);
+impl_number!(i64);
+impl_number!(i128);
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::primitives::number::Number;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_number() {
+        let data = "42€".chars().collect::<Vec<_>>();
+        let mut scanner = Scanner::new(&data);
+        assert_eq!(
+            scanner.visit::<Number<u8>>().expect("failed to parse"),
+            Number(42)
+        );
+        assert_eq!(scanner.remaining(), ['€']);
+
+        let data = "256".chars().collect::<Vec<_>>();
+        let result = Scanner::new(&data).visit::<Number<u8>>();
+        assert!(matches!(result, Err(ParseError::ParseIntError(_))));
+    }
+}
@@ -0,0 +1,59 @@
+//! String primitives of a char scanner.
+//!
+//! The string is the one of [crate::bytes::primitives::string], only owned as
+//! the chars are decoded from the data.
+
+use crate::chars::matchers::match_string;
+use crate::errors::ParseResult;
+use crate::matcher::Match;
+use crate::recognizer::recognize_slice;
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+
+pub use crate::bytes::primitives::string::DataString;
+
+/// Match a string of chars
+struct TokenString;
+
+impl Match<char> for TokenString {
+    fn is_matching(&self, data: &[char]) -> (bool, usize) {
+        match_string(data)
+    }
+
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+impl<'a> Visitor<'a, char> for DataString<String> {
+    fn accept(scanner: &mut Scanner<'a, char>) -> ParseResult<Self> {
+        let raw_data = recognize_slice(TokenString, scanner)?;
+        Ok(DataString(raw_data.iter().collect()))
+    }
+}
+
+impl<'a> Validate<'a, char> for DataString<String> {
+    fn validate(scanner: &mut Scanner<'a, char>) -> ParseResult<()> {
+        recognize_slice(TokenString, scanner)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::primitives::string::DataString;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_string() {
+        let data = "Grüße, Welt".chars().collect::<Vec<_>>();
+        let mut scanner = Scanner::new(&data);
+        let DataString(word) = scanner
+            .visit::<DataString<String>>()
+            .expect("failed to parse");
+        assert_eq!(word, "Grüße");
+        assert_eq!(scanner.current_position(), 5);
+        assert!(scanner.validate::<DataString<String>>().is_err());
+    }
+}
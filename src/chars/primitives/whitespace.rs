@@ -0,0 +1,62 @@
+//! Recognize the whitespaces of a char scanner.
+//!
+//! The whitespaces are the ones of [crate::bytes::primitives::whitespace].
+
+use crate::chars::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::recognizer::Recognizable;
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+
+pub use crate::bytes::primitives::whitespace::{
+    OptionalWhitespaces, OptionalWhitespacesOf, UnicodeWhitespace, WhitespaceClass, Whitespaces,
+    WhitespacesOf,
+};
+
+impl<'a> Visitor<'a, char> for Whitespaces {
+    fn accept(scanner: &mut Scanner<'a, char>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        while !scanner.is_empty() && Token::Whitespace.recognize(scanner)?.is_some() {}
+        if scanner.current_position() == start {
+            return Err(ParseError::unexpected_token(start).expecting("a whitespace"));
+        }
+        Ok(Whitespaces)
+    }
+}
+
+impl<'a> Visitor<'a, char> for OptionalWhitespaces {
+    fn accept(scanner: &mut Scanner<'a, char>) -> ParseResult<Self> {
+        while !scanner.is_empty() && Token::Whitespace.recognize(scanner)?.is_some() {}
+        Ok(OptionalWhitespaces)
+    }
+}
+
+impl Validate<'_, char> for Whitespaces {}
+
+impl Validate<'_, char> for OptionalWhitespaces {}
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::primitives::whitespace::{OptionalWhitespaces, Whitespaces};
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_whitespaces() {
+        let data = "   ß".chars().collect::<Vec<_>>();
+        let mut scanner = Scanner::new(&data);
+        scanner.visit::<Whitespaces>().expect("failed to parse");
+        assert_eq!(scanner.remaining(), ['ß']);
+        assert!(scanner.visit::<Whitespaces>().is_err());
+        scanner
+            .visit::<OptionalWhitespaces>()
+            .expect("failed to parse");
+        assert_eq!(scanner.current_position(), 3);
+
+        let mut scanner = Scanner::new(&[' ', ' ']);
+        scanner
+            .visit::<OptionalWhitespaces>()
+            .expect("failed to parse");
+        assert!(scanner.is_empty());
+    }
+}
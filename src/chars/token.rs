@@ -0,0 +1,269 @@
+//! Classic tokens of a char scanner.
+//!
+//! The tokens mirror the ones of [crate::bytes::token].
+
+use crate::chars::matchers::{match_char, match_pattern};
+use crate::errors::{ParseError, ParseResult};
+use crate::matcher::Match;
+use crate::peek::{CustomizedPeekableImplementation, PeekResult, Peekable, PeekableImplementation};
+use crate::recognizer::{recognize, Recognizer};
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+
+/// The token type
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Token {
+    /// The "(" character
+    OpenParen,
+    /// The `)` character
+    CloseParen,
+    /// The `[` character
+    OpenBracket,
+    /// The `]` character
+    CloseBracket,
+    /// The `{` character
+    OpenBrace,
+    /// The `}` character
+    CloseBrace,
+    /// The `,` character
+    Comma,
+    /// The `;` character
+    Semicolon,
+    /// The `:` character
+    Colon,
+    /// The whitespace character
+    Whitespace,
+    /// The `>` character
+    GreaterThan,
+    /// The `<` character
+    LessThan,
+    /// The `!` character
+    Exclamation,
+    /// The `'` character
+    Quote,
+    /// The `"` character
+    DoubleQuote,
+    /// The `=` character
+    Equal,
+    /// The `+` character
+    Plus,
+    /// The `-` character
+    Dash,
+    /// The `/` character
+    Slash,
+    /// The `*` character
+    Star,
+    /// The `%` character
+    Percent,
+    /// The `&` character
+    Ampersand,
+    /// The `|` character
+    Pipe,
+    /// The `^` character
+    Caret,
+    /// The `~` character
+    Tilde,
+    /// The `.` character
+    Dot,
+    /// The `?` character
+    Question,
+    /// The `@` character
+    At,
+    /// The `#` character
+    Hash,
+    /// The `$` character
+    Dollar,
+    /// The `\\` character
+    Backslash,
+    /// The `_` character
+    Underscore,
+    /// The `#` character
+    Sharp,
+    /// The `\n` character
+    Ln,
+    /// The `\r` character
+    Cr,
+    /// The `\t` character
+    Tab,
+    /// The `\r\n` character
+    CrLn,
+}
+
+impl Token {
+    /// The chars of the token, all ASCII
+    fn pattern(&self) -> &'static str {
+        match self {
+            Token::OpenParen => "(",
+            Token::CloseParen => ")",
+            Token::OpenBracket => "[",
+            Token::CloseBracket => "]",
+            Token::OpenBrace => "{",
+            Token::CloseBrace => "}",
+            Token::Comma => ",",
+            Token::Semicolon => ";",
+            Token::Colon => ":",
+            Token::Whitespace => " ",
+            Token::GreaterThan => ">",
+            Token::LessThan => "<",
+            Token::Exclamation => "!",
+            Token::Quote => "'",
+            Token::DoubleQuote => "\"",
+            Token::Equal => "=",
+            Token::Plus => "+",
+            Token::Dash => "-",
+            Token::Slash => "/",
+            Token::Star => "*",
+            Token::Percent => "%",
+            Token::Ampersand => "&",
+            Token::Pipe => "|",
+            Token::Caret => "^",
+            Token::Tilde => "~",
+            Token::Dot => ".",
+            Token::Question => "?",
+            Token::At => "@",
+            Token::Hash => "#",
+            Token::Dollar => "$",
+            Token::Backslash => "\\",
+            Token::Underscore => "_",
+            Token::Sharp => "#",
+            Token::Ln => "\n",
+            Token::Cr => "\r",
+            Token::Tab => "\t",
+            Token::CrLn => "\r\n",
+        }
+    }
+}
+
+/// The tokens in the order they are tried by the visitor
+const TOKENS: [Token; 37] = [
+    Token::OpenParen,
+    Token::CloseParen,
+    Token::OpenBracket,
+    Token::CloseBracket,
+    Token::OpenBrace,
+    Token::CloseBrace,
+    Token::Comma,
+    Token::Semicolon,
+    Token::Colon,
+    Token::Whitespace,
+    Token::GreaterThan,
+    Token::LessThan,
+    Token::Exclamation,
+    Token::Quote,
+    Token::DoubleQuote,
+    Token::Equal,
+    Token::Plus,
+    Token::Dash,
+    Token::Slash,
+    Token::Star,
+    Token::Percent,
+    Token::Ampersand,
+    Token::Pipe,
+    Token::Caret,
+    Token::Tilde,
+    Token::Dot,
+    Token::Question,
+    Token::At,
+    Token::Hash,
+    Token::Dollar,
+    Token::Backslash,
+    Token::Underscore,
+    Token::Sharp,
+    Token::Ln,
+    Token::Cr,
+    Token::Tab,
+    Token::CrLn,
+];
+
+impl Match<char> for Token {
+    fn is_matching(&self, data: &[char]) -> (bool, usize) {
+        match self.pattern() {
+            "\r\n" => match_pattern("\r\n", data),
+            pattern => match_char(pattern.as_bytes()[0] as char, data),
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.pattern().len()
+    }
+}
+
+impl<'a> Visitor<'a, char> for Token {
+    fn accept(scanner: &mut Scanner<'a, char>) -> ParseResult<Self> {
+        let mut recognizer = Recognizer::new(scanner);
+        for token in TOKENS {
+            recognizer = recognizer.try_or(token)?;
+        }
+        recognizer
+            .finish()
+            .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+    }
+}
+
+impl Validate<'_, char> for Token {}
+
+impl PeekableImplementation for Token {
+    type Type = CustomizedPeekableImplementation;
+}
+
+impl<'a> Peekable<'a, char> for Token {
+    fn peek(&self, data: &Scanner<'a, char>) -> ParseResult<PeekResult> {
+        // create a temporary scanner to peek data
+        let mut scanner = Scanner::new(data.remaining());
+        while !scanner.is_empty() {
+            match recognize(*self, &mut scanner) {
+                Ok(element) => {
+                    return Ok(PeekResult::Found {
+                        end_slice: scanner.current_position(),
+                        start_element_size: 0,
+                        end_element_size: element.size(),
+                    });
+                }
+                Err(ParseError::UnexpectedToken { .. }) => {
+                    scanner.advance_checked(1)?;
+                    continue;
+                }
+                Err(err) => {
+                    return Err(err);
+                }
+            }
+        }
+        Ok(PeekResult::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::token::Token;
+    use crate::matcher::Match;
+    use crate::peek::peek;
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_token() {
+        let data = "(é)\r\n".chars().collect::<Vec<_>>();
+        let mut scanner = Scanner::new(&data);
+        assert_eq!(
+            scanner.visit::<Token>().expect("failed to parse"),
+            Token::OpenParen
+        );
+        assert!(scanner.visit::<Token>().is_err());
+        scanner.bump_by(1);
+        recognize(Token::CloseParen, &mut scanner).expect("failed to parse");
+        assert_eq!(Token::CrLn.is_matching(scanner.remaining()), (true, 2));
+        assert_eq!(Token::CrLn.is_matching(&['\r']), (false, 0));
+    }
+
+    #[test]
+    fn test_peek_token() {
+        let data = "a → b; c".chars().collect::<Vec<_>>();
+        let scanner = Scanner::new(&data);
+        let peeked = peek(Token::Semicolon, &scanner)
+            .expect("failed to peek")
+            .expect("no semicolon");
+        assert_eq!(peeked.peeked_slice(), ['a', ' ', '→', ' ', 'b']);
+    }
+}
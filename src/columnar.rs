@@ -0,0 +1,291 @@
+//! Parse repeated records column-wise.
+//!
+//! Analytics-style consumers read one field of many records at a time. A
+//! [RecordBatch] stores the fields of homogeneous records in a `Vec` per
+//! field, so such a read walks contiguous memory instead of jumping from one
+//! record to the next.
+
+use crate::errors::ParseResult;
+use crate::invariants::ensure_progress;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Derive [Record] for a struct, generating a `<Name>Columns` struct holding
+/// a `Vec` per field, under the name, or the index, of the field.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::primitives::whitespace::Whitespaces;
+/// use elyze::bytes::token::Token;
+/// use elyze::columnar::{parse_records, Record};
+/// use elyze::errors::ParseResult;
+/// use elyze::recognizer::recognize;
+/// use elyze::scanner::Scanner;
+/// use elyze::visitor::Visitor;
+///
+/// struct Newline;
+///
+/// impl<'a> Visitor<'a, u8> for Newline {
+///     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+///         recognize(Token::Ln, scanner)?;
+///         Ok(Newline)
+///     }
+/// }
+///
+/// #[derive(Visitor, Record)]
+/// struct Point(Number<i32>, Whitespaces, Number<i32>, Newline);
+///
+/// let batch = parse_records::<u8, Point>(b"1 2\n3 4\n").expect("failed to parse");
+/// assert_eq!(batch.len(), 2);
+/// assert_eq!(batch.columns().0, [Number(1), Number(3)]);
+/// assert_eq!(batch.columns().2, [Number(2), Number(4)]);
+/// ```
+#[cfg(feature = "derive")]
+pub use elyze_derive::Record;
+
+/// A record whose fields can be stored column-wise.
+///
+/// The implementation is usually derived, see the `Record` derive of the
+/// `derive` feature.
+pub trait Record: Sized {
+    /// The columns of the records, a `Vec` per field
+    type Columns: Default;
+
+    /// Move the fields of the record at the end of their column.
+    fn push_columns(self, columns: &mut Self::Columns);
+}
+
+/// Records stored column-wise.
+pub struct RecordBatch<R: Record> {
+    /// The fields of the records
+    columns: R::Columns,
+    /// The number of records
+    len: usize,
+}
+
+impl<R: Record> Default for RecordBatch<R> {
+    fn default() -> Self {
+        RecordBatch {
+            columns: R::Columns::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<R: Record> RecordBatch<R> {
+    /// Create an empty `RecordBatch`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a record at the end of the batch.
+    pub fn push(&mut self, record: R) {
+        record.push_columns(&mut self.columns);
+        self.len += 1;
+    }
+
+    /// Return the number of records of the batch.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if the batch holds no record.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the columns of the records.
+    pub fn columns(&self) -> &R::Columns {
+        &self.columns
+    }
+
+    /// Return the columns of the records, consuming the batch.
+    pub fn into_columns(self) -> R::Columns {
+        self.columns
+    }
+}
+
+impl<R: Record> Extend<R> for RecordBatch<R> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, records: I) {
+        records.into_iter().for_each(|record| self.push(record));
+    }
+}
+
+impl<R: Record> FromIterator<R> for RecordBatch<R> {
+    fn from_iter<I: IntoIterator<Item = R>>(records: I) -> Self {
+        let mut batch = Self::new();
+        batch.extend(records);
+        batch
+    }
+}
+
+/// Parse the data as a sequence of records stored column-wise.
+///
+/// The records follow each other up to the end of the data, the separator
+/// between two records, if any, belongs to the record.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data
+/// * `R` - The record
+///
+/// # Arguments
+///
+/// * `data` - The data to parse
+///
+/// # Errors
+///
+/// The error of the first record which can't be parsed, or an
+/// `UnexpectedToken` if a record doesn't consume any data, see
+/// [ensure_progress].
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::token::Token;
+/// use elyze::columnar::{parse_records, Record};
+/// use elyze::errors::ParseResult;
+/// use elyze::recognizer::recognize;
+/// use elyze::scanner::Scanner;
+/// use elyze::visitor::Visitor;
+///
+/// /// A rainfall in millimeters followed by a `;`
+/// struct Reading {
+///     millimeters: u16,
+/// }
+///
+/// #[derive(Default)]
+/// struct ReadingColumns {
+///     millimeters: Vec<u16>,
+/// }
+///
+/// impl Record for Reading {
+///     type Columns = ReadingColumns;
+///
+///     fn push_columns(self, columns: &mut ReadingColumns) {
+///         columns.millimeters.push(self.millimeters);
+///     }
+/// }
+///
+/// impl<'a> Visitor<'a, u8> for Reading {
+///     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+///         let Number(millimeters) = scanner.visit()?;
+///         recognize(Token::Semicolon, scanner)?;
+///         Ok(Reading { millimeters })
+///     }
+/// }
+///
+/// let batch = parse_records::<u8, Reading>(b"21;19;3;").expect("failed to parse");
+/// assert_eq!(batch.columns().millimeters, [21, 19, 3]);
+/// assert!(parse_records::<u8, Reading>(b"21;19").is_err());
+/// ```
+pub fn parse_records<'a, T, R>(data: &'a [T]) -> ParseResult<RecordBatch<R>>
+where
+    R: Visitor<'a, T> + Record,
+{
+    let mut scanner = Scanner::new(data);
+    let mut batch = RecordBatch::new();
+    while !scanner.is_empty() {
+        let start = scanner.current_position();
+        batch.push(scanner.visit::<R>()?);
+        ensure_progress::<R, T>(&scanner, start)?;
+    }
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::columnar::{parse_records, Record, RecordBatch};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    /// The `,` separator
+    #[derive(Debug, PartialEq)]
+    struct Comma;
+
+    impl<'a> Visitor<'a, u8> for Comma {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            recognize(Token::Comma, scanner)?;
+            Ok(Comma)
+        }
+    }
+
+    /// A number followed by a `,`
+    struct Cell(u8);
+
+    impl Record for Cell {
+        type Columns = Vec<u8>;
+
+        fn push_columns(self, columns: &mut Vec<u8>) {
+            columns.push(self.0);
+        }
+    }
+
+    impl<'a> Visitor<'a, u8> for Cell {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            let Number(value) = scanner.visit()?;
+            scanner.visit::<Comma>()?;
+            Ok(Cell(value))
+        }
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let batch = parse_records::<u8, Cell>(b"1,2,3,").expect("failed to parse");
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.into_columns(), [1, 2, 3]);
+
+        let batch = parse_records::<u8, Cell>(b"").expect("failed to parse");
+        assert!(batch.is_empty());
+
+        let result = parse_records::<u8, Cell>(b"1,2;");
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 3, .. })
+        ));
+
+        let batch = [Cell(4), Cell(2)].into_iter().collect::<RecordBatch<_>>();
+        assert_eq!(batch.columns(), &[4, 2]);
+    }
+
+    /// A named record, the columns being derived
+    #[cfg(feature = "derive")]
+    #[derive(Visitor, Record)]
+    struct Entry<'a> {
+        key: crate::bytes::primitives::string::DataString<&'a str>,
+        comma: Comma,
+        value: Number<u32>,
+        end: Comma,
+    }
+
+    /// A generic tuple record, the columns being derived
+    #[cfg(feature = "derive")]
+    #[derive(Visitor, Record)]
+    struct Pair<V>(V, Comma)
+    where
+        V: std::fmt::Debug;
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_record() {
+        let batch = parse_records::<u8, Entry>(b"a,1,bc,22,").expect("failed to parse");
+        let columns: EntryColumns = batch.into_columns();
+        let keys = columns.key.iter().map(|key| key.0).collect::<Vec<_>>();
+        assert_eq!(keys, ["a", "bc"]);
+        assert_eq!(columns.comma, [Comma, Comma]);
+        assert_eq!(columns.value, [Number(1), Number(22)]);
+        assert_eq!(columns.end.len(), 2);
+
+        let batch = parse_records::<u8, Pair<Number<u8>>>(b"7,8,").expect("failed to parse");
+        let columns: &PairColumns<Number<u8>> = batch.columns();
+        assert_eq!(columns.0, [Number(7), Number(8)]);
+        assert_eq!(columns.1.len(), 2);
+    }
+}
@@ -0,0 +1,358 @@
+//! Generic combinators of visitors.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::invariants::ensure_progress;
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Whether an error only means the rule doesn't apply at this position
+pub(crate) fn is_recoverable(err: &ParseError) -> bool {
+    matches!(
+        err.root(),
+        ParseError::UnexpectedToken { .. } | ParseError::UnexpectedEndOfInput
+    )
+}
+
+/// Apply the rule as many times as possible.
+///
+/// The scanner is restored to the end of the last successful application.
+///
+/// # Returns
+///
+/// The number of applications.
+///
+/// # Errors
+///
+/// The error of the rule if it isn't a mismatch, or an error if the rule
+/// succeeds without consuming anything.
+pub(crate) fn repeat<'a, V, T, F>(scanner: &mut Scanner<'a, T>, mut rule: F) -> ParseResult<usize>
+where
+    F: FnMut(&mut Scanner<'a, T>) -> ParseResult<()>,
+{
+    let mut count = 0;
+    loop {
+        let start = scanner.current_position();
+        match rule(scanner) {
+            Ok(()) => {
+                // a rule consuming nothing would be applied forever
+                ensure_progress::<V, T>(scanner, start)?;
+                count += 1;
+            }
+            Err(err) if is_recoverable(&err) => {
+                scanner.jump_to(start);
+                return Ok(count);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Zero or more `V` in a row.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::combinators::Many0;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"abc");
+/// let Many0(numbers) = scanner.visit::<Many0<Number<u8>>>().expect("failed to parse");
+/// assert!(numbers.is_empty());
+/// assert_eq!(scanner.current_position(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Many0<V>(pub Vec<V>);
+
+/// One or more `V` in a row.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::combinators::Many1;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"+-*1");
+/// let Many1(tokens) = scanner.visit::<Many1<Token>>().expect("failed to parse");
+/// assert_eq!(tokens, vec![Token::Plus, Token::Dash, Token::Star]);
+/// assert_eq!(scanner.remaining(), b"1");
+/// assert!(scanner.visit::<Many1<Token>>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Many1<V>(pub Vec<V>);
+
+impl<V> Many0<V> {
+    /// Return the accepted values
+    pub fn into_inner(self) -> Vec<V> {
+        self.0
+    }
+}
+
+impl<V> Many1<V> {
+    /// Return the accepted values
+    pub fn into_inner(self) -> Vec<V> {
+        self.0
+    }
+}
+
+impl<V> Deref for Many0<V> {
+    type Target = Vec<V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<V> Deref for Many1<V> {
+    type Target = Vec<V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T, V: Visitor<'a, T>> Visitor<'a, T> for Many0<V> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let mut values = vec![];
+        repeat::<V, T, _>(scanner, |scanner| {
+            values.push(V::accept(scanner)?);
+            Ok(())
+        })?;
+        Ok(Many0(values))
+    }
+}
+
+impl<'a, T, V: Visitor<'a, T>> Visitor<'a, T> for Many1<V> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut values = vec![V::accept(&mut checkpoint)?];
+        repeat::<V, T, _>(&mut checkpoint, |scanner| {
+            values.push(V::accept(scanner)?);
+            Ok(())
+        })?;
+        checkpoint.commit();
+        Ok(Many1(values))
+    }
+}
+
+impl<'a, T, V: Validate<'a, T>> Validate<'a, T> for Many0<V> {
+    fn validate(scanner: &mut Scanner<'a, T>) -> ParseResult<()> {
+        repeat::<V, T, _>(scanner, V::validate).map(drop)
+    }
+}
+
+impl<'a, T, V: Validate<'a, T>> Validate<'a, T> for Many1<V> {
+    fn validate(scanner: &mut Scanner<'a, T>) -> ParseResult<()> {
+        let mut checkpoint = scanner.checkpoint();
+        V::validate(&mut checkpoint)?;
+        repeat::<V, T, _>(&mut checkpoint, V::validate)?;
+        checkpoint.commit();
+        Ok(())
+    }
+}
+
+/// Apply a visitor repeatedly and fold the accepted values into an
+/// accumulator, without collecting them.
+///
+/// The scanner is restored after the last failed application, as with
+/// [Many0].
+///
+/// # Type Parameters
+///
+/// * `V` - The visitor to apply.
+/// * `Acc` - The type of the accumulator.
+/// * `F` - The closure folding a value into the accumulator.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::combinators::FoldMany;
+/// use elyze::scanner::Scanner;
+///
+/// let mut scanner = Scanner::new(b"+-+1");
+/// let pluses = FoldMany::<Token, _, _>::new(0, |count, token| match token {
+///     Token::Plus => count + 1,
+///     _ => count,
+/// });
+/// assert_eq!(pluses.fold(&mut scanner).expect("failed to parse"), 2);
+/// assert_eq!(scanner.remaining(), b"1");
+/// ```
+pub struct FoldMany<V, Acc, F> {
+    initial: Acc,
+    fold: F,
+    min: usize,
+    visitor: PhantomData<V>,
+}
+
+impl<V, Acc, F> FoldMany<V, Acc, F> {
+    /// Create a fold starting from the initial accumulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The accumulator before the first value.
+    /// * `fold` - The closure receiving the accumulator and an accepted
+    ///   value, and returning the next accumulator.
+    pub fn new(initial: Acc, fold: F) -> Self {
+        FoldMany {
+            initial,
+            fold,
+            min: 0,
+            visitor: PhantomData,
+        }
+    }
+
+    /// Require at least `min` values, 0 by default.
+    pub fn with_min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Apply the visitor as many times as possible and fold the values.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to visit.
+    ///
+    /// # Returns
+    ///
+    /// The accumulator after the last value.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` where the fold stopped if there are less
+    /// than the minimum of values, the scanner being restored. Any error of
+    /// the visitor which isn't a mismatch.
+    pub fn fold<'a, T>(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Acc>
+    where
+        V: Visitor<'a, T>,
+        F: FnMut(Acc, V) -> Acc,
+    {
+        let FoldMany {
+            initial,
+            mut fold,
+            min,
+            ..
+        } = self;
+        let mut checkpoint = scanner.checkpoint();
+        let mut accumulator = Some(initial);
+        let count = repeat::<V, T, _>(&mut checkpoint, |scanner| {
+            let value = V::accept(scanner)?;
+            accumulator = accumulator
+                .take()
+                .map(|accumulator| fold(accumulator, value));
+            Ok(())
+        })?;
+        if count < min {
+            return Err(ParseError::unexpected_token(checkpoint.current_position()));
+        }
+        checkpoint.commit();
+        Ok(accumulator.expect("the accumulator is only taken to be folded"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::primitives::whitespace::OptionalWhitespaces;
+    use crate::bytes::token::Token;
+    use crate::combinators::{FoldMany, Many0, Many1};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::validate::Validate;
+    use crate::visitor::Visitor;
+
+    /// A number followed by a comma
+    #[derive(Debug, PartialEq)]
+    struct Item(u8);
+
+    impl<'a> Visitor<'a, u8> for Item {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            let Number(number) = scanner.visit()?;
+            recognize(Token::Comma, scanner)?;
+            Ok(Item(number))
+        }
+    }
+
+    impl Validate<'_, u8> for Item {}
+
+    #[test]
+    fn test_many0() {
+        // the last attempt fails after consuming `3`
+        let mut scanner = Scanner::new(b"1,2,3;");
+        let items = scanner.visit::<Many0<Item>>().expect("failed to parse");
+        assert_eq!(items.0, vec![Item(1), Item(2)]);
+        assert_eq!(scanner.remaining(), b"3;");
+
+        let items = Scanner::new(b"")
+            .visit::<Many0<Item>>()
+            .expect("failed to parse");
+        assert!(items.is_empty());
+
+        // an error which isn't a mismatch stops the repetition
+        let result = Scanner::new(b"1,300,").visit::<Many0<Item>>();
+        assert!(matches!(result, Err(ParseError::ParseIntError(_))));
+    }
+
+    #[test]
+    fn test_many1() {
+        let mut scanner = Scanner::new(b"7,8,");
+        let items = scanner.visit::<Many1<Item>>().expect("failed to parse");
+        assert_eq!(items.into_inner(), vec![Item(7), Item(8)]);
+        assert!(scanner.is_empty());
+
+        let mut scanner = Scanner::new(b"7;");
+        assert!(matches!(
+            scanner.visit::<Many1<Item>>(),
+            Err(ParseError::UnexpectedToken { offset: 1, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        let mut scanner = Scanner::new(b"1,2,x");
+        scanner
+            .validate::<Many1<Item>>()
+            .expect("failed to validate");
+        assert_eq!(scanner.remaining(), b"x");
+    }
+
+    #[test]
+    fn test_fold_many() {
+        let sum = |sum: u32, Item(value)| sum + value as u32;
+        let mut scanner = Scanner::new(b"1,2,3,4,;");
+        let total = FoldMany::<Item, _, _>::new(0, sum)
+            .fold(&mut scanner)
+            .expect("failed to parse");
+        assert_eq!(total, 10);
+        assert_eq!(scanner.remaining(), b";");
+
+        let total = FoldMany::<Item, _, _>::new(7, sum)
+            .fold(&mut Scanner::new(b""))
+            .expect("failed to parse");
+        assert_eq!(total, 7);
+
+        // not enough values, the scanner is restored
+        let mut scanner = Scanner::new(b"1,2,x");
+        assert!(matches!(
+            FoldMany::<Item, _, _>::new(0, sum)
+                .with_min(3)
+                .fold(&mut scanner),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_many_no_progress() {
+        // the rule stalls once the whitespaces are consumed
+        let mut scanner = Scanner::new(b"  abc");
+        assert!(matches!(
+            scanner.visit::<Many0<OptionalWhitespaces>>(),
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+//! Completion hints: what a grammar expects at a cursor position.
+//!
+//! The grammar is run on the input truncated at the cursor by a scanner
+//! carrying the completion, the elements it expects at the cursor are
+//! reported to it: the matchers [recognize](crate::recognizer::recognize)
+//! fails to find, if they [describe](crate::matcher::Match::describe)
+//! themselves, the names of the [Context](crate::context::Context) rules
+//! starting at the cursor and the descriptions given to
+//! [Scanner::report_expected]. As the input ends at the cursor, an element
+//! expected at the end of the input is expected at the cursor.
+//!
+//! A grammar parsing a slice of the input with a scanner of its own reports
+//! nothing, the positions of this scanner not being the ones of the input.
+
+use crate::errors::ParseError;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The completion running on a scanner
+struct Session {
+    /// The cursor position
+    cursor: usize,
+    /// The expected elements, in their reporting order
+    expected: Mutex<Vec<String>>,
+}
+
+/// The completion of a scanner, two scanners sharing the same completion
+/// being equal
+#[derive(Clone)]
+pub(crate) struct CompletionRef(Arc<Session>);
+
+impl Debug for CompletionRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CompletionRef({})", self.0.cursor)
+    }
+}
+
+impl PartialEq for CompletionRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl CompletionRef {
+    /// Record an element if it is expected at the cursor, a missing
+    /// position being the end of the input
+    pub(crate) fn record(&self, position: Option<usize>, description: impl FnOnce() -> String) {
+        if position.is_some_and(|position| position != self.0.cursor) {
+            return;
+        }
+        let description = description();
+        let mut expected = self
+            .0
+            .expected
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !expected.contains(&description) {
+            expected.push(description);
+        }
+    }
+
+    /// Record the element expected by an error
+    pub(crate) fn record_error(&self, err: &ParseError, description: impl FnOnce() -> String) {
+        match err {
+            ParseError::UnexpectedToken { offset, .. } => self.record(Some(*offset), description),
+            ParseError::UnexpectedEndOfInput => self.record(None, description),
+            _ => {}
+        }
+    }
+}
+
+/// The elements a grammar expects at a cursor position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The cursor position
+    pub cursor: usize,
+    /// The descriptions of the expected elements and the names of the rules
+    /// which could start at the cursor, without duplicates, in their
+    /// recording order
+    pub expected: Vec<String>,
+}
+
+/// Run the grammar `V` on the data up to the cursor and return what it
+/// expects at the cursor.
+///
+/// # Type Parameters
+///
+/// * `V` - The grammar
+///
+/// # Arguments
+///
+/// * `data` - The data being edited
+/// * `cursor` - The cursor position, the data after it being ignored
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::token::Token;
+/// use elyze::completion::complete;
+/// use elyze::context::Context;
+/// use elyze::errors::ParseResult;
+/// use elyze::recognizer::recognize;
+/// use elyze::scanner::Scanner;
+/// use elyze::visitor::Visitor;
+///
+/// /// A pair of numbers `a,b`
+/// struct Pair;
+///
+/// impl<'a> Visitor<'a, u8> for Pair {
+///     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+///         scanner.visit::<Context<Number<u32>>>()?;
+///         recognize(Token::Comma, scanner)?;
+///         scanner.visit::<Context<Number<u32>>>()?;
+///         Ok(Pair)
+///     }
+/// }
+///
+/// assert_eq!(complete::<_, Pair>(b"12,34", 2).expected, ["`,`"]);
+/// assert_eq!(
+///     complete::<_, Pair>(b"12,34", 3).expected,
+///     ["a number", "Number<u32>"]
+/// );
+/// ```
+pub fn complete<'a, T, V: Visitor<'a, T>>(data: &'a [T], cursor: usize) -> Completion {
+    let cursor = cursor.min(data.len());
+    let completion = CompletionRef(Arc::new(Session {
+        cursor,
+        expected: Mutex::new(vec![]),
+    }));
+    let mut scanner = Scanner::with_completion(&data[..cursor], completion.clone());
+    // the result doesn't matter, only the expected elements
+    let _ = scanner.visit::<V>();
+    drop(scanner);
+    let expected = std::mem::take(
+        &mut *completion
+            .0
+            .expected
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+    Completion { cursor, expected }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::combinators::Many0;
+    use crate::completion::complete;
+    use crate::context::Context;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    /// A keyword of the commands
+    struct Keyword;
+
+    impl<'a> Visitor<'a, u8> for Keyword {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            for keyword in [&b"get "[..], b"set "] {
+                if scanner.remaining().starts_with(keyword) {
+                    scanner.bump_by(keyword.len());
+                    return Ok(Keyword);
+                }
+            }
+            // a keyword being typed is expected at the end of the input
+            let remaining = scanner.remaining();
+            let err = if [&b"get "[..], b"set "]
+                .iter()
+                .any(|keyword| keyword.starts_with(remaining))
+            {
+                ParseError::UnexpectedEndOfInput
+            } else {
+                ParseError::unexpected_token(scanner.current_position())
+            };
+            scanner.report_expected(&err, "get");
+            scanner.report_expected(&err, "set");
+            Err(err)
+        }
+    }
+
+    /// A command `get 1`, `set 1 2`
+    struct Command;
+
+    impl<'a> Visitor<'a, u8> for Command {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            scanner.visit::<Keyword>()?;
+            scanner.visit::<Context<Number<u32>>>()?;
+            scanner.visit::<Many0<Value>>()?;
+            Ok(Command)
+        }
+    }
+
+    /// A value after the key
+    struct Value;
+
+    impl<'a> Visitor<'a, u8> for Value {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            recognize(Token::Whitespace, scanner)?;
+            scanner.visit::<Context<Number<u32>>>()?;
+            Ok(Value)
+        }
+    }
+
+    #[test]
+    fn test_complete() {
+        let data = b"set 12 3";
+        assert_eq!(complete::<_, Command>(data, 0).expected, ["get", "set"]);
+        assert_eq!(complete::<_, Command>(data, 2).expected, ["get", "set"]);
+        assert_eq!(
+            complete::<_, Command>(data, 4).expected,
+            ["a number", "Number<u32>"]
+        );
+        // the grammar may stop or go on after the key
+        assert_eq!(complete::<_, Command>(data, 6).expected, ["` `"]);
+        assert_eq!(
+            complete::<_, Command>(data, 7).expected,
+            ["a number", "Number<u32>"]
+        );
+
+        // an error before the cursor expects nothing at the cursor
+        let completion = complete::<_, Command>(b"put 1", 5);
+        assert_eq!(completion.cursor, 5);
+        assert!(completion.expected.is_empty());
+        assert_eq!(complete::<_, Command>(b"get", 99).cursor, 3);
+    }
+
+    #[test]
+    fn test_complete_nested() {
+        // a completion run by a grammar doesn't alter the running one
+        struct Nested;
+
+        impl<'a> Visitor<'a, u8> for Nested {
+            fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+                let completion = complete::<_, Command>(b"get ", 4);
+                assert_eq!(completion.expected, ["a number", "Number<u32>"]);
+                scanner.visit::<Keyword>()?;
+                Ok(Nested)
+            }
+        }
+
+        assert_eq!(complete::<_, Nested>(b"", 0).expected, ["get", "set"]);
+        assert_eq!(complete::<_, Number<u32>>(b"", 0).expected, ["a number"]);
+    }
+
+    #[test]
+    fn test_complete_thread() {
+        let expected = std::thread::spawn(|| complete::<_, Command>(b"get ", 4).expected)
+            .join()
+            .expect("the completion panicked");
+        assert_eq!(expected, ["a number", "Number<u32>"]);
+    }
+}
@@ -0,0 +1,232 @@
+//! Describe the rules being parsed when an error occurs.
+//!
+//! Each rule an error propagates out of can push a label on it, the error then
+//! carries a trace like `in Color > in Number` locating it in the grammar.
+
+use crate::errors::ParseResult;
+use crate::scanner::Scanner;
+use crate::sealed::Sealed;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+use std::any::type_name;
+use std::ops::Deref;
+
+/// Add a context to the error of a [ParseResult].
+///
+/// The trait is sealed, it is only implemented for [ParseResult].
+///
+/// ```compile_fail
+/// use elyze::context::WithContext;
+/// use elyze::errors::ParseResult;
+///
+/// struct Report;
+///
+/// impl WithContext<()> for Report {
+///     fn context(self, _context: &str) -> ParseResult<()> {
+///         Ok(())
+///     }
+///
+///     fn with_context<F: FnOnce() -> String>(self, _context: F) -> ParseResult<()> {
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait WithContext<T>: Sealed {
+    /// Describe the rule being parsed if the result is an error
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The description of the rule
+    fn context(self, context: &str) -> ParseResult<T>;
+
+    /// Describe the rule being parsed if the result is an error, the
+    /// description being only built on error
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Build the description of the rule
+    fn with_context<F: FnOnce() -> String>(self, context: F) -> ParseResult<T>;
+}
+
+impl<T> WithContext<T> for ParseResult<T> {
+    fn context(self, context: &str) -> ParseResult<T> {
+        self.map_err(|err| err.context(context))
+    }
+
+    fn with_context<F: FnOnce() -> String>(self, context: F) -> ParseResult<T> {
+        self.map_err(|err| err.context(&context()))
+    }
+}
+
+/// Get the name of a type without its module paths, `Number<u8>` for
+/// `elyze::bytes::primitives::number::Number<u8>`.
+pub(crate) fn short_type_name<V>() -> String {
+    let mut name = String::new();
+    // the start of the current path segment in the name
+    let mut segment = 0;
+    for c in type_name::<V>().chars() {
+        match c {
+            ':' => name.truncate(segment),
+            c if c.is_alphanumeric() || c == '_' => name.push(c),
+            c => {
+                name.push(c);
+                segment = name.len();
+            }
+        }
+    }
+    name
+}
+
+/// A value accepted by the visitor `V`, whose errors are labelled with the
+/// name of `V`.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::context::Context;
+/// use elyze::scanner::Scanner;
+///
+/// let err = Scanner::new(b"x")
+///     .visit::<Context<Number<u8>>>()
+///     .expect_err("parsed a letter");
+/// assert_eq!(err.contexts(), vec!["Number<u8>"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Context<V>(pub V);
+
+impl<V> Context<V> {
+    /// Return the accepted value
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<V> Deref for Context<V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Report the rule failing at its start to the completion running on the
+/// scanner, if any
+fn report_rule<T, V>(scanner: &Scanner<T>, start: usize) {
+    if let Some(completion) = scanner.completion() {
+        completion.record(Some(start), short_type_name::<V>);
+    }
+}
+
+impl<'a, T, V: Visitor<'a, T>> Visitor<'a, T> for Context<V> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        V::accept(scanner)
+            .inspect_err(|_| report_rule::<T, V>(scanner, start))
+            .with_context(short_type_name::<V>)
+            .map(Context)
+    }
+}
+
+impl<'a, T, V: Validate<'a, T>> Validate<'a, T> for Context<V> {
+    fn validate(scanner: &mut Scanner<'a, T>) -> ParseResult<()> {
+        let start = scanner.current_position();
+        V::validate(scanner)
+            .inspect_err(|_| report_rule::<T, V>(scanner, start))
+            .with_context(short_type_name::<V>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acceptor::Acceptor;
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::context::{short_type_name, Context, WithContext};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::visitor::Visitor;
+
+    /// A color `(r,g,b)`
+    #[derive(Debug)]
+    struct Rgb(u8, u8, u8);
+
+    impl<'a> Visitor<'a, u8> for Rgb {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            recognize(Token::OpenParen, scanner)?;
+            let Context(Number(red)) = scanner.visit()?;
+            recognize(Token::Comma, scanner)?;
+            let Context(Number(green)) = scanner.visit()?;
+            recognize(Token::Comma, scanner)?;
+            let Context(Number(blue)) = scanner.visit()?;
+            recognize(Token::CloseParen, scanner)?;
+            Ok(Rgb(red, green, blue))
+        }
+    }
+
+    /// A color, an rgb tuple or a grey level
+    #[derive(Debug)]
+    enum Color {
+        Rgb(Rgb),
+        Grey(u8),
+    }
+
+    impl<'a> Visitor<'a, u8> for Color {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            Acceptor::new(scanner)
+                .try_or(Color::Rgb)?
+                .try_or(|Number(grey)| Color::Grey(grey))?
+                .finish()
+                .ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+                .context("parsing a color")
+        }
+    }
+
+    #[test]
+    fn test_context() {
+        let color = Scanner::new(b"(1,2,3)").visit::<Color>();
+        assert!(matches!(color, Ok(Color::Rgb(Rgb(1, 2, 3)))));
+        let color = Scanner::new(b"12").visit::<Color>();
+        assert!(matches!(color, Ok(Color::Grey(12))));
+
+        let err = Scanner::new(b"(1,x,3)")
+            .visit::<Color>()
+            .expect_err("parsed an invalid color");
+        assert_eq!(err.contexts(), vec!["parsing a color"]);
+        assert!(matches!(
+            err.root(),
+            ParseError::UnexpectedToken { offset: 0, .. }
+        ));
+
+        // the error of a rule is kept with its context
+        let err = Scanner::new(b"(1,x,3)")
+            .visit::<Context<Rgb>>()
+            .expect_err("parsed an invalid color");
+        assert_eq!(
+            err.to_string(),
+            "in Rgb > in Number<u8>: Unexpected token have been encountered at 3"
+        );
+    }
+
+    #[test]
+    fn test_with_context() {
+        let result: ParseResult<()> = Err(ParseError::UnbalancedGroup);
+        let err = result
+            .with_context(|| format!("group at {}", 4))
+            .expect_err("not an error");
+        assert_eq!(err.to_string(), "in group at 4: Unbalanced group");
+
+        let value = Ok::<_, ParseError>(1).with_context(|| unreachable!("built on success"));
+        assert_eq!(value.expect("not a value"), 1);
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name::<Number<u8>>(), "Number<u8>");
+        assert_eq!(
+            short_type_name::<Vec<(Context<Rgb>, &str)>>(),
+            "Vec<(Context<Rgb>, &str)>"
+        );
+    }
+}
@@ -1,19 +1,592 @@
 //! Error types
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
 /// The result of a parse operation
 pub type ParseResult<T> = Result<T, ParseError>;
 
-#[derive(Debug, thiserror::Error)]
+/// The errors of a parse.
+///
+/// The `Display` messages are meant for the end users, the contexts of an
+/// error being part of its message. The wrapped errors, like the UTF-8 ones,
+/// are exposed as the `source` of the error.
+///
+/// New variants can be added in a minor release, a `match` on the errors
+/// needs a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum ParseError {
     /// The parser reached the end of the input
-    #[error("Unexpected end of input")]
     UnexpectedEndOfInput,
-    #[error("Unexpected token have been encountered")]
-    /// The parser encountered an unexpected token
-    UnexpectedToken,
+    /// The parser encountered an unexpected token at `offset`, `expected`
+    /// describes what was expected if known
+    UnexpectedToken {
+        offset: usize,
+        expected: Option<String>,
+    },
+    /// Several peekables matched a group of the same size
+    AmbiguousPeeking,
+    /// The end of the input is reached before a group is closed
+    UnbalancedGroup,
+    /// The cursor of the scanner is moved out of the bounds of the input
+    CursorOutOfBounds,
+    /// A key is defined several times in the same object, `start` and `end`
+    /// are the positions of the duplicate key
+    DuplicateKey { start: usize, end: usize },
+    /// A source is expanded inside its own expansion
+    ExpansionCycle(String),
+    /// An error raised while parsing the rule described by `context`
+    Context {
+        context: String,
+        source: Box<ParseError>,
+    },
     /// Unable to decode a string as UTF-8
-    #[error("UTF-8 error: {0}")]
-    Utf8Error(#[from] std::str::Utf8Error),
+    Utf8Error(std::str::Utf8Error),
     /// Unable to parse an integer from a string
-    #[error("ParseIntError: {0}")]
-    ParseIntError(#[from] std::num::ParseIntError),
+    ParseIntError(std::num::ParseIntError),
+    /// Unable to parse a float from a string
+    ParseFloatError(std::num::ParseFloatError),
+    /// Unable to convert an integer to a smaller type
+    TryFromIntError(std::num::TryFromIntError),
+    /// Unable to convert a slice to an array of another size
+    TryFromSliceError(std::array::TryFromSliceError),
+    /// Unable to convert an integer to a char
+    CharTryFromError(std::char::CharTryFromError),
+    /// An error raised by the code using elyze, displayed as is
+    Custom(Box<dyn Error + Send + Sync>),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedEndOfInput => write!(f, "Unexpected end of input"),
+            ParseError::UnexpectedToken { offset, expected } => {
+                write!(f, "Unexpected token have been encountered at {offset}")?;
+                match expected {
+                    Some(expected) => write!(f, ", expected {expected}"),
+                    None => Ok(()),
+                }
+            }
+            ParseError::AmbiguousPeeking => write!(
+                f,
+                "Ambiguous peeking, several peekables matched a group of the same size"
+            ),
+            ParseError::UnbalancedGroup => write!(f, "Unbalanced group"),
+            ParseError::CursorOutOfBounds => {
+                write!(f, "Cursor moved out of the bounds of the input")
+            }
+            ParseError::DuplicateKey { start, end } => {
+                write!(f, "Duplicate key at {start}..{end}")
+            }
+            ParseError::ExpansionCycle(source) => {
+                write!(f, "Expansion cycle detected on {source}")
+            }
+            // the nested contexts are chained
+            ParseError::Context { context, source } => match **source {
+                ParseError::Context { .. } => write!(f, "in {context} > {source}"),
+                _ => write!(f, "in {context}: {source}"),
+            },
+            ParseError::Utf8Error(_) => write!(f, "Invalid UTF-8 data"),
+            ParseError::ParseIntError(_) => write!(f, "Invalid integer"),
+            ParseError::ParseFloatError(_) => write!(f, "Invalid float"),
+            ParseError::TryFromIntError(_) => write!(f, "Integer out of range"),
+            ParseError::TryFromSliceError(_) => write!(f, "Invalid slice length"),
+            ParseError::CharTryFromError(_) => write!(f, "Invalid char code"),
+            ParseError::Custom(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    /// The contexts being part of the message, the source of an error with
+    /// contexts is the source of its root error.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Context { source, .. } => source.source(),
+            ParseError::Utf8Error(err) => Some(err),
+            ParseError::ParseIntError(err) => Some(err),
+            ParseError::ParseFloatError(err) => Some(err),
+            ParseError::TryFromIntError(err) => Some(err),
+            ParseError::TryFromSliceError(err) => Some(err),
+            ParseError::CharTryFromError(err) => Some(err),
+            ParseError::Custom(err) => err.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::str::Utf8Error> for ParseError {
+    fn from(err: std::str::Utf8Error) -> Self {
+        ParseError::Utf8Error(err)
+    }
+}
+
+impl From<std::num::ParseIntError> for ParseError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ParseError::ParseIntError(err)
+    }
+}
+
+impl From<std::num::ParseFloatError> for ParseError {
+    fn from(err: std::num::ParseFloatError) -> Self {
+        ParseError::ParseFloatError(err)
+    }
+}
+
+impl From<std::num::TryFromIntError> for ParseError {
+    fn from(err: std::num::TryFromIntError) -> Self {
+        ParseError::TryFromIntError(err)
+    }
+}
+
+impl From<std::array::TryFromSliceError> for ParseError {
+    fn from(err: std::array::TryFromSliceError) -> Self {
+        ParseError::TryFromSliceError(err)
+    }
+}
+
+impl From<std::char::CharTryFromError> for ParseError {
+    fn from(err: std::char::CharTryFromError) -> Self {
+        ParseError::CharTryFromError(err)
+    }
+}
+
+impl ParseError {
+    /// Wrap an error raised by the code using elyze
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error, or a message
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::errors::ParseError;
+    ///
+    /// let err = ParseError::custom("unknown color");
+    /// assert_eq!(err.to_string(), "unknown color");
+    /// ```
+    pub fn custom<E: Into<Box<dyn Error + Send + Sync>>>(err: E) -> Self {
+        ParseError::Custom(err.into())
+    }
+
+    /// Create an unexpected token error
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The position of the unexpected token
+    pub fn unexpected_token(offset: usize) -> Self {
+        ParseError::UnexpectedToken {
+            offset,
+            expected: None,
+        }
+    }
+
+    /// Describe what was expected by an unexpected token error
+    ///
+    /// The other errors are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the expected element
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::errors::ParseError;
+    ///
+    /// let err = ParseError::unexpected_token(3).expecting("a number");
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "Unexpected token have been encountered at 3, expected a number"
+    /// );
+    /// ```
+    pub fn expecting(self, description: &str) -> Self {
+        match self {
+            ParseError::UnexpectedToken { offset, .. } => ParseError::UnexpectedToken {
+                offset,
+                expected: Some(description.to_string()),
+            },
+            ParseError::Context { context, source } => ParseError::Context {
+                context,
+                source: Box::new(source.expecting(description)),
+            },
+            err => err,
+        }
+    }
+
+    /// Describe the rule being parsed when the error occurred
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The description of the rule
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::errors::ParseError;
+    ///
+    /// let err = ParseError::unexpected_token(3)
+    ///     .context("Number")
+    ///     .context("Color");
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "in Color > in Number: Unexpected token have been encountered at 3"
+    /// );
+    /// assert_eq!(err.contexts(), vec!["Color", "Number"]);
+    /// ```
+    pub fn context(self, context: &str) -> Self {
+        ParseError::Context {
+            context: context.to_string(),
+            source: Box::new(self),
+        }
+    }
+
+    /// Return the error without its contexts
+    pub fn root(&self) -> &ParseError {
+        match self {
+            ParseError::Context { source, .. } => source.root(),
+            err => err,
+        }
+    }
+
+    /// Return the contexts of the error, outermost first
+    pub fn contexts(&self) -> Vec<&str> {
+        let mut contexts = vec![];
+        let mut err = self;
+        while let ParseError::Context { context, source } = err {
+            contexts.push(context.as_str());
+            err = source;
+        }
+        contexts
+    }
+
+    /// Move the position of the error by `offset`
+    ///
+    /// An error found in a slice of the input is reported relative to this
+    /// slice, shifting it by the position of the slice makes it relative to
+    /// the whole input. The errors without position are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The position of the slice in the input
+    pub fn shifted(self, offset: usize) -> Self {
+        match self {
+            ParseError::UnexpectedToken {
+                offset: position,
+                expected,
+            } => ParseError::UnexpectedToken {
+                offset: position + offset,
+                expected,
+            },
+            ParseError::DuplicateKey { start, end } => ParseError::DuplicateKey {
+                start: start + offset,
+                end: end + offset,
+            },
+            ParseError::Context { context, source } => ParseError::Context {
+                context,
+                source: Box::new(source.shifted(offset)),
+            },
+            err => err,
+        }
+    }
+
+    /// Return the position of the error in the input, if known
+    pub fn offset(&self) -> Option<usize> {
+        match self.root() {
+            ParseError::UnexpectedToken { offset, .. } => Some(*offset),
+            ParseError::DuplicateKey { start, .. } => Some(*start),
+            _ => None,
+        }
+    }
+
+    /// Return a code identifying the kind of the error
+    ///
+    /// The codes are stable across versions, unlike the messages, and allow to
+    /// match the errors programmatically. The code of an error with contexts
+    /// is the code of its root error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::UnexpectedEndOfInput => "unexpected_end_of_input",
+            ParseError::UnexpectedToken { .. } => "unexpected_token",
+            ParseError::AmbiguousPeeking => "ambiguous_peeking",
+            ParseError::UnbalancedGroup => "unbalanced_group",
+            ParseError::CursorOutOfBounds => "cursor_out_of_bounds",
+            ParseError::DuplicateKey { .. } => "duplicate_key",
+            ParseError::ExpansionCycle(_) => "expansion_cycle",
+            ParseError::Context { source, .. } => source.code(),
+            ParseError::Utf8Error(_) => "utf8_error",
+            ParseError::ParseIntError(_) => "parse_int_error",
+            ParseError::ParseFloatError(_) => "parse_float_error",
+            ParseError::TryFromIntError(_) => "try_from_int_error",
+            ParseError::TryFromSliceError(_) => "try_from_slice_error",
+            ParseError::CharTryFromError(_) => "char_try_from_error",
+            ParseError::Custom(_) => "custom",
+        }
+    }
+}
+
+/// A machine-readable report of a [ParseError].
+///
+/// The report is meant for the tools built on elyze: its fields and its JSON
+/// serialization are stable across versions.
+///
+/// # Example
+///
+/// ```
+/// use elyze::errors::{ParseError, ParseErrorReport};
+///
+/// let err = ParseError::unexpected_token(12).expecting("number");
+/// let report = ParseErrorReport::from(&err).with_rule("assignment");
+/// assert_eq!(report.code, "unexpected_token");
+/// assert_eq!(
+///     report.to_json(),
+///     r#"{"code":"unexpected_token","message":"Unexpected token have been encountered at 12, expected number","offset":12,"expected":["number"],"rules":["assignment"]}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrorReport {
+    /// The code of the error, see [ParseError::code]
+    pub code: &'static str,
+    /// The human-readable message of the error
+    pub message: String,
+    /// The position of the error in the input, if known
+    pub offset: Option<usize>,
+    /// The descriptions of the expected elements
+    pub expected: Vec<String>,
+    /// The rules being parsed when the error occurred, outermost first
+    pub rules: Vec<String>,
+}
+
+impl From<&ParseError> for ParseErrorReport {
+    /// The contexts of the error are the rules of the report
+    fn from(err: &ParseError) -> Self {
+        let root = err.root();
+        let expected = match root {
+            ParseError::UnexpectedToken {
+                expected: Some(expected),
+                ..
+            } => vec![expected.clone()],
+            _ => vec![],
+        };
+        ParseErrorReport {
+            code: root.code(),
+            message: root.to_string(),
+            offset: root.offset(),
+            expected,
+            rules: err.contexts().into_iter().map(str::to_string).collect(),
+        }
+    }
+}
+
+impl ParseErrorReport {
+    /// Set the position of the error in the input
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The position of the error
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Add a description of an expected element
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The description of the expected element
+    pub fn with_expected(mut self, expected: &str) -> Self {
+        self.expected.push(expected.to_string());
+        self
+    }
+
+    /// Add a rule to the stack of the rules being parsed
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The name of the rule, nested in the previous ones
+    pub fn with_rule(mut self, rule: &str) -> Self {
+        self.rules.push(rule.to_string());
+        self
+    }
+
+    /// Serialize the report as a JSON object
+    ///
+    /// The object has the `code`, `message`, `offset`, `expected` and `rules`
+    /// fields in this order, `offset` being `null` if unknown.
+    pub fn to_json(&self) -> String {
+        let strings = |values: &[String]| {
+            let values = values.iter().map(|value| json_string(value));
+            format!("[{}]", values.collect::<Vec<_>>().join(","))
+        };
+        let offset = self
+            .offset
+            .map_or("null".to_string(), |offset| offset.to_string());
+        format!(
+            r#"{{"code":{},"message":{},"offset":{offset},"expected":{},"rules":{}}}"#,
+            json_string(self.code),
+            json_string(&self.message),
+            strings(&self.expected),
+            strings(&self.rules),
+        )
+    }
+}
+
+/// Quote and escape a string as a JSON string
+pub(crate) fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{ParseError, ParseErrorReport, ParseResult};
+    use std::error::Error;
+
+    #[test]
+    fn test_error_report() {
+        let report = ParseErrorReport::from(&ParseError::DuplicateKey { start: 4, end: 7 });
+        assert_eq!(report.code, "duplicate_key");
+        assert_eq!(report.offset, Some(4));
+        assert_eq!(
+            report.to_json(),
+            r#"{"code":"duplicate_key","message":"Duplicate key at 4..7","offset":4,"expected":[],"rules":[]}"#
+        );
+
+        let report = ParseErrorReport::from(&ParseError::ExpansionCycle("a \"b\"\n".to_string()))
+            .with_expected("a\tb")
+            .with_expected("\u{1}")
+            .with_rule("include")
+            .with_rule("file");
+        assert_eq!(report.offset, None);
+        assert_eq!(
+            report.to_json(),
+            r#"{"code":"expansion_cycle","message":"Expansion cycle detected on a \"b\"\n","offset":null,"expected":["a\tb","\u0001"],"rules":["include","file"]}"#
+        );
+    }
+
+    #[test]
+    fn test_error_offset() {
+        let err = ParseError::unexpected_token(2)
+            .expecting("a number")
+            .shifted(10);
+        assert_eq!(err.offset(), Some(12));
+        let report = ParseErrorReport::from(&err);
+        assert_eq!(report.offset, Some(12));
+        assert_eq!(report.expected, vec!["a number".to_string()]);
+
+        let err = ParseError::DuplicateKey { start: 1, end: 3 }.shifted(4);
+        assert!(matches!(err, ParseError::DuplicateKey { start: 5, end: 7 }));
+        let err = ParseError::UnbalancedGroup.shifted(4).expecting("`)`");
+        assert!(matches!(err, ParseError::UnbalancedGroup));
+        assert_eq!(err.offset(), None);
+    }
+
+    #[test]
+    fn test_error_context() {
+        let err = ParseError::unexpected_token(2)
+            .context("Number")
+            .context("Color")
+            .shifted(10)
+            .expecting("a digit");
+        assert!(matches!(
+            err.root(),
+            ParseError::UnexpectedToken { offset: 12, expected: Some(expected) } if expected == "a digit"
+        ));
+        assert_eq!(err.code(), "unexpected_token");
+        assert_eq!(err.offset(), Some(12));
+        assert_eq!(
+            err.to_string(),
+            "in Color > in Number: Unexpected token have been encountered at 12, expected a digit"
+        );
+
+        let report = ParseErrorReport::from(&err);
+        assert_eq!(report.rules, vec!["Color", "Number"]);
+        assert_eq!(
+            report.message,
+            "Unexpected token have been encountered at 12, expected a digit"
+        );
+    }
+
+    /// List the messages of an error and of its sources
+    fn chain(err: &ParseError) -> Vec<String> {
+        std::iter::successors(Some(err as &dyn Error), |&err| err.source())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_error_source() {
+        // usable with the error handling libraries
+        fn assert_error<E: Error + Send + Sync + 'static>() {}
+        assert_error::<ParseError>();
+
+        let err = ParseError::from("300".parse::<u8>().expect_err("parsed 300"))
+            .context("Number")
+            .context("Color");
+        assert_eq!(
+            chain(&err),
+            vec![
+                "in Color > in Number: Invalid integer",
+                "number too large to fit in target type"
+            ]
+        );
+        assert!(err
+            .source()
+            .and_then(|source| source.downcast_ref::<std::num::ParseIntError>())
+            .is_some());
+
+        let data = vec![b'a', 0xff];
+        let err = ParseError::from(std::str::from_utf8(&data).expect_err("decoded 0xff"));
+        assert_eq!(chain(&err).len(), 2);
+        assert_eq!(err.to_string(), "Invalid UTF-8 data");
+
+        // a custom error is transparent
+        let err = ParseError::custom(ParseError::from("x".parse::<u8>().expect_err("parsed x")));
+        assert_eq!(err.code(), "custom");
+        assert_eq!(
+            chain(&err),
+            vec!["Invalid integer", "invalid digit found in string"]
+        );
+        assert_eq!(chain(&ParseError::UnbalancedGroup).len(), 1);
+    }
+
+    /// Convert a recognized slice without mapping the errors
+    fn convert(data: &[u8]) -> ParseResult<(f64, u8, [u8; 2], char)> {
+        let text = std::str::from_utf8(data)?;
+        let float = text.parse::<f64>()?;
+        let byte = u8::try_from(data.len() * 100)?;
+        let array = <[u8; 2]>::try_from(&data[..data.len().min(3)])?;
+        let char = char::try_from(0xd800 + u32::from(byte))?;
+        Ok((float, byte, array, char))
+    }
+
+    #[test]
+    fn test_error_conversions() {
+        let code = |data: &[u8]| convert(data).expect_err("converted").code();
+        assert_eq!(code(b"x"), "parse_float_error");
+        assert_eq!(code(b"1.5"), "try_from_int_error");
+        assert_eq!(code(b"1"), "try_from_slice_error");
+        assert_eq!(code(b"12"), "char_try_from_error");
+
+        let err = convert(b"x").expect_err("converted");
+        assert_eq!(err.to_string(), "Invalid float");
+        assert!(err
+            .source()
+            .and_then(|source| source.downcast_ref::<std::num::ParseFloatError>())
+            .is_some());
+    }
 }
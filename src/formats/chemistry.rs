@@ -0,0 +1,244 @@
+//! Chemical formulas.
+//!
+//! A formula is a sequence of element symbols and parenthesized groups, each
+//! optionally followed by a count: `C6H12O6`, `Ca(OH)2`,
+//! `Al2(SO4)3`. The groups may be nested.
+//!
+//! ```
+//! use elyze::formats::chemistry::ChemicalFormula;
+//!
+//! let formula = ChemicalFormula::parse(b"Ca(OH)2").expect("failed to parse");
+//! let atoms = formula.atoms();
+//! assert_eq!(atoms.get("Ca"), Some(&1));
+//! assert_eq!(atoms.get("O"), Some(&2));
+//! assert_eq!(atoms.get("H"), Some(&2));
+//! ```
+
+use crate::bytes::components::groups::GroupKind;
+use crate::errors::{ParseError, ParseResult};
+use crate::peek::peek;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::collections::BTreeMap;
+
+/// The symbols of the chemical elements
+const ELEMENTS: [&str; 118] = [
+    "H", "He", "Li", "Be", "B", "C", "N", "O", "F", "Ne", "Na", "Mg", "Al", "Si", "P", "S", "Cl",
+    "Ar", "K", "Ca", "Sc", "Ti", "V", "Cr", "Mn", "Fe", "Co", "Ni", "Cu", "Zn", "Ga", "Ge", "As",
+    "Se", "Br", "Kr", "Rb", "Sr", "Y", "Zr", "Nb", "Mo", "Tc", "Ru", "Rh", "Pd", "Ag", "Cd", "In",
+    "Sn", "Sb", "Te", "I", "Xe", "Cs", "Ba", "La", "Ce", "Pr", "Nd", "Pm", "Sm", "Eu", "Gd", "Tb",
+    "Dy", "Ho", "Er", "Tm", "Yb", "Lu", "Hf", "Ta", "W", "Re", "Os", "Ir", "Pt", "Au", "Hg", "Tl",
+    "Pb", "Bi", "Po", "At", "Rn", "Fr", "Ra", "Ac", "Th", "Pa", "U", "Np", "Pu", "Am", "Cm", "Bk",
+    "Cf", "Es", "Fm", "Md", "No", "Lr", "Rf", "Db", "Sg", "Bh", "Hs", "Mt", "Ds", "Rg", "Cn", "Nh",
+    "Fl", "Mc", "Lv", "Ts", "Og",
+];
+
+/// Accept an optional count, 1 when absent
+fn count(scanner: &mut Scanner<u8>) -> ParseResult<u32> {
+    let start = scanner.current_position();
+    let digits = scanner.take_while(u8::is_ascii_digit);
+    if digits.is_empty() {
+        return Ok(1);
+    }
+    match std::str::from_utf8(digits)?.parse()? {
+        0 => Err(ParseError::unexpected_token(start).expecting("a positive count")),
+        count => Ok(count),
+    }
+}
+
+/// A part of a chemical formula.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FormulaPart<'a> {
+    /// An element and its count, `H2`
+    Element {
+        /// The symbol of the element
+        symbol: &'a str,
+        /// The number of atoms
+        count: u32,
+    },
+    /// A parenthesized group and its count, `(OH)2`
+    Group {
+        /// The parts of the group
+        parts: Vec<FormulaPart<'a>>,
+        /// The number of times the group is repeated
+        count: u32,
+    },
+}
+
+impl<'a> Visitor<'a, u8> for FormulaPart<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let part = match checkpoint.first() {
+            Some(b'(') => {
+                let group = peek(GroupKind::Parenthesis, &checkpoint)?
+                    .ok_or_else(|| ParseError::unexpected_token(start).expecting("a group"))?;
+                let parts = parts(group.peeked_slice())
+                    .map_err(|err| err.shifted(start + group.start_element_size))?;
+                checkpoint.bump_by(group.end_slice);
+                FormulaPart::Group {
+                    parts,
+                    count: count(&mut checkpoint)?,
+                }
+            }
+            Some(byte) if byte.is_ascii_uppercase() => {
+                let size = 1 + checkpoint.get(1).is_some_and(u8::is_ascii_lowercase) as usize;
+                let symbol = std::str::from_utf8(&checkpoint.remaining()[..size])?;
+                let symbol = ELEMENTS
+                    .iter()
+                    .find(|element| **element == symbol)
+                    .ok_or_else(|| ParseError::unexpected_token(start).expecting("an element"))?;
+                checkpoint.bump_by(size);
+                FormulaPart::Element {
+                    symbol,
+                    count: count(&mut checkpoint)?,
+                }
+            }
+            Some(_) => {
+                return Err(ParseError::unexpected_token(start).expecting("an element or a group"));
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        };
+        checkpoint.commit();
+        Ok(part)
+    }
+}
+
+/// Accept the parts of a whole formula or group
+fn parts(data: &[u8]) -> ParseResult<Vec<FormulaPart<'_>>> {
+    let mut scanner = Scanner::new(data);
+    let mut parts = vec![scanner.visit()?];
+    while !scanner.is_empty() {
+        parts.push(scanner.visit()?);
+    }
+    Ok(parts)
+}
+
+/// A chemical formula.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChemicalFormula<'a>(pub Vec<FormulaPart<'a>>);
+
+impl<'a> ChemicalFormula<'a> {
+    /// Parse a whole chemical formula.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The formula
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` on an unknown element or a zero count,
+    /// `ParseError::UnbalancedGroup` on an unclosed group.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        parts(data).map(ChemicalFormula)
+    }
+
+    /// Return the number of atoms of each element, the groups being
+    /// expanded. The numbers saturate at `u32::MAX`.
+    pub fn atoms(&self) -> BTreeMap<&'a str, u32> {
+        fn count<'a>(parts: &[FormulaPart<'a>], factor: u32, atoms: &mut BTreeMap<&'a str, u32>) {
+            for part in parts {
+                match part {
+                    FormulaPart::Element { symbol, count } => {
+                        let total = atoms.entry(*symbol).or_default();
+                        *total = total.saturating_add(factor.saturating_mul(*count));
+                    }
+                    FormulaPart::Group {
+                        parts,
+                        count: group,
+                    } => count(parts, factor.saturating_mul(*group), atoms),
+                }
+            }
+        }
+
+        let mut atoms = BTreeMap::new();
+        count(&self.0, 1, &mut atoms);
+        atoms
+    }
+}
+
+impl<'a> Visitor<'a, u8> for ChemicalFormula<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut parts = vec![checkpoint.visit()?];
+        while matches!(checkpoint.first(), Some(byte) if *byte == b'(' || byte.is_ascii_uppercase())
+        {
+            parts.push(checkpoint.visit()?);
+        }
+        checkpoint.commit();
+        Ok(ChemicalFormula(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::chemistry::{ChemicalFormula, FormulaPart};
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_formula() {
+        let formula = ChemicalFormula::parse(b"C6H12O6").expect("failed to parse");
+        assert_eq!(
+            formula.0,
+            [
+                FormulaPart::Element {
+                    symbol: "C",
+                    count: 6
+                },
+                FormulaPart::Element {
+                    symbol: "H",
+                    count: 12
+                },
+                FormulaPart::Element {
+                    symbol: "O",
+                    count: 6
+                },
+            ]
+        );
+
+        let formula = ChemicalFormula::parse(b"K4(Fe(CN)6)").expect("failed to parse");
+        let atoms: Vec<_> = formula.atoms().into_iter().collect();
+        assert_eq!(atoms, [("C", 6), ("Fe", 1), ("K", 4), ("N", 6)]);
+
+        let formula = ChemicalFormula::parse(b"Al2(SO4)3").expect("failed to parse");
+        assert_eq!(formula.atoms().get("O"), Some(&12));
+
+        let formula = ChemicalFormula::parse(b"((H100000)100000)100000").expect("failed to parse");
+        assert_eq!(formula.atoms().get("H"), Some(&u32::MAX));
+    }
+
+    #[test]
+    fn test_invalid_formula() {
+        for (data, position) in [
+            (&b"Xy2"[..], 0),
+            (b"H2o", 2),
+            (b"Ca(OH)0", 6),
+            (b"Ca(Oh)2", 3),
+        ] {
+            assert!(
+                matches!(
+                    ChemicalFormula::parse(data),
+                    Err(ParseError::UnexpectedToken { offset, .. }) if offset == position
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+        assert!(matches!(
+            ChemicalFormula::parse(b"Ca(OH"),
+            Err(ParseError::UnbalancedGroup)
+        ));
+        assert!(ChemicalFormula::parse(b"").is_err());
+        assert!(ChemicalFormula::parse(b"()").is_err());
+    }
+
+    #[test]
+    fn test_formula_visitor() {
+        let mut scanner = Scanner::new(b"2 NaCl + H2O");
+        scanner.bump_by(2);
+        let formula: ChemicalFormula = scanner.visit().expect("failed to parse");
+        assert_eq!(formula.atoms().len(), 2);
+        assert_eq!(scanner.remaining(), b" + H2O");
+    }
+}
@@ -0,0 +1,269 @@
+//! Command lines typed in a REPL.
+//!
+//! Splits a single string into flags, options and positional arguments, the
+//! way a shell would before handing them to a program:
+//!
+//! * `--flag`, a long flag
+//! * `--key=value`, a long option
+//! * `-abc`, a group of short flags
+//! * `--`, after which every argument is positional
+//! * anything else, a positional argument
+//!
+//! A value may be quoted: `"..."` with its backslash escapes decoded, or
+//! `'...'` kept as written.
+//!
+//! ```
+//! use elyze::formats::cmdline::CommandLine;
+//!
+//! let line = CommandLine::parse(br#"deploy -fv --env=prod --message="fix \"login\"" api"#)
+//!     .expect("failed to parse");
+//! assert!(line.flag("f"));
+//! assert!(line.flag("v"));
+//! assert_eq!(line.option("env"), Some("prod"));
+//! assert_eq!(line.option("message"), Some("fix \"login\""));
+//! assert_eq!(line.positionals().collect::<Vec<_>>(), vec!["deploy", "api"]);
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::formats::logline::Quoted;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// Consume the whitespaces separating the arguments
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    scanner.take_while(u8::is_ascii_whitespace);
+}
+
+/// Accept a value: a quoted string or a bare word ending at a whitespace
+fn value<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Cow<'a, str>> {
+    match scanner.first() {
+        Some(b'"') => Ok(scanner.visit::<Quoted>()?.0),
+        Some(b'\'') => {
+            let mut checkpoint = scanner.checkpoint();
+            checkpoint.bump_by(1);
+            let quoted = std::str::from_utf8(checkpoint.take_while(|byte| *byte != b'\''))?;
+            expect(&mut checkpoint, b'\'')?;
+            checkpoint.commit();
+            Ok(Cow::Borrowed(quoted))
+        }
+        _ => Ok(Cow::Borrowed(std::str::from_utf8(
+            scanner.take_while(|byte| !byte.is_ascii_whitespace()),
+        )?)),
+    }
+}
+
+/// An argument of a command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arg<'a> {
+    /// A long flag `--name`
+    Flag(&'a str),
+    /// A long option `--name=value`
+    Option {
+        /// The name of the option
+        name: &'a str,
+        /// The value, unquoted
+        value: Cow<'a, str>,
+    },
+    /// A group of short flags `-abc`, without its dash
+    Shorts(&'a str),
+    /// The end of the options `--`
+    EndOfOptions,
+    /// A positional argument, unquoted
+    Positional(Cow<'a, str>),
+}
+
+impl<'a> Visitor<'a, u8> for Arg<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let remaining = checkpoint.remaining();
+        let arg = match remaining {
+            [] => return Err(ParseError::UnexpectedEndOfInput),
+            [b'-', b'-', rest @ ..] => {
+                checkpoint.bump_by(2);
+                let name = std::str::from_utf8(
+                    checkpoint.take_while(|byte| *byte != b'=' && !byte.is_ascii_whitespace()),
+                )?;
+                match checkpoint.first() {
+                    Some(b'=') if name.is_empty() => {
+                        return Err(
+                            ParseError::unexpected_token(start + 2).expecting("an option name")
+                        );
+                    }
+                    Some(b'=') => {
+                        checkpoint.bump_by(1);
+                        let value = value(&mut checkpoint)?;
+                        Arg::Option { name, value }
+                    }
+                    _ if rest.first().is_none_or(u8::is_ascii_whitespace) => Arg::EndOfOptions,
+                    _ => Arg::Flag(name),
+                }
+            }
+            // a dash alone or a negative number is positional
+            [b'-', next, ..] if !next.is_ascii_whitespace() && !next.is_ascii_digit() => {
+                checkpoint.bump_by(1);
+                Arg::Shorts(std::str::from_utf8(
+                    checkpoint.take_while(|byte| !byte.is_ascii_whitespace()),
+                )?)
+            }
+            _ => Arg::Positional(value(&mut checkpoint)?),
+        };
+        checkpoint.commit();
+        Ok(arg)
+    }
+}
+
+/// The arguments of a command line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommandLine<'a> {
+    /// The arguments, in their order on the line. The arguments after the end
+    /// of the options are all positional.
+    pub args: Vec<Arg<'a>>,
+}
+
+impl<'a> CommandLine<'a> {
+    /// Parse a whole command line.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The command line
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if a quoted value is not closed,
+    /// `ParseError::UnexpectedToken` on an invalid argument.
+    pub fn parse(line: &'a [u8]) -> ParseResult<Self> {
+        let mut scanner = Scanner::new(line);
+        let command_line = scanner.visit()?;
+        if !scanner.is_empty() {
+            return Err(ParseError::unexpected_token(scanner.current_position()));
+        }
+        Ok(command_line)
+    }
+
+    /// Return whether the long flag, or the short flag if `name` is a single
+    /// character, is set
+    pub fn flag(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let short = match (chars.next(), chars.next()) {
+            (Some(short), None) => Some(short),
+            _ => None,
+        };
+        self.args.iter().any(|arg| match arg {
+            Arg::Flag(flag) => *flag == name,
+            Arg::Shorts(shorts) => short.is_some_and(|short| shorts.contains(short)),
+            _ => false,
+        })
+    }
+
+    /// Return the value of the last long option with the given name
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.args.iter().rev().find_map(|arg| match arg {
+            Arg::Option {
+                name: option,
+                value,
+            } if *option == name => Some(value.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Return the positional arguments
+    pub fn positionals(&self) -> impl Iterator<Item = &str> {
+        self.args.iter().filter_map(|arg| match arg {
+            Arg::Positional(value) => Some(value.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl<'a> Visitor<'a, u8> for CommandLine<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut args = vec![];
+        let mut options_ended = false;
+        loop {
+            skip_whitespaces(&mut checkpoint);
+            if checkpoint.is_empty() {
+                break;
+            }
+            if options_ended {
+                args.push(Arg::Positional(value(&mut checkpoint)?));
+                continue;
+            }
+            let arg = checkpoint.visit()?;
+            options_ended = arg == Arg::EndOfOptions;
+            args.push(arg);
+        }
+        checkpoint.commit();
+        Ok(CommandLine { args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::cmdline::{Arg, CommandLine};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_arg() {
+        let parse = |data| Scanner::new(data).visit::<Arg>().expect("failed to parse");
+        assert_eq!(parse(b"--verbose"), Arg::Flag("verbose"));
+        assert_eq!(
+            parse(b"--path='a b' c"),
+            Arg::Option {
+                name: "path",
+                value: Cow::Borrowed("a b")
+            }
+        );
+        assert_eq!(
+            parse(b"--empty="),
+            Arg::Option {
+                name: "empty",
+                value: Cow::Borrowed("")
+            }
+        );
+        assert_eq!(parse(b"-xzf"), Arg::Shorts("xzf"));
+        assert_eq!(parse(b"-- -x"), Arg::EndOfOptions);
+        assert_eq!(parse(b"-"), Arg::Positional(Cow::Borrowed("-")));
+        assert_eq!(parse(b"-12"), Arg::Positional(Cow::Borrowed("-12")));
+        assert_eq!(
+            parse(br#""a \"b\"""#),
+            Arg::Positional(Cow::Owned("a \"b\"".to_string()))
+        );
+
+        assert!(matches!(
+            Scanner::new(b"--=x").visit::<Arg>(),
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_command_line() {
+        let line = CommandLine::parse(b"  get --all -- --raw -v  ").expect("failed to parse");
+        assert!(line.flag("all"));
+        // the arguments after `--` are positional
+        assert!(!line.flag("raw"));
+        assert!(!line.flag("v"));
+        assert_eq!(
+            line.positionals().collect::<Vec<_>>(),
+            vec!["get", "--raw", "-v"]
+        );
+
+        let line = CommandLine::parse(b"set --level=1 --level=2").expect("failed to parse");
+        assert_eq!(line.option("level"), Some("2"));
+        assert_eq!(line.option("missing"), None);
+
+        assert!(CommandLine::parse(b"")
+            .expect("failed to parse")
+            .args
+            .is_empty());
+        assert!(matches!(
+            CommandLine::parse(b"echo 'unterminated"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+}
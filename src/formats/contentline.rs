@@ -0,0 +1,340 @@
+//! Content lines, the base of the vCard and iCalendar formats.
+//!
+//! Each line is written `NAME;PARAM=VALUE,VALUE:value`, the name being
+//! optionally prefixed by a group `item1.EMAIL`. The names are
+//! case-insensitive. A long line is folded over several lines, each
+//! continuation line starting with a space or a tab: the data is unfolded by
+//! a [LineFolding] before the parse, and the positions of the errors can be
+//! mapped back to the folded data.
+//!
+//! ```
+//! use elyze::bytes::continuation::LineFolding;
+//! use elyze::formats::contentline::ContentLines;
+//!
+//! let data = b"BEGIN:VCARD\r\nFN:Jane\r\n  Doe\r\nEMAIL;TYPE=work,pref:jane@example.com\r\nEND:VCARD\r\n";
+//! let unfolded = LineFolding::default().unfold(data);
+//! let lines = ContentLines::parse(unfolded.data()).expect("failed to parse");
+//! assert_eq!(lines.get("fn").map(|line| line.value), Some("Jane Doe"));
+//! let email = lines.get("EMAIL").expect("no email");
+//! assert_eq!(email.parameter("type").expect("no type").values, ["work", "pref"]);
+//! ```
+//!
+//! [LineFolding]: crate::bytes::continuation::LineFolding
+
+use crate::bytes::escape::{unescape_str, EscapeDecoder};
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// Whether the byte is a control character, the tab excepted
+fn is_control(byte: u8) -> bool {
+    (byte < 0x20 && byte != b'\t') || byte == 0x7f
+}
+
+/// Accept a name made of letters, digits and `-`
+fn name<'a>(scanner: &mut Scanner<'a, u8>, expecting: &str) -> ParseResult<&'a str> {
+    let start = scanner.current_position();
+    let name = scanner.take_while(|byte| byte.is_ascii_alphanumeric() || *byte == b'-');
+    if name.is_empty() {
+        return Err(ParseError::unexpected_token(start).expecting(expecting));
+    }
+    // the names are made of ASCII bytes only
+    Ok(std::str::from_utf8(name)?)
+}
+
+/// Decode the `^n`, `^^` and `^'` escapes of a parameter value (RFC 6868),
+/// the other carets being kept
+fn decode_carets(value: &str) -> Cow<'_, str> {
+    if !value.contains('^') {
+        return Cow::Borrowed(value);
+    }
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(char) = chars.next() {
+        if char != '^' {
+            decoded.push(char);
+            continue;
+        }
+        let mut lookahead = chars.clone();
+        match lookahead.next() {
+            Some('n') => decoded.push('\n'),
+            Some('^') => decoded.push('^'),
+            Some('\'') => decoded.push('"'),
+            _ => {
+                decoded.push('^');
+                continue;
+            }
+        }
+        chars = lookahead;
+    }
+    Cow::Owned(decoded)
+}
+
+/// Accept a parameter value, quoted or not
+fn parameter_value<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Cow<'a, str>> {
+    let value = if scanner.first() == Some(&b'"') {
+        scanner.bump_by(1);
+        let value = scanner.take_till(|byte| *byte == b'"' || is_control(*byte));
+        expect(scanner, b'"')?;
+        value
+    } else {
+        scanner.take_till(|byte| matches!(byte, b'"' | b';' | b':' | b',') || is_control(*byte))
+    };
+    Ok(decode_carets(std::str::from_utf8(value)?))
+}
+
+/// A parameter of a content line, `NAME=VALUE,VALUE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parameter<'a> {
+    /// The name of the parameter, case-insensitive
+    pub name: &'a str,
+    /// The values, with their quotes removed and their `^` escapes decoded.
+    /// The vCard 2.1 parameters without `=` have no value.
+    pub values: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Visitor<'a, u8> for Parameter<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let name = name(&mut checkpoint, "a parameter name")?;
+        let mut values = vec![];
+        if checkpoint.first() == Some(&b'=') {
+            checkpoint.bump_by(1);
+            values.push(parameter_value(&mut checkpoint)?);
+            while checkpoint.first() == Some(&b',') {
+                checkpoint.bump_by(1);
+                values.push(parameter_value(&mut checkpoint)?);
+            }
+        }
+        checkpoint.commit();
+        Ok(Parameter { name, values })
+    }
+}
+
+/// The escapes of the text values: `\\`, `\;`, `\,` and `\n` or `\N`. Any
+/// other backslash is kept.
+struct TextEscapes;
+
+impl EscapeDecoder for TextEscapes {
+    fn escape_byte(&self) -> u8 {
+        b'\\'
+    }
+
+    fn decode(&self, data: &[u8], output: &mut Vec<u8>) -> ParseResult<usize> {
+        match data.get(1) {
+            Some(byte @ (b'\\' | b';' | b',')) => output.push(*byte),
+            Some(b'n' | b'N') => output.push(b'\n'),
+            _ => {
+                output.push(b'\\');
+                return Ok(1);
+            }
+        }
+        Ok(2)
+    }
+}
+
+/// An unfolded content line.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentLine<'a> {
+    /// The group of the line, `item1` in `item1.EMAIL`
+    pub group: Option<&'a str>,
+    /// The name of the line, case-insensitive
+    pub name: &'a str,
+    /// The parameters, in their order in the line
+    pub parameters: Vec<Parameter<'a>>,
+    /// The value as written, its meaning depending on the property
+    pub value: &'a str,
+}
+
+impl ContentLine<'_> {
+    /// Return the first parameter with the name, case-insensitive
+    pub fn parameter(&self, name: &str) -> Option<&Parameter<'_>> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Return the value decoded as a text, with its `\\`, `\;`, `\,` and
+    /// `\n` escapes decoded.
+    pub fn text(&self) -> Cow<'_, str> {
+        // the escapes only replace ASCII bytes by ASCII bytes
+        unescape_str(&TextEscapes, self.value.as_bytes())
+            .expect("the text escapes keep the value valid UTF-8")
+    }
+}
+
+impl<'a> Visitor<'a, u8> for ContentLine<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut group = None;
+        let mut name = name(&mut checkpoint, "a property name")?;
+        if checkpoint.first() == Some(&b'.') {
+            checkpoint.bump_by(1);
+            group = Some(name);
+            name = self::name(&mut checkpoint, "a property name")?;
+        }
+
+        let mut parameters = vec![];
+        while checkpoint.first() == Some(&b';') {
+            checkpoint.bump_by(1);
+            parameters.push(checkpoint.visit()?);
+        }
+        expect(&mut checkpoint, b':')?;
+
+        let value = checkpoint.take_till(|byte| *byte == b'\n');
+        let value = value.strip_suffix(b"\r").unwrap_or(value);
+        if !checkpoint.is_empty() {
+            checkpoint.bump_by(1);
+        }
+        checkpoint.commit();
+        Ok(ContentLine {
+            group,
+            name,
+            parameters,
+            value: std::str::from_utf8(value)?,
+        })
+    }
+}
+
+/// The unfolded content lines of a document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ContentLines<'a>(pub Vec<ContentLine<'a>>);
+
+impl<'a> ContentLines<'a> {
+    /// Parse the content lines of an unfolded document, the empty lines being
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The unfolded document
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` on an invalid line, at its position in
+    /// the unfolded document.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        Scanner::new(data).visit()
+    }
+
+    /// Return the first line with the name, case-insensitive
+    pub fn get(&self, name: &str) -> Option<&ContentLine<'a>> {
+        self.0
+            .iter()
+            .find(|line| line.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl<'a> Visitor<'a, u8> for ContentLines<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut lines = vec![];
+        loop {
+            checkpoint.take_while(|byte| matches!(byte, b'\r' | b'\n'));
+            if checkpoint.is_empty() {
+                break;
+            }
+            lines.push(checkpoint.visit()?);
+        }
+        checkpoint.commit();
+        Ok(ContentLines(lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::continuation::LineFolding;
+    use crate::errors::ParseError;
+    use crate::formats::contentline::{ContentLine, ContentLines, Parameter};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_content_line() {
+        let line: ContentLine =
+            Scanner::new(b"item1.ADR;TYPE=\"home,pref\";LABEL=\"^'a^'^n^b\";X-Y=:;;Main St\r\nEND")
+                .visit()
+                .expect("failed to parse");
+        assert_eq!(line.group, Some("item1"));
+        assert_eq!(line.name, "ADR");
+        assert_eq!(
+            line.parameters,
+            [
+                Parameter {
+                    name: "TYPE",
+                    values: vec![Cow::Borrowed("home,pref")],
+                },
+                Parameter {
+                    name: "LABEL",
+                    values: vec![Cow::Borrowed("\"a\"\n^b")],
+                },
+                Parameter {
+                    name: "X-Y",
+                    values: vec![Cow::Borrowed("")],
+                },
+            ]
+        );
+        assert_eq!(line.value, ";;Main St");
+
+        // a vCard 2.1 parameter without value
+        let line: ContentLine = Scanner::new(b"TEL;WORK;VOICE:+1-555")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(line.parameter("voice").map(|p| p.values.len()), Some(0));
+        assert_eq!(line.value, "+1-555");
+
+        assert!(matches!(
+            Scanner::new(b"NOTE value").visit::<ContentLine>(),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert!(matches!(
+            Scanner::new(b":value").visit::<ContentLine>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert!(Scanner::new(b"A;B=\"open:x")
+            .visit::<ContentLine>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_text() {
+        let line: ContentLine = Scanner::new(br"NOTE:a\, b\; c\\d\ne\:f")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(line.text(), "a, b; c\\d\ne\\:f");
+        let line: ContentLine = Scanner::new(b"NOTE:plain")
+            .visit()
+            .expect("failed to parse");
+        assert!(matches!(line.text(), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn test_folded_lines() {
+        let data = b"BEGIN:VEVENT\r\nSUMMARY:long \r\n summary\r\n\r\nDTSTART;TZID=Europe/Paris:2024\r\n 0101T100000\r\nEND:VEVENT";
+        let unfolded = LineFolding::default().unfold(data);
+        let lines = ContentLines::parse(unfolded.data()).expect("failed to parse");
+        let names: Vec<_> = lines.0.iter().map(|line| line.name).collect();
+        assert_eq!(names, ["BEGIN", "SUMMARY", "DTSTART", "END"]);
+        assert_eq!(
+            lines.get("summary").map(|line| line.value),
+            Some("long summary")
+        );
+        assert_eq!(
+            lines.get("dtstart").map(|line| line.value),
+            Some("20240101T100000")
+        );
+        assert_eq!(lines.get("location"), None);
+
+        // the error is reported in the folded data
+        let data = b"SUMMARY:a\r\n b\r\nDT\r\n START 1\r\n";
+        let unfolded = LineFolding::default().unfold(data);
+        let Err(ParseError::UnexpectedToken { offset, .. }) = ContentLines::parse(unfolded.data())
+        else {
+            panic!("expected an error");
+        };
+        assert_eq!(offset, 19);
+        assert_eq!(unfolded.original_offset(offset), 25);
+        assert_eq!(&data[25..], b" 1\r\n");
+    }
+}
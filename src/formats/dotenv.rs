@@ -0,0 +1,271 @@
+//! Dotenv files.
+//!
+//! Each line assigns a value to a variable, `KEY=value`, with an optional
+//! `export` prefix. A value is either bare, up to the end of the line or a
+//! comment, single-quoted and taken as written, or double-quoted with its
+//! backslash escapes decoded. The quoted values may span several lines.
+//!
+//! The entries keep the spans of their key and value, to point at them when
+//! a value turns out to be invalid.
+//!
+//! ```
+//! use elyze::formats::dotenv::Dotenv;
+//!
+//! let data = b"# database\nexport DB_HOST=localhost # local\nDB_PASSWORD=\"s3cr\\\"et\"\n";
+//! let dotenv = Dotenv::parse(data).expect("failed to parse");
+//! assert_eq!(dotenv.get("DB_HOST"), Some("localhost"));
+//! assert_eq!(dotenv.get("DB_PASSWORD"), Some("s3cr\"et"));
+//! assert_eq!(dotenv.entries[1].value_span.slice(data), Some(&b"\"s3cr\\\"et\""[..]));
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::formats::logline::Quoted;
+use crate::scanner::Scanner;
+use crate::span::Span;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// Whether the byte is a space or a tab
+fn is_blank(byte: &u8) -> bool {
+    matches!(byte, b' ' | b'\t')
+}
+
+/// Consume the rest of the line, its line ending included
+fn skip_line(scanner: &mut Scanner<u8>) {
+    scanner.take_till(|byte| *byte == b'\n');
+    if !scanner.is_empty() {
+        scanner.bump_by(1);
+    }
+}
+
+/// Accept a bare value, up to the end of the line, without its trailing
+/// blanks and a comment introduced by a `#` after a blank
+fn bare_value<'a>(scanner: &mut Scanner<'a, u8>, start: usize) -> ParseResult<(&'a str, Span)> {
+    let line = scanner.take_till(|byte| *byte == b'\n');
+    let end = line
+        .iter()
+        .enumerate()
+        .position(|(index, byte)| *byte == b'#' && (index == 0 || is_blank(&line[index - 1])))
+        .unwrap_or(line.len());
+    let value = line[..end].trim_ascii_end();
+    Ok((
+        std::str::from_utf8(value)?,
+        Span::new(start, start + value.len()),
+    ))
+}
+
+/// Accept a single-quoted value, taken as written
+fn single_quoted<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<&'a str> {
+    expect(scanner, b'\'')?;
+    let value = scanner.take_till(|byte| *byte == b'\'');
+    if scanner.is_empty() {
+        return Err(ParseError::UnexpectedEndOfInput);
+    }
+    scanner.bump_by(1);
+    Ok(std::str::from_utf8(value)?)
+}
+
+/// Consume the end of a line, after optional blanks and a comment
+fn end_of_line(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+    scanner.take_while(is_blank);
+    match scanner.first() {
+        None | Some(b'\n' | b'#') => {}
+        Some(b'\r') if scanner.get(1).is_none_or(|byte| *byte == b'\n') => {}
+        Some(_) => {
+            return Err(ParseError::unexpected_token(scanner.current_position())
+                .expecting("the end of the line"));
+        }
+    }
+    skip_line(scanner);
+    Ok(())
+}
+
+/// A `KEY=value` assignment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DotenvEntry<'a> {
+    /// The name of the variable, made of letters, digits, `_` and `.`
+    pub key: &'a str,
+    /// The value, with its quotes removed and its escapes decoded
+    pub value: Cow<'a, str>,
+    /// Whether the assignment has the `export` prefix
+    pub exported: bool,
+    /// The span of the key
+    pub key_span: Span,
+    /// The span of the value as written, quotes included
+    pub value_span: Span,
+}
+
+impl<'a> Visitor<'a, u8> for DotenvEntry<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let remaining = checkpoint.remaining();
+        let exported = remaining.starts_with(b"export") && remaining.get(6).is_some_and(is_blank);
+        if exported {
+            checkpoint.bump_by(6);
+            checkpoint.take_while(is_blank);
+        }
+
+        let key_start = checkpoint.current_position();
+        if !checkpoint
+            .first()
+            .is_some_and(|byte| byte.is_ascii_alphabetic() || *byte == b'_')
+        {
+            return Err(ParseError::unexpected_token(key_start).expecting("a variable name"));
+        }
+        let key = checkpoint
+            .take_while(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.'));
+        let key_span = Span::new(key_start, checkpoint.current_position());
+        checkpoint.take_while(is_blank);
+        expect(&mut checkpoint, b'=')?;
+        checkpoint.take_while(is_blank);
+
+        let value_start = checkpoint.current_position();
+        let (value, value_span) = match checkpoint.first() {
+            Some(b'"') => {
+                let Quoted(value) = checkpoint.visit()?;
+                (value, Span::new(value_start, checkpoint.current_position()))
+            }
+            Some(b'\'') => {
+                let value = single_quoted(&mut checkpoint)?;
+                (
+                    Cow::Borrowed(value),
+                    Span::new(value_start, checkpoint.current_position()),
+                )
+            }
+            _ => {
+                let (value, value_span) = bare_value(&mut checkpoint, value_start)?;
+                (Cow::Borrowed(value), value_span)
+            }
+        };
+        end_of_line(&mut checkpoint)?;
+        checkpoint.commit();
+        Ok(DotenvEntry {
+            key: std::str::from_utf8(key)?,
+            value,
+            exported,
+            key_span,
+            value_span,
+        })
+    }
+}
+
+/// A dotenv file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Dotenv<'a> {
+    /// The assignments, in their order in the file
+    pub entries: Vec<DotenvEntry<'a>>,
+}
+
+impl<'a> Dotenv<'a> {
+    /// Parse a whole dotenv file.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The dotenv file
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` on an invalid line or an invalid escape
+    /// sequence, `ParseError::UnexpectedEndOfInput` on an unclosed quote.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        Scanner::new(data).visit()
+    }
+
+    /// Return the value of a variable, the last assignment winning.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rfind(|entry| entry.key == key)
+            .map(|entry| entry.value.as_ref())
+    }
+}
+
+impl<'a> Visitor<'a, u8> for Dotenv<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut entries = vec![];
+        loop {
+            checkpoint.take_while(u8::is_ascii_whitespace);
+            match checkpoint.first() {
+                None => break,
+                Some(b'#') => skip_line(&mut checkpoint),
+                Some(_) => entries.push(checkpoint.visit()?),
+            }
+        }
+        checkpoint.commit();
+        Ok(Dotenv { entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::dotenv::{Dotenv, DotenvEntry};
+    use crate::scanner::Scanner;
+    use crate::span::Span;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_entry() {
+        let entry: DotenvEntry = Scanner::new(b"export  A_1 = some value # comment\n")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(entry.key, "A_1");
+        assert_eq!(entry.value, Cow::Borrowed("some value"));
+        assert!(entry.exported);
+        assert_eq!(entry.key_span, Span::new(8, 11));
+        assert_eq!(entry.value_span, Span::new(14, 24));
+
+        let parse = |data| {
+            Scanner::new(data)
+                .visit::<DotenvEntry>()
+                .expect("failed to parse")
+                .value
+        };
+        assert_eq!(parse(b"export=1"), "1");
+        assert_eq!(parse(b"URL=http://a#anchor"), "http://a#anchor");
+        assert_eq!(parse(b"EMPTY=\r\n"), "");
+        assert_eq!(parse(b"RAW='a\\n $b'"), "a\\n $b");
+        assert_eq!(parse(b"TEXT=\"line\\nnext\" # comment"), "line\nnext");
+        assert_eq!(parse(b"MULTI=\"a\nb\""), "a\nb");
+    }
+
+    #[test]
+    fn test_malformed_entry() {
+        assert!(matches!(
+            Scanner::new(b"1A=x").visit::<DotenvEntry>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert!(matches!(
+            Scanner::new(b"A x").visit::<DotenvEntry>(),
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+        assert!(matches!(
+            Scanner::new(b"A='open").visit::<DotenvEntry>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            Scanner::new(b"A=\"x\" y").visit::<DotenvEntry>(),
+            Err(ParseError::UnexpectedToken { offset: 6, .. })
+        ));
+        // the invalid escape sequence is pointed at
+        assert!(matches!(
+            Scanner::new(b"A=\"ab\\q\"").visit::<DotenvEntry>(),
+            Err(ParseError::UnexpectedToken { offset: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_dotenv() {
+        let dotenv = Dotenv::parse(b"\n# comment\nA=1\r\n  B='2'\nA=3").expect("failed to parse");
+        let keys: Vec<_> = dotenv.entries.iter().map(|entry| entry.key).collect();
+        assert_eq!(keys, ["A", "B", "A"]);
+        assert_eq!(dotenv.get("A"), Some("3"));
+        assert_eq!(dotenv.get("B"), Some("2"));
+        assert_eq!(dotenv.get("C"), None);
+
+        assert!(Dotenv::parse(b"A=1\n=2").is_err());
+        assert_eq!(Dotenv::parse(b"").expect("failed to parse").entries, vec![]);
+    }
+}
@@ -0,0 +1,333 @@
+//! FASTA and FASTQ sequence files.
+//!
+//! A FASTA record is a header line `>id description` followed by the lines
+//! of its sequence. A FASTQ record is a header line `@id description`, the
+//! lines of its sequence, a separator line starting with `+`, and the lines
+//! of the qualities, one per base of the sequence.
+//!
+//! The records are iterated lazily, and a sequence written on a single line
+//! is borrowed from the data, so large files are parsed without copying.
+//!
+//! ```
+//! use elyze::formats::fasta::records;
+//!
+//! let data = b">seq1 first sequence\nACGT\nTTGA\n>seq2\nGGCC\n";
+//! let records = records(data)
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .expect("failed to parse");
+//! assert_eq!(records[0].id, "seq1");
+//! assert_eq!(records[0].description, Some("first sequence"));
+//! assert_eq!(records[0].sequence.as_ref(), b"ACGTTTGA");
+//! assert_eq!(records[1].sequence.as_ref(), b"GGCC");
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Consume a line and return it without its line ending
+fn line<'a>(scanner: &mut Scanner<'a, u8>) -> &'a [u8] {
+    let line = scanner.take_till(|byte| *byte == b'\n');
+    if !scanner.is_empty() {
+        scanner.bump_by(1);
+    }
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Consume the blank lines
+fn skip_blank_lines(scanner: &mut Scanner<u8>) {
+    scanner.take_while(u8::is_ascii_whitespace);
+}
+
+/// Accept a header line introduced by the marker
+///
+/// # Returns
+///
+/// The identifier and the description of the record.
+fn header<'a>(
+    scanner: &mut Scanner<'a, u8>,
+    marker: u8,
+) -> ParseResult<(&'a str, Option<&'a str>)> {
+    let start = scanner.current_position();
+    if scanner.first() != Some(&marker) {
+        let expected = if marker == b'>' { "`>`" } else { "`@`" };
+        return Err(ParseError::unexpected_token(start).expecting(expected));
+    }
+    let line = std::str::from_utf8(&line(scanner)[1..])?;
+    let (id, description) = match line.split_once(|char: char| char.is_ascii_whitespace()) {
+        Some((id, description)) => (id, Some(description.trim()).filter(|d| !d.is_empty())),
+        None => (line.trim_end(), None),
+    };
+    if id.is_empty() {
+        return Err(ParseError::unexpected_token(start + 1).expecting("an identifier"));
+    }
+    Ok((id, description))
+}
+
+/// Append a line to a sequence, only copying the lines when there are
+/// several of them
+fn append<'a>(sequence: &mut Option<Cow<'a, [u8]>>, line: &'a [u8]) {
+    let line = line.trim_ascii_end();
+    match sequence {
+        None => *sequence = Some(Cow::Borrowed(line)),
+        Some(sequence) => sequence.to_mut().extend_from_slice(line),
+    }
+}
+
+/// A FASTA record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FastaRecord<'a> {
+    /// The identifier, up to the first whitespace of the header
+    pub id: &'a str,
+    /// The rest of the header
+    pub description: Option<&'a str>,
+    /// The sequence, its lines joined
+    pub sequence: Cow<'a, [u8]>,
+}
+
+impl<'a> Visitor<'a, u8> for FastaRecord<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let (id, description) = header(&mut checkpoint, b'>')?;
+        let mut sequence = None;
+        while let Some(byte) = checkpoint.first() {
+            match byte {
+                b'>' => break,
+                // the comments of the old FASTA files
+                b';' => {
+                    line(&mut checkpoint);
+                }
+                _ => append(&mut sequence, line(&mut checkpoint)),
+            }
+        }
+        checkpoint.commit();
+        Ok(FastaRecord {
+            id,
+            description,
+            sequence: sequence.unwrap_or_default(),
+        })
+    }
+}
+
+/// A FASTQ record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FastqRecord<'a> {
+    /// The identifier, up to the first whitespace of the header
+    pub id: &'a str,
+    /// The rest of the header
+    pub description: Option<&'a str>,
+    /// The sequence, its lines joined
+    pub sequence: Cow<'a, [u8]>,
+    /// The qualities of the bases, encoded as ASCII characters, their lines
+    /// joined
+    pub quality: Cow<'a, [u8]>,
+}
+
+impl FastqRecord<'_> {
+    /// Return the Phred scores of the bases, the qualities being encoded
+    /// with an offset of 33
+    pub fn phred_scores(&self) -> impl Iterator<Item = u8> + '_ {
+        self.quality
+            .iter()
+            .map(|quality| quality.saturating_sub(33))
+    }
+}
+
+impl<'a> Visitor<'a, u8> for FastqRecord<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let (id, description) = header(&mut checkpoint, b'@')?;
+
+        let mut sequence = None;
+        loop {
+            match checkpoint.first() {
+                Some(b'+') => break,
+                Some(_) => append(&mut sequence, line(&mut checkpoint)),
+                None => return Err(ParseError::UnexpectedEndOfInput),
+            }
+        }
+        let sequence = sequence.unwrap_or_default();
+        line(&mut checkpoint);
+
+        // a quality line may start with `@`, the qualities are read up to the
+        // size of the sequence
+        let quality_start = checkpoint.current_position();
+        let mut quality: Option<Cow<[u8]>> = None;
+        while quality.as_ref().map_or(0, |quality| quality.len()) < sequence.len() {
+            if checkpoint.is_empty() {
+                return Err(ParseError::UnexpectedEndOfInput);
+            }
+            append(&mut quality, line(&mut checkpoint));
+        }
+        let quality = quality.unwrap_or_default();
+        if quality.len() != sequence.len() {
+            return Err(
+                ParseError::unexpected_token(quality_start).expecting("as many qualities as bases")
+            );
+        }
+        checkpoint.commit();
+        Ok(FastqRecord {
+            id,
+            description,
+            sequence,
+            quality,
+        })
+    }
+}
+
+/// An iterator over the records of a FASTA or FASTQ file
+///
+/// The blank lines between the records are skipped, the iteration stops
+/// after the first error.
+pub struct Records<'a, R> {
+    scanner: Scanner<'a, u8>,
+    failed: bool,
+    _record: PhantomData<R>,
+}
+
+impl<'a, R: Visitor<'a, u8>> Iterator for Records<'a, R> {
+    type Item = ParseResult<R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        skip_blank_lines(&mut self.scanner);
+        if self.failed || self.scanner.is_empty() {
+            return None;
+        }
+        let record = self.scanner.visit::<R>();
+        self.failed = record.is_err();
+        Some(record)
+    }
+}
+
+impl<'a, R> Records<'a, R> {
+    /// Create a new `Records` iterator
+    fn new(data: &'a [u8]) -> Self {
+        Records {
+            scanner: Scanner::new(data),
+            failed: false,
+            _record: PhantomData,
+        }
+    }
+}
+
+/// Iterate over the records of a FASTA file
+///
+/// # Arguments
+///
+/// * `data` - The FASTA file
+///
+/// # Returns
+///
+/// An iterator of the records of the file.
+pub fn records(data: &[u8]) -> Records<'_, FastaRecord<'_>> {
+    Records::new(data)
+}
+
+/// Iterate over the records of a FASTQ file
+///
+/// # Arguments
+///
+/// * `data` - The FASTQ file
+///
+/// # Returns
+///
+/// An iterator of the records of the file.
+pub fn fastq_records(data: &[u8]) -> Records<'_, FastqRecord<'_>> {
+    Records::new(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::fasta::{fastq_records, records, FastaRecord};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_fasta_record() {
+        let record: FastaRecord =
+            Scanner::new(b">sp|P1|X \t a protein \r\nMKV\r\n; comment\r\nLLA*\r\n")
+                .visit()
+                .expect("failed to parse");
+        assert_eq!(record.id, "sp|P1|X");
+        assert_eq!(record.description, Some("a protein"));
+        assert_eq!(record.sequence.as_ref(), b"MKVLLA*");
+
+        let record: FastaRecord = Scanner::new(b">id\nACGT\n>next")
+            .visit()
+            .expect("failed to parse");
+        assert!(matches!(record.sequence, Cow::Borrowed(b"ACGT")));
+        assert_eq!(record.description, None);
+
+        assert!(matches!(
+            Scanner::new(b"ACGT").visit::<FastaRecord>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert!(matches!(
+            Scanner::new(b"> desc\nACGT").visit::<FastaRecord>(),
+            Err(ParseError::UnexpectedToken { offset: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_fasta_records() {
+        let data = b"\n>a\nAC\n\n>b\n>c\nGT";
+        let records: Vec<_> = records(data)
+            .map(|record| record.map(|record| (record.id, record.sequence)))
+            .collect::<Result<_, _>>()
+            .expect("failed to parse");
+        assert_eq!(
+            records,
+            [
+                ("a", Cow::Borrowed(&b"AC"[..])),
+                ("b", Cow::Borrowed(&b""[..])),
+                ("c", Cow::Borrowed(&b"GT"[..]))
+            ]
+        );
+
+        // the iteration stops after the first error
+        let mut records = super::records(b">a\nAC\n>\nGT\n>b\nGT");
+        assert!(records.next().is_some_and(|record| record.is_ok()));
+        assert!(records.next().is_some_and(|record| record.is_err()));
+        assert_eq!(records.count(), 0);
+        let mut records = super::records(b"junk\n>a\nAC");
+        assert!(records.next().is_some_and(|record| record.is_err()));
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn test_fastq_records() {
+        let data = b"@r1 run=1\nACGT\n+\n@@II\n@r2\nAC\nGT\n+r2\nII\n#!\n";
+        let records = fastq_records(data)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to parse");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "r1");
+        assert_eq!(records[0].description, Some("run=1"));
+        assert_eq!(records[0].quality.as_ref(), b"@@II");
+        assert_eq!(
+            records[0].phred_scores().collect::<Vec<_>>(),
+            [31, 31, 40, 40]
+        );
+        assert_eq!(records[1].sequence.as_ref(), b"ACGT");
+        assert_eq!(records[1].quality.as_ref(), b"II#!");
+
+        let mut records = fastq_records(b"@r1\nACGT\n+\nII!\n@r2");
+        assert!(matches!(
+            records.next(),
+            Some(Err(ParseError::UnexpectedToken { offset: 11, .. }))
+        ));
+        let mut records = fastq_records(b"@r1\nACGT\n+\nII");
+        assert!(matches!(
+            records.next(),
+            Some(Err(ParseError::UnexpectedEndOfInput))
+        ));
+        let mut records = fastq_records(b"@r1\nACGT\n");
+        assert!(matches!(
+            records.next(),
+            Some(Err(ParseError::UnexpectedEndOfInput))
+        ));
+    }
+}
@@ -0,0 +1,733 @@
+//! Query filters.
+//!
+//! A filter compares fields to values, and combines the comparisons with
+//! boolean operators:
+//!
+//! * `field == value`, with the operators of [BinaryOperator]
+//! * `a AND b`, also written `a && b`
+//! * `a OR b`, also written `a || b`
+//! * `NOT a`, also written `!a`
+//! * `(a)`, to group a filter
+//!
+//! `NOT` binds tighter than `AND`, which binds tighter than `OR`. The
+//! keywords are case-insensitive. A field is a dotted path of identifiers, a
+//! value is a number, a double-quoted string, `true`, `false` or `null`.
+//!
+//! [SqlFilter] also accepts the SQL syntax of a `WHERE` clause: quoted
+//! identifiers, single-quoted strings, `IN` and `BETWEEN`.
+//!
+//! ```
+//! use elyze::bytes::primitives::binary_operator::BinaryOperator;
+//! use elyze::formats::filter::{Comparison, Filter, Value};
+//!
+//! let filter = Filter::parse(br#"status == "open" or priority >= 2 and not user.banned == true"#)
+//!     .expect("failed to parse");
+//! let Filter::Or(left, right) = filter else {
+//!     panic!("`or` has the lowest precedence");
+//! };
+//! assert_eq!(
+//!     *left,
+//!     Filter::Comparison(Comparison {
+//!         field: "status".into(),
+//!         operator: BinaryOperator::Equal,
+//!         value: Value::String("open".into()),
+//!     })
+//! );
+//! assert!(matches!(*right, Filter::And(..)));
+//! ```
+
+use crate::bytes::primitives::binary_operator::BinaryOperator;
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::formats::logline::Quoted;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// Consume the whitespaces separating the terms
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    scanner.take_while(u8::is_ascii_whitespace);
+}
+
+/// Whether the byte may be part of an identifier
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Consume one of the symbols, or the keyword if it isn't the start of a
+/// longer identifier
+fn operator(scanner: &mut Scanner<u8>, keyword: &[u8], symbols: &[&[u8]]) -> bool {
+    let remaining = scanner.remaining();
+    if let Some(symbol) = symbols.iter().find(|symbol| remaining.starts_with(symbol)) {
+        scanner.bump_by(symbol.len());
+        return true;
+    }
+    let is_keyword = remaining.len() >= keyword.len()
+        && remaining[..keyword.len()].eq_ignore_ascii_case(keyword)
+        && !remaining
+            .get(keyword.len())
+            .is_some_and(|byte| is_identifier_byte(*byte));
+    if is_keyword {
+        scanner.bump_by(keyword.len());
+    }
+    is_keyword
+}
+
+/// The syntax of a filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    /// The operators of [BinaryOperator], double-quoted strings
+    Default,
+    /// Also `=` and `<>`, quoted identifiers, single-quoted strings, `IN`
+    /// and `BETWEEN`
+    Sql,
+}
+
+/// Accept a string between `quote`, a doubled `quote` standing for itself
+fn doubled_quoted<'a>(scanner: &mut Scanner<'a, u8>, quote: u8) -> ParseResult<Cow<'a, str>> {
+    let mut checkpoint = scanner.checkpoint();
+    expect(&mut checkpoint, quote)?;
+    let remaining = checkpoint.remaining();
+    let mut position = 0;
+    let mut escaped = false;
+    loop {
+        match remaining.get(position) {
+            Some(byte) if *byte == quote && remaining.get(position + 1) == Some(&quote) => {
+                escaped = true;
+                position += 2;
+            }
+            Some(byte) if *byte == quote => break,
+            Some(_) => position += 1,
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        }
+    }
+    let content = std::str::from_utf8(&remaining[..position])?;
+    let value = if escaped {
+        let quote = (quote as char).to_string();
+        Cow::Owned(content.replace(&quote.repeat(2), &quote))
+    } else {
+        Cow::Borrowed(content)
+    };
+    checkpoint.bump_by(position + 1);
+    checkpoint.commit();
+    Ok(value)
+}
+
+/// An identifier quoted the SQL way, `"column"` or `` `column` ``, a doubled
+/// quote standing for itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuotedIdentifier<'a>(pub Cow<'a, str>);
+
+impl<'a> Visitor<'a, u8> for QuotedIdentifier<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        match scanner.first() {
+            Some(quote @ (b'"' | b'`')) => Ok(QuotedIdentifier(doubled_quoted(scanner, *quote)?)),
+            Some(_) => Err(ParseError::unexpected_token(scanner.current_position())
+                .expecting("a quoted identifier")),
+            None => Err(ParseError::UnexpectedEndOfInput),
+        }
+    }
+}
+
+/// A string quoted the SQL way, `'it''s'`, a doubled quote standing for
+/// itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SqlString<'a>(pub Cow<'a, str>);
+
+impl<'a> Visitor<'a, u8> for SqlString<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        Ok(SqlString(doubled_quoted(scanner, b'\'')?))
+    }
+}
+
+/// A field, a dotted path of identifiers `user.name`.
+///
+/// In the SQL syntax, each identifier may be a [QuotedIdentifier], the path
+/// is then made of the unquoted identifiers joined by `.`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Field<'a>(pub Cow<'a, str>);
+
+/// Accept a field
+fn field<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Cow<'a, str>> {
+    let mut checkpoint = scanner.checkpoint();
+    let start = checkpoint.current_position();
+    let mut segments = vec![];
+    let mut quoted = false;
+    loop {
+        if checkpoint.is_empty() {
+            return Err(ParseError::UnexpectedEndOfInput);
+        }
+        let segment = checkpoint.current_position();
+        if syntax == Syntax::Sql && matches!(checkpoint.first(), Some(b'"' | b'`')) {
+            segments.push(checkpoint.visit::<QuotedIdentifier>()?.0);
+            quoted = true;
+        } else {
+            let identifier =
+                std::str::from_utf8(checkpoint.take_while(|byte| is_identifier_byte(*byte)))?;
+            if identifier.is_empty() || identifier.as_bytes()[0].is_ascii_digit() {
+                return Err(ParseError::unexpected_token(segment).expecting("a field"));
+            }
+            segments.push(Cow::Borrowed(identifier));
+        }
+        if checkpoint.first() != Some(&b'.') {
+            break;
+        }
+        checkpoint.bump_by(1);
+    }
+    let field = if quoted {
+        Cow::Owned(segments.join("."))
+    } else {
+        // the identifiers are made of ASCII bytes only
+        Cow::Borrowed(std::str::from_utf8(checkpoint.since(start))?)
+    };
+    checkpoint.commit();
+    Ok(field)
+}
+
+impl<'a> Visitor<'a, u8> for Field<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        Ok(Field(field(scanner, Syntax::Default)?))
+    }
+}
+
+/// A value compared to a field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    /// An integer `42`
+    Integer(i64),
+    /// A number with a fraction or an exponent `4.2e1`
+    Float(f64),
+    /// A string, with its escapes decoded
+    String(Cow<'a, str>),
+    /// `true` or `false`
+    Boolean(bool),
+    /// `null`
+    Null,
+}
+
+/// Accept a value
+fn value<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Value<'a>> {
+    let start = scanner.current_position();
+    match scanner.first() {
+        Some(b'"') if syntax == Syntax::Default => {
+            return Ok(Value::String(scanner.visit::<Quoted>()?.0));
+        }
+        Some(b'\'') if syntax == Syntax::Sql => {
+            return Ok(Value::String(scanner.visit::<SqlString>()?.0));
+        }
+        Some(byte) if *byte == b'-' || byte.is_ascii_digit() => {}
+        Some(_) => {
+            for (keyword, value) in [
+                (&b"true"[..], Value::Boolean(true)),
+                (b"false", Value::Boolean(false)),
+                (b"null", Value::Null),
+            ] {
+                if operator(scanner, keyword, &[]) {
+                    return Ok(value);
+                }
+            }
+            return Err(ParseError::unexpected_token(start).expecting("a value"));
+        }
+        None => return Err(ParseError::UnexpectedEndOfInput),
+    }
+
+    let mut checkpoint = scanner.checkpoint();
+    if checkpoint.first() == Some(&b'-') {
+        checkpoint.bump_by(1);
+    }
+    let mut is_float = false;
+    let integer = std::str::from_utf8(checkpoint.take_while(u8::is_ascii_digit))?;
+    if integer.is_empty() {
+        return Err(ParseError::unexpected_token(start).expecting("a number"));
+    }
+    if checkpoint.first() == Some(&b'.') {
+        checkpoint.bump_by(1);
+        is_float = true;
+        let position = checkpoint.current_position();
+        if std::str::from_utf8(checkpoint.take_while(u8::is_ascii_digit))?.is_empty() {
+            return Err(ParseError::unexpected_token(position).expecting("a digit"));
+        }
+    }
+    if let Some(b'e' | b'E') = checkpoint.first() {
+        checkpoint.bump_by(1);
+        is_float = true;
+        if let Some(b'+' | b'-') = checkpoint.first() {
+            checkpoint.bump_by(1);
+        }
+        let position = checkpoint.current_position();
+        if std::str::from_utf8(checkpoint.take_while(u8::is_ascii_digit))?.is_empty() {
+            return Err(ParseError::unexpected_token(position).expecting("a digit"));
+        }
+    }
+    // the number is made of ASCII bytes only
+    let number = std::str::from_utf8(checkpoint.since(start))?;
+    let value = if is_float {
+        Value::Float(number.parse()?)
+    } else {
+        Value::Integer(number.parse()?)
+    };
+    checkpoint.commit();
+    Ok(value)
+}
+
+impl<'a> Visitor<'a, u8> for Value<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        value(scanner, Syntax::Default)
+    }
+}
+
+/// A comparison of a field to a value `field >= value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<'a> {
+    /// The compared field
+    pub field: Cow<'a, str>,
+    /// The operator
+    pub operator: BinaryOperator,
+    /// The value the field is compared to
+    pub value: Value<'a>,
+}
+
+/// Accept a comparison
+fn comparison<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Comparison<'a>> {
+    let mut checkpoint = scanner.checkpoint();
+    let field = field(&mut checkpoint, syntax)?;
+    skip_whitespaces(&mut checkpoint);
+    let remaining = checkpoint.remaining();
+    let operator = if syntax == Syntax::Sql && remaining.starts_with(b"<>") {
+        checkpoint.bump_by(2);
+        BinaryOperator::NotEqual
+    } else if syntax == Syntax::Sql && remaining.starts_with(b"=") && !remaining.starts_with(b"==")
+    {
+        checkpoint.bump_by(1);
+        BinaryOperator::Equal
+    } else {
+        checkpoint.visit()?
+    };
+    skip_whitespaces(&mut checkpoint);
+    let value = value(&mut checkpoint, syntax)?;
+    checkpoint.commit();
+    Ok(Comparison {
+        field,
+        operator,
+        value,
+    })
+}
+
+impl<'a> Visitor<'a, u8> for Comparison<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        comparison(scanner, Syntax::Default)
+    }
+}
+
+/// A boolean expression of comparisons.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter<'a> {
+    /// A comparison
+    Comparison(Comparison<'a>),
+    /// The field is one of the values `field IN (1, 2)`, in the SQL syntax
+    In {
+        /// The compared field
+        field: Cow<'a, str>,
+        /// The values, at least one
+        values: Vec<Value<'a>>,
+    },
+    /// The field is in the inclusive range `field BETWEEN 1 AND 2`, in the
+    /// SQL syntax
+    Between {
+        /// The compared field
+        field: Cow<'a, str>,
+        /// The lower bound
+        low: Value<'a>,
+        /// The upper bound
+        high: Value<'a>,
+    },
+    /// The negation of a filter
+    Not(Box<Filter<'a>>),
+    /// Both filters hold
+    And(Box<Filter<'a>>, Box<Filter<'a>>),
+    /// Either filter holds
+    Or(Box<Filter<'a>>, Box<Filter<'a>>),
+}
+
+impl<'a> Filter<'a> {
+    /// Parse a whole filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The filter
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` at the first invalid term, or at the
+    /// first term remaining after the filter.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        parse(data, Syntax::Default)
+    }
+
+    /// Parse a whole filter in the SQL syntax, see [SqlFilter].
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The filter
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` at the first invalid term, or at the
+    /// first term remaining after the filter.
+    pub fn parse_sql(data: &'a [u8]) -> ParseResult<Self> {
+        parse(data, Syntax::Sql)
+    }
+}
+
+/// Parse a whole filter in the syntax
+fn parse(data: &[u8], syntax: Syntax) -> ParseResult<Filter<'_>> {
+    let mut scanner = Scanner::new(data);
+    skip_whitespaces(&mut scanner);
+    let filter = disjunction(&mut scanner, syntax)?;
+    skip_whitespaces(&mut scanner);
+    if !scanner.is_empty() {
+        return Err(ParseError::unexpected_token(scanner.current_position()));
+    }
+    Ok(filter)
+}
+
+/// Accept the filters joined by `OR`
+fn disjunction<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Filter<'a>> {
+    let mut filter = conjunction(scanner, syntax)?;
+    loop {
+        let mut checkpoint = scanner.checkpoint();
+        skip_whitespaces(&mut checkpoint);
+        if !operator(&mut checkpoint, b"or", &[b"||"]) {
+            return Ok(filter);
+        }
+        skip_whitespaces(&mut checkpoint);
+        let right = conjunction(&mut checkpoint, syntax)?;
+        checkpoint.commit();
+        filter = Filter::Or(Box::new(filter), Box::new(right));
+    }
+}
+
+/// Accept the filters joined by `AND`
+fn conjunction<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Filter<'a>> {
+    let mut filter = negation(scanner, syntax)?;
+    loop {
+        let mut checkpoint = scanner.checkpoint();
+        skip_whitespaces(&mut checkpoint);
+        if !operator(&mut checkpoint, b"and", &[b"&&"]) {
+            return Ok(filter);
+        }
+        skip_whitespaces(&mut checkpoint);
+        let right = negation(&mut checkpoint, syntax)?;
+        checkpoint.commit();
+        filter = Filter::And(Box::new(filter), Box::new(right));
+    }
+}
+
+/// Accept a filter, negated by `NOT`
+fn negation<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Filter<'a>> {
+    let mut checkpoint = scanner.checkpoint();
+    // `!` followed by `=` is the start of a comparison operator
+    let is_bang = checkpoint.first() == Some(&b'!') && checkpoint.get(1) != Some(&b'=');
+    if is_bang || operator(&mut checkpoint, b"not", &[]) {
+        if is_bang {
+            checkpoint.bump_by(1);
+        }
+        skip_whitespaces(&mut checkpoint);
+        let filter = negation(&mut checkpoint, syntax)?;
+        checkpoint.commit();
+        return Ok(Filter::Not(Box::new(filter)));
+    }
+    drop(checkpoint);
+    primary(scanner, syntax)
+}
+
+/// Accept a predicate or a filter between parentheses
+fn primary<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Filter<'a>> {
+    if scanner.first() != Some(&b'(') {
+        return predicate(scanner, syntax);
+    }
+    let mut checkpoint = scanner.checkpoint();
+    checkpoint.bump_by(1);
+    skip_whitespaces(&mut checkpoint);
+    let filter = disjunction(&mut checkpoint, syntax)?;
+    skip_whitespaces(&mut checkpoint);
+    expect(&mut checkpoint, b')')?;
+    checkpoint.commit();
+    Ok(filter)
+}
+
+/// Accept a comparison, or in the SQL syntax a `[NOT] IN` or a
+/// `[NOT] BETWEEN`
+fn predicate<'a>(scanner: &mut Scanner<'a, u8>, syntax: Syntax) -> ParseResult<Filter<'a>> {
+    if syntax == Syntax::Default {
+        return Ok(Filter::Comparison(comparison(scanner, syntax)?));
+    }
+
+    let mut checkpoint = scanner.checkpoint();
+    let field = field(&mut checkpoint, syntax)?;
+    skip_whitespaces(&mut checkpoint);
+    let position = checkpoint.current_position();
+    let negated = operator(&mut checkpoint, b"not", &[]);
+    skip_whitespaces(&mut checkpoint);
+    let filter = if operator(&mut checkpoint, b"in", &[]) {
+        skip_whitespaces(&mut checkpoint);
+        expect(&mut checkpoint, b'(')?;
+        let mut values = vec![];
+        loop {
+            skip_whitespaces(&mut checkpoint);
+            values.push(value(&mut checkpoint, syntax)?);
+            skip_whitespaces(&mut checkpoint);
+            if checkpoint.first() != Some(&b',') {
+                break;
+            }
+            checkpoint.bump_by(1);
+        }
+        expect(&mut checkpoint, b')')?;
+        Filter::In { field, values }
+    } else if operator(&mut checkpoint, b"between", &[]) {
+        skip_whitespaces(&mut checkpoint);
+        let low = value(&mut checkpoint, syntax)?;
+        skip_whitespaces(&mut checkpoint);
+        let and = checkpoint.current_position();
+        if !operator(&mut checkpoint, b"and", &[]) {
+            return Err(ParseError::unexpected_token(and).expecting("`AND`"));
+        }
+        skip_whitespaces(&mut checkpoint);
+        let high = value(&mut checkpoint, syntax)?;
+        Filter::Between { field, low, high }
+    } else if negated {
+        return Err(ParseError::unexpected_token(position).expecting("`IN` or `BETWEEN`"));
+    } else {
+        drop(checkpoint);
+        return Ok(Filter::Comparison(comparison(scanner, syntax)?));
+    };
+    checkpoint.commit();
+    Ok(match negated {
+        true => Filter::Not(Box::new(filter)),
+        false => filter,
+    })
+}
+
+impl<'a> Visitor<'a, u8> for Filter<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        disjunction(scanner, Syntax::Default)
+    }
+}
+
+/// A filter in the SQL syntax of a `WHERE` clause.
+///
+/// On top of the default syntax:
+///
+/// * `=` and `<>` compare as `==` and `!=`
+/// * a field may be made of [QuotedIdentifier]
+/// * a string is a [SqlString] rather than a double-quoted string
+/// * `field [NOT] IN (value, ...)`
+/// * `field [NOT] BETWEEN low AND high`
+///
+/// ```
+/// use elyze::formats::filter::{Filter, SqlFilter, Value};
+/// use elyze::scanner::Scanner;
+///
+/// let data = br#""order"."status" in ('open', 'it''s late') and price not between 10 and 20"#;
+/// let SqlFilter(filter) = Scanner::new(data).visit().expect("failed to parse");
+/// let Filter::And(status, price) = filter else {
+///     panic!("expected a conjunction");
+/// };
+/// assert_eq!(
+///     *status,
+///     Filter::In {
+///         field: "order.status".into(),
+///         values: vec![Value::String("open".into()), Value::String("it's late".into())],
+///     }
+/// );
+/// assert!(matches!(*price, Filter::Not(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlFilter<'a>(pub Filter<'a>);
+
+impl<'a> Visitor<'a, u8> for SqlFilter<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        Ok(SqlFilter(disjunction(scanner, Syntax::Sql)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::binary_operator::BinaryOperator;
+    use crate::errors::ParseError;
+    use crate::formats::filter::{Comparison, Field, Filter, QuotedIdentifier, SqlString, Value};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    fn comparison<'a>(field: &'a str, operator: BinaryOperator, value: Value<'a>) -> Filter<'a> {
+        Filter::Comparison(Comparison {
+            field: field.into(),
+            operator,
+            value,
+        })
+    }
+
+    #[test]
+    fn test_value() {
+        let parse = |data| {
+            Scanner::new(data)
+                .visit::<Value>()
+                .expect("failed to parse")
+        };
+        assert_eq!(parse(b"42"), Value::Integer(42));
+        assert_eq!(parse(b"-7"), Value::Integer(-7));
+        assert_eq!(parse(b"2.5e-1"), Value::Float(0.25));
+        assert_eq!(parse(br#""a\"b""#), Value::String("a\"b".into()));
+        assert_eq!(parse(b"TRUE"), Value::Boolean(true));
+        assert_eq!(parse(b"null"), Value::Null);
+
+        assert!(Scanner::new(b"nullable").visit::<Value>().is_err());
+        assert!(Scanner::new(b"1.").visit::<Value>().is_err());
+        assert!(matches!(
+            Scanner::new(b"99999999999999999999").visit::<Value>(),
+            Err(ParseError::ParseIntError(_))
+        ));
+    }
+
+    #[test]
+    fn test_field() {
+        let field: Field = Scanner::new(b"user.first_name ==")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(field, Field("user.first_name".into()));
+        assert!(Scanner::new(b"user.").visit::<Field>().is_err());
+        assert!(Scanner::new(b"1user").visit::<Field>().is_err());
+    }
+
+    #[test]
+    fn test_precedence() {
+        let a = || comparison("a", BinaryOperator::Equal, Value::Integer(1));
+        let b = || comparison("b", BinaryOperator::LessThanOrEqual, Value::Integer(2));
+        let c = || comparison("c", BinaryOperator::NotEqual, Value::Null);
+
+        assert_eq!(
+            Filter::parse(b"a == 1 || b <= 2 && c != null").expect("failed to parse"),
+            Filter::Or(
+                Box::new(a()),
+                Box::new(Filter::And(Box::new(b()), Box::new(c())))
+            )
+        );
+        assert_eq!(
+            Filter::parse(b" ( a==1 OR b<=2 ) and !c != null ").expect("failed to parse"),
+            Filter::And(
+                Box::new(Filter::Or(Box::new(a()), Box::new(b()))),
+                Box::new(Filter::Not(Box::new(c())))
+            )
+        );
+        assert_eq!(
+            Filter::parse(b"not not a == 1").expect("failed to parse"),
+            Filter::Not(Box::new(Filter::Not(Box::new(a()))))
+        );
+        // a field named like a keyword
+        assert_eq!(
+            Filter::parse(b"order == 1").expect("failed to parse"),
+            comparison("order", BinaryOperator::Equal, Value::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_invalid_filter() {
+        assert!(matches!(
+            Filter::parse(b"(a == 1"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            Filter::parse(b"a == 1 and"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            Filter::parse(b"a == 1 b == 2"),
+            Err(ParseError::UnexpectedToken { offset: 7, .. })
+        ));
+        assert!(Filter::parse(b"a = 1").is_err());
+    }
+
+    #[test]
+    fn test_sql_components() {
+        let identifier: QuotedIdentifier = Scanner::new(br#""say ""hi""""#)
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(identifier.0, r#"say "hi""#);
+        let identifier: QuotedIdentifier =
+            Scanner::new(b"`a``b`").visit().expect("failed to parse");
+        assert_eq!(identifier.0, "a`b");
+        assert!(Scanner::new(b"'a'").visit::<QuotedIdentifier>().is_err());
+
+        let string: SqlString = Scanner::new(b"'plain' rest")
+            .visit()
+            .expect("failed to parse");
+        assert!(matches!(string.0, Cow::Borrowed("plain")));
+        let string: SqlString = Scanner::new(b"''''").visit().expect("failed to parse");
+        assert_eq!(string.0, "'");
+        assert!(matches!(
+            Scanner::new(b"'open").visit::<SqlString>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_sql_filter() {
+        assert_eq!(
+            Filter::parse_sql(b"`user`.name = 'bob' and age <> 3").expect("failed to parse"),
+            Filter::And(
+                Box::new(comparison(
+                    "user.name",
+                    BinaryOperator::Equal,
+                    Value::String("bob".into())
+                )),
+                Box::new(comparison(
+                    "age",
+                    BinaryOperator::NotEqual,
+                    Value::Integer(3)
+                ))
+            )
+        );
+        assert_eq!(
+            Filter::parse_sql(b"id NOT IN (1,2 , 3) OR x between -1 and 1.5")
+                .expect("failed to parse"),
+            Filter::Or(
+                Box::new(Filter::Not(Box::new(Filter::In {
+                    field: "id".into(),
+                    values: vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)],
+                }))),
+                Box::new(Filter::Between {
+                    field: "x".into(),
+                    low: Value::Integer(-1),
+                    high: Value::Float(1.5),
+                })
+            )
+        );
+        // a field named like a keyword
+        assert_eq!(
+            Filter::parse_sql(b"inside >= 2").expect("failed to parse"),
+            comparison(
+                "inside",
+                BinaryOperator::GreaterThanOrEqual,
+                Value::Integer(2)
+            )
+        );
+
+        // the SQL components are only available in the SQL syntax
+        assert!(Filter::parse(b"id in (1)").is_err());
+        assert!(Filter::parse(b"name == 'bob'").is_err());
+        assert!(Filter::parse_sql(br#"name = "bob""#).is_err());
+
+        assert!(matches!(
+            Filter::parse_sql(b"id not = 1"),
+            Err(ParseError::UnexpectedToken { offset: 3, .. })
+        ));
+        assert!(matches!(
+            Filter::parse_sql(b"x between 1 or 2"),
+            Err(ParseError::UnexpectedToken { offset: 12, .. })
+        ));
+        assert!(matches!(
+            Filter::parse_sql(b"id in ()"),
+            Err(ParseError::UnexpectedToken { offset: 7, .. })
+        ));
+    }
+}
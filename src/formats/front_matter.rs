@@ -0,0 +1,146 @@
+//! Front matter of documents.
+//!
+//! Static site generators read metadata from a block at the very start of a
+//! document, between two `---` lines. The block is extracted as written, to
+//! be handed to a YAML or INI visitor, and the scanner is left at the body.
+//!
+//! ```
+//! use elyze::formats::front_matter::FrontMatter;
+//!
+//! let (front_matter, body) =
+//!     FrontMatter::extract(b"---\ntitle: Hello\n---\n# Hello\n").expect("failed to parse");
+//! assert_eq!(front_matter, Some(FrontMatter("title: Hello\n")));
+//! assert_eq!(body.remaining(), b"# Hello\n");
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// The byte order mark of UTF-8 documents
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// The size of the line, with its line ending
+fn line_size(data: &[u8]) -> usize {
+    data.iter()
+        .position(|byte| *byte == b'\n')
+        .map_or(data.len(), |position| position + 1)
+}
+
+/// Whether the line, with its line ending, is the fence, with trailing
+/// spaces allowed
+fn is_fence(line: &[u8], fence: &[u8]) -> bool {
+    line.strip_prefix(fence).is_some_and(|rest| {
+        rest.iter()
+            .all(|byte| matches!(byte, b' ' | b'\t' | b'\r' | b'\n'))
+    })
+}
+
+/// A front matter, the lines between an opening `---` line and a closing
+/// `---` or `...` line, with their line endings.
+///
+/// The opening line must be the first line of the document, after an
+/// optional byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrontMatter<'a>(pub &'a str);
+
+impl<'a> FrontMatter<'a> {
+    /// Extract the front matter of a document, if it has one.
+    ///
+    /// A document starting with a `---` line which is never closed has no
+    /// front matter, the line being a thematic break.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The document
+    ///
+    /// # Returns
+    ///
+    /// The front matter, and a scanner positioned at the body of the
+    /// document.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::Utf8Error` if the front matter is not valid UTF-8.
+    pub fn extract(document: &'a [u8]) -> ParseResult<(Option<Self>, Scanner<'a, u8>)> {
+        let mut scanner = Scanner::new(document);
+        let front_matter = match scanner.visit::<FrontMatter>() {
+            Ok(front_matter) => Some(front_matter),
+            Err(ParseError::UnexpectedToken { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        Ok((front_matter, scanner))
+    }
+}
+
+impl<'a> Visitor<'a, u8> for FrontMatter<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let remaining = scanner.remaining();
+        let bom = if remaining.starts_with(BOM) {
+            BOM.len()
+        } else {
+            0
+        };
+        let opening = line_size(&remaining[bom..]);
+        let opening_line = &remaining[bom..bom + opening];
+        if !is_fence(opening_line, b"---") || !opening_line.ends_with(b"\n") {
+            return Err(ParseError::unexpected_token(start).expecting("`---`"));
+        }
+
+        let content_start = bom + opening;
+        let mut position = content_start;
+        while position < remaining.len() {
+            let size = line_size(&remaining[position..]);
+            let line = &remaining[position..position + size];
+            if is_fence(line, b"---") || is_fence(line, b"...") {
+                let front_matter = std::str::from_utf8(&remaining[content_start..position])?;
+                scanner.bump_by(position + size);
+                return Ok(FrontMatter(front_matter));
+            }
+            position += size;
+        }
+        Err(ParseError::unexpected_token(start).expecting("a closed front matter"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formats::front_matter::FrontMatter;
+
+    #[test]
+    fn test_extract() {
+        let (front_matter, body) =
+            FrontMatter::extract(b"\xEF\xBB\xBF---  \r\na = 1\r\nb = 2\r\n...\r\nbody")
+                .expect("failed to parse");
+        assert_eq!(front_matter, Some(FrontMatter("a = 1\r\nb = 2\r\n")));
+        assert_eq!(body.remaining(), b"body");
+
+        // an empty front matter closing the document
+        let (front_matter, body) = FrontMatter::extract(b"---\n---").expect("failed to parse");
+        assert_eq!(front_matter, Some(FrontMatter("")));
+        assert!(body.is_empty());
+    }
+
+    #[test]
+    fn test_no_front_matter() {
+        for document in [
+            &b"# Title\n---\n"[..],
+            b"",
+            b"---",
+            b"----\nx\n---\n",
+            b" ---\nx\n---\n",
+            // a thematic break
+            b"---\nsome text\n",
+        ] {
+            let (front_matter, body) = FrontMatter::extract(document).expect("failed to parse");
+            assert_eq!(front_matter, None);
+            assert_eq!(body.remaining(), document);
+        }
+    }
+
+    #[test]
+    fn test_invalid_front_matter() {
+        assert!(FrontMatter::extract(b"---\n\xFF\n---\n").is_err());
+    }
+}
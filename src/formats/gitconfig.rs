@@ -0,0 +1,422 @@
+//! Git configuration files.
+//!
+//! The format looks like INI, with a few differences:
+//!
+//! * a section may have a quoted subsection `[remote "origin"]`, case
+//!   sensitive, while the section and variable names are not
+//! * a variable without `=` is a boolean set to `true`
+//! * a value may be partly quoted, keeping its whitespaces and comment
+//!   characters, and may contain the escapes `\\`, `\"`, `\n`, `\t` and `\b`
+//! * a value may be continued on the next line by ending the line with `\`
+//! * comments start with `#` or `;`
+//!
+//! ```
+//! use elyze::formats::gitconfig::GitConfig;
+//!
+//! let config = GitConfig::parse(b"[core]\n\tbare = false # comment\n[remote \"origin\"]\n\turl = https://example.com/repo.git\n")
+//!     .expect("failed to parse");
+//! assert_eq!(config.get("core.bare"), Some("false"));
+//! assert_eq!(config.get("remote.origin.url"), Some("https://example.com/repo.git"));
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// Consume the spaces and tabs
+fn skip_blanks(scanner: &mut Scanner<u8>) {
+    scanner.take_while(|byte| *byte == b' ' || *byte == b'\t');
+}
+
+/// Consume the rest of the line, its line ending included
+fn skip_line(scanner: &mut Scanner<u8>) {
+    scanner.take_till(|byte| *byte == b'\n');
+    if !scanner.is_empty() {
+        scanner.bump_by(1);
+    }
+}
+
+/// Consume the end of a line, after optional blanks and a comment
+fn end_of_line(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+    skip_blanks(scanner);
+    match scanner.first() {
+        None | Some(b'\n' | b'#' | b';') => {
+            skip_line(scanner);
+            Ok(())
+        }
+        Some(b'\r') if scanner.get(1).is_none_or(|byte| *byte == b'\n') => {
+            skip_line(scanner);
+            Ok(())
+        }
+        Some(_) => Err(ParseError::unexpected_token(scanner.current_position())
+            .expecting("the end of the line")),
+    }
+}
+
+/// Accept a name made of the bytes satisfying the predicate, starting with
+/// a letter
+fn name<'a>(
+    scanner: &mut Scanner<'a, u8>,
+    predicate: impl Fn(u8) -> bool,
+    expecting: &str,
+) -> ParseResult<&'a str> {
+    let start = scanner.current_position();
+    if !scanner.first().is_some_and(u8::is_ascii_alphabetic) {
+        return Err(ParseError::unexpected_token(start).expecting(expecting));
+    }
+    let name = scanner.take_while(|byte| predicate(*byte));
+    // the names are made of ASCII bytes only
+    Ok(std::str::from_utf8(name)?)
+}
+
+/// The header of a section `[name]` or `[name "subsection"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SectionHeader<'a> {
+    /// The name of the section, case-insensitive. The legacy subsections
+    /// `[name.subsection]` are kept in the name.
+    pub name: &'a str,
+    /// The subsection, with its escapes decoded
+    pub subsection: Option<Cow<'a, str>>,
+}
+
+impl<'a> Visitor<'a, u8> for SectionHeader<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        expect(&mut checkpoint, b'[')?;
+        skip_blanks(&mut checkpoint);
+        let name = name(
+            &mut checkpoint,
+            |byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.'),
+            "a section name",
+        )?;
+        skip_blanks(&mut checkpoint);
+        let subsection = match checkpoint.first() {
+            Some(b'"') => {
+                checkpoint.bump_by(1);
+                let subsection = subsection(&mut checkpoint)?;
+                skip_blanks(&mut checkpoint);
+                Some(subsection)
+            }
+            _ => None,
+        };
+        expect(&mut checkpoint, b']')?;
+        checkpoint.commit();
+        Ok(SectionHeader { name, subsection })
+    }
+}
+
+/// Accept a subsection after its opening quote, up to its closing quote
+fn subsection<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Cow<'a, str>> {
+    let start = scanner.current_position();
+    let mut decoded: Option<Vec<u8>> = None;
+    loop {
+        let position = scanner.current_position();
+        match scanner.first() {
+            None => return Err(ParseError::UnexpectedEndOfInput),
+            Some(b'\n') => {
+                return Err(ParseError::unexpected_token(position).expecting("`\"`"));
+            }
+            Some(b'"') => break,
+            // any escaped byte stands for itself
+            Some(b'\\') => {
+                let escaped = *scanner.get(1).ok_or(ParseError::UnexpectedEndOfInput)?;
+                let decoded = decoded.get_or_insert_with(|| scanner.since(start).to_vec());
+                decoded.push(escaped);
+                scanner.bump_by(2);
+            }
+            Some(byte) => {
+                if let Some(decoded) = decoded.as_mut() {
+                    decoded.push(*byte);
+                }
+                scanner.bump_by(1);
+            }
+        }
+    }
+    let subsection = match decoded {
+        Some(decoded) => Cow::Owned(String::from_utf8(decoded).map_err(|err| err.utf8_error())?),
+        None => Cow::Borrowed(std::str::from_utf8(scanner.since(start))?),
+    };
+    scanner.bump_by(1);
+    Ok(subsection)
+}
+
+/// A variable `name = value`, or `name` alone.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Variable<'a> {
+    /// The name of the variable, case-insensitive
+    pub name: &'a str,
+    /// The value with its quotes removed and its escapes decoded, `None` for
+    /// a variable without `=`
+    pub value: Option<Cow<'a, str>>,
+}
+
+impl<'a> Visitor<'a, u8> for Variable<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let name = name(
+            &mut checkpoint,
+            |byte| byte.is_ascii_alphanumeric() || byte == b'-',
+            "a variable name",
+        )?;
+        skip_blanks(&mut checkpoint);
+        let value = if checkpoint.first() == Some(&b'=') {
+            checkpoint.bump_by(1);
+            skip_blanks(&mut checkpoint);
+            Some(value(&mut checkpoint)?)
+        } else {
+            end_of_line(&mut checkpoint)?;
+            None
+        };
+        checkpoint.commit();
+        Ok(Variable { name, value })
+    }
+}
+
+/// Accept a value up to the end of its line, the line ending included
+fn value<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Cow<'a, str>> {
+    let raw = scanner.remaining();
+    let start = scanner.current_position();
+    let mut decoded = Vec::new();
+    // whether the decoded value differs from the raw one
+    let mut modified = false;
+    let mut quoted = false;
+    // the size of the value without its trailing blanks
+    let mut significant = 0;
+    let mut position = 0;
+    loop {
+        match raw.get(position..) {
+            None | Some([]) if quoted => return Err(ParseError::UnexpectedEndOfInput),
+            None | Some([]) => break,
+            Some([b'\n', ..]) | Some([b'\r', b'\n', ..]) if quoted => {
+                return Err(ParseError::unexpected_token(start + position).expecting("`\"`"));
+            }
+            Some([b'\n', ..]) | Some([b'\r', b'\n', ..]) => break,
+            Some([b'#' | b';', ..]) if !quoted => break,
+            Some([b'"', ..]) => {
+                quoted = !quoted;
+                modified = true;
+                significant = decoded.len();
+                position += 1;
+            }
+            Some([b'\\', escaped @ ..]) => {
+                modified = true;
+                let (byte, size) = match escaped {
+                    // a continued line
+                    [b'\n', ..] => (None, 2),
+                    [b'\r', b'\n', ..] => (None, 3),
+                    [b'\\', ..] => (Some(b'\\'), 2),
+                    [b'"', ..] => (Some(b'"'), 2),
+                    [b'n', ..] => (Some(b'\n'), 2),
+                    [b't', ..] => (Some(b'\t'), 2),
+                    [b'b', ..] => (Some(0x08), 2),
+                    [] => return Err(ParseError::UnexpectedEndOfInput),
+                    _ => {
+                        return Err(ParseError::unexpected_token(start + position)
+                            .expecting("an escape sequence"));
+                    }
+                };
+                if let Some(byte) = byte {
+                    decoded.push(byte);
+                    significant = decoded.len();
+                }
+                position += size;
+            }
+            Some([byte, ..]) => {
+                decoded.push(*byte);
+                if quoted || !matches!(byte, b' ' | b'\t') {
+                    significant = decoded.len();
+                }
+                position += 1;
+            }
+        }
+    }
+    let value = if modified {
+        decoded.truncate(significant);
+        Cow::Owned(String::from_utf8(decoded).map_err(|err| err.utf8_error())?)
+    } else {
+        Cow::Borrowed(std::str::from_utf8(&raw[..significant])?)
+    };
+    scanner.bump_by(position);
+    skip_line(scanner);
+    Ok(value)
+}
+
+/// A section and its variables.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Section<'a> {
+    /// The header of the section
+    pub header: SectionHeader<'a>,
+    /// The variables, in their order in the file
+    pub variables: Vec<Variable<'a>>,
+}
+
+/// A git configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct GitConfig<'a> {
+    /// The sections, in their order in the file. A section may appear
+    /// several times.
+    pub sections: Vec<Section<'a>>,
+}
+
+impl<'a> GitConfig<'a> {
+    /// Parse a whole configuration file.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The configuration file
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` on an invalid line or a variable outside
+    /// of any section, `ParseError::UnexpectedEndOfInput` on an unclosed
+    /// quote.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        Scanner::new(data).visit()
+    }
+
+    /// Return the value of a variable, `section.key` or
+    /// `section.subsection.key` as with `git config --get`.
+    ///
+    /// The section and variable names are case-insensitive, the subsection
+    /// is not. The last value wins, and a variable without value is `true`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let (section, rest) = key.split_once('.')?;
+        let (subsection, name) = match rest.rsplit_once('.') {
+            Some((subsection, name)) => (Some(subsection), name),
+            None => (None, rest),
+        };
+        self.sections
+            .iter()
+            .filter(|candidate| {
+                candidate.header.name.eq_ignore_ascii_case(section)
+                    && candidate.header.subsection.as_deref() == subsection
+            })
+            .flat_map(|section| section.variables.iter())
+            .filter(|variable| variable.name.eq_ignore_ascii_case(name))
+            .map(|variable| variable.value.as_deref().unwrap_or("true"))
+            .next_back()
+    }
+}
+
+impl<'a> Visitor<'a, u8> for GitConfig<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut sections: Vec<Section> = vec![];
+        loop {
+            checkpoint.take_while(u8::is_ascii_whitespace);
+            match checkpoint.first() {
+                None => break,
+                Some(b'#' | b';') => skip_line(&mut checkpoint),
+                // a variable may follow the header on the same line
+                Some(b'[') => sections.push(Section {
+                    header: checkpoint.visit()?,
+                    variables: vec![],
+                }),
+                Some(_) => {
+                    let position = checkpoint.current_position();
+                    let variable = checkpoint.visit()?;
+                    let section = sections.last_mut().ok_or_else(|| {
+                        ParseError::unexpected_token(position).expecting("a section")
+                    })?;
+                    section.variables.push(variable);
+                }
+            }
+        }
+        checkpoint.commit();
+        Ok(GitConfig { sections })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::gitconfig::{GitConfig, SectionHeader, Variable};
+    use crate::scanner::Scanner;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_section_header() {
+        let header: SectionHeader = Scanner::new(br#"[branch "feat/\"x\" \\ y"]"#)
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(header.name, "branch");
+        assert_eq!(header.subsection.as_deref(), Some(r#"feat/"x" \ y"#));
+
+        let header: SectionHeader = Scanner::new(b"[ core.legacy ]")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!((header.name, header.subsection), ("core.legacy", None));
+
+        assert!(Scanner::new(b"[1core]").visit::<SectionHeader>().is_err());
+        assert!(Scanner::new(b"[remote \"origin]")
+            .visit::<SectionHeader>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_variable() {
+        let parse = |data| {
+            Scanner::new(data)
+                .visit::<Variable>()
+                .expect("failed to parse")
+                .value
+        };
+        assert_eq!(
+            parse(b"a = plain value  "),
+            Some(Cow::Borrowed("plain value"))
+        );
+        assert_eq!(parse(b"a=x;comment"), Some(Cow::Borrowed("x")));
+        assert_eq!(parse(b"a ="), Some(Cow::Borrowed("")));
+        assert_eq!(parse(b"a # no value"), None);
+        assert_eq!(
+            parse(br#"a = " keep # this "\t\"end\""#).as_deref(),
+            Some(" keep # this \t\"end\"")
+        );
+        assert_eq!(
+            parse(b"a = first \\\n  second\r\nb = 2").as_deref(),
+            Some("first   second")
+        );
+
+        assert!(matches!(
+            Scanner::new(br"a = \q").visit::<Variable>(),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert!(matches!(
+            Scanner::new(b"a = \"open").visit::<Variable>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(Scanner::new(b"a b").visit::<Variable>().is_err());
+    }
+
+    #[test]
+    fn test_config() {
+        let config = GitConfig::parse(
+            b"# global
+[user]
+\tname = Alice
+[Core] autocrlf
+[remote \"origin\"]
+\tfetch = +refs/heads/*:refs/remotes/origin/*
+[USER]
+\tNAME = Bob
+",
+        )
+        .expect("failed to parse");
+        assert_eq!(config.sections.len(), 4);
+        assert_eq!(config.get("user.name"), Some("Bob"));
+        assert_eq!(config.get("core.autocrlf"), Some("true"));
+        assert_eq!(
+            config.get("remote.origin.fetch"),
+            Some("+refs/heads/*:refs/remotes/origin/*")
+        );
+        assert_eq!(config.get("remote.ORIGIN.fetch"), None);
+        assert_eq!(config.get("user"), None);
+
+        assert!(matches!(
+            GitConfig::parse(b"\nname = Alice"),
+            Err(ParseError::UnexpectedToken { offset: 1, .. })
+        ));
+    }
+}
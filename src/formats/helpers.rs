@@ -0,0 +1,37 @@
+//! The helpers shared by the parsers of the formats.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+
+/// Consume the expected byte
+pub(crate) fn expect(scanner: &mut Scanner<u8>, expected: u8) -> ParseResult<()> {
+    match scanner.first() {
+        Some(byte) if *byte == expected => {
+            scanner.bump_by(1);
+            Ok(())
+        }
+        Some(_) => Err(ParseError::unexpected_token(scanner.current_position())
+            .expecting(&format!("`{}`", expected as char))),
+        None => Err(ParseError::UnexpectedEndOfInput),
+    }
+}
+
+/// Consume exactly `count` digits, return their value
+pub(crate) fn digits(scanner: &mut Scanner<u8>, count: usize) -> ParseResult<u32> {
+    let mut value = 0;
+    for _ in 0..count {
+        match scanner.first() {
+            Some(byte) if byte.is_ascii_digit() => {
+                value = value * 10 + (byte - b'0') as u32;
+                scanner.bump_by(1);
+            }
+            Some(_) => {
+                return Err(
+                    ParseError::unexpected_token(scanner.current_position()).expecting("a digit")
+                );
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        }
+    }
+    Ok(value)
+}
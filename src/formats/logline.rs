@@ -0,0 +1,584 @@
+//! Structured log lines.
+//!
+//! Each application writes its logs in its own layout, but built from the
+//! same fields: a timestamp, a level, some `key=value` pairs, a message,
+//! sometimes a JSON object. The fields are visitors which can be composed in
+//! a grammar, and a [LogSchema] assembles them in the order of a given layout.
+//!
+//! ```
+//! use elyze::formats::logline::{Field, Level, LogSchema};
+//!
+//! let schema = LogSchema::new()
+//!     .with_field(Field::Timestamp)
+//!     .with_field(Field::Level)
+//!     .with_field(Field::Bracketed("thread"))
+//!     .with_field(Field::Pairs)
+//!     .with_field(Field::Message);
+//! let line = schema
+//!     .parse(b"2024-05-01 12:30:45 WARN [main] user=alice retry=3 disk is almost full")
+//!     .expect("failed to parse");
+//! assert_eq!(line.level, Some(Level::Warn));
+//! assert_eq!(line.word("thread"), Some("main"));
+//! assert_eq!(line.pair("retry"), Some("3"));
+//! assert_eq!(line.message.as_deref(), Some("disk is almost full"));
+//! ```
+
+use crate::bytes::escape::{unescape_str, BackslashEscapes};
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::{digits, expect};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// Consume the spaces and tabs separating the fields
+fn skip_blanks(scanner: &mut Scanner<u8>) {
+    scanner.take_while(|byte| *byte == b' ' || *byte == b'\t');
+}
+
+/// Consume `count` digits whose value is in the range
+fn bounded_digits(
+    scanner: &mut Scanner<u8>,
+    count: usize,
+    range: std::ops::RangeInclusive<u32>,
+    name: &str,
+) -> ParseResult<u32> {
+    let start = scanner.current_position();
+    let value = digits(scanner, count)?;
+    if !range.contains(&value) {
+        return Err(ParseError::unexpected_token(start).expecting(name));
+    }
+    Ok(value)
+}
+
+/// A timestamp `2024-05-01T12:30:45.123+02:00`, in the RFC 3339 layout.
+///
+/// The date and the time may also be separated by a space, the fraction of
+/// second may be introduced by a comma, and the UTC offset is optional.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp<'a> {
+    /// The year
+    pub year: u16,
+    /// The month, from 1
+    pub month: u8,
+    /// The day of the month, from 1
+    pub day: u8,
+    /// The hour
+    pub hour: u8,
+    /// The minute
+    pub minute: u8,
+    /// The second, 60 for a leap second
+    pub second: u8,
+    /// The nanoseconds of the fraction of second
+    pub nanosecond: u32,
+    /// The offset from UTC in minutes, `None` for a local time
+    pub offset: Option<i16>,
+    /// The timestamp as written
+    pub raw: &'a str,
+}
+
+/// Accept the fraction of second, return its nanoseconds
+fn fraction(scanner: &mut Scanner<u8>) -> ParseResult<u32> {
+    let start = scanner.current_position();
+    let fraction = std::str::from_utf8(scanner.take_while(u8::is_ascii_digit))?;
+    if fraction.is_empty() {
+        return Err(ParseError::unexpected_token(start).expecting("a digit"));
+    }
+    // the digits after the nanoseconds are truncated
+    let nanosecond = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |value, digit| value * 10 + (digit - b'0') as u32);
+    Ok(nanosecond)
+}
+
+/// Accept the UTC offset, if any
+fn utc_offset(scanner: &mut Scanner<u8>) -> ParseResult<Option<i16>> {
+    let sign = match scanner.first() {
+        Some(b'Z') | Some(b'z') => {
+            scanner.bump_by(1);
+            return Ok(Some(0));
+        }
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => return Ok(None),
+    };
+    scanner.bump_by(1);
+    let hours = bounded_digits(scanner, 2, 0..=23, "an hour")?;
+    if scanner.first() == Some(&b':') {
+        scanner.bump_by(1);
+    }
+    let minutes = bounded_digits(scanner, 2, 0..=59, "a minute")?;
+    Ok(Some(sign * (hours * 60 + minutes) as i16))
+}
+
+impl<'a> Visitor<'a, u8> for Timestamp<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let year = digits(&mut checkpoint, 4)? as u16;
+        expect(&mut checkpoint, b'-')?;
+        let month = bounded_digits(&mut checkpoint, 2, 1..=12, "a month")? as u8;
+        expect(&mut checkpoint, b'-')?;
+        let day = bounded_digits(&mut checkpoint, 2, 1..=31, "a day")? as u8;
+        match checkpoint.first() {
+            Some(b'T') | Some(b't') | Some(b' ') => checkpoint.bump_by(1),
+            Some(_) => {
+                let err = ParseError::unexpected_token(checkpoint.current_position());
+                return Err(err.expecting("`T`"));
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput),
+        }
+        let hour = bounded_digits(&mut checkpoint, 2, 0..=23, "an hour")? as u8;
+        expect(&mut checkpoint, b':')?;
+        let minute = bounded_digits(&mut checkpoint, 2, 0..=59, "a minute")? as u8;
+        expect(&mut checkpoint, b':')?;
+        let second = bounded_digits(&mut checkpoint, 2, 0..=60, "a second")? as u8;
+        let nanosecond = match checkpoint.first() {
+            Some(b'.') | Some(b',') => {
+                checkpoint.bump_by(1);
+                fraction(&mut checkpoint)?
+            }
+            _ => 0,
+        };
+        let offset = utc_offset(&mut checkpoint)?;
+        // the timestamp is made of ASCII bytes only
+        let raw = std::str::from_utf8(checkpoint.since(start))?;
+        checkpoint.commit();
+        Ok(Timestamp {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+            raw,
+        })
+    }
+}
+
+/// The severity of a log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// `TRACE`
+    Trace,
+    /// `DEBUG`
+    Debug,
+    /// `INFO`
+    Info,
+    /// `WARN` or `WARNING`
+    Warn,
+    /// `ERROR` or `ERR`
+    Error,
+    /// `FATAL`, `CRITICAL` or `CRIT`
+    Fatal,
+}
+
+impl Level {
+    /// Return the level of a keyword, ignoring its case
+    ///
+    /// # Arguments
+    ///
+    /// * `keyword` - The keyword of the level
+    pub fn from_keyword(keyword: &str) -> Option<Level> {
+        let levels = [
+            ("TRACE", Level::Trace),
+            ("DEBUG", Level::Debug),
+            ("INFO", Level::Info),
+            ("WARN", Level::Warn),
+            ("WARNING", Level::Warn),
+            ("ERROR", Level::Error),
+            ("ERR", Level::Error),
+            ("FATAL", Level::Fatal),
+            ("CRITICAL", Level::Fatal),
+            ("CRIT", Level::Fatal),
+        ];
+        levels
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(keyword))
+            .map(|(_, level)| *level)
+    }
+}
+
+impl<'a> Visitor<'a, u8> for Level {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let keyword = std::str::from_utf8(checkpoint.take_while(u8::is_ascii_alphabetic))?;
+        let level = Level::from_keyword(keyword)
+            .ok_or_else(|| ParseError::unexpected_token(start).expecting("a log level"))?;
+        checkpoint.commit();
+        Ok(level)
+    }
+}
+
+/// A double-quoted string, with its backslash escapes decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quoted<'a>(pub Cow<'a, str>);
+
+impl<'a> Visitor<'a, u8> for Quoted<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        expect(&mut checkpoint, b'"')?;
+        let start = checkpoint.current_position();
+        let remaining = checkpoint.remaining();
+        let mut position = 0;
+        loop {
+            match remaining.get(position) {
+                Some(b'"') => break,
+                Some(b'\\') => position += 2,
+                Some(_) => position += 1,
+                None => return Err(ParseError::UnexpectedEndOfInput),
+            }
+        }
+        let value = unescape_str(&BackslashEscapes, &remaining[..position])
+            .map_err(|err| err.shifted(start))?;
+        checkpoint.bump_by(position + 1);
+        checkpoint.commit();
+        Ok(Quoted(value))
+    }
+}
+
+/// A `key=value` pair, the value being a bare word or a quoted string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValue<'a> {
+    /// The key, made of letters, digits, `_`, `.` and `-`
+    pub key: &'a str,
+    /// The value
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> Visitor<'a, u8> for KeyValue<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let key = std::str::from_utf8(checkpoint.take_while(|byte| {
+            byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'-')
+        }))?;
+        if key.is_empty() {
+            return Err(ParseError::unexpected_token(start).expecting("a key"));
+        }
+        expect(&mut checkpoint, b'=')?;
+        let value = if checkpoint.first() == Some(&b'"') {
+            checkpoint.visit::<Quoted>()?.0
+        } else {
+            Cow::Borrowed(std::str::from_utf8(
+                checkpoint.take_while(|byte| !byte.is_ascii_whitespace()),
+            )?)
+        };
+        checkpoint.commit();
+        Ok(KeyValue { key, value })
+    }
+}
+
+/// A JSON object ending a log line, kept as written.
+///
+/// Only the balance of the braces and brackets is checked, the object is
+/// meant to be handed to a JSON parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonTail<'a>(pub &'a str);
+
+impl<'a> Visitor<'a, u8> for JsonTail<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let remaining = scanner.remaining();
+        if remaining.first() != Some(&b'{') {
+            return Err(ParseError::unexpected_token(start).expecting("`{`"));
+        }
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut position = 0;
+        while let Some(byte) = remaining.get(position) {
+            match (in_string, byte) {
+                (true, b'\\') => position += 1,
+                (true, b'"') | (false, b'"') => in_string = !in_string,
+                (false, b'{') | (false, b'[') => depth += 1,
+                (false, b'}') | (false, b']') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        let object = std::str::from_utf8(&remaining[..=position])?;
+                        scanner.bump_by(position + 1);
+                        return Ok(JsonTail(object));
+                    }
+                }
+                _ => {}
+            }
+            position += 1;
+        }
+        Err(ParseError::UnexpectedEndOfInput)
+    }
+}
+
+/// A field of a [LogSchema]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Field {
+    /// A [Timestamp]
+    Timestamp,
+    /// A [Level]
+    Level,
+    /// A word without whitespace, stored under the name
+    Word(&'static str),
+    /// A word between square brackets like `[main]`, stored under the name
+    Bracketed(&'static str),
+    /// A text which must be present as is
+    Literal(&'static str),
+    /// Zero or more [KeyValue] pairs
+    Pairs,
+    /// A [Quoted] message
+    Quoted,
+    /// The rest of the line as message, up to a [Field::Json] following it
+    Message,
+    /// A [JsonTail]
+    Json,
+}
+
+/// The fields of a log line parsed by a [LogSchema]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogLine<'a> {
+    /// The timestamp
+    pub timestamp: Option<Timestamp<'a>>,
+    /// The level
+    pub level: Option<Level>,
+    /// The named words
+    pub words: Vec<(&'static str, &'a str)>,
+    /// The `key=value` pairs
+    pub pairs: Vec<KeyValue<'a>>,
+    /// The message, quoted or not
+    pub message: Option<Cow<'a, str>>,
+    /// The JSON object
+    pub json: Option<&'a str>,
+}
+
+impl<'a> LogLine<'a> {
+    /// Return the word stored under the name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the word
+    pub fn word(&self, name: &str) -> Option<&'a str> {
+        self.words
+            .iter()
+            .find(|(word, _)| *word == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// Return the value of the first pair with the key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the pair
+    pub fn pair(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.key == key)
+            .map(|pair| pair.value.as_ref())
+    }
+}
+
+/// The layout of the log lines of an application, as a sequence of fields
+/// separated by blanks.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogSchema {
+    /// The fields in their order in the line
+    fields: Vec<Field>,
+}
+
+impl LogSchema {
+    /// Create a new `LogSchema` without fields
+    pub fn new() -> Self {
+        LogSchema::default()
+    }
+
+    /// Append a field to the layout
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The next field of the line
+    pub fn with_field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Parse a log line
+    ///
+    /// A trailing line break is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The log line
+    ///
+    /// # Errors
+    ///
+    /// The error of the first field which can't be parsed, or
+    /// `ParseError::UnexpectedToken` if data remains after the last field.
+    pub fn parse<'a>(&self, line: &'a [u8]) -> ParseResult<LogLine<'a>> {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let mut scanner = Scanner::new(line);
+        let mut log_line = LogLine::default();
+        for (index, field) in self.fields.iter().enumerate() {
+            skip_blanks(&mut scanner);
+            match field {
+                Field::Timestamp => log_line.timestamp = Some(scanner.visit()?),
+                Field::Level => log_line.level = Some(scanner.visit()?),
+                Field::Word(name) => {
+                    let start = scanner.current_position();
+                    let word = std::str::from_utf8(
+                        scanner.take_while(|byte| !byte.is_ascii_whitespace()),
+                    )?;
+                    if word.is_empty() {
+                        return Err(ParseError::unexpected_token(start).expecting(name));
+                    }
+                    log_line.words.push((name, word));
+                }
+                Field::Bracketed(name) => {
+                    expect(&mut scanner, b'[')?;
+                    let word = std::str::from_utf8(scanner.take_while(|byte| *byte != b']'))?;
+                    expect(&mut scanner, b']')?;
+                    log_line.words.push((name, word));
+                }
+                Field::Literal(text) => {
+                    if !scanner.remaining().starts_with(text.as_bytes()) {
+                        let err = ParseError::unexpected_token(scanner.current_position());
+                        return Err(err.expecting(&format!("`{text}`")));
+                    }
+                    scanner.bump_by(text.len());
+                }
+                Field::Pairs => {
+                    while let Ok(pair) = scanner.visit::<KeyValue>() {
+                        log_line.pairs.push(pair);
+                        skip_blanks(&mut scanner);
+                    }
+                }
+                Field::Quoted => log_line.message = Some(scanner.visit::<Quoted>()?.0),
+                Field::Message => {
+                    let json_follows = self.fields.get(index + 1) == Some(&Field::Json);
+                    let message = message(&mut scanner, json_follows)?;
+                    log_line.message = Some(Cow::Borrowed(message));
+                }
+                Field::Json => log_line.json = Some(scanner.visit::<JsonTail>()?.0),
+            }
+        }
+        skip_blanks(&mut scanner);
+        if !scanner.is_empty() {
+            return Err(ParseError::unexpected_token(scanner.current_position()));
+        }
+        Ok(log_line)
+    }
+}
+
+/// Accept the rest of the line as message, up to a JSON object ending the
+/// line if one follows
+fn message<'a>(scanner: &mut Scanner<'a, u8>, json_follows: bool) -> ParseResult<&'a str> {
+    let remaining = scanner.remaining();
+    let mut size = remaining.len();
+    if json_follows {
+        // the first object running to the end of the line
+        let json_start = remaining.iter().enumerate().position(|(position, byte)| {
+            let mut tail = Scanner::new(&remaining[position..]);
+            *byte == b'{' && tail.visit::<JsonTail>().is_ok() && tail.trim_ascii().is_empty()
+        });
+        size = json_start.unwrap_or(size);
+    }
+    let message = std::str::from_utf8(&remaining[..size])?.trim_end();
+    scanner.bump_by(message.len());
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::logline::{Field, JsonTail, KeyValue, Level, LogSchema, Quoted, Timestamp};
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_timestamp() {
+        let mut scanner = Scanner::new(b"2024-05-01T12:30:45.123+02:00 INFO");
+        let timestamp = scanner.visit::<Timestamp>().expect("failed to parse");
+        assert_eq!(
+            (timestamp.year, timestamp.month, timestamp.day),
+            (2024, 5, 1)
+        );
+        assert_eq!(
+            (timestamp.hour, timestamp.minute, timestamp.second),
+            (12, 30, 45)
+        );
+        assert_eq!(timestamp.nanosecond, 123_000_000);
+        assert_eq!(timestamp.offset, Some(120));
+        assert_eq!(timestamp.raw, "2024-05-01T12:30:45.123+02:00");
+        assert_eq!(scanner.remaining(), b" INFO");
+
+        let timestamp = Scanner::new(b"2024-05-01 00:00:00,5Z")
+            .visit::<Timestamp>()
+            .expect("failed to parse");
+        assert_eq!(
+            (timestamp.nanosecond, timestamp.offset),
+            (500_000_000, Some(0))
+        );
+
+        let mut scanner = Scanner::new(b"2024-13-01T00:00:00");
+        assert!(matches!(
+            scanner.visit::<Timestamp>(),
+            Err(ParseError::UnexpectedToken { offset: 5, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_fields() {
+        let mut scanner = Scanner::new(b"warning");
+        assert_eq!(
+            scanner.visit::<Level>().expect("failed to parse"),
+            Level::Warn
+        );
+        assert!(Scanner::new(b"WARNINGS").visit::<Level>().is_err());
+
+        let quoted = Scanner::new(br#""say \"hi\"" rest"#)
+            .visit::<Quoted>()
+            .expect("failed to parse");
+        assert_eq!(quoted.0, r#"say "hi""#);
+
+        let pair = Scanner::new(b"http.status=404 ")
+            .visit::<KeyValue>()
+            .expect("failed to parse");
+        assert_eq!((pair.key, pair.value.as_ref()), ("http.status", "404"));
+
+        let mut scanner = Scanner::new(br#"{"a":[1,{"b":"}"}]} tail"#);
+        let JsonTail(json) = scanner.visit().expect("failed to parse");
+        assert_eq!(json, r#"{"a":[1,{"b":"}"}]}"#);
+        assert!(Scanner::new(b"{\"a\":1").visit::<JsonTail>().is_err());
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = LogSchema::new()
+            .with_field(Field::Timestamp)
+            .with_field(Field::Literal("|"))
+            .with_field(Field::Level)
+            .with_field(Field::Word("service"))
+            .with_field(Field::Pairs)
+            .with_field(Field::Message)
+            .with_field(Field::Json);
+        let line = schema
+            .parse(b"2024-05-01T12:30:45Z | error api user=\"J. Doe\" login failed {\"ip\": \"10.0.0.1\"}\r\n")
+            .expect("failed to parse");
+        assert_eq!(line.level, Some(Level::Error));
+        assert_eq!(line.word("service"), Some("api"));
+        assert_eq!(line.pair("user"), Some("J. Doe"));
+        assert_eq!(line.message.as_deref(), Some("login failed"));
+        assert_eq!(line.json, Some(r#"{"ip": "10.0.0.1"}"#));
+
+        let schema = LogSchema::new()
+            .with_field(Field::Level)
+            .with_field(Field::Quoted);
+        let line = schema.parse(br#"INFO "started""#).expect("failed to parse");
+        assert_eq!(line.message.as_deref(), Some("started"));
+        assert!(matches!(
+            schema.parse(br#"INFO "started" twice"#),
+            Err(ParseError::UnexpectedToken { offset: 15, .. })
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+//! Ready-made parsers for common data formats.
+pub mod chemistry;
+pub mod cmdline;
+pub mod contentline;
+pub mod dotenv;
+pub mod fasta;
+pub mod filter;
+pub mod front_matter;
+pub mod gitconfig;
+mod helpers;
+pub mod logline;
+pub mod san;
+pub mod syslog;
+pub mod template;
+pub mod unidiff;
+pub mod xml_lite;
@@ -0,0 +1,655 @@
+//! Chess moves in standard algebraic notation (SAN) and PGN games.
+//!
+//! A move names the moving piece, an optional origin to disambiguate it, an
+//! optional capture and the destination square: `Nf3`, `exd5`, `R1a3`,
+//! `Qh4xe1`. A pawn reaching the last rank is promoted, `e8=Q`, the castlings
+//! are written `O-O` and `O-O-O`, and a move may be followed by a check or
+//! checkmate suffix and an annotation: `Bxf7+!`, `Qh5#`.
+//!
+//! A PGN game is a list of tag pairs, `[Event "Casual game"]`, followed by
+//! the movetext: the numbered moves, their comments in braces or up to the
+//! end of the line, the numeric annotations `$1`, the variations in
+//! parentheses and the result of the game.
+//!
+//! ```
+//! use elyze::formats::san::{GameResult, PgnGame};
+//!
+//! let data = b"[White \"Morphy\"]\n[Black \"Allies\"]\n\n1. e4 e5 {open game} 2. Nf3 d6 1-0";
+//! let game = PgnGame::parse(data).expect("failed to parse");
+//! assert_eq!(game.tag("White"), Some("Morphy"));
+//! assert_eq!(game.moves().count(), 4);
+//! assert_eq!(game.result, Some(GameResult::WhiteWins));
+//! ```
+
+use crate::bytes::components::groups::match_group;
+use crate::bytes::token::Token;
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::formats::logline::Quoted;
+use crate::peek::PeekResult;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// The castlings, the longest first
+const CASTLINGS: [(&[u8], CastlingSide); 4] = [
+    (b"O-O-O", CastlingSide::Queenside),
+    (b"0-0-0", CastlingSide::Queenside),
+    (b"O-O", CastlingSide::Kingside),
+    (b"0-0", CastlingSide::Kingside),
+];
+
+/// The check suffixes
+const CHECKS: [(&[u8], Check); 2] = [(b"+", Check::Check), (b"#", Check::Checkmate)];
+
+/// The move annotations, the longest first
+const ANNOTATIONS: [(&[u8], Annotation); 6] = [
+    (b"!!", Annotation::Brilliant),
+    (b"!?", Annotation::Interesting),
+    (b"?!", Annotation::Dubious),
+    (b"??", Annotation::Blunder),
+    (b"!", Annotation::Good),
+    (b"?", Annotation::Mistake),
+];
+
+/// The game termination markers
+const RESULTS: [(&[u8], GameResult); 4] = [
+    (b"1-0", GameResult::WhiteWins),
+    (b"0-1", GameResult::BlackWins),
+    (b"1/2-1/2", GameResult::Draw),
+    (b"*", GameResult::Unknown),
+];
+
+/// Accept the first keyword of the table starting the data
+fn keyword<T: Copy>(scanner: &mut Scanner<u8>, table: &[(&[u8], T)]) -> Option<T> {
+    let remaining = scanner.remaining();
+    let (keyword, value) = table
+        .iter()
+        .find(|(keyword, _)| remaining.starts_with(keyword))?;
+    scanner.bump_by(keyword.len());
+    Some(*value)
+}
+
+/// Whether the byte is a file of the board, `a` to `h`
+fn is_file(byte: &u8) -> bool {
+    (b'a'..=b'h').contains(byte)
+}
+
+/// Whether the byte is a rank of the board, `1` to `8`
+fn is_rank(byte: &u8) -> bool {
+    (b'1'..=b'8').contains(byte)
+}
+
+/// A chess piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Piece {
+    /// `K`
+    King,
+    /// `Q`
+    Queen,
+    /// `R`
+    Rook,
+    /// `B`
+    Bishop,
+    /// `N`
+    Knight,
+    /// A pawn, which has no letter
+    Pawn,
+}
+
+impl Piece {
+    /// Return the piece written with the letter, the pawns have none
+    pub fn from_letter(letter: u8) -> Option<Piece> {
+        match letter {
+            b'K' => Some(Piece::King),
+            b'Q' => Some(Piece::Queen),
+            b'R' => Some(Piece::Rook),
+            b'B' => Some(Piece::Bishop),
+            b'N' => Some(Piece::Knight),
+            _ => None,
+        }
+    }
+}
+
+/// A square of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Square {
+    /// The file, from 0 for `a` to 7 for `h`
+    pub file: u8,
+    /// The rank, from 0 for `1` to 7 for `8`
+    pub rank: u8,
+}
+
+/// The side of a castling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastlingSide {
+    /// `O-O`
+    Kingside,
+    /// `O-O-O`
+    Queenside,
+}
+
+/// The check suffix of a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// `+`
+    Check,
+    /// `#`
+    Checkmate,
+}
+
+/// The annotation of a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Annotation {
+    /// `!`
+    Good,
+    /// `?`
+    Mistake,
+    /// `!!`
+    Brilliant,
+    /// `??`
+    Blunder,
+    /// `!?`
+    Interesting,
+    /// `?!`
+    Dubious,
+}
+
+/// What a move does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// A piece moves to a square
+    Move {
+        /// The moving piece
+        piece: Piece,
+        /// The file of the origin, when needed to disambiguate the move
+        from_file: Option<u8>,
+        /// The rank of the origin, when needed to disambiguate the move
+        from_rank: Option<u8>,
+        /// Whether the move captures a piece
+        capture: bool,
+        /// The destination
+        to: Square,
+        /// The piece a pawn is promoted to on the last rank
+        promotion: Option<Piece>,
+    },
+    /// The king castles
+    Castling(CastlingSide),
+}
+
+/// A move in standard algebraic notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SanMove {
+    /// What the move does
+    pub action: Action,
+    /// The check suffix
+    pub check: Option<Check>,
+    /// The annotation
+    pub annotation: Option<Annotation>,
+}
+
+/// Accept the move of a piece or a pawn
+fn movement(scanner: &mut Scanner<u8>) -> ParseResult<Action> {
+    let start = scanner.current_position();
+    let piece = match scanner.first().copied().and_then(Piece::from_letter) {
+        Some(piece) => {
+            scanner.bump_by(1);
+            piece
+        }
+        None => Piece::Pawn,
+    };
+
+    // the origin, the capture and the destination
+    let body_start = scanner.current_position();
+    let body = scanner.take_while(|byte| is_file(byte) || is_rank(byte) || *byte == b'x');
+    let Some((rest, [file, rank])) = body.split_last_chunk::<2>() else {
+        return Err(ParseError::unexpected_token(body_start + body.len()).expecting("a square"));
+    };
+    if !is_file(file) || !is_rank(rank) {
+        return Err(ParseError::unexpected_token(body_start + rest.len()).expecting("a square"));
+    }
+    let to = Square {
+        file: file - b'a',
+        rank: rank - b'1',
+    };
+    let (origin, capture) = match rest.strip_suffix(b"x") {
+        Some(origin) => (origin, true),
+        None => (rest, false),
+    };
+    let (from_file, from_rank) = match origin {
+        [] => (None, None),
+        [file] if is_file(file) => (Some(file - b'a'), None),
+        [rank] if is_rank(rank) => (None, Some(rank - b'1')),
+        [file, rank] if is_file(file) && is_rank(rank) => (Some(file - b'a'), Some(rank - b'1')),
+        _ => {
+            return Err(ParseError::unexpected_token(body_start).expecting("an origin"));
+        }
+    };
+    // a pawn only names its file, when it captures
+    if piece == Piece::Pawn && (from_rank.is_some() || from_file.is_some() != capture) {
+        return Err(ParseError::unexpected_token(start).expecting("a pawn move"));
+    }
+
+    let promotion_start = scanner.current_position();
+    let promotion = if piece == Piece::Pawn && (to.rank == 0 || to.rank == 7) {
+        if scanner.first() == Some(&b'=') {
+            scanner.bump_by(1);
+        }
+        match scanner.first().copied().and_then(Piece::from_letter) {
+            Some(Piece::King) | None => {
+                return Err(ParseError::unexpected_token(scanner.current_position())
+                    .expecting("a promotion"));
+            }
+            promotion => {
+                scanner.bump_by(1);
+                promotion
+            }
+        }
+    } else {
+        None
+    };
+    if scanner.first() == Some(&b'=') {
+        return Err(ParseError::unexpected_token(promotion_start).expecting("the end of the move"));
+    }
+
+    Ok(Action::Move {
+        piece,
+        from_file,
+        from_rank,
+        capture,
+        to,
+        promotion,
+    })
+}
+
+impl<'a> Visitor<'a, u8> for SanMove {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let action = match keyword(&mut checkpoint, &CASTLINGS) {
+            Some(side) => Action::Castling(side),
+            None => movement(&mut checkpoint)?,
+        };
+        let check = keyword(&mut checkpoint, &CHECKS);
+        let annotation = keyword(&mut checkpoint, &ANNOTATIONS);
+        checkpoint.commit();
+        Ok(SanMove {
+            action,
+            check,
+            annotation,
+        })
+    }
+}
+
+/// A `[Name "value"]` tag pair of a PGN game.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TagPair<'a> {
+    /// The name of the tag
+    pub name: &'a str,
+    /// The value, with its escapes decoded
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> Visitor<'a, u8> for TagPair<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        expect(&mut checkpoint, b'[')?;
+        checkpoint.take_while(u8::is_ascii_whitespace);
+        let start = checkpoint.current_position();
+        let name = checkpoint.take_while(|byte| byte.is_ascii_alphanumeric() || *byte == b'_');
+        if name.is_empty() {
+            return Err(ParseError::unexpected_token(start).expecting("a tag name"));
+        }
+        checkpoint.take_while(u8::is_ascii_whitespace);
+        let Quoted(value) = checkpoint.visit()?;
+        checkpoint.take_while(u8::is_ascii_whitespace);
+        expect(&mut checkpoint, b']')?;
+        checkpoint.commit();
+        Ok(TagPair {
+            name: std::str::from_utf8(name)?,
+            value,
+        })
+    }
+}
+
+/// The result of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameResult {
+    /// `1-0`
+    WhiteWins,
+    /// `0-1`
+    BlackWins,
+    /// `1/2-1/2`
+    Draw,
+    /// `*`, the game is ongoing or its result is unknown
+    Unknown,
+}
+
+/// An element of the movetext of a PGN game.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MovetextItem<'a> {
+    /// A move number, `12.` before a white move or `12...` before a black one
+    MoveNumber {
+        /// The number
+        number: u32,
+        /// Whether the number introduces a black move
+        black: bool,
+    },
+    /// A move
+    Move(SanMove),
+    /// A comment, in braces or up to the end of the line
+    Comment(&'a str),
+    /// A numeric annotation glyph, `$1`
+    Nag(u8),
+    /// An alternative line in parentheses
+    Variation(Vec<MovetextItem<'a>>),
+}
+
+/// Accept a comment in braces
+///
+/// The comments don't nest in PGN, the braces found in a comment are still
+/// balanced.
+fn brace_comment<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<&'a str> {
+    let remaining = scanner.remaining();
+    let matcher = match_group(Token::OpenBrace, Token::CloseBrace, Token::Backslash);
+    match matcher(remaining).map_err(|err| err.shifted(scanner.current_position()))? {
+        PeekResult::Found { end_slice, .. } => {
+            scanner.bump_by(end_slice);
+            Ok(std::str::from_utf8(&remaining[1..end_slice - 1])?.trim())
+        }
+        PeekResult::NotFound => {
+            Err(ParseError::unexpected_token(scanner.current_position()).expecting("a comment"))
+        }
+    }
+}
+
+/// Accept a move number and its dots
+fn move_number(scanner: &mut Scanner<u8>) -> ParseResult<MovetextItem<'static>> {
+    let start = scanner.current_position();
+    let number = std::str::from_utf8(scanner.take_while(u8::is_ascii_digit))?.parse()?;
+    let dots = scanner.take_while(|byte| *byte == b'.');
+    match dots.len() {
+        1 => Ok(MovetextItem::MoveNumber {
+            number,
+            black: false,
+        }),
+        3 => Ok(MovetextItem::MoveNumber {
+            number,
+            black: true,
+        }),
+        _ => Err(ParseError::unexpected_token(start).expecting("a move number")),
+    }
+}
+
+/// Accept the items of a movetext, up to the result of the game, the end of
+/// a variation or the end of the data
+fn movetext<'a>(
+    scanner: &mut Scanner<'a, u8>,
+    items: &mut Vec<MovetextItem<'a>>,
+    in_variation: bool,
+) -> ParseResult<Option<GameResult>> {
+    loop {
+        scanner.take_while(u8::is_ascii_whitespace);
+        if !in_variation && let Some(result) = keyword(scanner, &RESULTS) {
+            return Ok(Some(result));
+        }
+        match scanner.first() {
+            None if in_variation => return Err(ParseError::UnexpectedEndOfInput),
+            None => return Ok(None),
+            Some(b')') if in_variation => {
+                scanner.bump_by(1);
+                return Ok(None);
+            }
+            Some(b'{') => items.push(MovetextItem::Comment(brace_comment(scanner)?)),
+            Some(b';') => {
+                scanner.bump_by(1);
+                let line = scanner.take_till(|byte| *byte == b'\n');
+                items.push(MovetextItem::Comment(std::str::from_utf8(line)?.trim()));
+            }
+            Some(b'$') => {
+                scanner.bump_by(1);
+                let start = scanner.current_position();
+                let nag = scanner.take_while(u8::is_ascii_digit);
+                if nag.is_empty() {
+                    return Err(ParseError::unexpected_token(start).expecting("a number"));
+                }
+                items.push(MovetextItem::Nag(std::str::from_utf8(nag)?.parse()?));
+            }
+            Some(b'(') => {
+                scanner.bump_by(1);
+                let mut variation = vec![];
+                movetext(scanner, &mut variation, true)?;
+                items.push(MovetextItem::Variation(variation));
+            }
+            Some(byte) if byte.is_ascii_digit() && !scanner.remaining().starts_with(b"0-0") => {
+                items.push(move_number(scanner)?)
+            }
+            Some(_) => items.push(MovetextItem::Move(scanner.visit()?)),
+        }
+    }
+}
+
+/// A PGN game.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgnGame<'a> {
+    /// The tag pairs, in their order in the game
+    pub tags: Vec<TagPair<'a>>,
+    /// The movetext
+    pub movetext: Vec<MovetextItem<'a>>,
+    /// The result ending the movetext, if any
+    pub result: Option<GameResult>,
+}
+
+impl<'a> PgnGame<'a> {
+    /// Parse a single PGN game.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The game
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` on an invalid tag pair or move, or data
+    /// after the result, `ParseError::UnbalancedGroup` on an unclosed comment.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        let mut scanner = Scanner::new(data);
+        let game = scanner.visit()?;
+        scanner.take_while(u8::is_ascii_whitespace);
+        if !scanner.is_empty() {
+            return Err(ParseError::unexpected_token(scanner.current_position())
+                .expecting("the end of the game"));
+        }
+        Ok(game)
+    }
+
+    /// Return the value of a tag.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|tag| tag.name == name)
+            .map(|tag| tag.value.as_ref())
+    }
+
+    /// Return the moves of the main line, without the variations.
+    pub fn moves(&self) -> impl Iterator<Item = &SanMove> {
+        self.movetext.iter().filter_map(|item| match item {
+            MovetextItem::Move(san_move) => Some(san_move),
+            _ => None,
+        })
+    }
+}
+
+impl<'a> Visitor<'a, u8> for PgnGame<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut tags = vec![];
+        loop {
+            checkpoint.take_while(u8::is_ascii_whitespace);
+            if checkpoint.first() != Some(&b'[') {
+                break;
+            }
+            tags.push(checkpoint.visit()?);
+        }
+        let mut movetext_items = vec![];
+        let result = movetext(&mut checkpoint, &mut movetext_items, false)?;
+        checkpoint.commit();
+        Ok(PgnGame {
+            tags,
+            movetext: movetext_items,
+            result,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::san::{
+        Action, Annotation, CastlingSide, Check, GameResult, MovetextItem, PgnGame, Piece, SanMove,
+        Square,
+    };
+    use crate::scanner::Scanner;
+
+    fn parse(data: &[u8]) -> SanMove {
+        Scanner::new(data).visit().expect("failed to parse")
+    }
+
+    #[test]
+    fn test_san_move() {
+        assert_eq!(
+            parse(b"Nbxd7+!?"),
+            SanMove {
+                action: Action::Move {
+                    piece: Piece::Knight,
+                    from_file: Some(1),
+                    from_rank: None,
+                    capture: true,
+                    to: Square { file: 3, rank: 6 },
+                    promotion: None,
+                },
+                check: Some(Check::Check),
+                annotation: Some(Annotation::Interesting),
+            }
+        );
+        assert!(matches!(
+            parse(b"exd8=Q#").action,
+            Action::Move {
+                piece: Piece::Pawn,
+                from_file: Some(4),
+                capture: true,
+                promotion: Some(Piece::Queen),
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse(b"Qh4e1").action,
+            Action::Move {
+                from_file: Some(7),
+                from_rank: Some(3),
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse(b"R1a3").action,
+            Action::Move {
+                from_file: None,
+                from_rank: Some(0),
+                ..
+            }
+        ));
+        assert_eq!(
+            parse(b"O-O-O").action,
+            Action::Castling(CastlingSide::Queenside)
+        );
+        let san_move = parse(b"0-0??");
+        assert_eq!(san_move.action, Action::Castling(CastlingSide::Kingside));
+        assert_eq!(san_move.annotation, Some(Annotation::Blunder));
+    }
+
+    #[test]
+    fn test_invalid_san_move() {
+        for (data, position) in [
+            (&b"Nz3"[..], 1),
+            (b"N3", 2),
+            (b"e9", 1),
+            (b"e4e5", 0),
+            (b"ed5", 0),
+            (b"Nbbd7", 1),
+            (b"e8", 2),
+            (b"e8=K", 3),
+            (b"Ne4=Q", 3),
+        ] {
+            assert!(
+                matches!(
+                    Scanner::new(data).visit::<SanMove>(),
+                    Err(ParseError::UnexpectedToken { offset, .. }) if offset == position
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_pgn_game() {
+        let data = b"[Event \"F/S Return \\\"Match\\\"\"]\n[Round \"29\"]\n\n\
+            1.e4 e5 2. Nf3 Nc6 {This opening is called {the} Ruy Lopez.} 3. Bb5 $1 a6 \
+            (3... Nf6 ; Berlin\n 4. O-O) 4. Ba4 1/2-1/2\n";
+        let game = PgnGame::parse(data).expect("failed to parse");
+        assert_eq!(game.tag("Event"), Some("F/S Return \"Match\""));
+        assert_eq!(game.tag("Round"), Some("29"));
+        assert_eq!(game.tag("Site"), None);
+        assert_eq!(game.result, Some(GameResult::Draw));
+        assert_eq!(game.moves().count(), 7);
+        assert!(game.movetext.contains(&MovetextItem::Comment(
+            "This opening is called {the} Ruy Lopez."
+        )));
+        assert!(game.movetext.contains(&MovetextItem::Nag(1)));
+        let variation = game
+            .movetext
+            .iter()
+            .find_map(|item| match item {
+                MovetextItem::Variation(variation) => Some(variation),
+                _ => None,
+            })
+            .expect("no variation");
+        assert_eq!(
+            variation[..3],
+            [
+                MovetextItem::MoveNumber {
+                    number: 3,
+                    black: true
+                },
+                MovetextItem::Move(parse(b"Nf6")),
+                MovetextItem::Comment("Berlin"),
+            ]
+        );
+
+        let game = PgnGame::parse(b"1. d4 *").expect("failed to parse");
+        assert!(game.tags.is_empty());
+        assert_eq!(game.result, Some(GameResult::Unknown));
+    }
+
+    #[test]
+    fn test_invalid_pgn_game() {
+        assert!(matches!(
+            PgnGame::parse(b"1. e4 {unclosed"),
+            Err(ParseError::UnbalancedGroup)
+        ));
+        assert!(matches!(
+            PgnGame::parse(b"1. e4 (1. d4"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            PgnGame::parse(b"1. e4 1-0 2. e5"),
+            Err(ParseError::UnexpectedToken { offset: 10, .. })
+        ));
+        assert!(matches!(
+            PgnGame::parse(b"[Event]"),
+            Err(ParseError::UnexpectedToken { offset: 6, .. })
+        ));
+        assert!(matches!(
+            PgnGame::parse(b"1.. e4"),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+//! Syslog messages, in the RFC 5424 layout.
+//!
+//! A message is made of a header, some structured data and a free message:
+//!
+//! ```text
+//! <PRI>VERSION TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA MSG
+//! ```
+//!
+//! Every header field may be the nil value `-`, which is parsed as `None`.
+//!
+//! ```
+//! use elyze::formats::syslog::SyslogMessage;
+//! use elyze::scanner::Scanner;
+//!
+//! let data = br#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Application"] An application event"#;
+//! let message: SyslogMessage = Scanner::new(data).visit().expect("failed to parse");
+//! assert_eq!(message.priority.facility(), 20);
+//! assert_eq!(message.priority.severity(), 5);
+//! assert_eq!(message.hostname, Some("mymachine.example.com"));
+//! assert_eq!(message.proc_id, None);
+//! assert_eq!(message.param("exampleSDID@32473", "eventSource"), Some("Application"));
+//! assert_eq!(message.message, Some(&b"An application event"[..]));
+//! ```
+
+use crate::bytes::escape::{unescape_str, SyslogEscapes};
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::formats::logline::Timestamp;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// The byte order mark introducing an UTF-8 message
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Consume a field made of printable US-ASCII bytes, `None` for the nil value
+fn header_field<'a>(
+    scanner: &mut Scanner<'a, u8>,
+    max_size: usize,
+    name: &str,
+) -> ParseResult<Option<&'a str>> {
+    let start = scanner.current_position();
+    let field = std::str::from_utf8(scanner.take_while(u8::is_ascii_graphic))?;
+    if field.is_empty() || field.len() > max_size {
+        return Err(ParseError::unexpected_token(start).expecting(name));
+    }
+    Ok((field != "-").then_some(field))
+}
+
+/// Consume a name of structured data, printable US-ASCII except `=`, `]`
+/// and `"`
+fn sd_name<'a>(scanner: &mut Scanner<'a, u8>, name: &str) -> ParseResult<&'a str> {
+    let start = scanner.current_position();
+    let sd_name = std::str::from_utf8(
+        scanner.take_while(|byte| byte.is_ascii_graphic() && !matches!(byte, b'=' | b']' | b'"')),
+    )?;
+    if sd_name.is_empty() || sd_name.len() > 32 {
+        return Err(ParseError::unexpected_token(start).expecting(name));
+    }
+    Ok(sd_name)
+}
+
+/// The priority `<PRI>` of a message, combining its facility and severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority(pub u8);
+
+impl Priority {
+    /// The facility, from 0 (kernel) to 23 (local7)
+    pub fn facility(&self) -> u8 {
+        self.0 / 8
+    }
+
+    /// The severity, from 0 (emergency) to 7 (debug)
+    pub fn severity(&self) -> u8 {
+        self.0 % 8
+    }
+}
+
+impl<'a> Visitor<'a, u8> for Priority {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        expect(&mut checkpoint, b'<')?;
+        let start = checkpoint.current_position();
+        let digits = std::str::from_utf8(checkpoint.take_while(u8::is_ascii_digit))?;
+        // no leading zero, except for the priority 0 itself
+        let value = match digits.parse::<u8>() {
+            Ok(value) if value <= 191 && (digits == "0" || !digits.starts_with('0')) => value,
+            _ => return Err(ParseError::unexpected_token(start).expecting("a priority")),
+        };
+        expect(&mut checkpoint, b'>')?;
+        checkpoint.commit();
+        Ok(Priority(value))
+    }
+}
+
+/// A parameter `name="value"` of a structured data element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdParam<'a> {
+    /// The name of the parameter
+    pub name: &'a str,
+    /// The value, with its escapes decoded
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> Visitor<'a, u8> for SdParam<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let name = sd_name(&mut checkpoint, "a parameter name")?;
+        expect(&mut checkpoint, b'=')?;
+        expect(&mut checkpoint, b'"')?;
+        let start = checkpoint.current_position();
+        let remaining = checkpoint.remaining();
+        let mut position = 0;
+        loop {
+            match remaining.get(position) {
+                Some(b'"') => break,
+                Some(b'\\') => position += 2,
+                Some(_) => position += 1,
+                None => return Err(ParseError::UnexpectedEndOfInput),
+            }
+        }
+        let value = unescape_str(&SyslogEscapes, &remaining[..position])
+            .map_err(|err| err.shifted(start))?;
+        checkpoint.bump_by(position + 1);
+        checkpoint.commit();
+        Ok(SdParam { name, value })
+    }
+}
+
+/// A structured data element `[id name="value" ...]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdElement<'a> {
+    /// The identifier of the element
+    pub id: &'a str,
+    /// The parameters of the element
+    pub params: Vec<SdParam<'a>>,
+}
+
+impl<'a> SdElement<'a> {
+    /// Return the value of the first parameter with the given name
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|param| param.name == name)
+            .map(|param| param.value.as_ref())
+    }
+}
+
+impl<'a> Visitor<'a, u8> for SdElement<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        expect(&mut checkpoint, b'[')?;
+        let id = sd_name(&mut checkpoint, "an element identifier")?;
+        let mut params = vec![];
+        while checkpoint.first() == Some(&b' ') {
+            checkpoint.bump_by(1);
+            params.push(checkpoint.visit()?);
+        }
+        expect(&mut checkpoint, b']')?;
+        checkpoint.commit();
+        Ok(SdElement { id, params })
+    }
+}
+
+/// A syslog message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogMessage<'a> {
+    /// The priority
+    pub priority: Priority,
+    /// The version of the protocol, 1 for RFC 5424
+    pub version: u8,
+    /// The time the message was emitted
+    pub timestamp: Option<Timestamp<'a>>,
+    /// The machine emitting the message
+    pub hostname: Option<&'a str>,
+    /// The application emitting the message
+    pub app_name: Option<&'a str>,
+    /// The process emitting the message
+    pub proc_id: Option<&'a str>,
+    /// The type of message
+    pub msg_id: Option<&'a str>,
+    /// The structured data elements
+    pub structured_data: Vec<SdElement<'a>>,
+    /// The free message, without its byte order mark. It's UTF-8 if it had
+    /// one, but may be any data otherwise.
+    pub message: Option<&'a [u8]>,
+}
+
+impl<'a> SyslogMessage<'a> {
+    /// Return the value of a parameter of the first structured data element
+    /// with the given identifier
+    pub fn param(&self, id: &str, name: &str) -> Option<&str> {
+        self.structured_data
+            .iter()
+            .find(|element| element.id == id)
+            .and_then(|element| element.param(name))
+    }
+}
+
+impl<'a> Visitor<'a, u8> for SyslogMessage<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let priority = checkpoint.visit()?;
+        let start = checkpoint.current_position();
+        let version = std::str::from_utf8(checkpoint.take_while(u8::is_ascii_digit))?;
+        let version = match version.parse::<u8>() {
+            Ok(value) if value > 0 && version.len() <= 2 => value,
+            _ => return Err(ParseError::unexpected_token(start).expecting("a version")),
+        };
+        expect(&mut checkpoint, b' ')?;
+        let timestamp = if checkpoint.first() == Some(&b'-') {
+            checkpoint.bump_by(1);
+            None
+        } else {
+            let timestamp: Timestamp = checkpoint.visit()?;
+            // the space would be taken for the end of the field
+            if timestamp.raw.contains(' ') {
+                return Err(ParseError::unexpected_token(start + 2).expecting("a timestamp"));
+            }
+            Some(timestamp)
+        };
+        expect(&mut checkpoint, b' ')?;
+        let hostname = header_field(&mut checkpoint, 255, "a hostname")?;
+        expect(&mut checkpoint, b' ')?;
+        let app_name = header_field(&mut checkpoint, 48, "an application name")?;
+        expect(&mut checkpoint, b' ')?;
+        let proc_id = header_field(&mut checkpoint, 128, "a process identifier")?;
+        expect(&mut checkpoint, b' ')?;
+        let msg_id = header_field(&mut checkpoint, 32, "a message identifier")?;
+        expect(&mut checkpoint, b' ')?;
+        let mut structured_data = vec![];
+        if checkpoint.first() == Some(&b'-') {
+            checkpoint.bump_by(1);
+        } else {
+            structured_data.push(checkpoint.visit()?);
+            while checkpoint.first() == Some(&b'[') {
+                structured_data.push(checkpoint.visit()?);
+            }
+        }
+        let message = match checkpoint.first() {
+            Some(b' ') => {
+                checkpoint.bump_by(1);
+                let remaining = checkpoint.remaining();
+                checkpoint.bump_by(remaining.len());
+                Some(remaining.strip_prefix(BOM).unwrap_or(remaining))
+            }
+            Some(_) => {
+                let err = ParseError::unexpected_token(checkpoint.current_position());
+                return Err(err.expecting("a space"));
+            }
+            None => None,
+        };
+        checkpoint.commit();
+        Ok(SyslogMessage {
+            priority,
+            version,
+            timestamp,
+            hostname,
+            app_name,
+            proc_id,
+            msg_id,
+            structured_data,
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::syslog::{Priority, SdElement, SyslogMessage};
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_priority() {
+        let priority: Priority = Scanner::new(b"<34>").visit().expect("failed to parse");
+        assert_eq!((priority.facility(), priority.severity()), (4, 2));
+        assert!(Scanner::new(b"<0>").visit::<Priority>().is_ok());
+        assert!(Scanner::new(b"<192>").visit::<Priority>().is_err());
+        assert!(Scanner::new(b"<01>").visit::<Priority>().is_err());
+        assert!(Scanner::new(b"<>").visit::<Priority>().is_err());
+    }
+
+    #[test]
+    fn test_structured_data() {
+        let element: SdElement =
+            Scanner::new(br#"[origin ip="192.0.2.1" path="C:\\temp\]" x="\q"]"#)
+                .visit()
+                .expect("failed to parse");
+        assert_eq!(element.id, "origin");
+        assert_eq!(element.param("ip"), Some("192.0.2.1"));
+        assert_eq!(element.param("path"), Some(r"C:\temp]"));
+        assert_eq!(element.param("x"), Some(r"\q"));
+
+        let element: SdElement = Scanner::new(b"[empty]").visit().expect("failed to parse");
+        assert!(element.params.is_empty());
+
+        assert!(matches!(
+            Scanner::new(br#"[id name="unterminated]"#).visit::<SdElement>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(Scanner::new(b"[id name=bare]")
+            .visit::<SdElement>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_message() {
+        let message: SyslogMessage = Scanner::new(
+            b"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - \xEF\xBB\xBF'su root' failed",
+        )
+        .visit()
+        .expect("failed to parse");
+        assert_eq!(message.version, 1);
+        let timestamp = message.timestamp.expect("no timestamp");
+        assert_eq!((timestamp.year, timestamp.nanosecond), (2003, 3_000_000));
+        assert_eq!(message.app_name, Some("su"));
+        assert_eq!(message.msg_id, Some("ID47"));
+        assert!(message.structured_data.is_empty());
+        assert_eq!(message.message, Some(&b"'su root' failed"[..]));
+
+        // all the fields may be nil, and the message absent
+        let message: SyslogMessage = Scanner::new(b"<0>1 - - - - - [a][b x=\"1\"]")
+            .visit()
+            .expect("failed to parse");
+        assert_eq!(message.timestamp, None);
+        assert_eq!(message.hostname, None);
+        assert_eq!(message.structured_data.len(), 2);
+        assert_eq!(message.param("b", "x"), Some("1"));
+        assert_eq!(message.message, None);
+    }
+
+    #[test]
+    fn test_invalid_message() {
+        // the structured data is mandatory
+        let mut scanner = Scanner::new(b"<34>1 - host app - - ");
+        assert!(scanner.visit::<SyslogMessage>().is_err());
+        assert_eq!(scanner.current_position(), 0);
+        // an empty field
+        assert!(matches!(
+            Scanner::new(b"<34>1 -  app - - -").visit::<SyslogMessage>(),
+            Err(ParseError::UnexpectedToken { offset: 8, .. })
+        ));
+        assert!(Scanner::new(b"<34>0 - - - - - -")
+            .visit::<SyslogMessage>()
+            .is_err());
+        assert!(Scanner::new(b"<34>1 2003-10-11 22:14:15 host - - - -")
+            .visit::<SyslogMessage>()
+            .is_err());
+        assert!(Scanner::new(b"<34>1 - - - - -x")
+            .visit::<SyslogMessage>()
+            .is_err());
+    }
+}
@@ -0,0 +1,404 @@
+//! Text templates, in a lightweight mustache/jinja syntax.
+//!
+//! A template is text interleaved with tags:
+//!
+//! * `{{ expression }}`, an expression to render
+//! * `{# comment #}`, a comment
+//! * `{% if expression %}...{% elif expression %}...{% else %}...{% endif %}`
+//! * `{% for name in expression %}...{% endfor %}`
+//!
+//! The expressions are parsed by a visitor given as type parameter, so any
+//! grammar can be plugged in. [RawExpression] keeps them as written, to be
+//! parsed later.
+//!
+//! ```
+//! use elyze::formats::template::{Node, RawExpression, Template};
+//!
+//! let template: Template<RawExpression> =
+//!     Template::parse(b"Hello {{ user.name }}{% if admin %} (admin){% endif %}!")
+//!         .expect("failed to parse");
+//! assert_eq!(
+//!     template.0,
+//!     vec![
+//!         Node::Text("Hello "),
+//!         Node::Expression(RawExpression("user.name")),
+//!         Node::If {
+//!             condition: RawExpression("admin"),
+//!             then: vec![Node::Text(" (admin)")],
+//!             otherwise: vec![],
+//!         },
+//!         Node::Text("!"),
+//!     ]
+//! );
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// The openings of the tags
+const OPENINGS: [&[u8]; 3] = [b"{{", b"{%", b"{#"];
+
+/// Consume the whitespaces inside a tag
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    scanner.take_while(u8::is_ascii_whitespace);
+}
+
+/// Consume the expected delimiter
+fn expect_delimiter(scanner: &mut Scanner<u8>, delimiter: &[u8]) -> ParseResult<()> {
+    let remaining = scanner.remaining();
+    if remaining.starts_with(delimiter) {
+        scanner.bump_by(delimiter.len());
+        return Ok(());
+    }
+    if delimiter.starts_with(remaining) {
+        return Err(ParseError::UnexpectedEndOfInput);
+    }
+    let delimiter = String::from_utf8_lossy(delimiter);
+    Err(ParseError::unexpected_token(scanner.current_position())
+        .expecting(&format!("`{delimiter}`")))
+}
+
+/// Consume the end of a block tag `%}`
+fn close_tag(scanner: &mut Scanner<u8>) -> ParseResult<()> {
+    skip_whitespaces(scanner);
+    expect_delimiter(scanner, b"%}")
+}
+
+/// An expression kept as written, trimmed.
+///
+/// The expression ends at the first `}}` or `%}` which is neither nested in
+/// brackets, braces or parentheses, nor in a quoted string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawExpression<'a>(pub &'a str);
+
+impl<'a> Visitor<'a, u8> for RawExpression<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let remaining = scanner.remaining();
+        let mut depth = 0usize;
+        let mut position = 0;
+        loop {
+            match &remaining[position..] {
+                [] => return Err(ParseError::UnexpectedEndOfInput),
+                [b'}' | b'%', b'}', ..] if depth == 0 => break,
+                [b'(' | b'[' | b'{', ..] => depth += 1,
+                [b')' | b']' | b'}', ..] => {
+                    depth = depth.checked_sub(1).ok_or_else(|| {
+                        ParseError::unexpected_token(start + position)
+                            .expecting("a balanced expression")
+                    })?;
+                }
+                [quote @ (b'"' | b'\''), ..] => loop {
+                    position += 1;
+                    match remaining.get(position) {
+                        Some(b'\\') => position += 1,
+                        Some(byte) if byte == quote => break,
+                        Some(_) => {}
+                        None => return Err(ParseError::UnexpectedEndOfInput),
+                    }
+                },
+                _ => {}
+            }
+            position += 1;
+        }
+        let expression = std::str::from_utf8(&remaining[..position])?.trim_end();
+        if expression.is_empty() {
+            return Err(ParseError::unexpected_token(start).expecting("an expression"));
+        }
+        scanner.bump_by(position);
+        Ok(RawExpression(expression))
+    }
+}
+
+/// A node of a template, whose expressions are `E`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node<'a, E> {
+    /// Text rendered as is
+    Text(&'a str),
+    /// An expression `{{ expression }}`
+    Expression(E),
+    /// A comment `{# comment #}`, trimmed
+    Comment(&'a str),
+    /// A condition `{% if condition %}then{% else %}otherwise{% endif %}`.
+    /// An `{% elif %}` is an `If` alone in the `otherwise` nodes.
+    If {
+        /// The condition
+        condition: E,
+        /// The nodes rendered if the condition holds
+        then: Vec<Node<'a, E>>,
+        /// The nodes rendered otherwise
+        otherwise: Vec<Node<'a, E>>,
+    },
+    /// A loop `{% for variable in iterable %}body{% endfor %}`
+    For {
+        /// The name bound to each item
+        variable: &'a str,
+        /// The iterated expression
+        iterable: E,
+        /// The nodes rendered for each item
+        body: Vec<Node<'a, E>>,
+    },
+}
+
+/// A template, whose expressions are `E`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template<'a, E>(pub Vec<Node<'a, E>>);
+
+impl<'a, E: Visitor<'a, u8>> Template<'a, E> {
+    /// Parse a whole template.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The template
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedEndOfInput` if a tag or a block is not closed,
+    /// `ParseError::UnexpectedToken` on an unknown tag, or any error of the
+    /// expressions.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        Scanner::new(data).visit()
+    }
+}
+
+impl<'a, E: Visitor<'a, u8>> Visitor<'a, u8> for Template<'a, E> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let (nodes, _) = nodes(&mut checkpoint, &[])?;
+        checkpoint.commit();
+        Ok(Template(nodes))
+    }
+}
+
+/// Accept an expression surrounded by whitespaces
+fn expression<'a, E: Visitor<'a, u8>>(scanner: &mut Scanner<'a, u8>) -> ParseResult<E> {
+    skip_whitespaces(scanner);
+    let expression = E::accept(scanner)?;
+    skip_whitespaces(scanner);
+    Ok(expression)
+}
+
+/// Accept the nodes until the end of the data, or a block tag whose keyword
+/// is one of the terminators.
+///
+/// # Returns
+///
+/// The nodes and the terminator, the scanner being after its keyword.
+fn nodes<'a, E: Visitor<'a, u8>>(
+    scanner: &mut Scanner<'a, u8>,
+    terminators: &[&'static str],
+) -> ParseResult<(Vec<Node<'a, E>>, Option<&'static str>)> {
+    let mut nodes = vec![];
+    loop {
+        let remaining = scanner.remaining();
+        let size = (0..remaining.len())
+            .find(|position| {
+                OPENINGS
+                    .iter()
+                    .any(|opening| remaining[*position..].starts_with(opening))
+            })
+            .unwrap_or(remaining.len());
+        if size > 0 {
+            nodes.push(Node::Text(std::str::from_utf8(&remaining[..size])?));
+            scanner.bump_by(size);
+        }
+
+        match scanner.remaining() {
+            [] if terminators.is_empty() => return Ok((nodes, None)),
+            [] => return Err(ParseError::UnexpectedEndOfInput),
+            [b'{', b'{', ..] => {
+                scanner.bump_by(2);
+                nodes.push(Node::Expression(expression(scanner)?));
+                expect_delimiter(scanner, b"}}")?;
+            }
+            [b'{', b'#', ..] => {
+                scanner.bump_by(2);
+                let remaining = scanner.remaining();
+                let size = remaining
+                    .windows(2)
+                    .position(|window| window == b"#}")
+                    .ok_or(ParseError::UnexpectedEndOfInput)?;
+                nodes.push(Node::Comment(
+                    std::str::from_utf8(&remaining[..size])?.trim(),
+                ));
+                scanner.bump_by(size + 2);
+            }
+            _ => {
+                scanner.bump_by(2);
+                skip_whitespaces(scanner);
+                let start = scanner.current_position();
+                let keyword = std::str::from_utf8(scanner.take_while(u8::is_ascii_alphabetic))?;
+                if let Some(terminator) = terminators.iter().find(|end| **end == keyword) {
+                    return Ok((nodes, Some(terminator)));
+                }
+                let node = match keyword {
+                    "if" => condition(scanner)?,
+                    "for" => for_loop(scanner)?,
+                    _ => return Err(ParseError::unexpected_token(start).expecting("a tag")),
+                };
+                nodes.push(node);
+            }
+        }
+    }
+}
+
+/// Accept the rest of an `if` or `elif` block, after its keyword
+fn condition<'a, E: Visitor<'a, u8>>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Node<'a, E>> {
+    let condition = expression(scanner)?;
+    close_tag(scanner)?;
+    let (then, terminator) = nodes(scanner, &["elif", "else", "endif"])?;
+    let otherwise = match terminator {
+        // the nested condition ends the whole block
+        Some("elif") => vec![self::condition(scanner)?],
+        Some("else") => {
+            close_tag(scanner)?;
+            let (otherwise, _) = nodes(scanner, &["endif"])?;
+            close_tag(scanner)?;
+            otherwise
+        }
+        _ => {
+            close_tag(scanner)?;
+            vec![]
+        }
+    };
+    Ok(Node::If {
+        condition,
+        then,
+        otherwise,
+    })
+}
+
+/// Accept the rest of a `for` block, after its keyword
+fn for_loop<'a, E: Visitor<'a, u8>>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Node<'a, E>> {
+    skip_whitespaces(scanner);
+    let start = scanner.current_position();
+    let variable = std::str::from_utf8(
+        scanner.take_while(|byte| byte.is_ascii_alphanumeric() || *byte == b'_'),
+    )?;
+    if variable.is_empty() {
+        return Err(ParseError::unexpected_token(start).expecting("a variable"));
+    }
+    skip_whitespaces(scanner);
+    let position = scanner.current_position();
+    if std::str::from_utf8(scanner.take_while(u8::is_ascii_alphabetic))? != "in" {
+        return Err(ParseError::unexpected_token(position).expecting("`in`"));
+    }
+    let iterable = expression(scanner)?;
+    close_tag(scanner)?;
+    let (body, _) = nodes(scanner, &["endfor"])?;
+    close_tag(scanner)?;
+    Ok(Node::For {
+        variable,
+        iterable,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::ParseError;
+    use crate::formats::filter::Filter;
+    use crate::formats::template::{Node, RawExpression, Template};
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_raw_expression() {
+        let mut scanner = Scanner::new(br#"items | join(", ") %}"#);
+        let expression: RawExpression = scanner.visit().expect("failed to parse");
+        assert_eq!(expression.0, r#"items | join(", ")"#);
+        assert_eq!(scanner.remaining(), b"%}");
+
+        // the delimiters nested in the expression don't end it
+        let mut scanner = Scanner::new(br#"{"a": {"b": "}}"}} }}"#);
+        let expression: RawExpression = scanner.visit().expect("failed to parse");
+        assert_eq!(expression.0, r#"{"a": {"b": "}}"}}"#);
+
+        assert!(Scanner::new(b" }}").visit::<RawExpression>().is_err());
+        assert!(Scanner::new(b"a) }}").visit::<RawExpression>().is_err());
+        assert!(matches!(
+            Scanner::new(b"(a").visit::<RawExpression>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let template: Template<RawExpression> = Template::parse(
+            b"{# list #}{% for user in users %}{% if user.admin %}*{% elif user.guest %}?{% else %}-{% endif %}{{user.name}}{% endfor %}",
+        )
+        .expect("failed to parse");
+        let else_if = Node::If {
+            condition: RawExpression("user.guest"),
+            then: vec![Node::Text("?")],
+            otherwise: vec![Node::Text("-")],
+        };
+        assert_eq!(
+            template.0,
+            vec![
+                Node::Comment("list"),
+                Node::For {
+                    variable: "user",
+                    iterable: RawExpression("users"),
+                    body: vec![
+                        Node::If {
+                            condition: RawExpression("user.admin"),
+                            then: vec![Node::Text("*")],
+                            otherwise: vec![else_if],
+                        },
+                        Node::Expression(RawExpression("user.name")),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pluggable_expressions() {
+        let template: Template<Filter> =
+            Template::parse(b"{% if age >= 18 and country == \"FR\" %}adult{% endif %}")
+                .expect("failed to parse");
+        assert!(matches!(
+            &template.0[..],
+            [Node::If {
+                condition: Filter::And(..),
+                ..
+            }]
+        ));
+
+        let template: Template<Number<u8>> =
+            Template::parse(b"{{ 1 }} + {{2}}").expect("failed to parse");
+        assert_eq!(template.0.len(), 3);
+        // the expression must fill the tag
+        assert!(matches!(
+            Template::<Number<u8>>::parse(b"{{ 1 2 }}"),
+            Err(ParseError::UnexpectedToken { offset: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let parse = |data| Template::<RawExpression>::parse(data);
+        assert!(matches!(
+            parse(b"{% if a %}unterminated"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            parse(b"{{ a "),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            parse(b"{% endif %}"),
+            Err(ParseError::UnexpectedToken { offset: 3, .. })
+        ));
+        assert!(matches!(
+            parse(b"{% for x of xs %}{% endfor %}"),
+            Err(ParseError::UnexpectedToken { offset: 9, .. })
+        ));
+        assert!(matches!(
+            parse(b"{# open"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+    }
+}
@@ -0,0 +1,417 @@
+//! Unified diffs.
+//!
+//! A patch is a list of file diffs, each made of a header naming the old and
+//! the new file, and of hunks:
+//!
+//! ```text
+//! diff --git a/src/lib.rs b/src/lib.rs
+//! --- a/src/lib.rs
+//! +++ b/src/lib.rs
+//! @@ -1,2 +1,2 @@ fn main() {
+//!  unchanged
+//! -removed
+//! +added
+//! ```
+//!
+//! The lines of the hunks borrow the patch, without their prefix and line
+//! ending.
+//!
+//! ```
+//! use elyze::formats::unidiff::{LineKind, Patch};
+//!
+//! let patch = Patch::parse(b"--- a/hello.txt\n+++ b/hello.txt\n@@ -1 +1 @@\n-hello\n+hello world\n")
+//!     .expect("failed to parse");
+//! let hunk = &patch.files[0].hunks[0];
+//! assert_eq!((hunk.old_start, hunk.old_count), (1, 1));
+//! assert_eq!(hunk.lines[1].kind, LineKind::Added);
+//! assert_eq!(hunk.lines[1].content, b"hello world");
+//! ```
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Return the current line without its line ending
+fn peek_line<'a>(scanner: &Scanner<'a, u8>) -> Option<&'a [u8]> {
+    let remaining = scanner.remaining();
+    if remaining.is_empty() {
+        return None;
+    }
+    let size = remaining
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .unwrap_or(remaining.len());
+    Some(&remaining[..size])
+}
+
+/// Consume the current line
+fn bump_line(scanner: &mut Scanner<u8>, line: &[u8]) {
+    let size = (line.len() + 1).min(scanner.remaining().len());
+    scanner.bump_by(size);
+}
+
+/// Return the line as a string, without its carriage return
+fn line_str(line: &[u8]) -> ParseResult<&str> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    Ok(std::str::from_utf8(line)?)
+}
+
+/// Accept a file header line `--- path` or `+++ path`, return its path
+fn file_header<'a>(scanner: &mut Scanner<'a, u8>, prefix: &[u8]) -> ParseResult<&'a str> {
+    let start = scanner.current_position();
+    let line = peek_line(scanner).ok_or(ParseError::UnexpectedEndOfInput)?;
+    let path = line.strip_prefix(prefix).ok_or_else(|| {
+        let prefix = String::from_utf8_lossy(prefix);
+        ParseError::unexpected_token(start).expecting(&format!("`{prefix}`"))
+    })?;
+    // a timestamp may follow the path
+    let path = path.split(|byte| *byte == b'\t').next().unwrap_or(path);
+    let path = line_str(path)?;
+    bump_line(scanner, line);
+    Ok(path)
+}
+
+/// Parse a range of a hunk header `start[,count]`
+fn range(data: &[u8], offset: usize) -> ParseResult<(u32, u32)> {
+    let invalid = || ParseError::unexpected_token(offset).expecting("a line range");
+    let number = |data: &[u8]| -> ParseResult<u32> {
+        if data.is_empty() || !data.iter().all(u8::is_ascii_digit) {
+            return Err(invalid());
+        }
+        Ok(std::str::from_utf8(data)?.parse()?)
+    };
+    match data.iter().position(|byte| *byte == b',') {
+        Some(comma) => Ok((number(&data[..comma])?, number(&data[comma + 1..])?)),
+        None => Ok((number(data)?, 1)),
+    }
+}
+
+/// The kind of a line of a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineKind {
+    /// A line of both files, prefixed by a space
+    Context,
+    /// A line of the new file only, prefixed by `+`
+    Added,
+    /// A line of the old file only, prefixed by `-`
+    Removed,
+}
+
+/// A line of a hunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiffLine<'a> {
+    /// The kind of line
+    pub kind: LineKind,
+    /// The content, without its prefix and line ending
+    pub content: &'a [u8],
+    /// Whether the line is the last line of its file, without line ending,
+    /// as marked by `\ No newline at end of file`
+    pub no_newline: bool,
+}
+
+/// A hunk `@@ -old_start,old_count +new_start,new_count @@ section`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk<'a> {
+    /// The first line of the hunk in the old file, from 1
+    pub old_start: u32,
+    /// The number of lines of the hunk in the old file
+    pub old_count: u32,
+    /// The first line of the hunk in the new file, from 1
+    pub new_start: u32,
+    /// The number of lines of the hunk in the new file
+    pub new_count: u32,
+    /// The text following the header, usually the enclosing function
+    pub section: &'a str,
+    /// The lines of the hunk
+    pub lines: Vec<DiffLine<'a>>,
+}
+
+impl<'a> Visitor<'a, u8> for Hunk<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let start = checkpoint.current_position();
+        let line = peek_line(&checkpoint).ok_or(ParseError::UnexpectedEndOfInput)?;
+        let header = line
+            .strip_prefix(b"@@ -")
+            .ok_or_else(|| ParseError::unexpected_token(start).expecting("`@@`"))?;
+        let end = header
+            .windows(3)
+            .position(|window| window == b" @@")
+            .ok_or_else(|| ParseError::unexpected_token(start).expecting("`@@`"))?;
+        let ranges = &header[..end];
+        let space = ranges
+            .iter()
+            .position(|byte| *byte == b' ')
+            .filter(|space| ranges.get(space + 1) == Some(&b'+'))
+            .ok_or_else(|| ParseError::unexpected_token(start + 4).expecting("a line range"))?;
+        let (old_start, old_count) = range(&ranges[..space], start + 4)?;
+        let (new_start, new_count) = range(&ranges[space + 2..], start + 4 + space + 2)?;
+        let section = line_str(header[end + 3..].trim_ascii_start())?;
+        bump_line(&mut checkpoint, line);
+
+        let mut lines: Vec<DiffLine> = vec![];
+        let (mut old_seen, mut new_seen) = (0, 0);
+        loop {
+            let Some(line) = peek_line(&checkpoint) else {
+                if old_seen < old_count || new_seen < new_count {
+                    return Err(ParseError::UnexpectedEndOfInput);
+                }
+                break;
+            };
+            if line.starts_with(b"\\") {
+                // the marker applies to the line before
+                if let Some(last) = lines.last_mut() {
+                    last.no_newline = true;
+                }
+                bump_line(&mut checkpoint, line);
+                continue;
+            }
+            if old_seen == old_count && new_seen == new_count {
+                break;
+            }
+            let position = checkpoint.current_position();
+            let text = line.strip_suffix(b"\r").unwrap_or(line);
+            let (kind, content) = match text {
+                // some tools strip the space of the empty context lines
+                [] => (LineKind::Context, text),
+                [b' ', content @ ..] => (LineKind::Context, content),
+                [b'+', content @ ..] => (LineKind::Added, content),
+                [b'-', content @ ..] => (LineKind::Removed, content),
+                _ => {
+                    return Err(
+                        ParseError::unexpected_token(position).expecting("a line of the hunk")
+                    );
+                }
+            };
+            if kind != LineKind::Added {
+                old_seen += 1;
+            }
+            if kind != LineKind::Removed {
+                new_seen += 1;
+            }
+            if old_seen > old_count || new_seen > new_count {
+                return Err(ParseError::unexpected_token(position).expecting("the end of the hunk"));
+            }
+            lines.push(DiffLine {
+                kind,
+                content,
+                no_newline: false,
+            });
+            bump_line(&mut checkpoint, line);
+        }
+        checkpoint.commit();
+        Ok(Hunk {
+            old_start,
+            old_count,
+            new_start,
+            new_count,
+            section,
+            lines,
+        })
+    }
+}
+
+/// The diff of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileDiff<'a> {
+    /// The lines before the paths, `diff --git ...`, `index ...`,
+    /// `new file mode ...`
+    pub headers: Vec<&'a str>,
+    /// The path of the old file as written, `a/path` or `/dev/null`, `None`
+    /// if the diff has no hunks, for a binary file or a rename
+    pub old_path: Option<&'a str>,
+    /// The path of the new file as written, `b/path` or `/dev/null`
+    pub new_path: Option<&'a str>,
+    /// The hunks
+    pub hunks: Vec<Hunk<'a>>,
+}
+
+impl<'a> Visitor<'a, u8> for FileDiff<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut headers = vec![];
+        while let Some(line) = peek_line(&checkpoint) {
+            let is_next_file = !headers.is_empty() && line.starts_with(b"diff ");
+            if line.starts_with(b"--- ") || line.starts_with(b"@@ ") || is_next_file {
+                break;
+            }
+            headers.push(line_str(line)?);
+            bump_line(&mut checkpoint, line);
+        }
+
+        let (mut old_path, mut new_path) = (None, None);
+        let mut hunks = vec![];
+        if peek_line(&checkpoint).is_some_and(|line| line.starts_with(b"--- ")) {
+            old_path = Some(file_header(&mut checkpoint, b"--- ")?);
+            new_path = Some(file_header(&mut checkpoint, b"+++ ")?);
+            while peek_line(&checkpoint).is_some_and(|line| line.starts_with(b"@@ ")) {
+                hunks.push(checkpoint.visit()?);
+            }
+        } else if headers.is_empty() {
+            let position = checkpoint.current_position();
+            return Err(ParseError::unexpected_token(position).expecting("a file diff"));
+        }
+        checkpoint.commit();
+        Ok(FileDiff {
+            headers,
+            old_path,
+            new_path,
+            hunks,
+        })
+    }
+}
+
+/// A patch, the diffs of several files.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Patch<'a> {
+    /// The diffs of the files
+    pub files: Vec<FileDiff<'a>>,
+}
+
+impl<'a> Patch<'a> {
+    /// Parse a whole patch.
+    ///
+    /// The lines before the first `diff ` or `--- ` line, a commit message
+    /// for instance, are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The patch
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` on an invalid hunk,
+    /// `ParseError::UnexpectedEndOfInput` on a truncated hunk.
+    pub fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        Scanner::new(data).visit()
+    }
+}
+
+impl<'a> Visitor<'a, u8> for Patch<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let mut checkpoint = scanner.checkpoint();
+        let mut files = vec![];
+        while let Some(line) = peek_line(&checkpoint) {
+            if line.starts_with(b"diff ") || line.starts_with(b"--- ") {
+                files.push(checkpoint.visit()?);
+            } else {
+                bump_line(&mut checkpoint, line);
+            }
+        }
+        checkpoint.commit();
+        Ok(Patch { files })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::formats::unidiff::{DiffLine, Hunk, LineKind, Patch};
+    use crate::scanner::Scanner;
+
+    const PATCH: &[u8] = b"From 1234 Mon Sep 17 00:00:00 2001
+Subject: [PATCH] Greet the world
+
+diff --git a/greet.rs b/greet.rs
+index 83db48f..bf269f4 100644
+--- a/greet.rs\t2024-05-01 12:00:00
++++ b/greet.rs\t2024-05-01 12:30:00
+@@ -1,3 +1,3 @@ fn greet() {
+ fn greet() {
+-    println!(\"hello\");
++    println!(\"hello world\");
+
+@@ -10 +10,2 @@
+ }
++// end
+\\ No newline at end of file
+diff --git a/logo.png b/logo.png
+Binary files a/logo.png and b/logo.png differ
+diff --git a/new.txt b/new.txt
+new file mode 100644
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++created
+";
+
+    #[test]
+    fn test_patch() {
+        let patch = Patch::parse(PATCH).expect("failed to parse");
+        assert_eq!(patch.files.len(), 3);
+
+        let greet = &patch.files[0];
+        assert_eq!(
+            greet.headers,
+            vec![
+                "diff --git a/greet.rs b/greet.rs",
+                "index 83db48f..bf269f4 100644"
+            ]
+        );
+        assert_eq!(greet.old_path, Some("a/greet.rs"));
+        assert_eq!(greet.new_path, Some("b/greet.rs"));
+        assert_eq!(greet.hunks.len(), 2);
+        let hunk = &greet.hunks[0];
+        assert_eq!(hunk.section, "fn greet() {");
+        assert_eq!(
+            hunk.lines.iter().map(|line| line.kind).collect::<Vec<_>>(),
+            vec![
+                LineKind::Context,
+                LineKind::Removed,
+                LineKind::Added,
+                LineKind::Context
+            ]
+        );
+        assert_eq!(hunk.lines[2].content, b"    println!(\"hello world\");");
+        assert_eq!(
+            greet.hunks[1].lines[1],
+            DiffLine {
+                kind: LineKind::Added,
+                content: b"// end",
+                no_newline: true,
+            }
+        );
+
+        let logo = &patch.files[1];
+        assert_eq!(logo.headers.len(), 2);
+        assert_eq!((logo.old_path, logo.hunks.len()), (None, 0));
+
+        let new = &patch.files[2];
+        assert_eq!(new.old_path, Some("/dev/null"));
+        assert_eq!((new.hunks[0].old_start, new.hunks[0].old_count), (0, 0));
+    }
+
+    #[test]
+    fn test_crlf_patch() {
+        let patch =
+            Patch::parse(b"--- a/x\r\n+++ b/x\r\n@@ -1,3 +1,3 @@\r\n a\r\n\r\n-b\r\n+c\r\n")
+                .expect("failed to parse");
+        assert_eq!(patch.files[0].new_path, Some("b/x"));
+        let contents = patch.files[0].hunks[0]
+            .lines
+            .iter()
+            .map(|line| line.content)
+            .collect::<Vec<_>>();
+        assert_eq!(contents, [&b"a"[..], b"", b"b", b"c"]);
+    }
+
+    #[test]
+    fn test_invalid_hunk() {
+        assert!(matches!(
+            Scanner::new(b"@@ -1,2 +1,2 @@\n a\n").visit::<Hunk>(),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            Scanner::new(b"@@ -1 +1 @@\n*a\n").visit::<Hunk>(),
+            Err(ParseError::UnexpectedToken { offset: 12, .. })
+        ));
+        assert!(Scanner::new(b"@@ -1 1 @@\n a\n").visit::<Hunk>().is_err());
+        assert!(Scanner::new(b"@@ -x +1 @@\n a\n").visit::<Hunk>().is_err());
+        assert!(Patch::parse(b"--- a\n@@ -1 +1 @@\n a\n").is_err());
+
+        // the hunk stops after its lines
+        let mut scanner = Scanner::new(b"@@ -1 +1 @@\n a\n b\n");
+        scanner.visit::<Hunk>().expect("failed to parse");
+        assert_eq!(scanner.remaining(), b" b\n");
+    }
+}
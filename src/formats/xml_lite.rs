@@ -0,0 +1,396 @@
+//! A lightweight XML reader.
+//!
+//! Parses elements, attributes, text, comments and CDATA sections into
+//! events borrowing the input. It doesn't validate the document structure,
+//! the experimental `GroupKind::Tag` of the `unstable` feature extracts
+//! balanced elements.
+//!
+//! ```
+//! use elyze::formats::xml_lite::{events, XmlEvent};
+//!
+//! let mut names = vec![];
+//! for event in events(b"<a href=\"x\">link</a>") {
+//!     if let XmlEvent::Start { name, .. } = event.expect("failed to parse") {
+//!         names.push(name);
+//!     }
+//! }
+//! assert_eq!(names, vec!["a"]);
+//! ```
+
+use crate::bytes::escape::{unescape_str, XmlEntities};
+use crate::errors::{ParseError, ParseResult};
+use crate::formats::helpers::expect;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use std::borrow::Cow;
+
+/// An event of an XML document
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum XmlEvent<'a> {
+    /// An opening tag `<name attr="value">` or a self-closing tag `<name/>`
+    Start {
+        /// The name of the element
+        name: &'a str,
+        /// The attributes of the element
+        attributes: Attributes<'a>,
+        /// Whether the tag is self-closing
+        self_closing: bool,
+    },
+    /// A closing tag `</name>`
+    End {
+        /// The name of the element
+        name: &'a str,
+    },
+    /// Text between tags, with its entities decoded
+    Text(Cow<'a, str>),
+    /// The content of a `<![CDATA[...]]>` section
+    CData(&'a str),
+    /// The content of a `<!--...-->` comment
+    Comment(&'a str),
+    /// The content of a `<?...?>` processing instruction
+    ProcessingInstruction(&'a str),
+    /// The content of a `<!DOCTYPE ...>` declaration
+    Doctype(&'a str),
+}
+
+/// An attribute of an element
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute<'a> {
+    /// The name of the attribute
+    pub name: &'a str,
+    /// The value of the attribute, with its entities decoded
+    pub value: Cow<'a, str>,
+}
+
+/// An iterator over the attributes of an element
+///
+/// The attributes are parsed lazily from the tag, they have already been
+/// checked when the tag was parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attributes<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Attributes<'a> {
+    type Item = ParseResult<Attribute<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut scanner = Scanner::new(self.data);
+        skip_whitespaces(&mut scanner);
+        if scanner.is_empty() {
+            self.data = scanner.remaining();
+            return None;
+        }
+        let attribute = attribute(&mut scanner);
+        // stop on the first error
+        self.data = match attribute {
+            Ok(_) => scanner.remaining(),
+            Err(_) => &[],
+        };
+        Some(attribute)
+    }
+}
+
+/// Skip the XML whitespaces
+fn skip_whitespaces(scanner: &mut Scanner<u8>) {
+    scanner.take_while(u8::is_ascii_whitespace);
+}
+
+/// Consume the data until the pattern, and the pattern itself
+///
+/// Returns the data before the pattern.
+fn until<'a>(scanner: &mut Scanner<'a, u8>, pattern: &[u8]) -> ParseResult<&'a [u8]> {
+    let remaining = scanner.remaining();
+    let position = remaining
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .ok_or(ParseError::UnexpectedEndOfInput)?;
+    scanner.bump_by(position + pattern.len());
+    Ok(&remaining[..position])
+}
+
+/// Consume an XML name
+fn name<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<&'a str> {
+    let is_name_start =
+        |byte: &u8| byte.is_ascii_alphabetic() || b"_:".contains(byte) || *byte >= 0x80;
+    let is_name = |byte: &u8| is_name_start(byte) || byte.is_ascii_digit() || b".-".contains(byte);
+    let remaining = scanner.remaining();
+    match remaining.first() {
+        None => return Err(ParseError::UnexpectedEndOfInput),
+        Some(byte) if !is_name_start(byte) => {
+            return Err(ParseError::unexpected_token(scanner.current_position()).expecting("a name"))
+        }
+        _ => {}
+    }
+    let size = remaining.iter().take_while(|byte| is_name(byte)).count();
+    scanner.bump_by(size);
+    Ok(std::str::from_utf8(&remaining[..size])?)
+}
+
+/// Consume an attribute `name="value"`
+fn attribute<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<Attribute<'a>> {
+    let name = name(scanner)?;
+    skip_whitespaces(scanner);
+    expect(scanner, b'=')?;
+    skip_whitespaces(scanner);
+    let quote = match scanner.first() {
+        Some(quote @ (b'"' | b'\'')) => *quote,
+        Some(_) => {
+            let err = ParseError::unexpected_token(scanner.current_position());
+            return Err(err.expecting("a quote"));
+        }
+        None => return Err(ParseError::UnexpectedEndOfInput),
+    };
+    scanner.bump_by(1);
+    let start = scanner.current_position();
+    let value = until(scanner, &[quote])?;
+    Ok(Attribute {
+        name,
+        value: decode_entities(value, start)?,
+    })
+}
+
+/// Decode the predefined and the numeric character references of the data
+/// found at `start`
+fn decode_entities(data: &[u8], start: usize) -> ParseResult<Cow<'_, str>> {
+    unescape_str(&XmlEntities, data).map_err(|err| err.shifted(start))
+}
+
+impl<'a> Visitor<'a, u8> for XmlEvent<'a> {
+    fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        let remaining = scanner.remaining();
+        if remaining.is_empty() {
+            return Err(ParseError::UnexpectedEndOfInput);
+        }
+
+        // text until the next tag
+        if remaining[0] != b'<' {
+            let size = remaining
+                .iter()
+                .position(|byte| *byte == b'<')
+                .unwrap_or(remaining.len());
+            let text = decode_entities(&remaining[..size], scanner.current_position())?;
+            scanner.bump_by(size);
+            return Ok(XmlEvent::Text(text));
+        }
+
+        let cursor = scanner.current_position();
+        let result = tag(scanner);
+        if result.is_err() {
+            scanner.jump_to(cursor);
+        }
+        result
+    }
+}
+
+/// Consume a tag, a comment, a CDATA section or a declaration
+fn tag<'a>(scanner: &mut Scanner<'a, u8>) -> ParseResult<XmlEvent<'a>> {
+    let remaining = scanner.remaining();
+    let str_until = |scanner: &mut Scanner<'a, u8>, start: usize, end: &[u8]| {
+        scanner.bump_by(start);
+        Ok::<_, ParseError>(std::str::from_utf8(until(scanner, end)?)?)
+    };
+
+    if remaining.starts_with(b"<!--") {
+        return Ok(XmlEvent::Comment(str_until(scanner, 4, b"-->")?));
+    }
+    if remaining.starts_with(b"<![CDATA[") {
+        return Ok(XmlEvent::CData(str_until(scanner, 9, b"]]>")?));
+    }
+    if remaining.starts_with(b"<?") {
+        return Ok(XmlEvent::ProcessingInstruction(str_until(
+            scanner, 2, b"?>",
+        )?));
+    }
+    if remaining.starts_with(b"<!DOCTYPE") {
+        return Ok(XmlEvent::Doctype(str_until(scanner, 9, b">")?.trim()));
+    }
+
+    if remaining.starts_with(b"</") {
+        scanner.bump_by(2);
+        let name = name(scanner)?;
+        skip_whitespaces(scanner);
+        expect(scanner, b'>')?;
+        Ok(XmlEvent::End { name })
+    } else {
+        scanner.bump_by(1);
+        let name = name(scanner)?;
+        let start = scanner.current_position();
+        // check the attributes, values may contain `>` or `/`
+        loop {
+            let before = scanner.current_position();
+            skip_whitespaces(scanner);
+            match scanner.first() {
+                None => return Err(ParseError::UnexpectedEndOfInput),
+                Some(b'>' | b'/') => break,
+                // attributes must be separated by whitespaces
+                Some(_) if before == scanner.current_position() => {
+                    let err = ParseError::unexpected_token(before);
+                    return Err(err.expecting("a whitespace"));
+                }
+                Some(_) => {
+                    attribute(scanner)?;
+                }
+            }
+        }
+        let attributes = Attributes {
+            data: &scanner.data()[start..scanner.current_position()],
+        };
+        let self_closing = scanner.first() == Some(&b'/');
+        if self_closing {
+            scanner.bump_by(1);
+        }
+        expect(scanner, b'>')?;
+        Ok(XmlEvent::Start {
+            name,
+            attributes,
+            self_closing,
+        })
+    }
+}
+
+/// An iterator over the events of an XML document
+///
+/// The iteration stops after the first error.
+pub struct XmlEvents<'a> {
+    scanner: Scanner<'a, u8>,
+    failed: bool,
+}
+
+impl<'a> Iterator for XmlEvents<'a> {
+    type Item = ParseResult<XmlEvent<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.scanner.is_empty() {
+            return None;
+        }
+        let event = self.scanner.visit::<XmlEvent>();
+        self.failed = event.is_err();
+        Some(event)
+    }
+}
+
+/// Iterate over the events of an XML document
+///
+/// # Arguments
+///
+/// * `data` - The XML document
+///
+/// # Returns
+///
+/// An iterator of the events of the document.
+pub fn events(data: &[u8]) -> XmlEvents<'_> {
+    XmlEvents {
+        scanner: Scanner::new(data),
+        failed: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{ParseError, ParseResult};
+    use crate::formats::xml_lite::{events, Attribute, XmlEvent};
+
+    #[test]
+    fn test_events() -> ParseResult<()> {
+        let data = b"<?xml version=\"1.0\"?><config name='a &amp; b'>\
+            <!-- comment --><item id=\"1\" enabled = \"true\"/>x &lt; 3\
+            <![CDATA[<raw>]]></config >";
+        let events = events(data).collect::<ParseResult<Vec<_>>>()?;
+        assert_eq!(events.len(), 7);
+        assert_eq!(
+            events[0],
+            XmlEvent::ProcessingInstruction("xml version=\"1.0\"")
+        );
+
+        let XmlEvent::Start {
+            name,
+            attributes,
+            self_closing,
+        } = &events[1]
+        else {
+            panic!("expected a start tag");
+        };
+        assert_eq!(*name, "config");
+        assert!(!self_closing);
+        let attributes = attributes.clone().collect::<ParseResult<Vec<_>>>()?;
+        assert_eq!(
+            attributes,
+            vec![Attribute {
+                name: "name",
+                value: "a & b".into()
+            }]
+        );
+
+        assert_eq!(events[2], XmlEvent::Comment(" comment "));
+
+        let XmlEvent::Start {
+            name,
+            attributes,
+            self_closing,
+        } = &events[3]
+        else {
+            panic!("expected a start tag");
+        };
+        assert_eq!(*name, "item");
+        assert!(self_closing);
+        let attributes = attributes
+            .clone()
+            .map(|attribute| attribute.map(|a| a.name));
+        assert_eq!(
+            attributes.collect::<ParseResult<Vec<_>>>()?,
+            vec!["id", "enabled"]
+        );
+
+        assert_eq!(events[4], XmlEvent::Text("x < 3".into()));
+        assert_eq!(events[5], XmlEvent::CData("<raw>"));
+        assert_eq!(events[6], XmlEvent::End { name: "config" });
+        Ok(())
+    }
+
+    #[test]
+    fn test_entities() -> ParseResult<()> {
+        let events = events(b"&#65;&#x42;&quot;&apos;&gt;").collect::<ParseResult<Vec<_>>>()?;
+        assert_eq!(events, vec![XmlEvent::Text("AB\"'>".into())]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed() {
+        let last = |data: &'static [u8]| events(data).last().expect("no event");
+        assert!(matches!(last(b"<a"), Err(ParseError::UnexpectedEndOfInput)));
+        assert!(matches!(
+            last(b"<a b>"),
+            Err(ParseError::UnexpectedToken { offset: 4, expected: Some(expected) }) if expected == "`=`"
+        ));
+        assert!(matches!(
+            last(b"<a b='1'c='2'>"),
+            Err(ParseError::UnexpectedToken { offset: 8, .. })
+        ));
+        assert!(matches!(
+            last(b"<a b='1>"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            last(b"<!-- a"),
+            Err(ParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            last(b"</a b>"),
+            Err(ParseError::UnexpectedToken { offset: 4, .. })
+        ));
+        assert!(matches!(
+            last(b"&unknown;"),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+        assert!(matches!(
+            last(b"<a>&unknown;"),
+            Err(ParseError::UnexpectedToken { offset: 3, .. })
+        ));
+        assert!(matches!(
+            last(b"<a b='&unknown;'>"),
+            Err(ParseError::UnexpectedToken { offset: 6, .. })
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+//! Generate random inputs from the matchers.
+//!
+//! A matcher describes the inputs it accepts, so it can also produce them:
+//! a [Generate] matcher builds random inputs it's guaranteed to match, and
+//! near-miss inputs it's guaranteed not to match. Driven by a seeded random
+//! number generator, they give reproducible inputs to fuzzing and property
+//! tests.
+
+use rand::Rng;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// The maximal number of repetitions of the variable length inputs
+pub const MAX_REPETITIONS: usize = 16;
+
+/// A matcher able to generate the inputs it matches and the ones it doesn't.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data to generate.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::generate::{seeded_rng, Generate};
+/// use elyze::matcher::Match;
+///
+/// let mut rng = seeded_rng(42);
+/// let input = Token::CrLn.generate(&mut rng);
+/// assert_eq!(Token::CrLn.is_matching(&input), (true, 2));
+/// let miss = Token::CrLn.generate_miss(&mut rng);
+/// assert!(!Token::CrLn.is_matching(&miss).0);
+/// ```
+pub trait Generate<T> {
+    /// Generate a random input matched by the matcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// An input the matcher matches entirely.
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T>;
+
+    /// Generate a random input close to the matched ones, but not matched
+    /// by the matcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A non-empty input the matcher doesn't match.
+    fn generate_miss<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T>;
+}
+
+impl<T, G: Generate<T>> Generate<T> for &G {
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        (*self).generate(rng)
+    }
+
+    fn generate_miss<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        (*self).generate_miss(rng)
+    }
+}
+
+/// Create a random number generator, generating the same inputs for the same
+/// seed.
+///
+/// # Arguments
+///
+/// * `seed` - The seed of the generator.
+pub fn seeded_rng(seed: u64) -> ChaCha20Rng {
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+/// Generate a random printable ASCII character or ASCII whitespace, other
+/// than the excluded ones.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator.
+/// * `excluded` - Whether a byte can't be generated, it must reject some of
+///   the printable characters only.
+pub(crate) fn ascii_except<R: Rng + ?Sized>(rng: &mut R, excluded: impl Fn(u8) -> bool) -> u8 {
+    loop {
+        let byte = match rng.random_range(0..100) {
+            0..=2 => b"\t\n\r"[rng.random_range(0..3)],
+            _ => rng.random_range(b' '..=b'~'),
+        };
+        if !excluded(byte) {
+            return byte;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::TokenNumber;
+    use crate::bytes::token::Token;
+    use crate::generate::{seeded_rng, Generate, MAX_REPETITIONS};
+    use crate::matcher::Match;
+
+    /// Check the generated inputs of the matcher on many seeds
+    fn check<M: Match<u8> + Generate<u8>>(matcher: M) {
+        for seed in 0..200 {
+            let mut rng = seeded_rng(seed);
+            let input = matcher.generate(&mut rng);
+            assert_eq!(
+                matcher.is_matching(&input),
+                (true, input.len()),
+                "{input:?} isn't matched"
+            );
+            let miss = matcher.generate_miss(&mut rng);
+            assert!(!miss.is_empty());
+            assert!(!matcher.is_matching(&miss).0, "{miss:?} is matched");
+        }
+    }
+
+    #[test]
+    fn test_generate_tokens() {
+        check(Token::OpenParen);
+        check(Token::Whitespace);
+        check(Token::Hash);
+        check(Token::Sharp);
+        check(Token::Ln);
+        check(Token::CrLn);
+        check(Token::Backslash);
+    }
+
+    #[test]
+    fn test_generate_number() {
+        check(TokenNumber);
+        let mut rng = seeded_rng(0);
+        let lengths = (0..100)
+            .map(|_| TokenNumber.generate(&mut rng).len())
+            .collect::<Vec<_>>();
+        assert!(lengths
+            .iter()
+            .all(|len| (1..=MAX_REPETITIONS).contains(len)));
+    }
+
+    #[test]
+    fn test_generate_deterministic() {
+        let generate = |seed| TokenNumber.generate(&mut seeded_rng(seed));
+        assert_eq!(generate(7), generate(7));
+        assert_ne!(generate(7), generate(8));
+    }
+}
@@ -0,0 +1,210 @@
+//! Syntax highlighting on top of the [LosslessLexer].
+//!
+//! The lexemes are turned into highlight spans, a class and a range of the
+//! data, which can be serialized as JSON for an editor or emitted as HTML.
+
+use crate::errors::json_string;
+use crate::lexer::{Lexeme, LosslessLexer};
+use crate::span::Span;
+
+/// Name the highlight class of a category of lexemes.
+///
+/// The classes are used as is by the editors and as CSS classes by the HTML
+/// emitter, so they should be made of letters, digits, `-` and `_`.
+pub trait HighlightClass {
+    /// Return the highlight class of the category
+    fn class(&self) -> &'static str;
+}
+
+/// A range of the data to highlight with a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HighlightSpan {
+    /// The class of the range, `None` for the bytes which no rule matches
+    pub class: Option<&'static str>,
+    /// The range of the data
+    pub span: Span,
+}
+
+impl HighlightSpan {
+    /// Serialize the span as a JSON object
+    ///
+    /// The object has the `class`, `start` and `end` fields in this order,
+    /// `class` being `null` for the unknown bytes.
+    pub fn to_json(&self) -> String {
+        let class = self.class.map_or("null".to_string(), json_string);
+        format!(
+            r#"{{"class":{class},"start":{},"end":{}}}"#,
+            self.span.start, self.span.end
+        )
+    }
+}
+
+/// Highlight the data lexed by the lexer.
+///
+/// The trivia are highlighted with the class of their category too, the
+/// spans cover the whole data.
+///
+/// # Arguments
+///
+/// * `lexer` - The lexer categorizing the data
+/// * `data` - The data to highlight
+///
+/// # Returns
+///
+/// The highlight spans, one per lexeme.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::TokenNumber;
+/// use elyze::bytes::token::Token;
+/// use elyze::highlight::{highlight, to_html, to_json, HighlightClass};
+/// use elyze::lexer::LosslessLexer;
+///
+/// #[derive(Clone)]
+/// enum Category {
+///     Number,
+///     Operator,
+/// }
+///
+/// impl HighlightClass for Category {
+///     fn class(&self) -> &'static str {
+///         match self {
+///             Category::Number => "number",
+///             Category::Operator => "operator",
+///         }
+///     }
+/// }
+///
+/// let lexer = LosslessLexer::new()
+///     .with_token(Category::Number, TokenNumber)
+///     .with_token(Category::Operator, Token::LessThan);
+/// let spans = highlight(&lexer, b"1<2?");
+/// assert_eq!(
+///     to_json(&spans),
+///     r#"[{"class":"number","start":0,"end":1},{"class":"operator","start":1,"end":2},{"class":"number","start":2,"end":3},{"class":null,"start":3,"end":4}]"#
+/// );
+/// assert_eq!(
+///     to_html(b"1<2?", &spans),
+///     r#"<span class="number">1</span><span class="operator">&lt;</span><span class="number">2</span>?"#
+/// );
+/// ```
+pub fn highlight<C: HighlightClass + Clone>(
+    lexer: &LosslessLexer<'_, C>,
+    data: &[u8],
+) -> Vec<HighlightSpan> {
+    lexer
+        .lex(data)
+        .map(|lexeme| HighlightSpan {
+            class: match &lexeme.value {
+                Lexeme::Token(category) | Lexeme::Trivia(category) => Some(category.class()),
+                Lexeme::Unknown => None,
+            },
+            span: lexeme.span,
+        })
+        .collect()
+}
+
+/// Serialize the highlight spans as a JSON array of the objects of
+/// [HighlightSpan::to_json].
+pub fn to_json(spans: &[HighlightSpan]) -> String {
+    let spans = spans.iter().map(HighlightSpan::to_json);
+    format!("[{}]", spans.collect::<Vec<_>>().join(","))
+}
+
+/// Escape the text of an HTML element or attribute
+fn html_escape(text: &str, html: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+}
+
+/// Emit the highlighted data as HTML.
+///
+/// Each highlighted range is a `<span>` whose class is the highlight class,
+/// the unknown bytes are left outside of any element. The text is escaped and
+/// the invalid UTF-8 sequences are replaced by `U+FFFD`.
+///
+/// # Arguments
+///
+/// * `data` - The highlighted data
+/// * `spans` - The highlight spans of the data
+///
+/// # Returns
+///
+/// The HTML fragment, to be wrapped in a `<pre>` or `<code>` element.
+pub fn to_html(data: &[u8], spans: &[HighlightSpan]) -> String {
+    let mut html = String::with_capacity(data.len() * 2);
+    for highlight_span in spans {
+        let Some(text) = highlight_span.span.slice(data) else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(text);
+        match highlight_span.class {
+            Some(class) => {
+                html.push_str("<span class=\"");
+                html_escape(class, &mut html);
+                html.push_str("\">");
+                html_escape(&text, &mut html);
+                html.push_str("</span>");
+            }
+            None => html_escape(&text, &mut html),
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::token::Token;
+    use crate::highlight::{highlight, to_html, to_json, HighlightClass, HighlightSpan};
+    use crate::lexer::LosslessLexer;
+    use crate::span::Span;
+
+    #[derive(Clone)]
+    struct Quote;
+
+    impl HighlightClass for Quote {
+        fn class(&self) -> &'static str {
+            "quote"
+        }
+    }
+
+    #[test]
+    fn test_highlight() {
+        let lexer = LosslessLexer::new().with_trivia(Quote, Token::DoubleQuote);
+        let data = "\"é&\"".as_bytes();
+        let spans = highlight(&lexer, data);
+        assert_eq!(
+            spans,
+            vec![
+                HighlightSpan {
+                    class: Some("quote"),
+                    span: Span::new(0, 1)
+                },
+                HighlightSpan {
+                    class: None,
+                    span: Span::new(1, 4)
+                },
+                HighlightSpan {
+                    class: Some("quote"),
+                    span: Span::new(4, 5)
+                },
+            ]
+        );
+        assert_eq!(
+            to_html(data, &spans),
+            "<span class=\"quote\">&quot;</span>é&amp;<span class=\"quote\">&quot;</span>"
+        );
+        assert_eq!(spans[1].to_json(), r#"{"class":null,"start":1,"end":4}"#);
+        assert_eq!(to_json(&[]), "[]");
+        assert_eq!(to_html(b"", &[]), "");
+    }
+}
@@ -0,0 +1,66 @@
+//! Checks of the grammar invariants.
+//!
+//! These helpers are meant to be used by combinators to detect grammar
+//! mistakes which would otherwise lead to hard to debug behaviours.
+
+use crate::context::short_type_name;
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+
+/// Ensure a rule applied inside an unbounded repetition has consumed input.
+///
+/// A rule which succeeds without consuming anything would be applied again
+/// at the same position forever, so the repetition is interrupted with an
+/// error naming the offending rule. Whether a rule consumes input may depend
+/// on the data, the error is then returned for the input on which the rule
+/// stalls only, in every build profile.
+///
+/// # Type Parameters
+///
+/// * `R` - The rule applied by the repetition, used to report the error.
+///
+/// # Arguments
+///
+/// * `scanner` - The scanner after the rule has been applied.
+/// * `start` - The position of the scanner before the rule was applied.
+///
+/// # Errors
+///
+/// `ParseError::UnexpectedToken` at `start` if the scanner didn't move
+/// forward, expecting the rule to consume input.
+pub fn ensure_progress<R, T>(scanner: &Scanner<'_, T>, start: usize) -> ParseResult<()> {
+    if scanner.current_position() > start {
+        return Ok(());
+    }
+    Err(ParseError::unexpected_token(start)
+        .expecting(&format!("`{}` to consume input", short_type_name::<R>())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::invariants::ensure_progress;
+    use crate::scanner::Scanner;
+
+    struct Rule;
+
+    #[test]
+    fn test_ensure_progress() {
+        let mut scanner = Scanner::new(b"abc");
+        scanner.bump_by(1);
+        assert!(ensure_progress::<Rule, u8>(&scanner, 0).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_progress_stalled() {
+        let scanner = Scanner::new(b"abc");
+        let err = ensure_progress::<Rule, u8>(&scanner, 0).expect_err("the rule stalled");
+        assert!(matches!(
+            err,
+            ParseError::UnexpectedToken {
+                offset: 0,
+                expected: Some(ref expected),
+            } if expected == "`Rule` to consume input"
+        ));
+    }
+}
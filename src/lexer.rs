@@ -0,0 +1,531 @@
+//! Two-phase parsing: lex the bytes into tokens, then parse the tokens.
+//!
+//! The lexer splits the data into a sequence of tokens, each one with its
+//! span, and skips the trivia between them. The tokens are then parsed by a
+//! `Scanner<Spanned<K>>` with the same visitors and acceptors as the bytes,
+//! the [Kind] matcher recognizing a token of a given kind.
+//!
+//! The [LosslessLexer] doesn't drop anything: it categorizes every byte of
+//! the data, the trivia and the unknown bytes included, which suits the
+//! syntax highlighting and the tools rewriting the data.
+
+use crate::errors::ParseResult;
+use crate::invariants::ensure_progress;
+use crate::matcher::Match;
+use crate::scanner::Scanner;
+use crate::span::{Span, Spanned};
+use crate::visitor::Visitor;
+
+/// A visitor accepting nothing, to lex without trivia
+struct NoTrivia;
+
+impl<'a> Visitor<'a, u8> for NoTrivia {
+    fn accept(_scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+        Ok(NoTrivia)
+    }
+}
+
+/// Lex the whole data into tokens of kind `K`.
+///
+/// # Type Parameters
+///
+/// * `K` - The kind of the tokens, accepting one token.
+///
+/// # Arguments
+///
+/// * `data` - The data to lex.
+///
+/// # Returns
+///
+/// The tokens and their spans in the data.
+///
+/// # Errors
+///
+/// The error of `K` at the first position where no token can be accepted.
+pub fn lex<'a, K: Visitor<'a, u8>>(data: &'a [u8]) -> ParseResult<Vec<Spanned<K>>> {
+    lex_skipping::<K, NoTrivia>(data)
+}
+
+/// Lex the whole data into tokens of kind `K`, skipping the trivia accepted
+/// by `S` around the tokens.
+///
+/// # Type Parameters
+///
+/// * `K` - The kind of the tokens, accepting one token.
+/// * `S` - The trivia, like whitespaces or comments, which may be empty.
+///
+/// # Arguments
+///
+/// * `data` - The data to lex.
+///
+/// # Returns
+///
+/// The tokens and their spans in the data.
+///
+/// # Errors
+///
+/// The error of `K` at the first position where no token can be accepted.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::primitives::whitespace::OptionalWhitespaces;
+/// use elyze::lexer::lex_skipping;
+/// use elyze::span::Span;
+///
+/// let tokens = lex_skipping::<Number<u32>, OptionalWhitespaces>(b" 1  22 ")
+///     .expect("failed to lex");
+/// let spans = tokens.iter().map(|token| token.span).collect::<Vec<_>>();
+/// assert_eq!(spans, vec![Span::new(1, 2), Span::new(4, 6)]);
+/// ```
+pub fn lex_skipping<'a, K, S>(data: &'a [u8]) -> ParseResult<Vec<Spanned<K>>>
+where
+    K: Visitor<'a, u8>,
+    S: Visitor<'a, u8>,
+{
+    let mut scanner = Scanner::new(data);
+    let mut tokens = vec![];
+    scanner.visit::<S>()?;
+    while !scanner.is_empty() {
+        let start = scanner.current_position();
+        let token = scanner.visit::<Spanned<K>>()?;
+        // a token consuming nothing would be lexed forever
+        ensure_progress::<K, u8>(&scanner, start)?;
+        tokens.push(token);
+        scanner.visit::<S>()?;
+    }
+    Ok(tokens)
+}
+
+/// Return the position in the data of the token at `index`, the end of the
+/// last token if the index is past the tokens.
+///
+/// The errors of a token scanner are positions in the tokens, this maps them
+/// back to the lexed data.
+///
+/// # Arguments
+///
+/// * `tokens` - The lexed tokens.
+/// * `index` - The position in the tokens.
+pub fn byte_offset<K>(tokens: &[Spanned<K>], index: usize) -> usize {
+    match tokens.get(index) {
+        Some(token) => token.span.start,
+        None => tokens.last().map_or(0, |token| token.span.end),
+    }
+}
+
+/// Match one token of the given kind in a `Scanner<Spanned<K>>`.
+///
+/// # Example
+///
+/// ```
+/// use elyze::lexer::Kind;
+/// use elyze::recognizer::recognize;
+/// use elyze::scanner::Scanner;
+/// use elyze::span::{Span, Spanned};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tok {
+///     Plus,
+///     Minus,
+/// }
+///
+/// let tokens = [Spanned { value: Tok::Plus, span: Span::new(0, 1) }];
+/// let mut scanner = Scanner::new(&tokens);
+/// assert!(recognize(Kind(Tok::Minus), &mut scanner).is_err());
+/// recognize(Kind(Tok::Plus), &mut scanner).expect("failed to recognize");
+/// assert!(scanner.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kind<K>(pub K);
+
+impl<K: PartialEq> Match<Spanned<K>> for Kind<K> {
+    fn is_matching(&self, data: &[Spanned<K>]) -> (bool, usize) {
+        match data.first() {
+            Some(token) if token.value == self.0 => (true, 1),
+            _ => (false, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// The category of a lexeme produced by a [LosslessLexer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Lexeme<C> {
+    /// A token of the category
+    Token(C),
+    /// Trivia of the category, like whitespaces or comments
+    Trivia(C),
+    /// A run of bytes which no rule matches
+    Unknown,
+}
+
+impl<C> Lexeme<C> {
+    /// Return true if the lexeme is trivia
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, Lexeme::Trivia(_))
+    }
+
+    /// Return true if no rule matches the lexeme
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Lexeme::Unknown)
+    }
+}
+
+/// A rule of a [LosslessLexer]
+struct Rule<'m, C> {
+    /// The lexeme of the matched bytes
+    lexeme: Lexeme<C>,
+    /// The matcher of the bytes
+    matcher: Box<dyn Match<u8> + 'm>,
+}
+
+/// A lexer categorizing every byte of the data with matchers.
+///
+/// At each position, the longest match of the rules wins, the first rule
+/// winning a tie. The bytes which no rule matches are grouped in
+/// [Lexeme::Unknown] lexemes, so the spans of the lexemes always cover the
+/// whole data, one after the other.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::TokenNumber;
+/// use elyze::bytes::token::Token;
+/// use elyze::lexer::{Lexeme, LosslessLexer};
+/// use elyze::span::Span;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Category {
+///     Number,
+///     Operator,
+///     Space,
+/// }
+///
+/// let lexer = LosslessLexer::new()
+///     .with_token(Category::Number, TokenNumber)
+///     .with_token(Category::Operator, Token::Plus)
+///     .with_trivia(Category::Space, Token::Whitespace);
+/// let lexemes = lexer.lex(b"1 + ?2").collect::<Vec<_>>();
+/// assert_eq!(lexemes[2].value, Lexeme::Token(Category::Operator));
+/// assert_eq!(lexemes[4].value, Lexeme::Unknown);
+/// assert_eq!(lexemes[4].span, Span::new(4, 5));
+/// assert_eq!(lexemes.last().map(|lexeme| lexeme.span.end), Some(6));
+/// ```
+pub struct LosslessLexer<'m, C> {
+    /// The rules, in their priority order
+    rules: Vec<Rule<'m, C>>,
+}
+
+impl<C> Default for LosslessLexer<'_, C> {
+    fn default() -> Self {
+        LosslessLexer { rules: vec![] }
+    }
+}
+
+impl<'m, C: Clone> LosslessLexer<'m, C> {
+    /// Create a new `LosslessLexer` without any rule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule lexing the matches of the matcher as tokens of the category
+    pub fn with_token<M: Match<u8> + 'm>(self, category: C, matcher: M) -> Self {
+        self.with_rule(Lexeme::Token(category), matcher)
+    }
+
+    /// Add a rule lexing the matches of the matcher as trivia of the category
+    pub fn with_trivia<M: Match<u8> + 'm>(self, category: C, matcher: M) -> Self {
+        self.with_rule(Lexeme::Trivia(category), matcher)
+    }
+
+    fn with_rule<M: Match<u8> + 'm>(mut self, lexeme: Lexeme<C>, matcher: M) -> Self {
+        self.rules.push(Rule {
+            lexeme,
+            matcher: Box::new(matcher),
+        });
+        self
+    }
+
+    /// Return the longest non-empty match of the rules at the start of the
+    /// data
+    fn longest(&self, data: &[u8]) -> Option<(&Lexeme<C>, usize)> {
+        let mut longest: Option<(&Lexeme<C>, usize)> = None;
+        for rule in &self.rules {
+            if rule.matcher.size() > data.len() {
+                continue;
+            }
+            let (matching, size) = rule.matcher.is_matching(data);
+            if matching && size > longest.map_or(0, |(_, longest)| longest) {
+                longest = Some((&rule.lexeme, size));
+            }
+        }
+        longest
+    }
+
+    /// Lex the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to lex.
+    ///
+    /// # Returns
+    ///
+    /// An iterator of the lexemes and their spans, covering the whole data.
+    pub fn lex<'l>(&'l self, data: &'l [u8]) -> Lexemes<'l, 'm, C> {
+        Lexemes {
+            lexer: self,
+            data,
+            position: 0,
+        }
+    }
+}
+
+/// An iterator over the lexemes of the data lexed by a [LosslessLexer].
+pub struct Lexemes<'l, 'm, C> {
+    /// The lexer
+    lexer: &'l LosslessLexer<'m, C>,
+    /// The data to lex
+    data: &'l [u8],
+    /// The start of the next lexeme
+    position: usize,
+}
+
+impl<C: Clone> Iterator for Lexemes<'_, '_, C> {
+    type Item = Spanned<Lexeme<C>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.position;
+        if start == self.data.len() {
+            return None;
+        }
+        let lexeme = match self.lexer.longest(&self.data[start..]) {
+            Some((lexeme, size)) => {
+                self.position += size;
+                lexeme.clone()
+            }
+            None => {
+                // the unknown bytes run up to the next match
+                self.position += 1;
+                while self.position < self.data.len()
+                    && self.lexer.longest(&self.data[self.position..]).is_none()
+                {
+                    self.position += 1;
+                }
+                Lexeme::Unknown
+            }
+        };
+        Some(Spanned {
+            value: lexeme,
+            span: Span::new(start, self.position),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acceptor::Acceptor;
+    use crate::alternatives::CompiledAlternatives;
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::primitives::number::TokenNumber;
+    use crate::bytes::primitives::whitespace::OptionalWhitespaces;
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::lexer::{byte_offset, lex, lex_skipping, Kind, Lexeme, LosslessLexer};
+    use crate::matcher::Match;
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::span::{Span, Spanned};
+    use crate::visitor::Visitor;
+
+    /// The tokens of an arithmetic expression
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tok {
+        Number(u32),
+        Plus,
+        Star,
+    }
+
+    impl<'a> Visitor<'a, u8> for Tok {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            match scanner.first() {
+                Some(b'+') => recognize(Token::Plus, scanner).map(|_| Tok::Plus),
+                Some(b'*') => recognize(Token::Star, scanner).map(|_| Tok::Star),
+                _ => scanner
+                    .visit::<Number<u32>>()
+                    .map(|Number(n)| Tok::Number(n)),
+            }
+        }
+    }
+
+    /// A number token
+    struct Operand(u32);
+
+    impl<'a> Visitor<'a, Spanned<Tok>> for Operand {
+        fn accept(scanner: &mut Scanner<'a, Spanned<Tok>>) -> ParseResult<Self> {
+            match scanner.first() {
+                Some(Spanned {
+                    value: Tok::Number(number),
+                    ..
+                }) => {
+                    let number = *number;
+                    scanner.bump_by(1);
+                    Ok(Operand(number))
+                }
+                _ => Err(ParseError::unexpected_token(scanner.current_position())),
+            }
+        }
+    }
+
+    /// A product `a * b` or an operand
+    struct Product(u32);
+
+    impl<'a> Visitor<'a, Spanned<Tok>> for Product {
+        fn accept(scanner: &mut Scanner<'a, Spanned<Tok>>) -> ParseResult<Self> {
+            let Operand(mut product) = scanner.visit()?;
+            while recognize(Kind(Tok::Star), scanner).is_ok() {
+                let Operand(factor) = scanner.visit()?;
+                product *= factor;
+            }
+            Ok(Product(product))
+        }
+    }
+
+    /// A sum of products
+    #[derive(Debug)]
+    struct Sum(u32);
+
+    impl<'a> Visitor<'a, Spanned<Tok>> for Sum {
+        fn accept(scanner: &mut Scanner<'a, Spanned<Tok>>) -> ParseResult<Self> {
+            let Product(mut sum) = scanner.visit()?;
+            while !scanner.is_empty() {
+                recognize(Kind(Tok::Plus), scanner)?;
+                let Product(term) = scanner.visit()?;
+                sum += term;
+            }
+            Ok(Sum(sum))
+        }
+    }
+
+    #[test]
+    fn test_lex() {
+        let tokens = lex_skipping::<Tok, OptionalWhitespaces>(b"1 + 2*3 ").expect("failed to lex");
+        let kinds = tokens.iter().map(|token| token.value).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                Tok::Number(1),
+                Tok::Plus,
+                Tok::Number(2),
+                Tok::Star,
+                Tok::Number(3)
+            ]
+        );
+        assert_eq!(tokens[2].span, Span::new(4, 5));
+
+        let result = lex::<Tok>(b"1+x");
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_tokens() {
+        let tokens =
+            lex_skipping::<Tok, OptionalWhitespaces>(b"1 + 2 * 3 + 4").expect("failed to lex");
+        let Sum(sum) = Scanner::new(&tokens).visit().expect("failed to parse");
+        assert_eq!(sum, 11);
+
+        // an acceptor over tokens
+        let operator = Acceptor::new(&mut Scanner::new(&tokens[1..]))
+            .try_or(|Operand(number)| number)
+            .expect("failed to accept")
+            .finish();
+        assert_eq!(operator, None);
+
+        // the error is reported in the tokens, then in the data
+        let tokens = lex_skipping::<Tok, OptionalWhitespaces>(b"1 + * 2").expect("failed to lex");
+        let err = Scanner::new(&tokens)
+            .visit::<Sum>()
+            .expect_err("parsed an invalid sum");
+        assert_eq!(err.offset(), Some(2));
+        assert_eq!(byte_offset(&tokens, 2), 4);
+        assert_eq!(byte_offset(&tokens, 9), 7);
+    }
+
+    /// A run of letters
+    struct Identifier;
+
+    impl Match<u8> for Identifier {
+        fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+            let size = data
+                .iter()
+                .take_while(|byte| byte.is_ascii_alphabetic())
+                .count();
+            (size > 0, size)
+        }
+
+        fn size(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_lossless_lexer() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Category {
+            Number,
+            Keyword,
+            Identifier,
+            Space,
+        }
+
+        let lexer = LosslessLexer::new()
+            .with_token(Category::Keyword, CompiledAlternatives::compile(&[b"let"]))
+            .with_token(Category::Identifier, Identifier)
+            .with_token(Category::Number, TokenNumber)
+            .with_trivia(Category::Space, Token::Whitespace);
+        let data = b"let letter = 42;;";
+        let lexemes = lexer.lex(data).collect::<Vec<_>>();
+        let values = lexemes
+            .iter()
+            .map(|lexeme| lexeme.value)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                // the keyword wins a tie, the identifier is longer
+                Lexeme::Token(Category::Keyword),
+                Lexeme::Trivia(Category::Space),
+                Lexeme::Token(Category::Identifier),
+                Lexeme::Trivia(Category::Space),
+                Lexeme::Unknown,
+                Lexeme::Trivia(Category::Space),
+                Lexeme::Token(Category::Number),
+                Lexeme::Unknown,
+            ]
+        );
+        assert_eq!(lexemes[7].span, Span::new(15, 17));
+
+        // the lexemes cover the whole data
+        let mut end = 0;
+        for lexeme in &lexemes {
+            assert_eq!(lexeme.span.start, end);
+            end = lexeme.span.end;
+        }
+        assert_eq!(end, data.len());
+
+        let lexemes = LosslessLexer::<Category>::new()
+            .lex(b"ab")
+            .collect::<Vec<_>>();
+        assert_eq!(lexemes.len(), 1);
+        assert!(lexemes[0].is_unknown());
+        assert_eq!(LosslessLexer::<Category>::new().lex(b"").count(), 0);
+    }
+}
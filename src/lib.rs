@@ -1,11 +1,42 @@
 #![doc = include_str!("../Readme.md")]
+// the derived visitors refer to `::elyze`, including the ones of the tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as elyze;
+
 pub mod acceptor;
+pub mod alternatives;
+#[cfg(feature = "bumpalo")]
+pub mod arena;
+#[cfg(feature = "bio")]
+pub mod bio;
 pub mod bytes;
+pub mod chars;
+pub mod columnar;
+pub mod combinators;
+pub mod completion;
+pub mod context;
 pub mod errors;
+pub mod formats;
+pub mod generate;
+pub mod highlight;
+pub mod invariants;
+pub mod lexer;
 pub mod matcher;
+#[cfg(feature = "nom-compat")]
+pub mod nom_compat;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 pub mod peek;
 pub mod peeker;
 pub mod recognizer;
 pub mod scanner;
+pub mod scrub;
+mod sealed;
+pub mod search;
 pub mod separated_list;
+pub mod span;
+pub mod testing;
+#[cfg(feature = "unstable")]
+pub mod unstable;
+pub mod validate;
 pub mod visitor;
@@ -1,5 +1,7 @@
 //! Provides the `Match` trait.
 
+use std::sync::OnceLock;
+
 /// Describes a matchable object.
 pub trait Match<T> {
     /// Returns true if the data matches the pattern.
@@ -13,4 +15,275 @@ pub trait Match<T> {
     fn is_matching(&self, data: &[T]) -> (bool, usize);
     /// Returns the size of the matchable object.
     fn size(&self) -> usize;
+    /// Returns the description of the matched element, reported to the
+    /// [completion](crate::completion) hints when it is expected.
+    ///
+    /// By default, the matched element isn't described.
+    fn describe(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Any reference to a matchable object is matchable, this allows to use
+/// matchers holding owned data without copying them.
+impl<T, M: Match<T> + ?Sized> Match<T> for &M {
+    #[inline]
+    fn is_matching(&self, data: &[T]) -> (bool, usize) {
+        (**self).is_matching(data)
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        (**self).size()
+    }
+
+    fn describe(&self) -> Option<String> {
+        (**self).describe()
+    }
+}
+
+/// Defines how a pattern is compared to the data.
+///
+/// By default, the comparison is case-sensitive.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::matchers::match_pattern_with;
+/// use elyze::matcher::MatchOptions;
+///
+/// let options = MatchOptions::default();
+/// assert_eq!(match_pattern_with(b"SELECT", b"select *", options), (false, 0));
+/// let options = options.case_sensitive(false);
+/// assert_eq!(match_pattern_with(b"SELECT", b"select *", options), (true, 6));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Whether the ASCII letters must have the case of the pattern
+    case_sensitive: bool,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatchOptions {
+    /// Create the default options, usable in `const` contexts.
+    pub const fn new() -> Self {
+        MatchOptions {
+            case_sensitive: true,
+        }
+    }
+
+    /// Compare the ASCII letters with or without their case.
+    ///
+    /// The other characters are always compared as is.
+    pub const fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Return whether the ASCII letters must have the case of the pattern
+    pub const fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+/// A matcher of a fixed sequence of elements, compared exactly.
+///
+/// A `Seq` can be built in `const` contexts, so token tables can live in
+/// constants and statics without any lazy initialization.
+///
+/// # Example
+///
+/// ```
+/// use elyze::matcher::{Match, Seq};
+/// use elyze::recognizer::recognize_slice;
+/// use elyze::scanner::Scanner;
+///
+/// const ARROW: Seq = Seq::new(b"->");
+/// static OPERATORS: [Seq; 3] = [ARROW, Seq::new(b"=>"), Seq::new(b"::")];
+///
+/// let mut scanner = Scanner::new(b"=>x");
+/// let operator = OPERATORS
+///     .iter()
+///     .find_map(|operator| recognize_slice(operator, &mut scanner).ok());
+/// assert_eq!(operator, Some(&b"=>"[..]));
+/// assert_eq!(ARROW.size(), 2);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Seq<'a, T = u8>(&'a [T]);
+
+impl<'a, T> Seq<'a, T> {
+    /// Create a matcher of the sequence.
+    pub const fn new(sequence: &'a [T]) -> Self {
+        Seq(sequence)
+    }
+
+    /// Return the matched sequence
+    pub const fn sequence(&self) -> &'a [T] {
+        self.0
+    }
+}
+
+impl<T> Clone for Seq<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Seq<'_, T> {}
+
+impl<T: PartialEq> Match<T> for Seq<'_, T> {
+    fn is_matching(&self, data: &[T]) -> (bool, usize) {
+        if !self.0.is_empty() && data.starts_with(self.0) {
+            (true, self.0.len())
+        } else {
+            (false, 0)
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A matcher built on its first use and kept for the next ones.
+///
+/// The heavy matchers, like the
+/// [CompiledAlternatives](crate::alternatives::CompiledAlternatives), are meant
+/// to be built once. Stored in a `static`, see
+/// [static_matcher](crate::static_matcher), a `LazyMatcher` is shared by all
+/// the calls of a visitor and all the threads.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::matcher::LazyMatcher;
+/// use elyze::recognizer::recognize_slice;
+/// use elyze::scanner::Scanner;
+///
+/// static KEYWORDS: LazyMatcher<CompiledAlternatives<u8>> =
+///     LazyMatcher::new(|| CompiledAlternatives::compile(&["let", "fn", "if"]));
+///
+/// let mut scanner = Scanner::new(b"fn main");
+/// let keyword = recognize_slice(&KEYWORDS, &mut scanner).expect("failed to parse");
+/// assert_eq!(keyword, b"fn");
+/// ```
+pub struct LazyMatcher<M, F = fn() -> M> {
+    /// The matcher, once built
+    matcher: OnceLock<M>,
+    /// Build the matcher
+    init: F,
+}
+
+impl<M, F> LazyMatcher<M, F> {
+    /// Create a new `LazyMatcher`
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - Build the matcher on its first use
+    pub const fn new(init: F) -> Self {
+        LazyMatcher {
+            matcher: OnceLock::new(),
+            init,
+        }
+    }
+}
+
+impl<M, F: Fn() -> M> LazyMatcher<M, F> {
+    /// Return the matcher, building it on the first call
+    #[inline]
+    pub fn get(&self) -> &M {
+        self.matcher.get_or_init(&self.init)
+    }
+}
+
+impl<T, M: Match<T>, F: Fn() -> M> Match<T> for LazyMatcher<M, F> {
+    #[inline]
+    fn is_matching(&self, data: &[T]) -> (bool, usize) {
+        self.get().is_matching(data)
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.get().size()
+    }
+
+    fn describe(&self) -> Option<String> {
+        self.get().describe()
+    }
+}
+
+/// Declare a `static` [LazyMatcher], built on its first use.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::matcher::Match;
+/// use elyze::static_matcher;
+///
+/// static_matcher! {
+///     /// The comparison operators
+///     static OPERATORS: CompiledAlternatives<u8> =
+///         CompiledAlternatives::compile(&["<", "<=", ">", ">=", "==", "!="]);
+/// }
+///
+/// assert_eq!(OPERATORS.is_matching(b"<= 2"), (true, 2));
+/// ```
+#[macro_export]
+macro_rules! static_matcher {
+    ($(#[$meta:meta])* $vis:vis static $name:ident: $type:ty = $init:expr;) => {
+        $(#[$meta])*
+        $vis static $name: $crate::matcher::LazyMatcher<$type> =
+            $crate::matcher::LazyMatcher::new(|| $init);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alternatives::CompiledAlternatives;
+    use crate::matcher::{LazyMatcher, Match, Seq};
+    use crate::recognizer::recognize_slice;
+    use crate::scanner::Scanner;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+    static_matcher! {
+        static UNITS: CompiledAlternatives<u8> = {
+            BUILDS.fetch_add(1, Ordering::SeqCst);
+            CompiledAlternatives::compile(&["ms", "s", "min"])
+        };
+    }
+
+    #[test]
+    fn test_lazy_matcher() {
+        for _ in 0..3 {
+            let mut scanner = Scanner::new(b"min");
+            let unit = recognize_slice(&UNITS, &mut scanner).expect("failed to parse");
+            assert_eq!(unit, b"min");
+        }
+        assert_eq!(UNITS.size(), 1);
+        assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+
+        // a capturing closure builds a local matcher
+        let separator = b",".to_vec();
+        let matcher = LazyMatcher::new(move || CompiledAlternatives::compile(&[&separator]));
+        assert_eq!(matcher.is_matching(b",x"), (true, 1));
+    }
+
+    #[test]
+    fn test_seq() {
+        const NEWLINE: Seq<char> = Seq::new(&['\r', '\n']);
+        assert_eq!(NEWLINE.is_matching(&['\r', '\n', 'a']), (true, 2));
+        assert_eq!(NEWLINE.is_matching(&['\r']), (false, 0));
+        assert_eq!(NEWLINE.sequence().len(), NEWLINE.size());
+        // the empty sequence never matches
+        assert_eq!(Seq::<u8>::new(b"").is_matching(b"a"), (false, 0));
+    }
 }
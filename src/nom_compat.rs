@@ -0,0 +1,267 @@
+//! Interoperate with the nom parsers, enabled by the `nom-compat` feature.
+//!
+//! A grammar can be migrated from nom to elyze one rule at a time: the
+//! elyze visitors and recognizables are turned into nom parsers with
+//! [visitor_parser] and [recognizer_parser], and the nom parsers are applied
+//! inside a visitor with [parse_nom].
+//!
+//! The mismatches map to `nom::Err::Error` and the other errors to
+//! `nom::Err::Failure`, so `nom::branch::alt` only tries its next branch when
+//! the rule doesn't apply, as the elyze alternatives do.
+
+use crate::combinators::is_recoverable;
+use crate::errors::{ParseError, ParseResult};
+use crate::recognizer::Recognizable;
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+use nom::error::{ErrorKind, FromExternalError};
+use nom::IResult;
+
+/// Convert an elyze error into a nom error.
+///
+/// The input of the nom error starts at the offset of the error, or at the
+/// current position of the scanner if the error has no offset.
+fn to_nom_err<'a, T, E>(err: ParseError, scanner: &Scanner<'a, T>) -> nom::Err<E>
+where
+    E: FromExternalError<&'a [T], ParseError>,
+{
+    let data = scanner.data();
+    let offset = err
+        .offset()
+        .unwrap_or(scanner.current_position())
+        .min(data.len());
+    let kind = match err.root() {
+        ParseError::UnexpectedEndOfInput => ErrorKind::Eof,
+        _ => ErrorKind::Fail,
+    };
+    if is_recoverable(&err) {
+        nom::Err::Error(E::from_external_error(&data[offset..], kind, err))
+    } else {
+        nom::Err::Failure(E::from_external_error(&data[offset..], kind, err))
+    }
+}
+
+/// Turn a visitor into a nom parser.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data
+/// * `V` - The visitor
+/// * `E` - The nom error, receiving the elyze error as an external error
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::nom_compat::visitor_parser;
+/// use nom::sequence::separated_pair;
+/// use nom::bytes::complete::tag;
+///
+/// let mut size = separated_pair(
+///     visitor_parser::<u8, Number<u16>, nom::error::Error<_>>(),
+///     tag("x"),
+///     visitor_parser::<u8, Number<u16>, nom::error::Error<_>>(),
+/// );
+/// let (rest, (Number(width), Number(height))) = size(&b"640x480;"[..]).expect("failed to parse");
+/// assert_eq!((width, height), (640, 480));
+/// assert_eq!(rest, b";");
+/// ```
+pub fn visitor_parser<'a, T: 'a, V, E>() -> impl Fn(&'a [T]) -> IResult<&'a [T], V, E>
+where
+    V: Visitor<'a, T>,
+    E: FromExternalError<&'a [T], ParseError>,
+{
+    |input| {
+        let mut scanner = Scanner::new(input);
+        match V::accept(&mut scanner) {
+            Ok(value) => Ok((scanner.remaining(), value)),
+            Err(err) => Err(to_nom_err(err, &scanner)),
+        }
+    }
+}
+
+/// Turn a recognizable into a nom parser.
+///
+/// # Arguments
+///
+/// * `recognizable` - The object to recognize, cloned by each call
+///
+/// # Errors
+///
+/// The parser fails with a `nom::Err::Error` if the recognizable isn't at
+/// the start of the input.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::nom_compat::recognizer_parser;
+/// use nom::multi::many1;
+///
+/// let mut pluses = many1(recognizer_parser::<u8, _, _, nom::error::Error<_>>(Token::Plus));
+/// let (rest, tokens) = pluses(&b"++-"[..]).expect("failed to parse");
+/// assert_eq!(tokens, [Token::Plus, Token::Plus]);
+/// assert_eq!(rest, b"-");
+/// ```
+pub fn recognizer_parser<'a, T: 'a, V, R, E>(
+    recognizable: R,
+) -> impl Fn(&'a [T]) -> IResult<&'a [T], V, E>
+where
+    R: Recognizable<'a, T, V> + Clone,
+    E: FromExternalError<&'a [T], ParseError>,
+{
+    move |input| {
+        let mut scanner = Scanner::new(input);
+        match recognizable.clone().recognize(&mut scanner) {
+            Ok(Some(value)) => Ok((scanner.remaining(), value)),
+            Ok(None) => {
+                let err = ParseError::unexpected_token(scanner.current_position());
+                Err(to_nom_err(err, &scanner))
+            }
+            Err(err) => Err(to_nom_err(err, &scanner)),
+        }
+    }
+}
+
+/// Apply a nom parser to the scanner, which is moved after the parsed data.
+///
+/// # Arguments
+///
+/// * `parser` - The nom parser
+/// * `scanner` - The scanner to parse
+///
+/// # Returns
+///
+/// The output of the parser.
+///
+/// # Errors
+///
+/// * `ParseError::UnexpectedToken` at the position of a `nom::Err::Error`
+/// * `ParseError::UnexpectedEndOfInput` if the parser needs more data
+/// * A custom error describing a `nom::Err::Failure`
+///
+/// # Example
+///
+/// ```
+/// use elyze::errors::ParseResult;
+/// use elyze::nom_compat::parse_nom;
+/// use elyze::scanner::Scanner;
+/// use elyze::visitor::Visitor;
+/// use nom::character::complete::{alpha1, digit1};
+/// use nom::sequence::pair;
+///
+/// /// An identifier like `abc123`, parsed by nom
+/// struct Identifier<'a>(&'a [u8], &'a [u8]);
+///
+/// impl<'a> Visitor<'a, u8> for Identifier<'a> {
+///     fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+///         let (letters, digits) = parse_nom(pair(alpha1, digit1), scanner)?;
+///         Ok(Identifier(letters, digits))
+///     }
+/// }
+///
+/// let mut scanner = Scanner::new(b"abc123 = 4");
+/// let Identifier(letters, digits) = scanner.visit().expect("failed to parse");
+/// assert_eq!((letters, digits), (&b"abc"[..], &b"123"[..]));
+/// assert_eq!(scanner.remaining(), b" = 4");
+/// ```
+pub fn parse_nom<'a, T, O, P>(mut parser: P, scanner: &mut Scanner<'a, T>) -> ParseResult<O>
+where
+    P: nom::Parser<&'a [T], O, nom::error::Error<&'a [T]>>,
+{
+    let remaining = scanner.remaining();
+    // the position of an input returned by the parser, a suffix of the
+    // remaining data
+    let position = |input: &[T]| scanner.current_position() + remaining.len() - input.len();
+    match parser.parse(remaining) {
+        Ok((rest, output)) => {
+            let position = position(rest);
+            scanner.jump_to(position);
+            Ok(output)
+        }
+        Err(nom::Err::Error(err)) => {
+            Err(ParseError::unexpected_token(position(err.input)).expecting(err.code.description()))
+        }
+        Err(nom::Err::Failure(err)) => Err(ParseError::custom(format!(
+            "{} failed at {}",
+            err.code.description(),
+            position(err.input)
+        ))),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::UnexpectedEndOfInput),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::{ParseError, ParseResult};
+    use crate::nom_compat::{parse_nom, recognizer_parser, visitor_parser};
+    use crate::scanner::Scanner;
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::bytes::streaming;
+    use nom::combinator::{cut, map};
+    use nom::error::{Error, ErrorKind};
+
+    #[test]
+    fn test_visitor_parser() {
+        let mut parser = alt((
+            map(visitor_parser::<u8, Number<u8>, Error<_>>(), |Number(n)| n),
+            map(tag("none"), |_| 0),
+        ));
+        assert_eq!(parser(&b"12,"[..]), Ok((&b","[..], 12)));
+        assert_eq!(parser(&b"none"[..]), Ok((&b""[..], 0)));
+        assert_eq!(
+            parser(&b"x"[..]),
+            Err(nom::Err::Error(Error::new(&b"x"[..], ErrorKind::Tag)))
+        );
+
+        // an error which isn't a mismatch stops the alternatives
+        assert!(matches!(
+            parser(&b"300"[..]),
+            Err(nom::Err::Failure(Error {
+                code: ErrorKind::Fail,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_recognizer_parser() {
+        let parser = recognizer_parser::<u8, _, _, Error<_>>(Token::Comma);
+        assert_eq!(parser(&b",1"[..]), Ok((&b"1"[..], Token::Comma)));
+        assert_eq!(
+            parser(&b"1,"[..]),
+            Err(nom::Err::Error(Error::new(&b"1,"[..], ErrorKind::Fail)))
+        );
+        assert_eq!(
+            parser(&b""[..]),
+            Err(nom::Err::Error(Error::new(&b""[..], ErrorKind::Eof)))
+        );
+    }
+
+    #[test]
+    fn test_parse_nom() {
+        let mut scanner = Scanner::new(b"1:ab");
+        scanner.bump_by(2);
+        let result: ParseResult<&[u8]> = parse_nom(tag("ab"), &mut scanner);
+        assert_eq!(result.expect("failed to parse"), b"ab");
+        assert!(scanner.is_empty());
+
+        let mut scanner = Scanner::new(b"1:ac");
+        scanner.bump_by(2);
+        let result: ParseResult<_> = parse_nom(tag("ab"), &mut scanner);
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+        assert_eq!(scanner.current_position(), 2);
+
+        let result: ParseResult<_> = parse_nom(cut(tag("ab")), &mut Scanner::new(b"x"));
+        assert!(matches!(result, Err(ParseError::Custom(_))));
+
+        let result: ParseResult<_> = parse_nom(streaming::tag("ab"), &mut Scanner::new(b"a"));
+        assert!(matches!(result, Err(ParseError::UnexpectedEndOfInput)));
+    }
+}
@@ -0,0 +1,228 @@
+//! Parse delimited records in parallel.
+//!
+//! The input is cut into chunks at separators found by a [Peekable], such as
+//! the newlines of a log file. The chunks are parsed on the current rayon
+//! pool, the global one unless the call runs inside
+//! `ThreadPool::install`, and their results are stitched back in the order of
+//! the input.
+//!
+//! A chunk starts right after a separator found by peeking from an arbitrary
+//! position, so the separator must never appear inside a record, a quoted
+//! newline for instance would break a newline-delimited input.
+
+use crate::errors::ParseError;
+use crate::peek::{peek_dyn, Peekable};
+use crate::scanner::Scanner;
+use crate::span::{Span, Spanned};
+use crate::visitor::Visitor;
+use rayon::prelude::*;
+
+/// The records and the diagnostics of a parallel parse, in the order of the
+/// input.
+#[derive(Debug)]
+pub struct ParallelParse<V> {
+    /// The parsed records
+    pub records: Vec<V>,
+    /// The errors of the records which couldn't be parsed, the span being
+    /// the one of the record and the error positions being positions in the
+    /// whole input
+    pub errors: Vec<Spanned<ParseError>>,
+}
+
+impl<V> Default for ParallelParse<V> {
+    fn default() -> Self {
+        ParallelParse {
+            records: vec![],
+            errors: vec![],
+        }
+    }
+}
+
+impl<V> ParallelParse<V> {
+    /// Append the results of the next chunk
+    fn append(mut self, mut chunk: ParallelParse<V>) -> Self {
+        self.records.append(&mut chunk.records);
+        self.errors.append(&mut chunk.errors);
+        self
+    }
+}
+
+/// Find the positions where the data can be cut into chunks of about
+/// `chunk_size` elements, each position following a separator.
+///
+/// # Arguments
+///
+/// * `data` - The data to cut
+/// * `separator` - The separator of the records
+/// * `chunk_size` - The minimal size of a chunk, 0 being treated as 1
+///
+/// # Returns
+///
+/// The increasing split positions, excluding 0 and the end of the data.
+pub fn split_points<'a, T>(
+    data: &'a [T],
+    separator: &dyn Peekable<'a, T>,
+    chunk_size: usize,
+) -> Vec<usize> {
+    let chunk_size = chunk_size.max(1);
+    let mut scanner = Scanner::new(data);
+    let mut points = vec![];
+    let mut target = chunk_size;
+    while target < data.len() {
+        scanner.jump_to(target);
+        let Ok(Some(peeked)) = peek_dyn(separator, &scanner) else {
+            break;
+        };
+        let point = target + peeked.end_slice;
+        if point >= data.len() {
+            break;
+        }
+        points.push(point);
+        target = point.saturating_add(chunk_size);
+    }
+    points
+}
+
+/// Parse the records of a chunk starting at `offset` in the input
+fn parse_chunk<'a, T, V>(
+    chunk: &'a [T],
+    offset: usize,
+    separator: &dyn Peekable<'a, T>,
+) -> ParallelParse<V>
+where
+    V: Visitor<'a, T>,
+{
+    let mut parse = ParallelParse::default();
+    let mut scanner = Scanner::new(chunk);
+    while !scanner.is_empty() {
+        let start = scanner.current_position();
+        let (record, next) = match peek_dyn(separator, &scanner) {
+            Ok(Some(peeked)) => (peeked.peeked_slice(), start + peeked.end_slice),
+            _ => (scanner.remaining(), chunk.len()),
+        };
+        scanner.jump_to(next);
+        if record.is_empty() {
+            continue;
+        }
+        let start = offset + start;
+        let mut record_scanner = Scanner::new(record);
+        let result = record_scanner.visit::<V>().and_then(|value| {
+            if record_scanner.is_empty() {
+                return Ok(value);
+            }
+            let position = record_scanner.current_position();
+            Err(ParseError::unexpected_token(position).expecting("the end of the record"))
+        });
+        match result {
+            Ok(value) => parse.records.push(value),
+            Err(err) => parse.errors.push(Spanned {
+                value: err.shifted(start),
+                span: Span::new(start, start + record.len()),
+            }),
+        }
+    }
+    parse
+}
+
+/// Parse the records delimited by a separator in parallel.
+///
+/// Each record is the data between two separators and must be accepted
+/// entirely by the visitor, the empty records are skipped. A record which
+/// can't be parsed is reported in the errors and the parse goes on with the
+/// next one.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data
+/// * `V` - The visitor of a record
+///
+/// # Arguments
+///
+/// * `data` - The data to parse
+/// * `separator` - The separator of the records
+/// * `chunk_size` - The minimal number of elements parsed by a task, see
+///   [split_points]
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::token::Token;
+/// use elyze::parallel::parse_parallel;
+///
+/// let log = b"200\n404\noops\n\n500\n";
+/// let parse = parse_parallel::<u8, Number<u16>, _>(log, Token::Ln, 4);
+/// let codes = parse.records.iter().map(|Number(code)| *code).collect::<Vec<_>>();
+/// assert_eq!(codes, [200, 404, 500]);
+/// assert_eq!(parse.errors.len(), 1);
+/// assert_eq!(parse.errors[0].span.slice(log), Some(&b"oops"[..]));
+/// ```
+pub fn parse_parallel<'a, T, V, P>(
+    data: &'a [T],
+    separator: P,
+    chunk_size: usize,
+) -> ParallelParse<V>
+where
+    T: Sync,
+    V: Visitor<'a, T> + Send,
+    P: Peekable<'a, T> + Sync,
+{
+    let mut bounds = vec![0];
+    bounds.extend(split_points(data, &separator, chunk_size));
+    bounds.push(data.len());
+    bounds
+        .par_windows(2)
+        .map(|bounds| parse_chunk(&data[bounds[0]..bounds[1]], bounds[0], &separator))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .fold(ParallelParse::default(), ParallelParse::append)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::parallel::{parse_parallel, split_points};
+    use crate::span::Span;
+
+    #[test]
+    fn test_split_points() {
+        let data = b"1,22,333,4444,5";
+        assert_eq!(split_points(data, &Token::Comma, 3), [5, 9, 14]);
+        assert_eq!(split_points(data, &Token::Comma, 100), [] as [usize; 0]);
+        assert_eq!(split_points(data, &Token::Comma, 0), [2, 5, 9, 14]);
+        assert_eq!(split_points(b"12345", &Token::Comma, 1), [] as [usize; 0]);
+        assert_eq!(split_points(b"1,", &Token::Comma, 1), [] as [usize; 0]);
+    }
+
+    #[test]
+    fn test_parse_parallel() {
+        let data = (0..1000)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        for chunk_size in [1, 7, 100, 10_000] {
+            let parse =
+                parse_parallel::<u8, Number<u32>, _>(data.as_bytes(), Token::Ln, chunk_size);
+            let numbers = parse.records.iter().map(|Number(n)| *n).collect::<Vec<_>>();
+            assert_eq!(numbers, (0..1000).collect::<Vec<_>>());
+            assert!(parse.errors.is_empty());
+        }
+
+        // the diagnostics are in order, with positions in the whole input
+        let data = b"1\nx\n2\n3y\n";
+        let parse = parse_parallel::<u8, Number<u32>, _>(data, Token::Ln, 2);
+        assert_eq!(parse.records.len(), 2);
+        let spans = parse.errors.iter().map(|err| err.span).collect::<Vec<_>>();
+        assert_eq!(spans, [Span::new(2, 3), Span::new(6, 8)]);
+        assert!(matches!(
+            parse.errors[0].value,
+            ParseError::UnexpectedToken { offset: 2, .. }
+        ));
+        assert!(matches!(
+            parse.errors[1].value,
+            ParseError::UnexpectedToken { offset: 7, .. }
+        ));
+    }
+}
@@ -116,6 +116,13 @@ impl<'a, T> From<Option<Peeking<'a, T>>> for PeekResult {
 //------------------------------------------------------------------------------
 
 /// A trait that can be used to define a peek size.
+///
+/// The `Peekable` implementation of visitors now uses the number of elements
+/// actually consumed by the visitor, this trait is no longer required.
+#[deprecated(
+    since = "2.0.0",
+    note = "the end element size is now the size consumed by the visitor"
+)]
 pub trait PeekSize<T> {
     /// The `peek_size` method should return the size of the `Peekable`.
     fn peek_size(&self) -> usize {
@@ -124,6 +131,7 @@ pub trait PeekSize<T> {
 }
 
 /// A default implementation of the `PeekSize` trait for any `Match`.
+#[allow(deprecated)]
 impl<T, M: Match<T>> PeekSize<T> for M {
     fn peek_size(&self) -> usize {
         self.size()
@@ -172,49 +180,163 @@ pub fn peek<'a, T, P: Peekable<'a, T>>(
     peekable: P,
     scanner: &Scanner<'a, T>,
 ) -> ParseResult<Option<Peeking<'a, T>>> {
-    let source_cursor = scanner.current_position();
-    match peekable.peek(scanner)? {
+    Ok(peeking(peekable.peek(scanner)?, scanner))
+}
+
+/// Attempt to match a type-erased `Peekable` against the current position of
+/// a `Scanner`.
+///
+/// Unlike [peek], this function is compiled once per type of data whatever
+/// the peekable, which keeps the code of big grammars small. It is meant for
+/// the cold paths, the hot ones should keep the generic function.
+///
+/// # Arguments
+///
+/// * `peekable` - The `Peekable` to attempt to match.
+/// * `scanner` - The `Scanner` to use when matching.
+///
+/// # Returns
+///
+/// A `Peeking` if the `Peekable` matches the current position of the `Scanner`,
+/// or an `Err` otherwise.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::peek::{peek_dyn, Peekable};
+/// use elyze::scanner::Scanner;
+///
+/// let terminators: [&dyn Peekable<u8>; 2] = [&Token::Semicolon, &Token::Comma];
+/// let scanner = Scanner::new(b"a, b; c");
+/// let peeked = terminators
+///     .iter()
+///     .find_map(|terminator| peek_dyn(*terminator, &scanner).ok().flatten());
+/// assert_eq!(peeked.expect("failed to peek").peeked_slice(), b"a, b");
+/// ```
+pub fn peek_dyn<'a, T>(
+    peekable: &dyn Peekable<'a, T>,
+    scanner: &Scanner<'a, T>,
+) -> ParseResult<Option<Peeking<'a, T>>> {
+    Ok(peeking(peekable.peek(scanner)?, scanner))
+}
+
+/// Turn the result of a peek from the current position of the scanner into
+/// a `Peeking`
+fn peeking<'a, T>(result: PeekResult, scanner: &Scanner<'a, T>) -> Option<Peeking<'a, T>> {
+    match result {
         PeekResult::Found {
             end_slice,
             start_element_size: start,
             end_element_size: end,
         } => {
+            let source_cursor = scanner.current_position();
             let data = &scanner.data()[source_cursor..source_cursor + end_slice];
-            Ok(Some(Peeking {
+            Some(Peeking {
                 start_element_size: start,
                 end_element_size: end,
                 end_slice,
                 data,
-            }))
+            })
         }
-        PeekResult::NotFound => Ok(None),
+        PeekResult::NotFound => None,
     }
 }
 
-/// Make Peekable any Visitor implementing the PeekSize trait
+/// Find the first position where the visitor succeeds.
+///
+/// The visitor is run at each position of the remaining data until it
+/// succeeds. The `end_element_size` function receives the visited element and
+/// the number of elements it consumed, and returns the size of the end element.
+fn peek_visitor<'a, T, V, F>(data: &Scanner<'a, T>, end_element_size: F) -> ParseResult<PeekResult>
+where
+    V: Visitor<'a, T>,
+    F: Fn(&V, usize) -> usize,
+{
+    // create a temporary scanner to peek data
+    let mut scanner = Scanner::new(data.remaining());
+    while !scanner.is_empty() {
+        let position = scanner.current_position();
+        match V::accept(&mut scanner) {
+            Ok(element) => {
+                let consumed = scanner.current_position() - position;
+                return Ok(PeekResult::Found {
+                    end_slice: scanner.current_position(),
+                    start_element_size: 0,
+                    end_element_size: end_element_size(&element, consumed),
+                });
+            }
+            Err(_err) => {
+                // the visitor may have consumed data before failing
+                scanner.jump_to(position);
+                scanner.advance_checked(1)?;
+                continue;
+            }
+        }
+    }
+    Ok(PeekResult::NotFound)
+}
+
+/// Make Peekable any Visitor using the default implementation
+///
+/// The end element size is the number of elements consumed by the visitor.
 impl<'a, T, V> Peekable<'a, T> for V
 where
-    V: Visitor<'a, T> + PeekSize<T> + PeekableImplementation<Type = DefaultPeekableImplementation>,
+    V: Visitor<'a, T> + PeekableImplementation<Type = DefaultPeekableImplementation>,
 {
     fn peek(&self, data: &Scanner<'a, T>) -> ParseResult<PeekResult> {
-        // create a temporary scanner to peek data
-        let mut scanner = Scanner::new(data.remaining());
-        while !scanner.is_empty() {
-            match V::accept(&mut scanner) {
-                Ok(element) => {
-                    return Ok(PeekResult::Found {
-                        end_slice: scanner.current_position(),
-                        start_element_size: 0,
-                        end_element_size: element.peek_size(),
-                    });
-                }
-                Err(_err) => {
-                    scanner.bump_by(1);
-                    continue;
-                }
-            }
+        peek_visitor(data, |_: &V, consumed| consumed)
+    }
+}
+
+//------------------------------------------------------------------------------
+// PeekVisitor implementation
+//------------------------------------------------------------------------------
+
+/// A `Peekable` which peeks until the first position where a `Visitor`
+/// succeeds.
+///
+/// This adapter makes any `Visitor` peekable without implementing
+/// `PeekableImplementation`. The peeked slice stops before the
+/// data consumed by the visitor.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::peek::{peek, PeekVisitor};
+/// use elyze::scanner::Scanner;
+///
+/// let scanner = Scanner::new(b"abc 123 def");
+/// let peeked = peek(PeekVisitor::<Number<u32>>::new(), &scanner)
+///     .expect("failed to parse")
+///     .expect("failed to peek");
+/// assert_eq!(peeked.peeked_slice(), b"abc ");
+/// ```
+pub struct PeekVisitor<V> {
+    _marker: PhantomData<V>,
+}
+
+impl<V> PeekVisitor<V> {
+    /// Create a new `PeekVisitor`
+    pub fn new() -> Self {
+        PeekVisitor {
+            _marker: PhantomData,
         }
-        Ok(PeekResult::NotFound)
+    }
+}
+
+impl<V> Default for PeekVisitor<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Because PeekVisitor doesn't implement PeekableImplementation there is no
+/// conflict with the Visitor based implementation
+impl<'a, T, V: Visitor<'a, T>> Peekable<'a, T> for PeekVisitor<V> {
+    fn peek(&self, data: &Scanner<'a, T>) -> ParseResult<PeekResult> {
+        peek_visitor(data, |_: &V, consumed| consumed)
     }
 }
 
@@ -271,8 +393,8 @@ impl<'a, T, V: Peekable<'a, T>> Peekable<'a, T> for Last<'a, T, V> {
 
             let peeked = match peeked {
                 Ok(peeked) => peeked,
-                Err(ParseError::UnexpectedToken) => {
-                    inner_scanner.bump_by(1);
+                Err(err) if matches!(err.root(), ParseError::UnexpectedToken { .. }) => {
+                    inner_scanner.advance_checked(1)?;
                     continue;
                 }
                 Err(err) => {
@@ -284,7 +406,7 @@ impl<'a, T, V: Peekable<'a, T>> Peekable<'a, T> for Last<'a, T, V> {
             // and advance the scanner by the end slice
             if let PeekResult::Found { end_slice, .. } = &peeked {
                 positions.push(*end_slice);
-                inner_scanner.bump_by(*end_slice);
+                inner_scanner.advance_checked(*end_slice)?;
                 state = peeked;
             } else {
                 if PeekResult::NotFound == state {
@@ -318,62 +440,609 @@ impl<'a, T, V: Peekable<'a, T>> Peekable<'a, T> for Last<'a, T, V> {
     }
 }
 
+//------------------------------------------------------------------------------
+// Nth implementation
+//------------------------------------------------------------------------------
+
+/// A `Peekable` which peeks up to the n-th occurrence of an element, between
+/// the first occurrence found by the element itself and the one found by
+/// [Last].
+///
+/// The occurrences are counted from 1 and don't overlap, `Nth::new(element, 1)`
+/// peeks like the element alone. Nothing is found when `n` is 0.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::peek::{peek, Nth};
+/// use elyze::scanner::Scanner;
+///
+/// let scanner = Scanner::new(b"a|b|c|d");
+/// let peeked = peek(Nth::new(Token::Pipe, 2), &scanner)
+///     .expect("failed to parse")
+///     .expect("failed to peek");
+/// assert_eq!(peeked.peeked_slice(), b"a|b");
+/// ```
+#[derive(Clone)]
+pub struct Nth<'a, T, V> {
+    pub element: V,
+    pub n: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+/// Construct a new `Nth`
+impl<'a, T, V: Peekable<'a, T>> Nth<'a, T, V> {
+    pub fn new(element: V, n: usize) -> Nth<'a, T, V> {
+        Nth {
+            element,
+            n,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Because Nth doesn't implement PeekableImplementation there is no conflict
+/// with the Visitor based implementation
+impl<'a, T, V: Peekable<'a, T>> Peekable<'a, T> for Nth<'a, T, V> {
+    fn peek(&self, scanner: &Scanner<'a, T>) -> ParseResult<PeekResult> {
+        let mut inner_scanner = Scanner::new(scanner.remaining());
+        let mut occurrences = 0;
+        while occurrences < self.n && !inner_scanner.is_empty() {
+            let peeked = match self.element.peek(&inner_scanner) {
+                Ok(peeked) => peeked,
+                Err(err) if matches!(err.root(), ParseError::UnexpectedToken { .. }) => {
+                    inner_scanner.advance_checked(1)?;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            match peeked {
+                // an empty occurrence would be found again and again
+                PeekResult::Found { end_slice: 0, .. } | PeekResult::NotFound => break,
+                PeekResult::Found {
+                    end_slice,
+                    start_element_size,
+                    end_element_size,
+                } => {
+                    occurrences += 1;
+                    if occurrences == self.n {
+                        // the end slice is relative to the peeking position
+                        return Ok(PeekResult::Found {
+                            end_slice: inner_scanner.current_position() + end_slice,
+                            start_element_size,
+                            end_element_size,
+                        });
+                    }
+                    inner_scanner.advance_checked(end_slice)?;
+                }
+            }
+        }
+        Ok(PeekResult::NotFound)
+    }
+}
+
+//------------------------------------------------------------------------------
+// BoundedUntil implementation
+//------------------------------------------------------------------------------
+
+/// A `Peekable` giving up after scanning a maximal number of elements.
+///
+/// Searching a `Peekable` scans the data up to its end when there is no
+/// match, which a hostile input can make arbitrarily long. The inner
+/// `Peekable` only sees the next `limit` elements, so a match must end
+/// within them, otherwise nothing is found, even if the inner `Peekable`
+/// reports a truncated match.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::peek::{peek, BoundedUntil};
+/// use elyze::scanner::Scanner;
+///
+/// let scanner = Scanner::new(b"key: value");
+/// let peeked = peek(BoundedUntil::new(Token::Colon, 16), &scanner)
+///     .expect("failed to parse")
+///     .expect("failed to peek");
+/// assert_eq!(peeked.peeked_slice(), b"key");
+///
+/// let scanner = Scanner::new(b"a very long key: value");
+/// let peeked = peek(BoundedUntil::new(Token::Colon, 8), &scanner).expect("failed to parse");
+/// assert_eq!(peeked, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundedUntil<P> {
+    /// The peekable to search
+    pub element: P,
+    /// The maximal number of elements scanned
+    pub limit: usize,
+}
+
+impl<P> BoundedUntil<P> {
+    /// Create a new `BoundedUntil`
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The peekable to search
+    /// * `limit` - The maximal number of elements scanned
+    pub fn new(element: P, limit: usize) -> Self {
+        BoundedUntil { element, limit }
+    }
+}
+
+/// Because BoundedUntil doesn't implement PeekableImplementation there is no
+/// conflict with the Visitor based implementation
+impl<'a, T, P: Peekable<'a, T>> Peekable<'a, T> for BoundedUntil<P> {
+    fn peek(&self, scanner: &Scanner<'a, T>) -> ParseResult<PeekResult> {
+        let remaining = scanner.remaining();
+        let window = Scanner::new(&remaining[..remaining.len().min(self.limit)]);
+        match self.element.peek(&window) {
+            // the end of the window is not the end of the data
+            Err(err)
+                if remaining.len() > self.limit
+                    && matches!(err.root(), ParseError::UnexpectedEndOfInput) =>
+            {
+                Ok(PeekResult::NotFound)
+            }
+            result => result,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// FirstOf implementation
+//------------------------------------------------------------------------------
+
+/// A `Peekable` over a tuple or an array of `Peekable`s, keeping the match
+/// with the smallest end offset.
+///
+/// This is the minimal group choice of [Peeker](crate::peeker::Peeker)
+/// without building a pool on each call. When several matches end at the
+/// same offset, the first one in the list wins.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::peek::{peek, FirstOf};
+/// use elyze::scanner::Scanner;
+///
+/// let scanner = Scanner::new(b"1 + 2 * 7");
+/// let peeked = peek(FirstOf((Token::Star, Token::Plus)), &scanner)
+///     .expect("failed to parse")
+///     .expect("failed to peek");
+/// assert_eq!(peeked.peeked_slice(), b"1 ");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FirstOf<P>(pub P);
+
+/// Keep the earliest of the best result so far and a new result, the best
+/// result winning ties
+fn earliest(best: PeekResult, candidate: PeekResult) -> PeekResult {
+    match (&best, &candidate) {
+        (PeekResult::NotFound, _) => candidate,
+        (
+            PeekResult::Found { end_slice, .. },
+            PeekResult::Found {
+                end_slice: candidate_end_slice,
+                ..
+            },
+        ) if candidate_end_slice < end_slice => candidate,
+        _ => best,
+    }
+}
+
+/// Implement the `Peekable` trait for `FirstOf` over a tuple of peekables.
+macro_rules! impl_first_of {
+    ($($peekable:ident $index:tt),+) => {
+        impl<'a, T, $($peekable: Peekable<'a, T>),+> Peekable<'a, T> for FirstOf<($($peekable,)+)> {
+            fn peek(&self, scanner: &Scanner<'a, T>) -> ParseResult<PeekResult> {
+                let mut result = PeekResult::NotFound;
+                $(result = earliest(result, self.0.$index.peek(scanner)?);)+
+                Ok(result)
+            }
+        }
+    };
+}
+
+impl_first_of!(A 0, B 1);
+impl_first_of!(A 0, B 1, C 2);
+impl_first_of!(A 0, B 1, C 2, D 3);
+impl_first_of!(A 0, B 1, C 2, D 3, E 4);
+impl_first_of!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_first_of!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_first_of!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<'a, T, P: Peekable<'a, T>, const N: usize> Peekable<'a, T> for FirstOf<[P; N]> {
+    fn peek(&self, scanner: &Scanner<'a, T>) -> ParseResult<PeekResult> {
+        self.0
+            .iter()
+            .try_fold(PeekResult::NotFound, |result, peekable| {
+                Ok(earliest(result, peekable.peek(scanner)?))
+            })
+    }
+}
+
+//------------------------------------------------------------------------------
+// Not implementation
+//------------------------------------------------------------------------------
+
+/// A negative lookahead, succeeding only when the inner `Peekable` does not
+/// match at the current position of the `Scanner`.
+///
+/// The inner `Peekable` may still match further in the data. Nothing is ever
+/// consumed: peeking a `Not` finds an empty slice, and visiting it leaves the
+/// scanner untouched.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::peek::Not;
+/// use elyze::scanner::Scanner;
+///
+/// // an identifier not followed by `(`
+/// let mut scanner = Scanner::new(b"name(");
+/// scanner.bump_by(4);
+/// assert!(Not(Token::OpenParen).check(&scanner).is_err());
+///
+/// let mut scanner = Scanner::new(b"name + f(");
+/// scanner.bump_by(4);
+/// assert!(Not(Token::OpenParen).check(&scanner).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Not<P>(pub P);
+
+impl<P> Not<P> {
+    /// Check that the inner `Peekable` does not match at the current position
+    /// of the `Scanner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The `Scanner` to check, left untouched
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` at the current position if the inner
+    /// `Peekable` matches there, or the error of the inner `Peekable` if it is
+    /// not an unexpected token.
+    pub fn check<'a, T>(&self, scanner: &Scanner<'a, T>) -> ParseResult<()>
+    where
+        P: Peekable<'a, T>,
+    {
+        let matched = match self.0.peek(scanner) {
+            // a start element, as the opening of a group, is always at the
+            // current position, otherwise the peekable matches here if its end
+            // element starts the remaining data
+            Ok(PeekResult::Found {
+                end_slice,
+                start_element_size,
+                end_element_size,
+            }) => start_element_size > 0 || end_slice == end_element_size,
+            Ok(PeekResult::NotFound) => false,
+            Err(err) if matches!(err.root(), ParseError::UnexpectedToken { .. }) => false,
+            Err(err) => return Err(err),
+        };
+        if matched {
+            return Err(ParseError::unexpected_token(scanner.current_position()));
+        }
+        Ok(())
+    }
+}
+
+/// Because Not doesn't implement PeekableImplementation there is no conflict
+/// with the Visitor based implementation
+impl<'a, T, P: Peekable<'a, T>> Peekable<'a, T> for Not<P> {
+    fn peek(&self, scanner: &Scanner<'a, T>) -> ParseResult<PeekResult> {
+        match self.check(scanner) {
+            Ok(()) => Ok(PeekResult::Found {
+                end_slice: 0,
+                start_element_size: 0,
+                end_element_size: 0,
+            }),
+            Err(ParseError::UnexpectedToken { .. }) => Ok(PeekResult::NotFound),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<'a, T, P: Peekable<'a, T> + Default> Visitor<'a, T> for Not<P> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let not = Not(P::default());
+        not.check(scanner)?;
+        Ok(not)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::bytes::primitives::number::Number;
     use crate::bytes::token::Token;
-    use crate::peek::{peek, Last, UntilEnd};
+    use crate::peek::{
+        peek, peek_dyn, BoundedUntil, FirstOf, Last, Not, Nth, PeekVisitor, Peekable, UntilEnd,
+    };
 
     #[test]
     fn test_until() {
         let data = b"abc|fdgf";
-        let mut scanner = crate::scanner::Scanner::new(data);
-        let peeked = peek(Token::Pipe, &mut scanner)
+        let scanner = crate::scanner::Scanner::new(data);
+        let peeked = peek(Token::Pipe, &scanner)
             .expect("failed to parse")
             .expect("failed to peek");
         assert_eq!(peeked.peeked_slice(), "abc".as_bytes());
     }
 
+    #[test]
+    fn test_peek_dyn() {
+        let mut scanner = crate::scanner::Scanner::new(b"ab|cd|");
+        scanner.bump_by(3);
+        let peekable: &dyn Peekable<u8> = &Token::Pipe;
+        let peeked = peek_dyn(peekable, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked, peek(Token::Pipe, &scanner).unwrap().unwrap());
+        assert_eq!(peeked.peeked_slice(), b"cd");
+        let peekable: &dyn Peekable<u8> = &Token::Comma;
+        assert_eq!(peek_dyn(peekable, &scanner).expect("failed to parse"), None);
+    }
+
     #[test]
     fn test_until_end() {
         let data = b"abc|fdgf";
-        let mut scanner = crate::scanner::Scanner::new(data);
+        let scanner = crate::scanner::Scanner::new(data);
         let token = UntilEnd::default();
-        let peeked = peek(token, &mut scanner)
+        let peeked = peek(token, &scanner)
             .expect("failed to parse")
             .expect("failed to peek");
         assert_eq!(peeked.peeked_slice(), "abc|fdgf".as_bytes());
     }
 
+    #[test]
+    fn test_peek_visitor() {
+        let data = b"abc 42 def";
+        let scanner = crate::scanner::Scanner::new(data);
+        let peeked = peek(PeekVisitor::<Number<u8>>::new(), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"abc ");
+        assert_eq!(peeked.data, b"abc 42");
+
+        let scanner = crate::scanner::Scanner::new(b"abc");
+        let peeked = peek(PeekVisitor::<Number<u8>>::new(), &scanner).expect("failed to parse");
+        assert_eq!(peeked, None);
+    }
+
+    #[test]
+    fn test_peek_visitor_consumed_size() {
+        use crate::errors::ParseResult;
+        use crate::peek::{DefaultPeekableImplementation, PeekableImplementation};
+        use crate::recognizer::recognize;
+        use crate::scanner::Scanner;
+        use crate::visitor::Visitor;
+
+        // one or more tildes
+        struct Tildes;
+
+        impl PeekableImplementation for Tildes {
+            type Type = DefaultPeekableImplementation;
+        }
+
+        impl<'a> Visitor<'a, u8> for Tildes {
+            fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+                recognize(Token::Tilde, scanner)?;
+                while recognize(Token::Tilde, scanner).is_ok() {}
+                Ok(Tildes)
+            }
+        }
+
+        let scanner = Scanner::new(b"1~~~2");
+        let peeked = peek(Tildes, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.end_element_size, 3);
+        assert_eq!(peeked.peeked_slice(), b"1");
+
+        let scanner = Scanner::new(b"1~2");
+        let peeked = peek(Tildes, &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.end_element_size, 1);
+        assert_eq!(peeked.peeked_slice(), b"1");
+    }
+
     #[test]
     fn test_last() {
         let data = b"abc|def|ghi|";
-        let mut scanner = crate::scanner::Scanner::new(data);
+        let scanner = crate::scanner::Scanner::new(data);
         let token = Last::new(Token::Pipe);
-        let peeked = peek(token, &mut scanner)
+        let peeked = peek(token, &scanner)
             .expect("failed to parse")
             .expect("failed to peek");
         assert_eq!(peeked.peeked_slice(), "abc|def|ghi".as_bytes());
 
         let data = b"abc|def|";
-        let mut scanner = crate::scanner::Scanner::new(data);
+        let scanner = crate::scanner::Scanner::new(data);
         let token = Last::new(Token::Pipe);
-        let peeked = peek(token, &mut scanner)
+        let peeked = peek(token, &scanner)
             .expect("failed to parse")
             .expect("failed to peek");
         assert_eq!(peeked.peeked_slice(), "abc|def".as_bytes());
 
         let data = b"abc|";
-        let mut scanner = crate::scanner::Scanner::new(data);
+        let scanner = crate::scanner::Scanner::new(data);
         let token = Last::new(Token::Pipe);
-        let peeked = peek(token, &mut scanner)
+        let peeked = peek(token, &scanner)
             .expect("failed to parse")
             .expect("failed to peek");
         assert_eq!(peeked.peeked_slice(), "abc".as_bytes());
 
         let data = b"abc";
-        let mut scanner = crate::scanner::Scanner::new(data);
+        let scanner = crate::scanner::Scanner::new(data);
         let token = Last::new(Token::Pipe);
-        let peeked = peek(token, &mut scanner).expect("failed to parse");
+        let peeked = peek(token, &scanner).expect("failed to parse");
+        assert_eq!(peeked, None);
+    }
+
+    #[test]
+    fn test_nth() {
+        let data = b"abc|def|ghi|";
+        let scanner = crate::scanner::Scanner::new(data);
+        for (n, expected) in [(1, &b"abc"[..]), (2, b"abc|def"), (3, b"abc|def|ghi")] {
+            let peeked = peek(Nth::new(Token::Pipe, n), &scanner)
+                .expect("failed to parse")
+                .expect("failed to peek");
+            assert_eq!(peeked.peeked_slice(), expected);
+        }
+        let peeked = peek(Nth::new(Token::Pipe, 4), &scanner).expect("failed to parse");
+        assert_eq!(peeked, None);
+        let peeked = peek(Nth::new(Token::Pipe, 0), &scanner).expect("failed to parse");
+        assert_eq!(peeked, None);
+
+        // the occurrences are counted from the current position
+        let mut scanner = crate::scanner::Scanner::new(data);
+        scanner.bump_by(4);
+        let peeked = peek(Nth::new(Token::Pipe, 2), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"def|ghi");
+    }
+
+    #[test]
+    fn test_not() {
+        use crate::errors::ParseError;
+        use crate::scanner::Scanner;
+
+        let mut scanner = Scanner::new(b"f(x)");
+        scanner.bump_by(1);
+        assert!(matches!(
+            Not(Token::OpenParen).check(&scanner),
+            Err(ParseError::UnexpectedToken { offset: 1, .. })
+        ));
+        assert_eq!(
+            peek(Not(Token::OpenParen), &scanner).expect("failed to parse"),
+            None
+        );
+
+        // a match further in the data doesn't count
+        let scanner = Scanner::new(b"x + f(y)");
+        let peeked = peek(Not(Token::OpenParen), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"");
+        assert!(Not(Token::OpenParen).check(&Scanner::new(b"")).is_ok());
+
+        // as a visitor, nothing is consumed
+        let mut scanner = Scanner::new(b"abc");
+        scanner
+            .visit::<Not<PeekVisitor<Number<u8>>>>()
+            .expect("failed to parse");
+        assert_eq!(scanner.current_position(), 0);
+        let mut scanner = Scanner::new(b"12c");
+        assert!(scanner.visit::<Not<PeekVisitor<Number<u8>>>>().is_err());
+        assert_eq!(scanner.current_position(), 0);
+    }
+
+    #[test]
+    fn test_not_group() {
+        use crate::bytes::components::groups::GroupKind;
+        use crate::errors::ParseError;
+        use crate::scanner::Scanner;
+
+        // the whole group is peeked, not only its delimiters
+        for data in [&b"(a) b"[..], b"()", b"((a) (b))"] {
+            assert!(
+                matches!(
+                    Not(GroupKind::Parenthesis).check(&Scanner::new(data)),
+                    Err(ParseError::UnexpectedToken { offset: 0, .. })
+                ),
+                "{:?}",
+                String::from_utf8_lossy(data)
+            );
+        }
+        assert!(Not(GroupKind::Parenthesis)
+            .check(&Scanner::new(b"a (b)"))
+            .is_ok());
+
+        // multi-byte delimiters
+        #[cfg(feature = "unstable")]
+        {
+            let scanner = Scanner::new(b"r#\"a\"# b");
+            assert!(Not(GroupKind::RustRawString).check(&scanner).is_err());
+            assert!(Not(GroupKind::RustRawString)
+                .check(&Scanner::new(b"b r#\"a\"#"))
+                .is_ok());
+        }
+
+        // a multi-byte element ends the peeked data
+        let scanner = Scanner::new(b"123 4");
+        assert!(Not(PeekVisitor::<Number<u16>>::new())
+            .check(&scanner)
+            .is_err());
+    }
+
+    #[test]
+    fn test_first_of() {
+        use crate::scanner::Scanner;
+
+        let scanner = Scanner::new(b"7 * ( 1 + 2 )");
+        let peeked = peek(FirstOf((Token::Plus, Token::Star)), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"7 ");
+        assert_eq!(peeked.end_slice, 3);
+
+        // an array of peekables, the peekables not found are ignored
+        let peeked = peek(
+            FirstOf([Token::Pipe, Token::CloseParen, Token::OpenParen]),
+            &scanner,
+        )
+        .expect("failed to parse")
+        .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"7 * ");
+
+        // on a tie, the first peekable wins
+        let scanner = Scanner::new(b"data\n");
+        let peeked = peek(FirstOf((UntilEnd::default(), Token::Ln)), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"data\n");
+
+        let peeked = peek(FirstOf((Token::Plus, Token::Star, Token::Pipe)), &scanner)
+            .expect("failed to parse");
+        assert_eq!(peeked, None);
+    }
+
+    #[test]
+    fn test_bounded_until() {
+        use crate::scanner::Scanner;
+
+        let mut data = vec![b'a'; 1 << 20];
+        data.push(b'|');
+        let scanner = Scanner::new(&data);
+        let peeked = peek(BoundedUntil::new(Token::Pipe, 64), &scanner).expect("failed to parse");
         assert_eq!(peeked, None);
+        let peeked = peek(BoundedUntil::new(Token::Pipe, data.len()), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice().len(), 1 << 20);
+
+        // the match must end within the limit
+        let mut scanner = Scanner::new(b"xx\r\nab\r\n");
+        assert_eq!(
+            peek(BoundedUntil::new(Token::CrLn, 3), &scanner).expect("failed to parse"),
+            None
+        );
+        let peeked = peek(BoundedUntil::new(Token::CrLn, 4), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"xx");
+
+        // the limit starts at the current position
+        scanner.bump_by(4);
+        let peeked = peek(BoundedUntil::new(Token::CrLn, 4), &scanner)
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"ab");
     }
 }
@@ -1,12 +1,54 @@
-use crate::errors::ParseResult;
+use crate::errors::{ParseError, ParseResult};
 use crate::peek::{PeekResult, Peekable, Peeking};
 use crate::scanner::Scanner;
 
+/// Defines how the [Peeker] decides between two [Peekable]s producing groups
+/// of the same size.
+///
+/// Dispatching on the peeked group is only correct if the winner of a tie is
+/// predictable, so the strategy must be chosen explicitly when several
+/// peekables may overlap.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TieBreak {
+    /// The first registered [Peekable] wins
+    #[default]
+    RegistrationOrder,
+    /// The [Peekable] with the highest priority wins, the registration order
+    /// is used when priorities are equal
+    Priority,
+    /// A tie is reported as a [ParseError::AmbiguousPeeking] error
+    Error,
+}
+
+/// Defines which group the [Peeker] selects among the groups found.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PeekStrategy {
+    /// The smallest group wins
+    #[default]
+    Shortest,
+    /// The largest group wins, as needed by operator tokenizers and keyword
+    /// matching
+    Longest,
+}
+
+/// A [Peekable] registered in the [Peeker] pool
+struct PeekableEntry<'a, T> {
+    peekable: Box<dyn Peekable<'a, T> + 'a>,
+    /// Weight used by [TieBreak::Priority]
+    priority: usize,
+}
+
 /// A [Peeker] is a type that is used to find the best group to forecast
 pub struct Peeker<'a, 'b, T> {
     scanner: &'b Scanner<'a, T>,
     /// Pool of [Peekable]
-    peekables: Vec<Box<dyn Peekable<'a, T> + 'a>>,
+    peekables: Vec<PeekableEntry<'a, T>>,
+    /// Which group to select
+    strategy: PeekStrategy,
+    /// How to resolve groups of the same size
+    tie_break: TieBreak,
 }
 
 impl<'a, 'b, T> Peeker<'a, 'b, T> {
@@ -14,25 +56,135 @@ impl<'a, 'b, T> Peeker<'a, 'b, T> {
         Self {
             scanner,
             peekables: vec![],
+            strategy: PeekStrategy::default(),
+            tie_break: TieBreak::default(),
         }
     }
 }
 
 impl<'a, T> Peeker<'a, '_, T> {
     /// Add new [Peekable] element to the peeking pool
-    pub fn add_peekable<F: Peekable<'a, T> + 'a>(mut self, peekable: F) -> Self {
-        self.peekables.push(Box::new(peekable));
+    pub fn add_peekable<F: Peekable<'a, T> + 'a>(self, peekable: F) -> Self {
+        self.add_peekable_with_priority(peekable, 0)
+    }
+
+    /// Add new [Peekable] element to the peeking pool with a priority weight
+    ///
+    /// The priority is only used by the [TieBreak::Priority] strategy, the
+    /// highest priority wins.
+    pub fn add_peekable_with_priority<F: Peekable<'a, T> + 'a>(
+        mut self,
+        peekable: F,
+        priority: usize,
+    ) -> Self {
+        self.peekables.push(PeekableEntry {
+            peekable: Box::new(peekable),
+            priority,
+        });
         self
     }
 
-    /// Run the [Forecast] pool, find the minimal group
+    /// Select the largest group found
+    pub fn longest(self) -> Self {
+        self.with_strategy(PeekStrategy::Longest)
+    }
+
+    /// Select the smallest group found, the default
+    pub fn shortest(self) -> Self {
+        self.with_strategy(PeekStrategy::Shortest)
+    }
+
+    /// Define which group is selected among the groups found
+    ///
+    /// Defaults to [PeekStrategy::Shortest].
+    pub fn with_strategy(mut self, strategy: PeekStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Define how groups of the same size are resolved
+    ///
+    /// Defaults to [TieBreak::RegistrationOrder].
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Run every [Peekable] of the pool and return all the successful
+    /// [Peeking]s
+    ///
+    /// Each [Peeking] is tagged with the registration index of the
+    /// [Peekable] which produced it, the results are in registration order.
+    /// This allows callers to implement their own selection logic instead of
+    /// the group choice of [Peeker::peek].
+    pub fn peek_all(self) -> ParseResult<Vec<(usize, Peeking<'a, T>)>> {
+        Ok(self
+            .peek_entries()?
+            .into_iter()
+            .map(|(index, _, peeking)| (index, peeking))
+            .collect())
+    }
+
+    /// Run the [Peeker] pool, find the minimal group, or the maximal one
+    /// with the [PeekStrategy::Longest] strategy
+    ///
+    /// When several groups have the selected size, the winner is chosen
+    /// according to the [TieBreak] strategy.
     pub fn peek(self) -> ParseResult<Option<Peeking<'a, T>>> {
-        let mut result = None;
+        let strategy = self.strategy;
+        let tie_break = self.tie_break;
+        // the best prediction and the priority of its peekable
+        let mut result: Option<(Peeking<'a, T>, usize)> = None;
+        // whether the best prediction is shared by several peekables
+        let mut tied = false;
+        // loop on the predictions
+        for (_, priority, new_forecast) in self.peek_entries()? {
+            match &result {
+                // if we have not predicted anything yet
+                None => {
+                    // the group found becomes the result
+                    result = Some((new_forecast, priority));
+                }
+                // if there is already a prediction
+                Some((best_forecast, best_priority)) => {
+                    // we compare the size of the group found with the
+                    // one already found
+                    let better = match strategy {
+                        PeekStrategy::Shortest => {
+                            new_forecast.data.len() < best_forecast.data.len()
+                        }
+                        PeekStrategy::Longest => new_forecast.data.len() > best_forecast.data.len(),
+                    };
+                    if better {
+                        // it becomes the new predicted group
+                        result = Some((new_forecast, priority));
+                        tied = false;
+                    } else if new_forecast.data.len() == best_forecast.data.len() {
+                        tied = true;
+                        // only a strictly higher priority wins the tie
+                        if tie_break == TieBreak::Priority && priority > *best_priority {
+                            result = Some((new_forecast, priority));
+                        }
+                    }
+                }
+            }
+        }
+
+        if tied && tie_break == TieBreak::Error {
+            return Err(ParseError::AmbiguousPeeking);
+        }
+
+        Ok(result.map(|(peeking, _)| peeking))
+    }
+
+    /// Run every [Peekable] of the pool and collect the successful predictions
+    /// along with the registration index and the priority of their peekable.
+    fn peek_entries(self) -> ParseResult<Vec<(usize, usize, Peeking<'a, T>)>> {
+        let mut results = vec![];
         // loop on the possibilities of predictions
-        for peekable in self.peekables.into_iter() {
-            let peek_result = peekable.peek(self.scanner)?;
+        for (index, entry) in self.peekables.into_iter().enumerate() {
             // we try to predict the element
-            match peek_result {
+            match entry.peekable.peek(self.scanner)? {
                 // if we have found something
                 PeekResult::Found {
                     start_element_size: start,
@@ -42,42 +194,28 @@ impl<'a, T> Peeker<'a, '_, T> {
                     // we get the predicted group
                     let remaining = self.scanner.remaining();
                     let data = &remaining[..end_slice];
-                    let new_forecast = Peeking {
+                    let peeking = Peeking {
                         start_element_size: start,
                         end_element_size: end,
                         data,
                         end_slice,
                     };
-                    match &result {
-                        // if we have not predicted anything yet
-                        None => {
-                            // the group found becomes the result
-                            result = Some(new_forecast);
-                        }
-                        // if there is already a prediction
-                        Some(min_forecast) => {
-                            // we compare the size of the group found with the
-                            // one already found
-                            if new_forecast.data.len() < min_forecast.data.len() {
-                                // it becomes the new predicted group
-                                result = Some(new_forecast);
-                            }
-                        }
-                    }
+                    results.push((index, entry.priority, peeking));
                 }
                 // if the prediction fails, we do nothing
                 PeekResult::NotFound => {}
             }
         }
-        Ok(result)
+        Ok(results)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::bytes::token::Token;
+    use crate::errors::ParseError;
     use crate::peek::UntilEnd;
-    use crate::peeker::Peeker;
+    use crate::peeker::{PeekStrategy, Peeker, TieBreak};
     use crate::scanner::Scanner;
 
     #[test]
@@ -104,4 +242,104 @@ mod tests {
             .expect("failed to peek");
         assert_eq!(result.data, "data".as_bytes());
     }
+
+    #[test]
+    fn test_peeker_tie_break() {
+        // "\n" and UntilEnd produce groups of the same size
+        let data = b"data\n";
+        let scanner = Scanner::new(data);
+
+        // registration order: the first peekable wins
+        let result = Peeker::new(&scanner)
+            .add_peekable(UntilEnd::default())
+            .add_peekable(Token::Ln)
+            .peek()
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(result.peeked_slice(), b"data\n");
+
+        // priority: the highest priority wins
+        let result = Peeker::new(&scanner)
+            .with_tie_break(TieBreak::Priority)
+            .add_peekable_with_priority(UntilEnd::default(), 1)
+            .add_peekable_with_priority(Token::Ln, 2)
+            .peek()
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(result.peeked_slice(), b"data");
+
+        // error: a tie is ambiguous
+        let result = Peeker::new(&scanner)
+            .with_tie_break(TieBreak::Error)
+            .add_peekable(UntilEnd::default())
+            .add_peekable(Token::Ln)
+            .peek();
+        assert!(matches!(result, Err(ParseError::AmbiguousPeeking)));
+
+        // error: a tie which is not minimal is not ambiguous
+        let data = b"da|ta\n";
+        let scanner = Scanner::new(data);
+        let result = Peeker::new(&scanner)
+            .with_tie_break(TieBreak::Error)
+            .add_peekable(UntilEnd::default())
+            .add_peekable(Token::Ln)
+            .add_peekable(Token::Pipe)
+            .peek()
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(result.peeked_slice(), b"da");
+    }
+
+    #[test]
+    fn test_peeker_peek_all() {
+        let data = b"da|ta\n";
+        let scanner = Scanner::new(data);
+        let result = Peeker::new(&scanner)
+            .add_peekable(Token::Ln)
+            .add_peekable(Token::Comma)
+            .add_peekable(Token::Pipe)
+            .peek_all()
+            .expect("failed to parse");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, 0);
+        assert_eq!(result[0].1.peeked_slice(), b"da|ta");
+        assert_eq!(result[1].0, 2);
+        assert_eq!(result[1].1.peeked_slice(), b"da");
+    }
+
+    #[test]
+    fn test_peeker_strategy() {
+        let data = b"da|ta\n";
+        let scanner = Scanner::new(data);
+        let peeker = || {
+            Peeker::new(&scanner)
+                .add_peekable(Token::Pipe)
+                .add_peekable(Token::Ln)
+                .add_peekable(Token::Comma)
+        };
+        let result = peeker()
+            .longest()
+            .peek()
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(result.peeked_slice(), b"da|ta");
+
+        let result = peeker()
+            .longest()
+            .shortest()
+            .peek()
+            .expect("failed to parse")
+            .expect("failed to peek");
+        assert_eq!(result.peeked_slice(), b"da");
+
+        // the tie break applies to the largest groups
+        let result = Peeker::new(&scanner)
+            .with_strategy(PeekStrategy::Longest)
+            .with_tie_break(TieBreak::Error)
+            .add_peekable(Token::Pipe)
+            .add_peekable(UntilEnd::default())
+            .add_peekable(Token::Ln)
+            .peek();
+        assert!(matches!(result, Err(ParseError::AmbiguousPeeking)));
+    }
 }
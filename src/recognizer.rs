@@ -39,6 +39,20 @@ pub trait Recognizable<'a, T, V>: Match<T> {
     fn recognize_slice(self, scanner: &mut Scanner<'a, T>) -> ParseResult<Option<&'a [T]>>;
 }
 
+/// The description of a matcher, only built if a completion runs on the
+/// scanner
+fn expected_description<T>(matcher: &impl Match<T>, scanner: &Scanner<T>) -> Option<String> {
+    scanner.completion().and_then(|_| matcher.describe())
+}
+
+/// Report the matcher which failed to be recognized to the completion
+/// running on the scanner, if any
+fn report_expected<T>(description: Option<String>, err: &ParseError, scanner: &Scanner<T>) {
+    if let (Some(completion), Some(description)) = (scanner.completion(), description) {
+        completion.record_error(err, || description);
+    }
+}
+
 /// Recognize an object for the given scanner.
 ///
 /// # Type Parameters
@@ -55,16 +69,21 @@ pub trait Recognizable<'a, T, V>: Match<T> {
 ///
 /// This function calls the `recognize` method of the recognizable object and
 /// returns its result. If the recognizable object was not recognized, an
-/// `Err(ParseError::UnexpectedToken)` is returned. If the scanner is at the end
-/// of its input and the recognizable object is longer than the remaining input,
-/// an `Err(ParseError::UnexpectedEndOfInput)` is returned.
+/// `Err(ParseError::UnexpectedToken)` is returned at the current position. If
+/// the scanner is at the end of its input and the recognizable object is longer
+/// than the remaining input, an `Err(ParseError::UnexpectedEndOfInput)` is
+/// returned.
 pub fn recognize<'a, T, V, R: Recognizable<'a, T, V>>(
     recognizable: R,
     scanner: &mut Scanner<'a, T>,
 ) -> ParseResult<V> {
+    let description = expected_description(&recognizable, scanner);
     recognizable
-        .recognize(scanner)?
-        .ok_or(ParseError::UnexpectedToken)
+        .recognize(scanner)
+        .and_then(|value| {
+            value.ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+        })
+        .inspect_err(|err| report_expected(description, err, scanner))
 }
 
 /// Recognize a slice of the object for the given scanner.
@@ -83,9 +102,10 @@ pub fn recognize<'a, T, V, R: Recognizable<'a, T, V>>(
 ///
 /// This function calls the `recognize_slice` method of the recognizable object
 /// and returns its result. If the recognizable object was not recognized, an
-/// `Err(ParseError::UnexpectedToken)` is returned. If the scanner is at the end
-/// of its input and the recognizable object is longer than the remaining input,
-/// an `Err(ParseError::UnexpectedEndOfInput)` is returned.
+/// `Err(ParseError::UnexpectedToken)` is returned at the current position. If
+/// the scanner is at the end of its input and the recognizable object is longer
+/// than the remaining input, an `Err(ParseError::UnexpectedEndOfInput)` is
+/// returned.
 pub fn recognize_slice<'a, T, V, R>(
     recognizable: R,
     scanner: &mut Scanner<'a, T>,
@@ -93,9 +113,49 @@ pub fn recognize_slice<'a, T, V, R>(
 where
     R: Recognizable<'a, T, V>,
 {
+    let description = expected_description(&recognizable, scanner);
     recognizable
-        .recognize_slice(scanner)?
-        .ok_or(ParseError::UnexpectedToken)
+        .recognize_slice(scanner)
+        .and_then(|slice| {
+            slice.ok_or_else(|| ParseError::unexpected_token(scanner.current_position()))
+        })
+        .inspect_err(|err| report_expected(description, err, scanner))
+}
+
+/// Recognize a slice of a type-erased matcher for the given scanner.
+///
+/// Unlike [recognize_slice], this function is compiled once per type of data
+/// whatever the matcher, which keeps the code of big grammars small. It is
+/// meant for the cold paths, the hot ones should keep the generic function.
+///
+/// # Arguments
+/// * `matcher` - The matcher to recognize
+/// * `scanner` - The scanner to recognize the slice for
+///
+/// # Returns
+/// * `Ok(&'a [T])` if the matcher was recognized,
+/// * `Err(ParseError)` as [recognize_slice] does otherwise
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::matcher::Match;
+/// use elyze::recognizer::recognize_slice_dyn;
+/// use elyze::scanner::Scanner;
+///
+/// let separators: [&dyn Match<u8>; 2] = [&Token::Equal, &Token::Comma];
+/// let mut scanner = Scanner::new(b"=,");
+/// for separator in separators {
+///     recognize_slice_dyn(separator, &mut scanner).expect("failed to parse");
+/// }
+/// assert!(scanner.is_empty());
+/// ```
+pub fn recognize_slice_dyn<'a, T>(
+    matcher: &dyn Match<T>,
+    scanner: &mut Scanner<'a, T>,
+) -> ParseResult<&'a [T]> {
+    recognize_slice(matcher, scanner)
 }
 
 /// Recognize an object for the given scanner.
@@ -114,7 +174,7 @@ impl<'a, T, M: Match<T>> Recognizable<'a, T, M> for M {
             return Ok(None);
         }
         if !scanner.is_empty() {
-            scanner.bump_by(size);
+            scanner.advance_checked(size)?;
         }
         Ok(Some(self))
     }
@@ -134,7 +194,7 @@ impl<'a, T, M: Match<T>> Recognizable<'a, T, M> for M {
             return Ok(None);
         }
         if !scanner.is_empty() {
-            scanner.bump_by(size);
+            scanner.advance_checked(size)?;
         }
         Ok(Some(&data[..size]))
     }
@@ -242,8 +302,9 @@ impl<'a, T, R: Recognizable<'a, T, R> + Default> Visitor<'a, T> for R {
 #[cfg(test)]
 mod tests {
     use crate::bytes::token::Token;
-    use crate::errors::ParseResult;
-    use crate::recognizer::{Recognizable, Recognizer};
+    use crate::errors::{ParseError, ParseResult};
+    use crate::matcher::Match;
+    use crate::recognizer::{recognize_slice_dyn, Recognizable, Recognizer};
 
     #[test]
     fn test_recognizer() {
@@ -267,4 +328,18 @@ mod tests {
         assert_eq!(result, Token::GreaterThan);
         Ok(())
     }
+
+    #[test]
+    fn test_recognize_slice_dyn() {
+        let matcher: &dyn Match<u8> = &Token::Dash;
+        let mut scanner = crate::scanner::Scanner::new(b"-+");
+        let result = recognize_slice_dyn(matcher, &mut scanner).expect("failed to parse");
+        assert_eq!(result, b"-");
+        let result = recognize_slice_dyn(matcher, &mut scanner);
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 1, .. })
+        ));
+        assert_eq!(scanner.current_position(), 1);
+    }
 }
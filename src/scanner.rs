@@ -1,21 +1,77 @@
 //! A scanner for a sequence of elements.
 
-use crate::errors::ParseResult;
+use crate::completion::CompletionRef;
+use crate::errors::{ParseError, ParseResult};
+use crate::validate::Validate;
 use crate::visitor::Visitor;
 use std::io::Cursor;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 
 /// Wrapper around a `Cursor`.
+///
+/// # Limits
+///
+/// All the positions are `usize` offsets in elements from the start of the
+/// data, so any slice, including a memory-mapped file larger than 4 GiB on a
+/// 64-bit target, can be scanned. The internal cursor stores them as `u64`,
+/// which holds any `usize` on the supported targets, this is checked at
+/// compile time.
+///
+/// Moving the cursor never wraps around: a move overflowing `usize` or going
+/// before the start of the data panics, whatever the target and the build
+/// profile. The checked variants, such as [Scanner::advance_checked], return
+/// an error instead.
 #[derive(Debug, PartialEq, Clone)]
 pub struct Scanner<'a, T> {
-    /// The internal cursor.
+    /// The internal cursor, its position always fits in a `usize`.
     cursor: Cursor<&'a [T]>,
+    /// The completion the expected elements are reported to, if any
+    completion: Option<CompletionRef>,
 }
 
+// the positions are stored in the `u64` of the cursor
+const _: () = assert!(usize::BITS <= u64::BITS);
+
+// a scanner can be moved to and shared between threads, whatever the features
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Scanner<'static, u8>>();
+};
+
 impl<'a, T> Scanner<'a, T> {
     pub fn new(data: &'a [T]) -> Scanner<'a, T> {
         Scanner {
             cursor: Cursor::new(data),
+            completion: None,
+        }
+    }
+
+    /// Create a scanner reporting the expected elements to a completion
+    pub(crate) fn with_completion(data: &'a [T], completion: CompletionRef) -> Scanner<'a, T> {
+        Scanner {
+            completion: Some(completion),
+            ..Scanner::new(data)
+        }
+    }
+
+    /// Return the completion running on the scanner, if any
+    pub(crate) fn completion(&self) -> Option<&CompletionRef> {
+        self.completion.as_ref()
+    }
+
+    /// Report the element expected by an error to the
+    /// [completion](crate::completion) running on the scanner, if any.
+    ///
+    /// The element is expected at the cursor of the completion if the error
+    /// is an unexpected token at the cursor or the end of the input.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error of the element
+    /// * `description` - The description of the expected element
+    pub fn report_expected(&self, err: &ParseError, description: &str) {
+        if let Some(completion) = &self.completion {
+            completion.record_error(err, || description.to_string());
         }
     }
 }
@@ -29,9 +85,16 @@ impl<'a, T> Scanner<'a, T> {
     ///
     /// # Panics
     ///
-    /// Panics if the internal cursor is moved past the end of the data.
+    /// Panics if the position overflows `usize`. The scanner panics later on
+    /// if the internal cursor is moved past the end of the data, see
+    /// [Scanner::advance_checked] for a checked variant.
+    #[inline]
     pub fn bump_by(&mut self, n: usize) {
-        self.cursor.set_position(self.cursor.position() + n as u64);
+        let position = self
+            .current_position()
+            .checked_add(n)
+            .expect("the scanner position overflows usize");
+        self.set_position(position);
     }
 
     /// Move the internal cursor to the specified position.
@@ -42,9 +105,11 @@ impl<'a, T> Scanner<'a, T> {
     ///
     /// # Panics
     ///
-    /// Panics if the internal cursor is moved past the end of the data.
+    /// The scanner panics later on if the internal cursor is moved past the
+    /// end of the data, see [Scanner::jump_to_checked] for a checked variant.
+    #[inline]
     pub fn jump_to(&mut self, n: usize) {
-        self.cursor.set_position(n as u64);
+        self.set_position(n);
     }
 
     /// Move the internal cursor backward by `n` positions.
@@ -55,9 +120,79 @@ impl<'a, T> Scanner<'a, T> {
     ///
     /// # Panics
     ///
-    /// Panics if the internal cursor is moved to a position before the start of the data.
+    /// Panics if the internal cursor is moved to a position before the start of the data,
+    /// see [Scanner::rewind_checked] for a checked variant.
+    #[inline]
     pub fn rewind(&mut self, n: usize) {
-        self.cursor.set_position(self.cursor.position() - n as u64);
+        let position = self
+            .current_position()
+            .checked_sub(n)
+            .expect("the scanner position is before the start of the data");
+        self.set_position(position);
+    }
+
+    /// Move the internal cursor to the position, the only way to move it.
+    #[inline]
+    fn set_position(&mut self, position: usize) {
+        // lossless, see the compile time check on the size of usize
+        self.cursor.set_position(position as u64);
+    }
+
+    /// Move the internal cursor forward by `n` positions, checking the bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of positions to move the cursor forward.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::CursorOutOfBounds` if the cursor would be moved past the
+    /// end of the data, the cursor is left untouched.
+    pub fn advance_checked(&mut self, n: usize) -> ParseResult<()> {
+        let position = self
+            .current_position()
+            .checked_add(n)
+            .filter(|position| *position <= self.data().len())
+            .ok_or(ParseError::CursorOutOfBounds)?;
+        self.set_position(position);
+        Ok(())
+    }
+
+    /// Move the internal cursor backward by `n` positions, checking the bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of positions to move the cursor backward.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::CursorOutOfBounds` if the cursor would be moved before the
+    /// start of the data, the cursor is left untouched.
+    pub fn rewind_checked(&mut self, n: usize) -> ParseResult<()> {
+        let position = self
+            .current_position()
+            .checked_sub(n)
+            .ok_or(ParseError::CursorOutOfBounds)?;
+        self.set_position(position);
+        Ok(())
+    }
+
+    /// Move the internal cursor to the specified position, checking the bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The position to move the cursor to.
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::CursorOutOfBounds` if the position is past the end of the
+    /// data, the cursor is left untouched.
+    pub fn jump_to_checked(&mut self, n: usize) -> ParseResult<()> {
+        if n > self.data().len() {
+            return Err(ParseError::CursorOutOfBounds);
+        }
+        self.set_position(n);
+        Ok(())
     }
 
     /// Return the current position of the internal cursor.
@@ -65,7 +200,9 @@ impl<'a, T> Scanner<'a, T> {
     /// # Returns
     ///
     /// The current position of the internal cursor.
+    #[inline]
     pub fn current_position(&self) -> usize {
+        // lossless, the position is only ever set from a usize
         self.cursor.position() as usize
     }
 
@@ -74,15 +211,46 @@ impl<'a, T> Scanner<'a, T> {
     /// # Returns
     ///
     /// A slice of the data that remains to be scanned.
+    #[inline]
     pub fn remaining(&self) -> &'a [T] {
         &self.cursor.get_ref()[self.current_position()..]
     }
 
+    /// Return a slice of the data that has already been scanned.
+    ///
+    /// # Returns
+    ///
+    /// A slice of the data from the start up to the current position.
+    #[inline]
+    pub fn consumed(&self) -> &'a [T] {
+        &self.cursor.get_ref()[..self.current_position()]
+    }
+
+    /// Return a slice of the data scanned since the given position.
+    ///
+    /// The position is usually saved with [Scanner::current_position] before
+    /// running a visitor, the slice is then the exact data it matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position to start the slice from.
+    ///
+    /// # Returns
+    ///
+    /// A slice of the data from the given position up to the current position,
+    /// empty if the given position is after the current position.
+    #[inline]
+    pub fn since(&self, position: usize) -> &'a [T] {
+        let current = self.current_position();
+        &self.cursor.get_ref()[position.min(current)..current]
+    }
+
     /// Return the original data given to the scanner.
     ///
     /// # Returns
     ///
     /// The original data given to the scanner.
+    #[inline]
     pub fn data(&self) -> &'a [T] {
         self.cursor.get_ref()
     }
@@ -92,9 +260,64 @@ impl<'a, T> Scanner<'a, T> {
     /// # Returns
     ///
     /// true if there are no more elements to scan, false otherwise.
+    #[inline]
     pub fn is_empty(&self) -> bool {
         self.remaining().is_empty()
     }
+
+    /// Consume the elements while the predicate holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The predicate the consumed elements satisfy.
+    ///
+    /// # Returns
+    ///
+    /// The consumed elements, empty if the predicate doesn't hold for the
+    /// first remaining element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::scanner::Scanner;
+    ///
+    /// let mut scanner = Scanner::new(b"hello world");
+    /// assert_eq!(scanner.take_while(u8::is_ascii_alphabetic), b"hello");
+    /// assert_eq!(scanner.remaining(), b" world");
+    /// ```
+    pub fn take_while<P: FnMut(&T) -> bool>(&mut self, mut predicate: P) -> &'a [T] {
+        let remaining = self.remaining();
+        let size = remaining
+            .iter()
+            .position(|element| !predicate(element))
+            .unwrap_or(remaining.len());
+        self.bump_by(size);
+        &remaining[..size]
+    }
+
+    /// Consume the elements until the predicate holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The predicate of the first element not to consume.
+    ///
+    /// # Returns
+    ///
+    /// The consumed elements, all the remaining ones if the predicate never
+    /// holds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::scanner::Scanner;
+    ///
+    /// let mut scanner = Scanner::new(b"key=value");
+    /// assert_eq!(scanner.take_till(|byte| *byte == b'='), b"key");
+    /// assert_eq!(scanner.remaining(), b"=value");
+    /// ```
+    pub fn take_till<P: FnMut(&T) -> bool>(&mut self, mut predicate: P) -> &'a [T] {
+        self.take_while(|element| !predicate(element))
+    }
 }
 
 impl<'a, T> Deref for Scanner<'a, T> {
@@ -121,4 +344,284 @@ impl<'a, T> Scanner<'a, T> {
     pub fn visit<V: Visitor<'a, T>>(&mut self) -> ParseResult<V> {
         V::accept(self)
     }
+
+    /// Check the scanner starts with a valid `V` and move past it, without
+    /// building it.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `V` - The type of the visitor to run.
+    ///
+    /// # Errors
+    ///
+    /// The error the visitor would return.
+    pub fn validate<V: Validate<'a, T>>(&mut self) -> ParseResult<()> {
+        V::validate(self)
+    }
+
+    /// Run a visitor on a copy of the scanner and measure what it consumes.
+    ///
+    /// The scanner itself is left untouched and the visited value is dropped.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `V` - The type of the visitor to run.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements the visitor would consume, or the error of the
+    /// visitor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::bytes::primitives::number::Number;
+    /// use elyze::scanner::Scanner;
+    ///
+    /// let scanner = Scanner::new(b"1234 + 5");
+    /// assert_eq!(scanner.measure::<Number<u32>>().expect("failed to parse"), 4);
+    /// assert_eq!(scanner.current_position(), 0);
+    /// ```
+    pub fn measure<V: Visitor<'a, T>>(&self) -> ParseResult<usize> {
+        let start = self.current_position();
+        let mut scanner = Scanner::new(self.data());
+        scanner.jump_to(start);
+        V::accept(&mut scanner)?;
+        Ok(scanner.current_position().saturating_sub(start))
+    }
+
+    /// Save the current position of the scanner.
+    ///
+    /// The returned guard gives access to the scanner. Unless it's committed,
+    /// the scanner is moved back to the saved position when the guard is
+    /// dropped, so an early return on error backtracks automatically.
+    ///
+    /// # Returns
+    ///
+    /// A [Checkpoint] of the current position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::bytes::primitives::number::Number;
+    /// use elyze::bytes::token::Token;
+    /// use elyze::errors::ParseResult;
+    /// use elyze::scanner::Scanner;
+    ///
+    /// fn negative(scanner: &mut Scanner<u8>) -> ParseResult<i32> {
+    ///     let mut checkpoint = scanner.checkpoint();
+    ///     checkpoint.visit::<Token>()?;
+    ///     let Number(number) = checkpoint.visit::<Number<i32>>()?;
+    ///     checkpoint.commit();
+    ///     Ok(-number)
+    /// }
+    ///
+    /// let mut scanner = Scanner::new(b"-x");
+    /// assert!(negative(&mut scanner).is_err());
+    /// assert_eq!(scanner.current_position(), 0);
+    /// ```
+    pub fn checkpoint(&mut self) -> Checkpoint<'_, 'a, T> {
+        Checkpoint {
+            position: self.current_position(),
+            scanner: self,
+            committed: false,
+        }
+    }
+}
+
+/// A saved position of a [Scanner], see [Scanner::checkpoint].
+///
+/// The guard dereferences to the scanner. The scanner is moved back to the
+/// saved position on drop, unless the checkpoint is committed.
+#[derive(Debug)]
+pub struct Checkpoint<'s, 'a, T> {
+    /// The scanner to restore
+    scanner: &'s mut Scanner<'a, T>,
+    /// The saved position
+    position: usize,
+    /// Whether the scanner keeps its position on drop
+    committed: bool,
+}
+
+impl<T> Checkpoint<'_, '_, T> {
+    /// Return the saved position.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Keep the current position of the scanner.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    /// Move the scanner back to the saved position now.
+    pub fn rollback(self) {
+        // restored on drop
+    }
+}
+
+impl<'a, T> Deref for Checkpoint<'_, 'a, T> {
+    type Target = Scanner<'a, T>;
+    fn deref(&self) -> &Self::Target {
+        self.scanner
+    }
+}
+
+impl<T> DerefMut for Checkpoint<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.scanner
+    }
+}
+
+impl<T> Drop for Checkpoint<'_, '_, T> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.scanner.jump_to(self.position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn test_positions_are_checked() {
+        let mut scanner = Scanner::new(b"ab");
+        scanner.jump_to(usize::MAX);
+        assert_eq!(scanner.current_position(), usize::MAX);
+        assert!(matches!(
+            scanner.advance_checked(1),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+        assert!(matches!(
+            scanner.jump_to_checked(usize::MAX),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+        scanner.rewind(usize::MAX);
+        assert_eq!(scanner.current_position(), 0);
+        assert!(matches!(
+            scanner.rewind_checked(1),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "the scanner position overflows usize")]
+    fn test_bump_by_overflow() {
+        let mut scanner = Scanner::new(b"ab");
+        scanner.bump_by(1);
+        scanner.bump_by(usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "the scanner position is before the start of the data")]
+    fn test_rewind_underflow() {
+        Scanner::new(b"ab").rewind(1);
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let mut scanner = Scanner::new(b"12 34");
+        scanner.bump_by(1);
+
+        // dropped without commit
+        {
+            let mut checkpoint = scanner.checkpoint();
+            checkpoint.bump_by(2);
+            assert_eq!(checkpoint.remaining(), b"34");
+            assert_eq!(checkpoint.position(), 1);
+        }
+        assert_eq!(scanner.current_position(), 1);
+
+        let mut checkpoint = scanner.checkpoint();
+        checkpoint.bump_by(2);
+        checkpoint.rollback();
+        assert_eq!(scanner.current_position(), 1);
+
+        let mut checkpoint = scanner.checkpoint();
+        checkpoint.bump_by(2);
+        let Number(number) = checkpoint.visit::<Number<u8>>().expect("failed to parse");
+        checkpoint.commit();
+        assert_eq!(number, 34);
+        assert!(scanner.is_empty());
+    }
+
+    #[test]
+    fn test_checked_cursor() {
+        let mut scanner = Scanner::new(b"abc");
+        assert!(scanner.advance_checked(2).is_ok());
+        assert_eq!(scanner.remaining(), b"c");
+        assert!(scanner.advance_checked(1).is_ok());
+        assert!(scanner.is_empty());
+        assert!(matches!(
+            scanner.advance_checked(1),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+        assert!(matches!(
+            scanner.advance_checked(usize::MAX),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+        assert_eq!(scanner.current_position(), 3);
+
+        assert!(scanner.rewind_checked(3).is_ok());
+        assert_eq!(scanner.current_position(), 0);
+        assert!(matches!(
+            scanner.rewind_checked(1),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+        assert_eq!(scanner.current_position(), 0);
+
+        assert!(scanner.jump_to_checked(3).is_ok());
+        assert!(scanner.is_empty());
+        assert!(scanner.jump_to_checked(1).is_ok());
+        assert_eq!(scanner.remaining(), b"bc");
+        assert!(matches!(
+            scanner.jump_to_checked(4),
+            Err(ParseError::CursorOutOfBounds)
+        ));
+        assert_eq!(scanner.current_position(), 1);
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut scanner = Scanner::new(b"a 12");
+        scanner.bump_by(2);
+        assert_eq!(scanner.measure::<Number<u8>>().expect("failed to parse"), 2);
+        assert_eq!(scanner.current_position(), 2);
+
+        let scanner = Scanner::new(b"abc");
+        assert!(matches!(
+            scanner.measure::<Number<u8>>(),
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_consumed() {
+        let mut scanner = Scanner::new(b"let x = 12;");
+        assert_eq!(scanner.consumed(), b"");
+        scanner.bump_by(4);
+        assert_eq!(scanner.consumed(), b"let ");
+
+        let position = scanner.current_position();
+        scanner.bump_by(5);
+        assert_eq!(scanner.since(position), b"x = 1");
+        assert_eq!(scanner.since(0), b"let x = 1");
+        assert_eq!(scanner.since(100), b"");
+    }
+
+    #[test]
+    fn test_take_while() {
+        let mut scanner = Scanner::new(b"  12ab");
+        assert_eq!(scanner.take_while(|byte| *byte == b' '), b"  ");
+        assert_eq!(scanner.take_while(|byte| *byte == b' '), b"");
+        assert_eq!(scanner.take_till(u8::is_ascii_alphabetic), b"12");
+        assert_eq!(scanner.current_position(), 4);
+        assert_eq!(scanner.take_till(|byte| *byte == b';'), b"ab");
+        assert!(scanner.is_empty());
+        assert_eq!(scanner.take_while(|_| true), b"");
+    }
 }
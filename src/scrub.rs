@@ -0,0 +1,205 @@
+//! Mask the occurrences of a set of matchers, such as card numbers or secret
+//! tokens, out of an input.
+
+use crate::matcher::Match;
+use crate::search::{replace_all, replace_all_to};
+use std::io::{BufRead, Write};
+
+/// Defines how a [Scrubber] masks an occurrence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Mask {
+    /// Each byte of the occurrence is replaced by the byte, the scrubbed data
+    /// keeps the length of the input
+    Fill(u8),
+    /// The whole occurrence is replaced by the bytes
+    Replace(Vec<u8>),
+}
+
+impl Default for Mask {
+    fn default() -> Self {
+        Mask::Fill(b'*')
+    }
+}
+
+/// A set of matchers whose occurrences are masked out of an input.
+///
+/// At each position, the longest occurrence of any of the matchers is masked,
+/// the first matcher winning a tie. The empty occurrences are ignored.
+///
+/// The scrubber is itself a [Match], so it can be used with the functions of
+/// the [search](crate::search) module.
+///
+/// # Example
+///
+/// ```
+/// use elyze::alternatives::CompiledAlternatives;
+/// use elyze::bytes::primitives::card::CardNumber;
+/// use elyze::scrub::{Mask, Scrubber};
+///
+/// let scrubber = Scrubber::new()
+///     .with_matcher(CardNumber)
+///     .with_matcher(CompiledAlternatives::compile(&[b"hunter2"]));
+/// let line = b"card=4111 1111 1111 1111 password=hunter2";
+/// assert_eq!(
+///     scrubber.scrub(line),
+///     b"card=******************* password=*******"
+/// );
+///
+/// let scrubber = scrubber.with_mask(Mask::Replace(b"[redacted]".to_vec()));
+/// assert_eq!(
+///     scrubber.scrub(line),
+///     b"card=[redacted] password=[redacted]"
+/// );
+/// ```
+#[derive(Default)]
+pub struct Scrubber<'a> {
+    /// The matchers of the regions to mask
+    matchers: Vec<Box<dyn Match<u8> + 'a>>,
+    /// How to mask an occurrence
+    mask: Mask,
+}
+
+impl<'a> Scrubber<'a> {
+    /// Create a new `Scrubber` without any matcher, masking with `*`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also mask the occurrences of the matcher.
+    pub fn with_matcher<M: Match<u8> + 'a>(mut self, matcher: M) -> Self {
+        self.matchers.push(Box::new(matcher));
+        self
+    }
+
+    /// Set how the occurrences are masked.
+    pub fn with_mask(mut self, mask: Mask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Return the replacement of an occurrence
+    fn masked(&self, occurrence: &[u8]) -> Vec<u8> {
+        match &self.mask {
+            Mask::Fill(byte) => vec![*byte; occurrence.len()],
+            Mask::Replace(replacement) => replacement.clone(),
+        }
+    }
+
+    /// Mask the occurrences of the matchers in the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to scrub
+    ///
+    /// # Returns
+    ///
+    /// A copy of the data where each occurrence is masked.
+    pub fn scrub(&self, data: &[u8]) -> Vec<u8> {
+        replace_all(self, |occurrence| self.masked(occurrence), data)
+    }
+
+    /// Mask the occurrences of the matchers in the data and stream the result
+    /// to a writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to scrub
+    /// * `writer` - The writer receiving the result
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the writer.
+    pub fn scrub_to<W: Write>(&self, data: &[u8], writer: &mut W) -> std::io::Result<()> {
+        replace_all_to(self, |occurrence| self.masked(occurrence), data, writer)
+    }
+
+    /// Scrub a reader line by line and stream the result to a writer.
+    ///
+    /// Only a line is held in memory at a time, so an occurrence can't span
+    /// several lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader of the data to scrub
+    /// * `writer` - The writer receiving the result
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the reader or the writer.
+    pub fn scrub_lines<R: BufRead, W: Write>(
+        &self,
+        mut reader: R,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        let mut line = vec![];
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            self.scrub_to(&line, writer)?;
+            line.clear();
+        }
+        Ok(())
+    }
+}
+
+impl Match<u8> for Scrubber<'_> {
+    fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+        let longest = self
+            .matchers
+            .iter()
+            .filter(|matcher| matcher.size() <= data.len())
+            .filter_map(|matcher| match matcher.is_matching(data) {
+                (true, size) if size > 0 => Some(size),
+                _ => None,
+            })
+            .fold(0, usize::max);
+        (longest > 0, longest)
+    }
+
+    fn size(&self) -> usize {
+        self.matchers
+            .iter()
+            .map(|matcher| matcher.size())
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alternatives::CompiledAlternatives;
+    use crate::bytes::primitives::card::CardNumber;
+    use crate::bytes::primitives::number::TokenNumber;
+    use crate::matcher::Match;
+    use crate::scrub::{Mask, Scrubber};
+
+    #[test]
+    fn test_scrub() {
+        // the longest occurrence wins over the shorter ones
+        let scrubber = Scrubber::new()
+            .with_matcher(TokenNumber)
+            .with_matcher(CardNumber)
+            .with_mask(Mask::Replace(b"<n>".to_vec()));
+        assert_eq!(
+            scrubber.scrub(b"id 42, card 5500-0000-0000-0004"),
+            b"id <n>, card <n>"
+        );
+        assert_eq!(scrubber.is_matching(b"4111-1111-1111-1111"), (true, 19));
+        assert_eq!(scrubber.size(), 0);
+
+        let scrubber = Scrubber::new();
+        assert_eq!(scrubber.scrub(b"nothing to hide"), b"nothing to hide");
+        assert_eq!(scrubber.is_matching(b"data"), (false, 0));
+    }
+
+    #[test]
+    fn test_scrub_lines() {
+        let scrubber = Scrubber::new()
+            .with_matcher(CompiledAlternatives::compile(&[b"token=abc", b"token=xyz"]));
+        let input = &b"login token=abc\nlogout\nrefresh token=xyz"[..];
+        let mut output = vec![];
+        scrubber
+            .scrub_lines(input, &mut output)
+            .expect("failed to scrub");
+        assert_eq!(output, b"login *********\nlogout\nrefresh *********");
+    }
+}
@@ -0,0 +1,15 @@
+//! Seal the helper traits of the crate.
+//!
+//! A trait whose supertrait is [Sealed] can't be implemented outside of the
+//! crate, as this module is private. Its methods can then be added in a minor
+//! release without breaking the downstream crates.
+
+use crate::errors::ParseResult;
+use crate::scanner::Scanner;
+
+/// The supertrait of the traits only implemented by the crate
+pub trait Sealed {}
+
+impl<T> Sealed for ParseResult<T> {}
+
+impl<T> Sealed for Scanner<'_, T> {}
@@ -0,0 +1,455 @@
+//! Search the occurrences of a `Match` over a whole input.
+
+use crate::matcher::Match;
+use std::io::Write;
+use std::marker::PhantomData;
+
+/// Options driving how a search iterates over the input.
+///
+/// By default, the search tries every position and occurrences don't overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Whether an occurrence may start inside the previous one
+    overlapping: bool,
+    /// The step between two attempted positions
+    stride: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            overlapping: false,
+            stride: 1,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Allow or forbid occurrences to overlap.
+    ///
+    /// When overlapping, the search resumes one stride after the start of the
+    /// previous occurrence instead of after its end.
+    pub fn overlapping(mut self, overlapping: bool) -> Self {
+        self.overlapping = overlapping;
+        self
+    }
+
+    /// Only attempt to match at offsets which are multiples of `stride`.
+    ///
+    /// For example a stride of 3 only finds codon-aligned occurrences in a
+    /// RNA sequence. A stride of 0 is treated as 1.
+    pub fn stride(mut self, stride: usize) -> Self {
+        self.stride = stride.max(1);
+        self
+    }
+}
+
+/// An iterator over the occurrences of a `Match` in a slice.
+///
+/// Each item is a tuple `(offset, length)` where `offset` is the index of the
+/// first element of the occurrence and `length` the number of elements
+/// matched.
+///
+/// The search slides one stride at a time until an occurrence is found, then
+/// resumes according to the [SearchOptions].
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data to search.
+/// * `M` - The type of the matcher.
+/// * `'a` - The lifetime of the data to search.
+pub struct FindAll<'a, T, M> {
+    /// The matcher to search for
+    matcher: M,
+    /// The data to search in
+    data: &'a [T],
+    /// The position of the next attempt
+    position: usize,
+    /// How to iterate over the data
+    options: SearchOptions,
+    _marker: PhantomData<T>,
+}
+
+impl<T, M: Match<T>> Iterator for FindAll<'_, T, M> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the matcher can't be applied on less data than its size
+        let min_size = self.matcher.size().max(1);
+        while self.position + min_size <= self.data.len() {
+            let offset = self.position;
+            let (matched, length) = self.matcher.is_matching(&self.data[offset..]);
+            if matched {
+                self.position = if self.options.overlapping {
+                    offset + self.options.stride
+                } else {
+                    // resume at the first aligned position after the occurrence,
+                    // always moving forward, even on an empty occurrence
+                    (offset + length.max(1)).next_multiple_of(self.options.stride)
+                };
+                return Some((offset, length));
+            }
+            self.position += self.options.stride;
+        }
+        None
+    }
+}
+
+/// Find all the non-overlapping occurrences of a `Match` in a slice.
+///
+/// # Arguments
+///
+/// * `matcher` - The matcher to search for.
+/// * `data` - The data to search in.
+///
+/// # Returns
+///
+/// An iterator of `(offset, length)` tuples, one per occurrence.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::search::find_all;
+///
+/// let matches = find_all(Token::Pipe, b"a|b|c").collect::<Vec<_>>();
+/// assert_eq!(matches, vec![(1, 1), (3, 1)]);
+/// ```
+pub fn find_all<T, M: Match<T>>(matcher: M, data: &[T]) -> FindAll<'_, T, M> {
+    find_all_with(matcher, data, SearchOptions::default())
+}
+
+/// Find all the occurrences of a `Match` in a slice using the given options.
+///
+/// # Arguments
+///
+/// * `matcher` - The matcher to search for.
+/// * `data` - The data to search in.
+/// * `options` - How to iterate over the data.
+///
+/// # Returns
+///
+/// An iterator of `(offset, length)` tuples, one per occurrence.
+pub fn find_all_with<T, M: Match<T>>(
+    matcher: M,
+    data: &[T],
+    options: SearchOptions,
+) -> FindAll<'_, T, M> {
+    FindAll {
+        matcher,
+        data,
+        position: 0,
+        options,
+        _marker: PhantomData,
+    }
+}
+
+/// Replace all the non-overlapping occurrences of a `Match` in a slice.
+///
+/// # Arguments
+///
+/// * `matcher` - The matcher to search for.
+/// * `replacement` - A function receiving the matched slice and returning its
+///   replacement.
+/// * `data` - The data to search in.
+///
+/// # Returns
+///
+/// A copy of the data where each occurrence is replaced.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::search::replace_all;
+///
+/// let result = replace_all(Token::Pipe, |_| b" OR ", b"a|b");
+/// assert_eq!(result, b"a OR b");
+/// ```
+pub fn replace_all<T, M, F, R>(matcher: M, mut replacement: F, data: &[T]) -> Vec<T>
+where
+    T: Clone,
+    M: Match<T>,
+    F: FnMut(&[T]) -> R,
+    R: AsRef<[T]>,
+{
+    let mut result = Vec::with_capacity(data.len());
+    let mut last = 0;
+    for (offset, length) in find_all(matcher, data) {
+        result.extend_from_slice(&data[last..offset]);
+        result.extend_from_slice(replacement(&data[offset..offset + length]).as_ref());
+        last = offset + length;
+    }
+    result.extend_from_slice(&data[last..]);
+    result
+}
+
+/// Replace all the non-overlapping occurrences of a `Match` in a byte slice
+/// and stream the result to a writer.
+///
+/// Unmatched regions are written directly from the input without any copy.
+///
+/// # Arguments
+///
+/// * `matcher` - The matcher to search for.
+/// * `replacement` - A function receiving the matched slice and returning its
+///   replacement.
+/// * `data` - The data to search in.
+/// * `writer` - The writer receiving the result.
+///
+/// # Errors
+///
+/// Any error returned by the writer.
+pub fn replace_all_to<M, F, R, W>(
+    matcher: M,
+    mut replacement: F,
+    data: &[u8],
+    writer: &mut W,
+) -> std::io::Result<()>
+where
+    M: Match<u8>,
+    F: FnMut(&[u8]) -> R,
+    R: AsRef<[u8]>,
+    W: Write,
+{
+    let mut last = 0;
+    for (offset, length) in find_all(matcher, data) {
+        writer.write_all(&data[last..offset])?;
+        writer.write_all(replacement(&data[offset..offset + length]).as_ref())?;
+        last = offset + length;
+    }
+    writer.write_all(&data[last..])
+}
+
+/// Defines what a [Split] does with the delimiters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DelimiterMode {
+    /// The delimiters are dropped
+    #[default]
+    Discard,
+    /// The delimiters are yielded as items of their own
+    Separate,
+    /// The delimiters are kept at the end of the preceding item
+    Trailing,
+}
+
+/// An iterator over the slices of a slice separated by the occurrences of a
+/// `Match`.
+///
+/// Like `str::split`, an input which starts or ends with a delimiter yields an
+/// empty first or last item, and an empty input yields a single empty item.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data to split.
+/// * `M` - The type of the matcher.
+/// * `'a` - The lifetime of the data to split.
+pub struct Split<'a, T, M> {
+    /// The occurrences of the delimiter
+    delimiters: FindAll<'a, T, M>,
+    /// The data to split
+    data: &'a [T],
+    /// The start of the next item
+    position: usize,
+    /// What to do with the delimiters
+    mode: DelimiterMode,
+    /// A delimiter waiting to be yielded in [DelimiterMode::Separate]
+    pending: Option<&'a [T]>,
+    /// Whether the last item has been yielded
+    finished: bool,
+}
+
+impl<'a, T, M: Match<T>> Iterator for Split<'a, T, M> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(delimiter) = self.pending.take() {
+            return Some(delimiter);
+        }
+        if self.finished {
+            return None;
+        }
+        match self.delimiters.next() {
+            Some((offset, length)) => {
+                let start = self.position;
+                self.position = offset + length;
+                match self.mode {
+                    DelimiterMode::Discard => Some(&self.data[start..offset]),
+                    DelimiterMode::Separate => {
+                        self.pending = Some(&self.data[offset..offset + length]);
+                        Some(&self.data[start..offset])
+                    }
+                    DelimiterMode::Trailing => Some(&self.data[start..offset + length]),
+                }
+            }
+            None => {
+                self.finished = true;
+                Some(&self.data[self.position..])
+            }
+        }
+    }
+}
+
+/// Split a slice by the occurrences of a `Match`, dropping the delimiters.
+///
+/// # Arguments
+///
+/// * `matcher` - The delimiter matcher.
+/// * `data` - The data to split.
+///
+/// # Returns
+///
+/// An iterator of the slices between the delimiters.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::token::Token;
+/// use elyze::search::split;
+///
+/// let items = split(Token::CrLn, b"a\r\nb").collect::<Vec<_>>();
+/// assert_eq!(items, vec![b"a", b"b"]);
+/// ```
+pub fn split<T, M: Match<T>>(matcher: M, data: &[T]) -> Split<'_, T, M> {
+    split_with(matcher, data, DelimiterMode::default())
+}
+
+/// Split a slice by the occurrences of a `Match`.
+///
+/// # Arguments
+///
+/// * `matcher` - The delimiter matcher.
+/// * `data` - The data to split.
+/// * `mode` - What to do with the delimiters.
+///
+/// # Returns
+///
+/// An iterator of the slices between the delimiters.
+pub fn split_with<T, M: Match<T>>(matcher: M, data: &[T], mode: DelimiterMode) -> Split<'_, T, M> {
+    Split {
+        delimiters: find_all(matcher, data),
+        data,
+        position: 0,
+        mode,
+        pending: None,
+        finished: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::matchers::match_pattern;
+    use crate::bytes::primitives::number::TokenNumber;
+    use crate::bytes::token::Token;
+    use crate::matcher::Match;
+    use crate::search::{
+        find_all, find_all_with, replace_all, replace_all_to, split, split_with, DelimiterMode,
+        SearchOptions,
+    };
+
+    struct Pattern(&'static [u8]);
+
+    impl Match<u8> for Pattern {
+        fn is_matching(&self, data: &[u8]) -> (bool, usize) {
+            match_pattern(self.0, data)
+        }
+
+        fn size(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn test_find_all() {
+        let matches = find_all(Token::CrLn, b"a\r\nb\r\n\r\nc").collect::<Vec<_>>();
+        assert_eq!(matches, vec![(1, 2), (4, 2), (6, 2)]);
+
+        let matches = find_all(Token::Pipe, b"abc").collect::<Vec<_>>();
+        assert_eq!(matches, vec![]);
+
+        let matches = find_all(Token::Pipe, b"").collect::<Vec<_>>();
+        assert_eq!(matches, vec![]);
+    }
+
+    #[test]
+    fn test_find_all_variable_length() {
+        let matches = find_all(TokenNumber, b"12 + 345 * 6").collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0, 2), (5, 3), (11, 1)]);
+    }
+
+    #[test]
+    fn test_find_all_overlapping() {
+        let options = SearchOptions::default().overlapping(true);
+        let matches = find_all_with(Pattern(b"aa"), b"aaaa", options).collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0, 2), (1, 2), (2, 2)]);
+
+        let matches = find_all(Pattern(b"aa"), b"aaaa").collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_find_all_stride() {
+        // only codon-aligned occurrences are found
+        let options = SearchOptions::default().stride(3);
+        let matches = find_all_with(Pattern(b"AUG"), b"CAUGAUGCCAUG", options).collect::<Vec<_>>();
+        assert_eq!(matches, vec![(9, 3)]);
+
+        let matches = find_all_with(Pattern(b"AUG"), b"AUGAUGCAUG", options).collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0, 3), (3, 3)]);
+
+        let options = options.overlapping(true);
+        let matches = find_all_with(Pattern(b"AA"), b"AAAAAA", options).collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let result = replace_all(Token::Pipe, |_| b"||", b"a|b|c");
+        assert_eq!(result, b"a||b||c");
+
+        let result = replace_all(
+            TokenNumber,
+            |number: &[u8]| vec![b'#'; number.len()],
+            b"id 123 or 45",
+        );
+        assert_eq!(result, b"id ### or ##");
+
+        let result = replace_all(Token::Pipe, |_| b"", b"abc");
+        assert_eq!(result, b"abc");
+    }
+
+    #[test]
+    fn test_replace_all_to() {
+        let mut output = vec![];
+        replace_all_to(
+            Pattern(b"{{name}}"),
+            |_| b"world",
+            b"hello {{name}}!",
+            &mut output,
+        )
+        .expect("failed to write");
+        assert_eq!(output, b"hello world!");
+    }
+
+    #[test]
+    fn test_split() {
+        let items = split(Token::Comma, b"a,bc,,d").collect::<Vec<_>>();
+        assert_eq!(items, vec![&b"a"[..], b"bc", b"", b"d"]);
+
+        let items = split(Pattern(b"::"), b"::std::io::").collect::<Vec<_>>();
+        assert_eq!(items, vec![&b""[..], b"std", b"io", b""]);
+
+        let items = split(Token::Comma, b"").collect::<Vec<_>>();
+        assert_eq!(items, vec![&b""[..]]);
+    }
+
+    #[test]
+    fn test_split_keep_delimiters() {
+        let items = split_with(TokenNumber, b"a1b22c", DelimiterMode::Separate).collect::<Vec<_>>();
+        assert_eq!(items, vec![&b"a"[..], b"1", b"b", b"22", b"c"]);
+
+        let items = split_with(Token::Ln, b"a\nb\n", DelimiterMode::Trailing).collect::<Vec<_>>();
+        assert_eq!(items, vec![&b"a\n"[..], b"b\n", b""]);
+    }
+}
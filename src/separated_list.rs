@@ -1,6 +1,8 @@
 use crate::errors::{ParseError, ParseResult};
+use crate::invariants::ensure_progress;
 use crate::peek::{peek, Last, Peekable};
 use crate::scanner::Scanner;
+use crate::validate::Validate;
 use crate::visitor::Visitor;
 use std::fmt::Debug;
 use std::marker::PhantomData;
@@ -11,9 +13,9 @@ pub struct SeparatedList<T, V, S> {
     separator: PhantomData<(S, T)>,
 }
 
-enum YieldResult<V> {
-    Last(V),
-    MaybeNext(V),
+enum YieldResult {
+    Last,
+    MaybeNext,
 }
 
 /// Yield the next element in the list and tell if it's the last one.
@@ -21,42 +23,91 @@ enum YieldResult<V> {
 /// # Type Parameters
 ///
 /// * `T` - The type of the data to scan.
-/// * `V` - The type of the element to yield.
 /// * `S` - The type of the separator to consume.
 ///
 /// # Arguments
 ///
 /// * `scanner` - The scanner to use.
+/// * `element` - Consume the element.
 ///
 /// # Returns
 ///
-/// A `YieldResult` containing the element and whether it's the last one.
+/// A `YieldResult` telling whether the element is the last one.
 ///
 /// # Errors
 ///
-/// Any error the visitor for the element or the separator returns.
-fn yield_element<'a, T, V, S>(scanner: &mut Scanner<'a, T>) -> ParseResult<YieldResult<V>>
+/// Any error the element or the visitor for the separator returns.
+fn yield_element<'a, T, S, F>(
+    scanner: &mut Scanner<'a, T>,
+    element: &mut F,
+) -> ParseResult<YieldResult>
 where
-    V: Visitor<'a, T>,
     S: Visitor<'a, T>,
+    F: FnMut(&mut Scanner<'a, T>) -> ParseResult<()>,
 {
     let cursor = scanner.current_position();
-    let element = match scanner.visit::<V>() {
-        Ok(element) => element,
-        Err(err) => {
-            scanner.jump_to(cursor);
-            return Err(err);
-        }
-    };
+    if let Err(err) = element(scanner) {
+        scanner.jump_to(cursor);
+        return Err(err);
+    }
 
     if scanner.remaining().is_empty() {
-        return Ok(YieldResult::Last(element));
+        return Ok(YieldResult::Last);
     }
 
     // consume the separator if not the end of the slice
     scanner.visit::<S>()?;
 
-    Ok(YieldResult::MaybeNext(element))
+    Ok(YieldResult::MaybeNext)
+}
+
+/// Consume a list of elements separated by a separator.
+///
+/// # Type Parameters
+///
+/// * `R` - The rule of the list, used to report the error of an element
+///   and a separator consuming nothing.
+/// * `T` - The type of the data to scan.
+/// * `S` - The type of the separator to consume.
+///
+/// # Arguments
+///
+/// * `scanner` - The scanner to use.
+/// * `element` - Consume an element.
+///
+/// # Errors
+///
+/// `ParseError::UnexpectedToken` at the position of the element which can't
+/// be consumed, the scanner is then moved back to the start of the list.
+fn walk_list<'a, R, T, S, F>(scanner: &mut Scanner<'a, T>, mut element: F) -> ParseResult<()>
+where
+    S: Visitor<'a, T>,
+    F: FnMut(&mut Scanner<'a, T>) -> ParseResult<()>,
+{
+    let cursor = scanner.current_position();
+
+    // if the scanner is empty, the list is empty
+    if scanner.remaining().is_empty() {
+        return Ok(());
+    }
+
+    loop {
+        let start = scanner.current_position();
+        match yield_element::<T, S, F>(scanner, &mut element) {
+            Ok(YieldResult::Last) => return Ok(()),
+            Ok(YieldResult::MaybeNext) => {
+                // an element and a separator consuming nothing would loop forever
+                if let Err(err) = ensure_progress::<R, T>(scanner, start) {
+                    scanner.jump_to(cursor);
+                    return Err(err);
+                }
+            }
+            Err(_) => {
+                scanner.jump_to(cursor);
+                return Err(ParseError::unexpected_token(start));
+            }
+        }
+    }
 }
 
 impl<'a, T, V, S> Visitor<'a, T> for SeparatedList<T, V, S>
@@ -78,38 +129,14 @@ where
     /// # Errors
     ///
     /// Any error the visitor for the element or the separator returns, or
-    /// `ParseError::UnexpectedToken` if the scanner is empty when attempting
-    /// to parse the separator.
+    /// `ParseError::UnexpectedToken` at the position of the element if the
+    /// scanner is empty when attempting to parse the separator.
     fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
         let mut elements = vec![];
-        let cursor = scanner.current_position();
-
-        // if the scanner is empty, return an empty list
-        if scanner.remaining().is_empty() {
-            return Ok(SeparatedList {
-                data: elements,
-                separator: PhantomData,
-            });
-        }
-
-        loop {
-            if let Ok(result) = yield_element::<T, V, S>(scanner) {
-                let element: YieldResult<V> = result;
-
-                match element {
-                    YieldResult::Last(element) => {
-                        elements.push(element);
-                        break;
-                    }
-                    YieldResult::MaybeNext(element) => {
-                        elements.push(element);
-                    }
-                }
-            } else {
-                scanner.jump_to(cursor);
-                return Err(ParseError::UnexpectedToken);
-            }
-        }
+        walk_list::<Self, T, S, _>(scanner, |scanner| {
+            elements.push(scanner.visit::<V>()?);
+            Ok(())
+        })?;
 
         Ok(SeparatedList {
             data: elements,
@@ -118,6 +145,17 @@ where
     }
 }
 
+/// Check the list without collecting its elements.
+impl<'a, T, V, S> Validate<'a, T> for SeparatedList<T, V, S>
+where
+    V: Validate<'a, T>,
+    S: Visitor<'a, T>,
+{
+    fn validate(scanner: &mut Scanner<'a, T>) -> ParseResult<()> {
+        walk_list::<Self, T, S, _>(scanner, V::validate)
+    }
+}
+
 /// Return a scanner without the trailing separator.
 ///
 /// # Arguments
@@ -145,7 +183,7 @@ where
         return Ok(Scanner::new(scanner.remaining()));
     };
 
-    let result_last_separator = peek(Last::new(separator), &scanner)?;
+    let result_last_separator = peek(Last::new(separator), scanner)?;
 
     // It seemingly a 1-element list
     let Some(result_last_separator) = result_last_separator else {
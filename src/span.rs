@@ -0,0 +1,179 @@
+//! Positions of the parsed values in the input.
+
+use crate::errors::ParseResult;
+use crate::scanner::Scanner;
+use crate::validate::Validate;
+use crate::visitor::Visitor;
+use std::ops::{Deref, Range};
+
+/// A range of positions in the input, `end` excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    /// The position of the first element
+    pub start: usize,
+    /// The position after the last element
+    pub end: usize,
+}
+
+impl Span {
+    /// Create a new `Span`
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The position of the first element
+    /// * `end` - The position after the last element
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// Return the number of elements of the span
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Return true if the span contains no element
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Return the smallest span containing both spans
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The span to merge with
+    pub fn merge(&self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    /// Return the elements of the span
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The input the span has been taken from
+    ///
+    /// # Returns
+    ///
+    /// `None` if the span is out of the bounds of the data.
+    pub fn slice<'a, T>(&self, data: &'a [T]) -> Option<&'a [T]> {
+        data.get(self.start..self.end)
+    }
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Span::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
+
+/// A value accepted by the visitor `V` and the span it has been parsed from.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::scanner::Scanner;
+/// use elyze::span::{Span, Spanned};
+///
+/// let mut scanner = Scanner::new(b"x=42");
+/// scanner.bump_by(2);
+/// let spanned = scanner.visit::<Spanned<Number<u32>>>().expect("failed to parse");
+/// assert_eq!(spanned.value, Number(42));
+/// assert_eq!(spanned.span, Span::new(2, 4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Spanned<V> {
+    /// The accepted value
+    pub value: V,
+    /// The span of the value in the input
+    pub span: Span,
+}
+
+impl<V> Spanned<V> {
+    /// Return the accepted value, dropping its span
+    pub fn into_inner(self) -> V {
+        self.value
+    }
+}
+
+impl<V> Deref for Spanned<V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'a, T, V: Visitor<'a, T>> Visitor<'a, T> for Spanned<V> {
+    fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self> {
+        let start = scanner.current_position();
+        let value = V::accept(scanner)?;
+        Ok(Spanned {
+            value,
+            span: Span::new(start, scanner.current_position()),
+        })
+    }
+}
+
+impl<'a, T, V: Validate<'a, T>> Validate<'a, T> for Spanned<V> {
+    fn validate(scanner: &mut Scanner<'a, T>) -> ParseResult<()> {
+        V::validate(scanner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::primitives::whitespace::OptionalWhitespaces;
+    use crate::bytes::token::Token;
+    use crate::errors::ParseResult;
+    use crate::recognizer::recognize;
+    use crate::scanner::Scanner;
+    use crate::span::{Span, Spanned};
+    use crate::visitor::Visitor;
+
+    /// A sum of two numbers, each one with its span
+    struct Sum {
+        left: Spanned<Number<u32>>,
+        right: Spanned<Number<u32>>,
+    }
+
+    impl<'a> Visitor<'a, u8> for Sum {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            let left = scanner.visit()?;
+            scanner.visit::<OptionalWhitespaces>()?;
+            recognize(Token::Plus, scanner)?;
+            scanner.visit::<OptionalWhitespaces>()?;
+            let right = scanner.visit()?;
+            Ok(Sum { left, right })
+        }
+    }
+
+    #[test]
+    fn test_spanned() {
+        let data = b"12 + 345";
+        let mut scanner = Scanner::new(data);
+        let sum = scanner.visit::<Spanned<Sum>>().expect("failed to parse");
+        assert_eq!(sum.span, Span::new(0, 8));
+        assert_eq!(sum.left.span, Span::new(0, 2));
+        assert_eq!(sum.right.span, Span::new(5, 8));
+        assert_eq!(*sum.right, Number(345));
+        assert_eq!(sum.right.span.slice(data), Some(&b"345"[..]));
+        assert_eq!(sum.left.span.merge(sum.right.span), sum.span);
+    }
+
+    #[test]
+    fn test_span() {
+        let span = Span::from(2..5);
+        assert_eq!(span.len(), 3);
+        assert!(!span.is_empty());
+        assert!(Span::new(4, 4).is_empty());
+        assert_eq!(span.slice(b"abc"), None);
+        assert_eq!(std::ops::Range::from(span), 2..5);
+    }
+}
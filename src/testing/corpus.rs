@@ -0,0 +1,299 @@
+//! Golden files test corpus.
+//!
+//! A corpus is a directory of input files, each one next to a `.expected`
+//! file holding the expected rendering of its parse: the `Debug` output of the
+//! parsed value, or `error: ` followed by the parse error. Running the corpus
+//! compares each rendering to the expected one, which gives a grammar crate
+//! regression tests by dropping files in a directory.
+//!
+//! The expected files are written instead of compared when the
+//! `ELYZE_UPDATE_CORPUS` environment variable is set, to create them or to
+//! accept a change of the grammar.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::visitor::{parse_partial, Visitor};
+use std::fmt::{Debug, Display, Formatter};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// The environment variable enabling the update of the expected files
+pub const UPDATE_VARIABLE: &str = "ELYZE_UPDATE_CORPUS";
+
+/// An input whose rendering differs from the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The path of the input file
+    pub input: PathBuf,
+    /// The expected rendering, `None` if the expected file is missing
+    pub expected: Option<String>,
+    /// The rendering of the parse
+    pub actual: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "--- {}", self.input.display())?;
+        match &self.expected {
+            Some(expected) => writeln!(f, "expected:\n{}", expected.trim_end())?,
+            None => writeln!(f, "expected: missing file")?,
+        }
+        write!(f, "actual:\n{}", self.actual.trim_end())
+    }
+}
+
+/// A directory of input files and their expected rendering.
+///
+/// # Example
+///
+/// ```no_run
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::testing::corpus::Corpus;
+///
+/// // tests/corpus/answer.input holds `42` and tests/corpus/answer.expected
+/// // holds the rendering `Number(\n    42,\n)`
+/// Corpus::new("tests/corpus").assert_visitor::<Number<u32>>();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Corpus {
+    /// The directory of the corpus
+    directory: PathBuf,
+    /// The extension of the input files
+    extension: String,
+    /// Whether the expected files are written instead of compared
+    update: bool,
+}
+
+impl Corpus {
+    /// Create a new `Corpus`
+    ///
+    /// The input files have the `input` extension, and the expected files are
+    /// updated if the `ELYZE_UPDATE_CORPUS` environment variable is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory of the corpus
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Corpus {
+            directory: directory.into(),
+            extension: "input".to_string(),
+            update: std::env::var_os(UPDATE_VARIABLE).is_some(),
+        }
+    }
+
+    /// Set the extension of the input files
+    ///
+    /// # Arguments
+    ///
+    /// * `extension` - The extension, without the leading dot
+    pub fn with_extension(mut self, extension: &str) -> Self {
+        self.extension = extension.to_string();
+        self
+    }
+
+    /// Set whether the expected files are written instead of compared
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - Whether to write the expected files
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// List the input files of the corpus, sorted by path
+    fn inputs(&self) -> std::io::Result<Vec<PathBuf>> {
+        let mut inputs = vec![];
+        for entry in std::fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| *ext == *self.extension) {
+                inputs.push(path);
+            }
+        }
+        inputs.sort();
+        Ok(inputs)
+    }
+
+    /// Parse each input file and compare its rendering to the expected one
+    ///
+    /// # Arguments
+    ///
+    /// * `parse` - Parse the content of an input file and render the result
+    ///
+    /// # Returns
+    ///
+    /// The inputs whose rendering differs from the expected one, always empty
+    /// in update mode.
+    ///
+    /// # Errors
+    ///
+    /// Any error reading the corpus or writing the expected files.
+    pub fn run<F>(&self, mut parse: F) -> std::io::Result<Vec<Mismatch>>
+    where
+        F: FnMut(&[u8]) -> ParseResult<String>,
+    {
+        let mut mismatches = vec![];
+        for input in self.inputs()? {
+            let data = std::fs::read(&input)?;
+            let actual = match parse(&data) {
+                Ok(rendering) => format!("{rendering}\n"),
+                Err(err) => format!("error: {err}\n"),
+            };
+
+            let expected_path = input.with_extension("expected");
+            let expected = match std::fs::read_to_string(&expected_path) {
+                Ok(expected) => Some(expected),
+                Err(err) if err.kind() == ErrorKind::NotFound => None,
+                Err(err) => return Err(err),
+            };
+            if expected.as_deref().map(str::trim_end) == Some(actual.trim_end()) {
+                continue;
+            }
+
+            if self.update {
+                std::fs::write(&expected_path, &actual)?;
+            } else {
+                mismatches.push(Mismatch {
+                    input,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Parse each input file as a `V`, rendered with its pretty `Debug` output
+    ///
+    /// The whole input must be consumed by the visitor.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `V` - The visitor to run
+    ///
+    /// # Returns
+    ///
+    /// The inputs whose rendering differs from the expected one.
+    ///
+    /// # Errors
+    ///
+    /// Any error reading the corpus or writing the expected files.
+    pub fn run_visitor<V>(&self) -> std::io::Result<Vec<Mismatch>>
+    where
+        V: for<'a> Visitor<'a, u8> + Debug,
+    {
+        self.run(|data| {
+            let (value, rest, consumed) = parse_partial::<u8, V>(data)?;
+            if !rest.is_empty() {
+                return Err(ParseError::unexpected_token(consumed));
+            }
+            Ok(format!("{value:#?}"))
+        })
+    }
+
+    /// Run the corpus as a `V` and panic on mismatches
+    ///
+    /// # Type Parameters
+    ///
+    /// * `V` - The visitor to run
+    ///
+    /// # Panics
+    ///
+    /// Panics if the corpus can't be read, listing the mismatches otherwise.
+    pub fn assert_visitor<V>(&self)
+    where
+        V: for<'a> Visitor<'a, u8> + Debug,
+    {
+        let mismatches = self
+            .run_visitor::<V>()
+            .unwrap_or_else(|err| panic!("failed to run the corpus: {err}"));
+        if !mismatches.is_empty() {
+            let report = mismatches
+                .iter()
+                .map(Mismatch::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+            panic!(
+                "{} input(s) of {} don't match, set {UPDATE_VARIABLE} to update them\n{report}",
+                mismatches.len(),
+                self.directory.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::testing::corpus::Corpus;
+    use std::path::PathBuf;
+
+    /// Create an empty corpus directory
+    fn corpus_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("elyze-corpus-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).expect("failed to create the corpus");
+        directory
+    }
+
+    #[test]
+    fn test_corpus() {
+        let directory = corpus_directory("run");
+        let write = |name: &str, content: &str| {
+            std::fs::write(directory.join(name), content).expect("failed to write");
+        };
+        write("answer.input", "42");
+        write("answer.expected", "Number(\n    42,\n)\n");
+        write("letter.input", "x");
+        write(
+            "letter.expected",
+            "error: Unexpected token have been encountered at 0",
+        );
+        write("trailing.input", "42 ");
+        write("trailing.expected", "Number(\n    42,\n)\n");
+        write("missing.input", "7");
+        write("notes.txt", "not an input");
+
+        let corpus = Corpus::new(&directory).with_update(false);
+        let mismatches = corpus.run_visitor::<Number<u32>>().expect("failed to run");
+        let inputs = mismatches
+            .iter()
+            .map(|mismatch| mismatch.input.file_name().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(inputs, ["missing.input", "trailing.input"]);
+        assert_eq!(mismatches[0].expected, None);
+        assert_eq!(mismatches[0].actual, "Number(\n    7,\n)\n");
+        assert_eq!(
+            mismatches[1].actual,
+            "error: Unexpected token have been encountered at 2\n"
+        );
+
+        // accept the current renderings
+        let mismatches = corpus
+            .clone()
+            .with_update(true)
+            .run_visitor::<Number<u32>>()
+            .expect("failed to update");
+        assert!(mismatches.is_empty());
+        corpus.assert_visitor::<Number<u32>>();
+
+        std::fs::remove_dir_all(&directory).expect("failed to clean up");
+    }
+
+    #[test]
+    fn test_corpus_extension() {
+        let directory = corpus_directory("extension");
+        std::fs::write(directory.join("a.num"), "12").expect("failed to write");
+        std::fs::write(directory.join("a.expected"), "12").expect("failed to write");
+
+        let corpus = Corpus::new(&directory)
+            .with_extension("num")
+            .with_update(false);
+        let mismatches = corpus
+            .run(|data| Ok(String::from_utf8_lossy(data).into_owned()))
+            .expect("failed to run");
+        assert!(mismatches.is_empty());
+
+        std::fs::remove_dir_all(&directory).expect("failed to clean up");
+    }
+}
@@ -0,0 +1,2 @@
+//! Utilities to test the grammars built on elyze.
+pub mod corpus;
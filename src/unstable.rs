@@ -0,0 +1,152 @@
+//! Experimental combinators, enabled by the `unstable` feature.
+//!
+//! The items of this module aren't covered by the semantic versioning of the
+//! crate, they may change or go away in any release, and move to their
+//! stable module once their design settles.
+//!
+//! The extension traits are sealed, they can't be implemented outside of the
+//! crate, so adding a method never breaks a downstream implementation.
+
+use crate::combinators::is_recoverable;
+use crate::errors::{ParseError, ParseResult};
+use crate::invariants::ensure_progress;
+use crate::scanner::Scanner;
+use crate::sealed::Sealed;
+use crate::visitor::Visitor;
+
+/// Experimental combinators applied to a [Scanner].
+pub trait ScannerExt<'a, T>: Sealed {
+    /// Apply the visitor if it matches.
+    ///
+    /// # Returns
+    ///
+    /// The accepted value, or `None` if the visitor doesn't match, the
+    /// scanner being restored.
+    ///
+    /// # Errors
+    ///
+    /// Any error of the visitor which isn't a mismatch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::bytes::primitives::number::Number;
+    /// use elyze::scanner::Scanner;
+    /// use elyze::unstable::ScannerExt;
+    ///
+    /// let mut scanner = Scanner::new(b"x");
+    /// let number = scanner.optional::<Number<u8>>().expect("failed to parse");
+    /// assert!(number.is_none());
+    /// assert_eq!(scanner.current_position(), 0);
+    /// ```
+    fn optional<V: Visitor<'a, T>>(&mut self) -> ParseResult<Option<V>>;
+
+    /// Apply the visitor from `min` to `max` times in a row.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimal number of values
+    /// * `max` - The maximal number of values, the repetition stopping there
+    ///
+    /// # Errors
+    ///
+    /// `ParseError::UnexpectedToken` where the repetition stopped if there
+    /// are less than `min` values, the scanner being restored. Any error of
+    /// the visitor which isn't a mismatch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use elyze::bytes::token::Token;
+    /// use elyze::scanner::Scanner;
+    /// use elyze::unstable::ScannerExt;
+    ///
+    /// let mut scanner = Scanner::new(b"++++");
+    /// let pluses = scanner.repeat_between::<Token>(1, 3).expect("failed to parse");
+    /// assert_eq!(pluses, [Token::Plus; 3]);
+    /// assert_eq!(scanner.remaining(), b"+");
+    /// ```
+    fn repeat_between<V: Visitor<'a, T>>(&mut self, min: usize, max: usize) -> ParseResult<Vec<V>>;
+}
+
+impl<'a, T> ScannerExt<'a, T> for Scanner<'a, T> {
+    fn optional<V: Visitor<'a, T>>(&mut self) -> ParseResult<Option<V>> {
+        let start = self.current_position();
+        match V::accept(self) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if is_recoverable(&err) => {
+                self.jump_to(start);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn repeat_between<V: Visitor<'a, T>>(&mut self, min: usize, max: usize) -> ParseResult<Vec<V>> {
+        let mut checkpoint = self.checkpoint();
+        let mut values = vec![];
+        while values.len() < max {
+            let start = checkpoint.current_position();
+            let Some(value) = checkpoint.optional::<V>()? else {
+                break;
+            };
+            // a visitor consuming nothing would be applied `max` times
+            ensure_progress::<V, T>(&checkpoint, start)?;
+            values.push(value);
+        }
+        if values.len() < min {
+            return Err(ParseError::unexpected_token(checkpoint.current_position()));
+        }
+        checkpoint.commit();
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+    use crate::unstable::ScannerExt;
+
+    #[test]
+    fn test_optional() {
+        let mut scanner = Scanner::new(b"12x");
+        let number = scanner.optional::<Number<u8>>().expect("failed to parse");
+        assert_eq!(number, Some(Number(12)));
+        assert_eq!(scanner.remaining(), b"x");
+
+        // an error which isn't a mismatch is returned
+        let result = Scanner::new(b"300").optional::<Number<u8>>();
+        assert!(matches!(result, Err(ParseError::ParseIntError(_))));
+    }
+
+    #[test]
+    fn test_repeat_between() {
+        let mut scanner = Scanner::new(b"+-+;");
+        let tokens = scanner
+            .repeat_between::<Token>(0, 10)
+            .expect("failed to parse");
+        assert_eq!(
+            tokens,
+            [Token::Plus, Token::Dash, Token::Plus, Token::Semicolon]
+        );
+        assert!(scanner.is_empty());
+
+        let mut scanner = Scanner::new(b"1");
+        let numbers = scanner
+            .repeat_between::<Number<u8>>(0, 0)
+            .expect("failed to parse");
+        assert!(numbers.is_empty());
+        assert_eq!(scanner.current_position(), 0);
+
+        // not enough values, the scanner is restored
+        let mut scanner = Scanner::new(b"++x");
+        assert!(matches!(
+            scanner.repeat_between::<Token>(3, 5),
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
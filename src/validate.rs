@@ -0,0 +1,152 @@
+//! Check the syntax of an input without building the parsed values.
+//!
+//! A grammar is made of visitors building values. Checking whether an input
+//! is valid, on every keystroke of an editor for instance, only needs to know
+//! where each rule stops: a [Validate] visitor walks the same rules without
+//! allocating the values it would build.
+
+use crate::errors::{ParseError, ParseResult};
+use crate::scanner::Scanner;
+use crate::visitor::Visitor;
+
+/// Derive [Validate] for a struct whose fields all implement [Validate], by
+/// validating the fields in their declaration order.
+///
+/// The struct must also implement [Visitor], usually derived alongside.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::primitives::whitespace::Whitespaces;
+/// use elyze::validate::{validate, Validate};
+/// use elyze::visitor::Visitor;
+///
+/// #[derive(Visitor, Validate)]
+/// struct Range(Number<u32>, Whitespaces, Number<u32>);
+///
+/// assert!(validate::<u8, Range>(b"12 42").is_ok());
+/// assert!(validate::<u8, Range>(b"12 x").is_err());
+/// ```
+#[cfg(feature = "derive")]
+pub use elyze_derive::Validate;
+
+/// A [Visitor] which can check its input without building its value.
+///
+/// The default implementation accepts the value and drops it, which suits
+/// the visitors building no allocated value. The visitors allocating their
+/// value, or containing visitors which do, override it to only walk their
+/// rule.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::token::Token;
+/// use elyze::separated_list::SeparatedList;
+/// use elyze::validate::validate;
+///
+/// type Numbers = SeparatedList<u8, Number<u32>, Token>;
+///
+/// assert!(validate::<u8, Numbers>(b"1,2,3").is_ok());
+/// assert!(validate::<u8, Numbers>(b"1,2,x").is_err());
+/// ```
+pub trait Validate<'a, T>: Visitor<'a, T> {
+    /// Check the scanner starts with a valid value and move past it.
+    ///
+    /// # Arguments
+    ///
+    /// * `scanner` - The scanner to check.
+    ///
+    /// # Errors
+    ///
+    /// The error [Visitor::accept] would return.
+    fn validate(scanner: &mut Scanner<'a, T>) -> ParseResult<()> {
+        Self::accept(scanner).map(drop)
+    }
+}
+
+/// Check the whole input is a valid `V`, without building it.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the data to check.
+/// * `V` - The visitor to run.
+///
+/// # Arguments
+///
+/// * `input` - The data to check.
+///
+/// # Errors
+///
+/// Any error of the visitor, or `ParseError::UnexpectedToken` at the first
+/// remaining element if the visitor doesn't consume the whole input.
+pub fn validate<'a, T, V: Validate<'a, T>>(input: &'a [T]) -> ParseResult<()> {
+    let mut scanner = Scanner::new(input);
+    V::validate(&mut scanner)?;
+    if !scanner.is_empty() {
+        return Err(ParseError::unexpected_token(scanner.current_position()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::primitives::string::DataString;
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::scanner::Scanner;
+    use crate::separated_list::SeparatedList;
+    use crate::span::Spanned;
+    use crate::validate::validate;
+    #[cfg(feature = "derive")]
+    use crate::{validate::Validate, visitor::Visitor};
+
+    type Numbers = SeparatedList<u8, Spanned<Number<u8>>, Token>;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate::<u8, Numbers>(b"1,22,3").is_ok());
+        assert!(validate::<u8, Numbers>(b"").is_ok());
+        // an out of range number is invalid as when it's built
+        assert!(validate::<u8, Numbers>(b"1,256").is_err());
+        assert!(matches!(
+            validate::<u8, Number<u8>>(b"12x"),
+            Err(ParseError::UnexpectedToken { offset: 2, .. })
+        ));
+
+        let mut scanner = Scanner::new(b"hello world");
+        scanner
+            .validate::<DataString<String>>()
+            .expect("failed to validate");
+        assert_eq!(scanner.remaining(), b" world");
+    }
+
+    /// An entry `key=value`
+    #[cfg(feature = "derive")]
+    #[derive(Visitor, Validate)]
+    struct Entry<'a> {
+        key: DataString<&'a str>,
+        _equal: Token,
+        value: Spanned<Number<u32>>,
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_validate() {
+        let mut scanner = Scanner::new(b"answer=42;");
+        scanner.validate::<Entry>().expect("failed to validate");
+        assert_eq!(scanner.remaining(), b";");
+
+        // the same rules build the entry
+        let entry = Scanner::new(b"answer=42")
+            .visit::<Entry>()
+            .expect("failed to parse");
+        assert_eq!((entry.key.0, entry.value.value.0), ("answer", 42));
+
+        let mut scanner = Scanner::new(b"answer=x");
+        assert!(scanner.validate::<Entry>().is_err());
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
@@ -3,6 +3,34 @@
 use crate::errors::ParseResult;
 use crate::scanner::Scanner;
 
+/// Derive [Visitor] for a struct whose fields all implement [Visitor], by
+/// accepting the fields in their declaration order.
+///
+/// The scanner is restored to its position before the struct if a field
+/// can't be accepted. If the struct has a lifetime parameter, the first one
+/// is the lifetime of the data.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::bytes::primitives::whitespace::Whitespaces;
+/// use elyze::scanner::Scanner;
+/// use elyze::visitor::Visitor;
+///
+/// #[derive(Visitor)]
+/// struct Range {
+///     start: Number<u32>,
+///     _separator: Whitespaces,
+///     end: Number<u32>,
+/// }
+///
+/// let range = Scanner::new(b"12 42").visit::<Range>().expect("failed to parse");
+/// assert_eq!((range.start.0, range.end.0), (12, 42));
+/// ```
+#[cfg(feature = "derive")]
+pub use elyze_derive::Visitor;
+
 /// A `Visitor` is a trait that allows to define how to visit a `Scanner`.
 ///
 /// When a `Visitor` is used on a `Scanner`, it will consume the input from the
@@ -27,3 +55,152 @@ pub trait Visitor<'a, T>: Sized {
     /// The result of the visit.
     fn accept(scanner: &mut Scanner<'a, T>) -> ParseResult<Self>;
 }
+
+/// Parse a `V` from the start of the input, and report where the parse
+/// stopped.
+///
+/// Unlike a full parse, the remaining data is not an error, which allows to
+/// parse one statement at a time from a buffer and to resume after it.
+///
+/// # Arguments
+///
+/// * `input` - The data to parse.
+///
+/// # Returns
+///
+/// The parsed value, the data remaining after it and the number of consumed
+/// elements.
+///
+/// # Errors
+///
+/// Any error of the visitor.
+///
+/// # Example
+///
+/// ```
+/// use elyze::bytes::primitives::number::Number;
+/// use elyze::visitor::parse_partial;
+///
+/// let (Number(number), rest, consumed) =
+///     parse_partial::<u8, Number<u32>>(b"42;17").expect("failed to parse");
+/// assert_eq!(number, 42);
+/// assert_eq!(rest, b";17");
+/// assert_eq!(consumed, 2);
+/// ```
+pub fn parse_partial<'a, T, V: Visitor<'a, T>>(input: &'a [T]) -> ParseResult<(V, &'a [T], usize)> {
+    let mut scanner = Scanner::new(input);
+    let value = V::accept(&mut scanner)?;
+    Ok((value, scanner.remaining(), scanner.current_position()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::primitives::number::Number;
+    use crate::bytes::token::Token;
+    use crate::errors::ParseError;
+    use crate::visitor::parse_partial;
+    #[cfg(feature = "derive")]
+    use crate::{
+        errors::ParseResult, formats::xml_lite::XmlEvent, recognizer::recognize, scanner::Scanner,
+        span::Spanned, visitor::Visitor,
+    };
+
+    #[test]
+    fn test_parse_partial() {
+        // parse one statement at a time
+        let mut buffer = &b"1;22;333"[..];
+        let mut numbers = vec![];
+        while !buffer.is_empty() {
+            let (Number(number), rest, consumed) =
+                parse_partial::<u8, Number<u32>>(buffer).expect("failed to parse");
+            numbers.push(number);
+            assert_eq!(&buffer[consumed..], rest);
+            buffer = rest;
+            if !buffer.is_empty() {
+                let (_, rest, consumed) =
+                    parse_partial::<u8, Token>(buffer).expect("failed to parse");
+                assert_eq!(consumed, 1);
+                buffer = rest;
+            }
+        }
+        assert_eq!(numbers, vec![1, 22, 333]);
+
+        let result = parse_partial::<u8, Number<u32>>(b";1");
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 0, .. })
+        ));
+    }
+
+    /// The `=` token
+    #[cfg(feature = "derive")]
+    #[derive(Debug)]
+    struct Equal;
+
+    #[cfg(feature = "derive")]
+    impl<'a> Visitor<'a, u8> for Equal {
+        fn accept(scanner: &mut Scanner<'a, u8>) -> ParseResult<Self> {
+            recognize(Token::Equal, scanner)?;
+            Ok(Equal)
+        }
+    }
+
+    /// An assignment `name=value` of numbers
+    #[cfg(feature = "derive")]
+    #[derive(Debug, Visitor)]
+    struct Assignment {
+        name: Spanned<Number<u32>>,
+        _equal: Equal,
+        value: Number<u32>,
+    }
+
+    /// Two values separated by `=`
+    #[cfg(feature = "derive")]
+    #[derive(Debug, Visitor)]
+    struct Pair<V>(V, Equal, V);
+
+    /// Two XML events, borrowing the data
+    #[cfg(feature = "derive")]
+    #[derive(Debug, Visitor)]
+    struct Events<'a>(XmlEvent<'a>, XmlEvent<'a>);
+
+    /// Accept the empty input
+    #[cfg(feature = "derive")]
+    #[derive(Debug, Visitor)]
+    struct Nothing;
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_visitor() {
+        let mut scanner = Scanner::new(b"12=42;");
+        let assignment = scanner.visit::<Assignment>().expect("failed to parse");
+        assert_eq!(assignment.name.span, crate::span::Span::new(0, 2));
+        assert_eq!(*assignment.name, Number(12));
+        assert_eq!(assignment.value, Number(42));
+        assert_eq!(scanner.current_position(), 5);
+
+        let Pair(Number(left), _, Number(right)) = Scanner::new(b"1=2")
+            .visit::<Pair<Number<u8>>>()
+            .expect("failed to parse");
+        assert_eq!((left, right), (1, 2));
+
+        let events = Scanner::new(b"<a>text")
+            .visit::<Events>()
+            .expect("failed to parse");
+        assert!(matches!(events.0, XmlEvent::Start { name: "a", .. }));
+        assert!(matches!(events.1, XmlEvent::Text(text) if text == "text"));
+
+        let mut scanner = Scanner::new(b"12");
+        assert!(matches!(scanner.visit::<Nothing>(), Ok(Nothing)));
+        assert_eq!(scanner.current_position(), 0);
+
+        // a field failing restores the scanner
+        let mut scanner = Scanner::new(b"12=x");
+        let result = scanner.visit::<Assignment>();
+        assert!(matches!(
+            result,
+            Err(ParseError::UnexpectedToken { offset: 3, .. })
+        ));
+        assert_eq!(scanner.current_position(), 0);
+    }
+}
@@ -0,0 +1,99 @@
+//! The core paths of elyze don't allocate: recognizing a `Match`, peeking a
+//! `Match`-based peekable and chaining a `Recognizer`.
+//!
+//! Each test counts the allocations of its own thread with a counting global
+//! allocator, which requires a test binary of its own.
+#![cfg(feature = "no-alloc-core")]
+
+use elyze::alternatives::CompiledAlternatives;
+use elyze::bytes::token::Token;
+use elyze::errors::ParseResult;
+use elyze::peek::{peek, UntilEnd};
+use elyze::recognizer::{recognize, recognize_slice, Recognizer};
+use elyze::scanner::Scanner;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// The system allocator counting the allocations of each thread
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Return the number of allocations performed by the function
+fn allocations<R>(function: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = function();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn test_recognize_does_not_allocate() {
+    let keywords = CompiledAlternatives::compile(&["let", "fn"]);
+    let (result, count) = allocations(|| -> ParseResult<()> {
+        let mut scanner = Scanner::new(b"fn(a, b)");
+        recognize_slice(&keywords, &mut scanner)?;
+        recognize(Token::OpenParen, &mut scanner)?;
+        // a mismatch doesn't allocate either
+        assert!(recognize(Token::Comma, &mut scanner).is_err());
+        assert!(recognize_slice(&keywords, &mut scanner).is_err());
+        Ok(())
+    });
+    result.expect("failed to recognize");
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_peek_does_not_allocate() {
+    let (result, count) = allocations(|| -> ParseResult<()> {
+        let scanner = Scanner::new(b"a + b, c");
+        let peeked = peek(Token::Comma, &scanner)?.expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"a + b");
+        assert!(peek(Token::Semicolon, &scanner)?.is_none());
+        let peeked = peek(UntilEnd::default(), &scanner)?.expect("failed to peek");
+        assert_eq!(peeked.peeked_slice(), b"a + b, c");
+        Ok(())
+    });
+    result.expect("failed to peek");
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_recognizer_does_not_allocate() {
+    let (result, count) = allocations(|| -> ParseResult<Option<Token>> {
+        let mut scanner = Scanner::new(b"-1");
+        Ok(Recognizer::new(&mut scanner)
+            .try_or(Token::Plus)?
+            .try_or(Token::Dash)?
+            .try_or(Token::Star)?
+            .finish())
+    });
+    assert_eq!(result.expect("failed to recognize"), Some(Token::Dash));
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_allocations_are_counted() {
+    let (_, count) = allocations(|| vec![1u8; 16]);
+    assert_eq!(count, 1);
+}